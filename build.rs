@@ -0,0 +1,5 @@
+fn main() {
+    // Node addons leave the N-API symbols for node to provide at load time
+    #[cfg(feature = "node")]
+    napi_build::setup();
+}
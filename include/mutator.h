@@ -0,0 +1,27 @@
+/* C ABI of the mutator library, built with
+ * `cargo rustc --release --lib --features ffi --crate-type cdylib`.
+ * Requests and responses are JSON; see src/ffi.rs for the formats. */
+
+#ifndef MUTATOR_H
+#define MUTATOR_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Discover mutations in a source text. Returns {"mutations": [...]} or
+ * {"error": "..."}; release it with mutator_free. */
+char *mutator_discover(const char *request);
+
+/* Apply one discovered mutation. Returns {"mutated": "...", "diff": "..."}
+ * or {"error": "..."}; release it with mutator_free. */
+char *mutator_apply(const char *request);
+
+/* Release a string returned by mutator_discover or mutator_apply. */
+void mutator_free(char *response);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
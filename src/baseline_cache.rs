@@ -0,0 +1,64 @@
+//! Baseline results cached across runs, in `.mutator/baseline-cache.json`
+//! under the project root.
+//!
+//! An agent re-running `mutator` in a tight loop pays for the unmutated
+//! suite every time. The cache key covers everything that decides the
+//! baseline's outcome: the test command and its arguments, the test file's
+//! contents and a fingerprint of the project tree
+//! (`copy_tree::tree_fingerprint`). Unchanged inputs reuse the recorded
+//! duration and pass/fail instead of running the suite again;
+//! `--no-baseline-cache` always runs it.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Entries kept; the oldest are dropped first.
+const MAX_ENTRIES: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedBaseline {
+    pub key: String,
+    pub passed: bool,
+    pub duration_ms: u64,
+    /// Test output of a failed baseline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+}
+
+pub fn cache_path(project_root: &Path) -> PathBuf {
+    project_root.join(".mutator").join("baseline-cache.json")
+}
+
+/// The entry stored under `key`, if any.
+pub fn lookup(path: &Path, key: &str) -> Option<CachedBaseline> {
+    load(path).into_iter().find(|e| e.key == key)
+}
+
+/// Record `entry`, replacing any entry with the same key. Best effort: a
+/// cache that can't be written only costs the next run a baseline.
+pub fn store(path: &Path, entry: CachedBaseline) {
+    let mut entries = load(path);
+    entries.retain(|e| e.key != entry.key);
+    entries.push(entry);
+    let excess = entries.len().saturating_sub(MAX_ENTRIES);
+    entries.drain(..excess);
+
+    let Ok(json) = serde_json::to_string(&entries) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    // Write-then-rename so concurrent sessions never read half a file
+    let tmp = path.with_extension(format!("json.{}", std::process::id()));
+    if std::fs::write(&tmp, json).is_ok() && std::fs::rename(&tmp, path).is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+}
+
+fn load(path: &Path) -> Vec<CachedBaseline> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
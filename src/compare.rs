@@ -0,0 +1,147 @@
+//! `mutator compare`: what changed between two recorded runs, e.g. before
+//! and after an agent adds tests.
+//!
+//! Mutants are matched by file and stable ID (`line:column:operator:replacement`,
+//! as in `Mutation::id`), so moving code changes its mutants' IDs: a
+//! survivor on a line that moved shows up as one mutant killed and another
+//! surviving.
+
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::mutants::MutantStatus;
+use crate::state::{FunctionScore, RunResult, SurvivedMutant};
+
+#[derive(Debug, Serialize)]
+pub struct Comparison {
+    pub score_before: f64,
+    pub score_after: f64,
+    pub score_delta: f64,
+    /// Survivors of the first run that the second run killed.
+    pub newly_killed: Vec<ComparedMutant>,
+    /// Survivors of the second run that didn't survive the first (new code,
+    /// or a test that no longer catches them).
+    pub newly_surviving: Vec<ComparedMutant>,
+    /// Functions whose counts changed, by name.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub functions: Vec<FunctionChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ComparedMutant {
+    pub id: String,
+    /// Ref in the run the mutant survived.
+    pub ref_id: String,
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub operator: String,
+    pub original: String,
+    pub replacement: String,
+}
+
+/// A function's counts in each run; `None` where the run had no mutants in it.
+#[derive(Debug, Serialize)]
+pub struct FunctionChange {
+    pub function: String,
+    pub before: Option<FunctionScore>,
+    pub after: Option<FunctionScore>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_delta: Option<f64>,
+}
+
+/// Stable ID of a recorded mutant, as the runner exports it in
+/// `MUTATOR_MUTANT_ID`.
+pub fn mutant_id(m: &SurvivedMutant) -> String {
+    id(m.line, m.column, &m.operator, &m.replacement)
+}
+
+fn id(line: usize, column: usize, operator: &str, replacement: &str) -> String {
+    format!("{}:{}:{}:{}", line, column, operator, replacement)
+}
+
+pub fn compare(before: &RunResult, after: &RunResult) -> Comparison {
+    let after_not_killed = keys(after.referenced());
+    let after_killed: BTreeSet<_> = after
+        .all_mutants
+        .iter()
+        .filter(|m| m.status == MutantStatus::Killed)
+        .map(|m| (m.file.clone(), id(m.line, m.column, &m.operator, &m.replacement)))
+        .collect();
+    let newly_killed = before
+        .survived_mutants
+        .iter()
+        .filter(|m| {
+            let key = key(m);
+            // Without --full-results killed mutants aren't recorded, so
+            // anything no longer referenced counts as killed
+            if after.all_mutants.is_empty() {
+                !after_not_killed.contains(&key)
+            } else {
+                after_killed.contains(&key)
+            }
+        })
+        .map(compared)
+        .collect();
+
+    let before_survivors = keys(before.survived_mutants.iter());
+    let newly_surviving = after
+        .survived_mutants
+        .iter()
+        .filter(|m| !before_survivors.contains(&key(m)))
+        .map(compared)
+        .collect();
+
+    Comparison {
+        score_before: before.score,
+        score_after: after.score,
+        score_delta: after.score - before.score,
+        newly_killed,
+        newly_surviving,
+        functions: function_changes(&before.functions, &after.functions),
+    }
+}
+
+fn function_changes(
+    before: &BTreeMap<String, FunctionScore>,
+    after: &BTreeMap<String, FunctionScore>,
+) -> Vec<FunctionChange> {
+    let names: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+    names
+        .into_iter()
+        .filter(|name| before.get(*name) != after.get(*name))
+        .map(|name| {
+            let (b, a) = (before.get(name), after.get(name));
+            FunctionChange {
+                function: name.clone(),
+                before: b.cloned(),
+                after: a.cloned(),
+                score_delta: b.zip(a).map(|(b, a)| a.score - b.score),
+            }
+        })
+        .collect()
+}
+
+fn key(m: &SurvivedMutant) -> (String, String) {
+    (m.file.clone(), mutant_id(m))
+}
+
+fn keys<'a>(mutants: impl Iterator<Item = &'a SurvivedMutant>) -> BTreeSet<(String, String)> {
+    mutants.map(key).collect()
+}
+
+fn compared(m: &SurvivedMutant) -> ComparedMutant {
+    ComparedMutant {
+        id: mutant_id(m),
+        ref_id: m.ref_id.clone(),
+        file: m.file.clone(),
+        line: m.line,
+        column: m.column,
+        function: m.function.clone(),
+        operator: m.operator.clone(),
+        original: m.original.clone(),
+        replacement: m.replacement.clone(),
+    }
+}
@@ -1,5 +1,7 @@
+use serde::Deserialize;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 const SKIP_NAMES: &[&str] = &[
     ".git",
@@ -19,6 +21,7 @@ const SKIP_NAMES: &[&str] = &[
     ".next",
     ".nuxt",
     ".mutator-state.json",
+    ".mutator",
 ];
 
 const SKIP_SUFFIXES: &[&str] = &[
@@ -34,33 +37,256 @@ pub struct CopyResult {
 }
 
 fn should_skip(name: &str) -> bool {
-    SKIP_NAMES.iter().any(|s| *s == name)
+    SKIP_NAMES.contains(&name)
         || SKIP_SUFFIXES.iter().any(|s| name.ends_with(s))
 }
 
-fn copy_dir_filtered(src: &Path, dst: &Path) -> std::io::Result<()> {
-    fs::create_dir_all(dst)?;
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-        if should_skip(&name_str) {
+/// How files are placed in the temp copy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyMode {
+    /// Plain byte copies.
+    #[default]
+    Copy,
+    /// Copy-on-write clones (Linux `FICLONE`, macOS `clonefile`) where the
+    /// filesystem supports them, plain copies elsewhere.
+    Reflink,
+    /// Clones where supported, hard links elsewhere, plain copies as a last
+    /// resort. Tests that write to tracked files would write through a hard
+    /// link into the original tree.
+    Link,
+}
+
+impl FromStr for CopyMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "copy" => Ok(CopyMode::Copy),
+            "reflink" => Ok(CopyMode::Reflink),
+            "link" => Ok(CopyMode::Link),
+            _ => Err(format!("unknown copy mode '{}' (expected copy, reflink or link)", s)),
+        }
+    }
+}
+
+/// Places files according to a `CopyMode`, remembering when clones or
+/// links fail (different filesystem, unsupported) so each file doesn't pay
+/// for a failed syscall.
+struct Placer {
+    can_reflink: bool,
+    can_link: bool,
+}
+
+impl Placer {
+    fn new(mode: CopyMode) -> Self {
+        Placer {
+            can_reflink: mode != CopyMode::Copy,
+            can_link: mode == CopyMode::Link,
+        }
+    }
+
+    fn place(&mut self, src: &Path, dst: &Path) -> std::io::Result<()> {
+        if self.can_reflink {
+            match reflink(src, dst) {
+                Ok(()) => return Ok(()),
+                Err(_) => self.can_reflink = false,
+            }
+        }
+        if self.can_link {
+            match fs::hard_link(src, dst) {
+                Ok(()) => return Ok(()),
+                Err(_) => self.can_link = false,
+            }
+        }
+        fs::copy(src, dst).map(drop)
+    }
+}
+
+/// Replace `dst` with a plain copy of `src`. The mutated source and the
+/// test file are rewritten during a run, so they must never be links.
+fn make_private(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let _ = fs::remove_file(dst);
+    fs::copy(src, dst).map(drop)
+}
+
+#[cfg(target_os = "linux")]
+fn reflink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    const FICLONE: u64 = 0x4004_9409;
+    let from = fs::File::open(src)?;
+    let to = fs::OpenOptions::new().write(true).create_new(true).open(dst)?;
+    // SAFETY: both descriptors are open for the duration of the call
+    let rc = unsafe { libc::ioctl(to.as_raw_fd(), FICLONE as _, from.as_raw_fd()) };
+    if rc == 0 {
+        return to.set_permissions(from.metadata()?.permissions());
+    }
+    let err = std::io::Error::last_os_error();
+    drop(to);
+    let _ = fs::remove_file(dst);
+    Err(err)
+}
+
+#[cfg(target_os = "macos")]
+fn reflink(src: &Path, dst: &Path) -> std::io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    unsafe extern "C" {
+        fn clonefile(src: *const libc::c_char, dst: *const libc::c_char, flags: u32) -> libc::c_int;
+    }
+    let from = CString::new(src.as_os_str().as_bytes())?;
+    let to = CString::new(dst.as_os_str().as_bytes())?;
+    // SAFETY: both are valid NUL-terminated paths
+    if unsafe { clonefile(from.as_ptr(), to.as_ptr(), 0) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn reflink(_src: &Path, _dst: &Path) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/// Custom ignore file, gitignore syntax. Its patterns take precedence over
+/// `.gitignore`, so `!pattern` re-includes a gitignored file tests need.
+pub const IGNORE_FILE: &str = ".mutatorignore";
+
+/// Entries under `root` worth copying, relative to it, directories before
+/// their contents. Honors `.gitignore` (and `.git/info/exclude`) whether or
+/// not `root` is a git checkout, `.mutatorignore`, and `SKIP_NAMES`.
+fn walk_filtered(root: &Path) -> std::io::Result<Vec<(PathBuf, fs::FileType)>> {
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .git_global(false)
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .filter_entry(|e| !should_skip(&e.file_name().to_string_lossy()))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    let mut entries = Vec::new();
+    for entry in walker {
+        let entry = entry.map_err(std::io::Error::other)?;
+        if entry.depth() == 0 {
             continue;
         }
-        let src_path = entry.path();
-        let dst_path = dst.join(&name);
-        let ft = entry.file_type()?;
+        let (Some(ft), Ok(rel)) = (entry.file_type(), entry.path().strip_prefix(root)) else {
+            continue;
+        };
+        entries.push((rel.to_path_buf(), ft));
+    }
+    Ok(entries)
+}
+
+/// Fingerprint of the files `copy_tree` would copy from `root`: each one's
+/// path, size and modification time, or its contents for `by_content`
+/// (files whose timestamps mutant runs disturb). Any edit, added or removed
+/// file changes it.
+pub fn tree_fingerprint(root: &Path, by_content: &[&Path]) -> std::io::Result<u64> {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for (rel, ft) in walk_filtered(root)? {
+        if !ft.is_file() {
+            continue;
+        }
+        let path = root.join(&rel);
+        rel.hash(&mut hasher);
+        if by_content.contains(&path.as_path()) {
+            fs::read(&path)?.hash(&mut hasher);
+        } else {
+            let meta = fs::metadata(&path)?;
+            meta.len().hash(&mut hasher);
+            meta.modified().ok().hash(&mut hasher);
+        }
+    }
+    Ok(hasher.finish())
+}
+
+fn copy_dir_filtered(src: &Path, dst: &Path, placer: &mut Placer) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for (rel, ft) in walk_filtered(src)? {
         if ft.is_dir() {
-            copy_dir_filtered(&src_path, &dst_path)?;
+            fs::create_dir_all(dst.join(&rel))?;
         } else if ft.is_file() {
-            fs::copy(&src_path, &dst_path)?;
+            placer.place(&src.join(&rel), &dst.join(&rel))?;
         }
         // Skip symlinks and other special files
     }
     Ok(())
 }
 
+/// Bring `dst` (made by `copy_dir_filtered`) up to date with `src`: replace
+/// files that are new, resized or modified since, and delete ones removed
+/// from `src`. Returns the number of files copied or removed.
+fn sync_dir_filtered(src: &Path, dst: &Path, placer: &mut Placer) -> std::io::Result<usize> {
+    fs::create_dir_all(dst)?;
+    let mut changed = 0;
+    let wanted = walk_filtered(src)?;
+    for (rel, ft) in &wanted {
+        let (src_path, dst_path) = (src.join(rel), dst.join(rel));
+        if ft.is_dir() {
+            fs::create_dir_all(&dst_path)?;
+        } else if ft.is_file() {
+            let src_meta = fs::metadata(&src_path)?;
+            let stale = match fs::metadata(&dst_path) {
+                Ok(dst_meta) => {
+                    src_meta.len() != dst_meta.len()
+                        || matches!((src_meta.modified(), dst_meta.modified()), (Ok(s), Ok(d)) if s > d)
+                }
+                Err(_) => true,
+            };
+            if stale {
+                // Unlink first so a hard-linked copy never writes through
+                let _ = fs::remove_file(&dst_path);
+                placer.place(&src_path, &dst_path)?;
+                changed += 1;
+            }
+        }
+    }
+    // The copy carries the same ignore files, so its own caches and build
+    // output are filtered out here and left alone
+    let wanted: std::collections::HashSet<_> = wanted.into_iter().map(|(rel, _)| rel).collect();
+    for (rel, ft) in walk_filtered(dst)? {
+        if wanted.contains(&rel) {
+            continue;
+        }
+        let path = dst.join(&rel);
+        let removed = if ft.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+        // Already gone with a removed parent
+        if removed.is_ok() {
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// Copy `src` to `dst` per `mode` with no filtering (for build output,
+/// which `copy_tree` skips).
+pub fn clone_dir(src: &Path, dst: &Path, mode: CopyMode) -> std::io::Result<()> {
+    fn walk(src: &Path, dst: &Path, placer: &mut Placer) -> std::io::Result<()> {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let ft = entry.file_type()?;
+            let dst_path = dst.join(entry.file_name());
+            if ft.is_dir() {
+                walk(&entry.path(), &dst_path, placer)?;
+            } else if ft.is_file() {
+                placer.place(&entry.path(), &dst_path)?;
+            }
+        }
+        Ok(())
+    }
+    walk(src, dst, &mut Placer::new(mode))
+}
+
 /// Find the project root by walking up from source_file looking for markers.
+/// A crate in a Cargo workspace belongs to the workspace root, since it
+/// can't build without the workspace manifest and its sibling crates; a
+/// Maven or Gradle module likewise belongs to its root build.
 pub fn find_project_root(source_file: &Path) -> PathBuf {
     let markers = &[
         "pyproject.toml",
@@ -69,12 +295,23 @@ pub fn find_project_root(source_file: &Path) -> PathBuf {
         "package.json",
         "Cargo.toml",
         "go.mod",
+        "pom.xml",
+        "build.gradle",
+        "build.gradle.kts",
+        "settings.gradle",
+        "settings.gradle.kts",
         ".git",
     ];
     let mut dir = source_file.parent().unwrap_or(source_file);
     loop {
         for marker in markers {
             if dir.join(marker).exists() {
+                if *marker == "Cargo.toml" {
+                    return find_cargo_workspace(dir).unwrap_or_else(|| dir.to_path_buf());
+                }
+                if JVM_BUILD_FILES.contains(marker) {
+                    return find_jvm_build_root(dir);
+                }
                 return dir.to_path_buf();
             }
         }
@@ -89,6 +326,49 @@ pub fn find_project_root(source_file: &Path) -> PathBuf {
         .to_path_buf()
 }
 
+/// The nearest package directory above `file`: one with a `package.json`,
+/// a `pyproject.toml`, a Maven or Gradle build, or a `Cargo.toml` with a
+/// `[package]` (not a virtual workspace manifest). In a monorepo this is
+/// where the package's test command has to run.
+pub fn find_package_dir(file: &Path) -> Option<PathBuf> {
+    let is_package = |dir: &Path| {
+        dir.join("package.json").is_file()
+            || dir.join("pyproject.toml").is_file()
+            || ["pom.xml", "build.gradle", "build.gradle.kts"].iter().any(|build| dir.join(build).is_file())
+            || has_cargo_table(dir, "package")
+    };
+    file.ancestors().skip(1).find(|dir| is_package(dir)).map(Path::to_path_buf)
+}
+
+/// The root of the Cargo workspace `dir` is in: `dir` or the nearest
+/// directory above it whose `Cargo.toml` has a `[workspace]`.
+pub fn find_cargo_workspace(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().find(|d| has_cargo_table(d, "workspace")).map(Path::to_path_buf)
+}
+
+const JVM_BUILD_FILES: &[&str] = &["pom.xml", "build.gradle", "build.gradle.kts", "settings.gradle", "settings.gradle.kts"];
+
+/// The root build of the Maven or Gradle module in `dir`: the nearest
+/// directory with a `settings.gradle(.kts)`, else the top of the unbroken
+/// run of `pom.xml` directories above `dir`.
+fn find_jvm_build_root(dir: &Path) -> PathBuf {
+    if !dir.join("pom.xml").is_file() {
+        let settings = |d: &Path| d.join("settings.gradle").is_file() || d.join("settings.gradle.kts").is_file();
+        return dir.ancestors().find(|d| settings(d)).unwrap_or(dir).to_path_buf();
+    }
+    dir.ancestors()
+        .take_while(|d| d.join("pom.xml").is_file())
+        .last()
+        .unwrap_or(dir)
+        .to_path_buf()
+}
+
+/// Whether `dir` has a `Cargo.toml` with a `[table]` header.
+fn has_cargo_table(dir: &Path, table: &str) -> bool {
+    let header = format!("[{}]", table);
+    fs::read_to_string(dir.join("Cargo.toml")).is_ok_and(|manifest| manifest.lines().any(|line| line.trim() == header))
+}
+
 /// Copy the project tree to a temp directory, returning paths mapped into the copy.
 pub fn copy_tree(
     project_root: &Path,
@@ -96,7 +376,19 @@ pub fn copy_tree(
     test_file: &Path,
     dest_root: &Path,
 ) -> std::io::Result<CopyResult> {
-    copy_dir_filtered(project_root, dest_root)?;
+    copy_tree_with(project_root, source_file, test_file, dest_root, CopyMode::Copy)
+}
+
+/// `copy_tree` placing files per `mode`. The source and test files are
+/// always real copies.
+pub fn copy_tree_with(
+    project_root: &Path,
+    source_file: &Path,
+    test_file: &Path,
+    dest_root: &Path,
+    mode: CopyMode,
+) -> std::io::Result<CopyResult> {
+    copy_dir_filtered(project_root, dest_root, &mut Placer::new(mode))?;
 
     let rel_source = source_file
         .strip_prefix(project_root)
@@ -105,11 +397,35 @@ pub fn copy_tree(
         .strip_prefix(project_root)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
 
-    Ok(CopyResult {
+    let result = CopyResult {
         root: dest_root.to_path_buf(),
         source_file: dest_root.join(rel_source),
         test_file: dest_root.join(rel_test),
-    })
+    };
+    // Also copied when an ignore file happens to exclude them. A source that
+    // isn't on disk (an unsaved buffer) is written by the caller.
+    for (src, dst) in [(source_file, &result.source_file), (test_file, &result.test_file)] {
+        if (mode != CopyMode::Copy || !dst.exists()) && src.exists() {
+            if let Some(parent) = dst.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            make_private(src, dst)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Update a copy made by `copy_tree_with` in place instead of copying
+/// again. The source and test files are always rewritten: mutant runs touch
+/// them, so their timestamps can't be trusted. Returns the number of other
+/// files copied or removed.
+pub fn sync_tree(project_root: &Path, copy: &CopyResult, source_file: &Path, test_file: &Path, mode: CopyMode) -> std::io::Result<usize> {
+    let changed = sync_dir_filtered(project_root, &copy.root, &mut Placer::new(mode))?;
+    if source_file.exists() {
+        make_private(source_file, &copy.source_file)?;
+    }
+    make_private(test_file, &copy.test_file)?;
+    Ok(changed)
 }
 
 #[cfg(test)]
@@ -168,6 +484,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sync_tree_copies_changes_and_removes_deleted_files() {
+        let src_dir = TempDir::new().unwrap();
+        let src = src_dir.path();
+        fs::write(src.join("app.py"), "x = 1").unwrap();
+        fs::write(src.join("lib.py"), "z = 1").unwrap();
+        fs::write(src.join("old.py"), "pass").unwrap();
+        fs::write(src.join("test.py"), "pass").unwrap();
+        let dst_dir = TempDir::new().unwrap();
+        let dst = dst_dir.path();
+        let copy = copy_tree(src, &src.join("app.py"), &src.join("test.py"), dst).unwrap();
+        fs::create_dir(dst.join("__pycache__")).unwrap();
+        // A mutant run leaves the copy's source newer than the original
+        fs::write(&copy.source_file, "x = 2").unwrap();
+
+        fs::write(src.join("lib.py"), "z = 12").unwrap();
+        fs::remove_file(src.join("old.py")).unwrap();
+        fs::write(src.join("new.py"), "y = 2").unwrap();
+        let changed = sync_tree(src, &copy, &src.join("app.py"), &src.join("test.py"), CopyMode::Copy).unwrap();
+
+        assert_eq!(changed, 3);
+        assert_eq!(fs::read_to_string(dst.join("lib.py")).unwrap(), "z = 12");
+        assert_eq!(fs::read_to_string(&copy.source_file).unwrap(), "x = 1");
+        assert!(dst.join("new.py").exists());
+        assert!(!dst.join("old.py").exists());
+        assert!(dst.join("__pycache__").exists(), "skipped dirs in the copy are kept");
+        assert_eq!(sync_tree(src, &copy, &src.join("app.py"), &src.join("test.py"), CopyMode::Copy).unwrap(), 0);
+    }
+
+    #[test]
+    fn copy_tree_honors_gitignore_and_mutatorignore() {
+        let src_dir = TempDir::new().unwrap();
+        let src = src_dir.path();
+        fs::write(src.join(".gitignore"), "generated/\n*.log\nfixture.json\n").unwrap();
+        fs::write(src.join(".mutatorignore"), "big_data/\n!fixture.json\n").unwrap();
+        for dir in ["generated", "big_data", "src"] {
+            fs::create_dir(src.join(dir)).unwrap();
+            fs::write(src.join(dir).join("file.py"), "x = 1").unwrap();
+        }
+        fs::write(src.join("run.log"), "log").unwrap();
+        fs::write(src.join("fixture.json"), "{}").unwrap();
+        fs::write(src.join("test.py"), "pass").unwrap();
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = dst_dir.path();
+        copy_tree(src, &src.join("generated").join("file.py"), &src.join("test.py"), dst).unwrap();
+
+        assert!(dst.join("src").join("file.py").exists());
+        assert!(dst.join(".gitignore").exists());
+        assert!(!dst.join("big_data").exists());
+        assert!(!dst.join("run.log").exists());
+        assert!(dst.join("fixture.json").exists(), "! in .mutatorignore re-includes");
+        assert!(dst.join("generated").join("file.py").exists(), "the source is copied even if ignored");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn link_mode_shares_other_files_but_never_source_or_test() {
+        use std::os::unix::fs::MetadataExt;
+
+        let src_dir = TempDir::new().unwrap();
+        let src = src_dir.path();
+        fs::write(src.join("app.py"), "x = 1").unwrap();
+        fs::write(src.join("lib.py"), "z = 1").unwrap();
+        fs::write(src.join("test.py"), "pass").unwrap();
+        let dst_dir = TempDir::new().unwrap();
+        let copy = copy_tree_with(src, &src.join("app.py"), &src.join("test.py"), dst_dir.path(), CopyMode::Link).unwrap();
+
+        let ino = |p: &Path| fs::metadata(p).unwrap().ino();
+        assert_eq!(fs::read_to_string(dst_dir.path().join("lib.py")).unwrap(), "z = 1");
+        assert_ne!(ino(&copy.source_file), ino(&src.join("app.py")));
+        assert_ne!(ino(&copy.test_file), ino(&src.join("test.py")));
+
+        fs::write(&copy.source_file, "x = 2").unwrap();
+        sync_tree(src, &copy, &src.join("app.py"), &src.join("test.py"), CopyMode::Link).unwrap();
+        fs::write(&copy.source_file, "x = 3").unwrap();
+        assert_eq!(fs::read_to_string(src.join("app.py")).unwrap(), "x = 1");
+    }
+
+    #[test]
+    fn copy_mode_parses_cli_names() {
+        assert_eq!("copy".parse::<CopyMode>(), Ok(CopyMode::Copy));
+        assert_eq!("reflink".parse::<CopyMode>(), Ok(CopyMode::Reflink));
+        assert_eq!("link".parse::<CopyMode>(), Ok(CopyMode::Link));
+        assert!("hardlink".parse::<CopyMode>().is_err());
+    }
+
     #[test]
     fn find_project_root_finds_pyproject() {
         let dir = TempDir::new().unwrap();
@@ -255,6 +658,46 @@ mod tests {
         assert_eq!(found, root);
     }
 
+    #[test]
+    fn find_package_dir_skips_workspace_manifests() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let member = root.join("crates").join("core");
+        fs::create_dir_all(member.join("src")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+        fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+        fs::create_dir_all(root.join("web").join("src")).unwrap();
+        fs::write(root.join("web").join("package.json"), "{}").unwrap();
+
+        assert_eq!(find_package_dir(&member.join("src").join("lib.rs")), Some(member.clone()));
+        assert_eq!(find_package_dir(&root.join("web").join("src").join("app.ts")), Some(root.join("web")));
+        assert_eq!(find_package_dir(&root.join("build.rs")), None);
+        // The member is copied along with the rest of the workspace
+        assert_eq!(find_project_root(&member.join("src").join("lib.rs")), root);
+        assert_eq!(find_cargo_workspace(&member), Some(root.to_path_buf()));
+    }
+
+    #[test]
+    fn jvm_modules_belong_to_their_root_build() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let maven = root.join("shop");
+        let module = maven.join("cart");
+        fs::create_dir_all(module.join("src/main/java")).unwrap();
+        fs::write(maven.join("pom.xml"), "<project/>").unwrap();
+        fs::write(module.join("pom.xml"), "<project/>").unwrap();
+        let source = module.join("src/main/java/Cart.java");
+        assert_eq!(find_project_root(&source), maven);
+        assert_eq!(find_package_dir(&source), Some(module.clone()));
+
+        let gradle = root.join("app");
+        let sub = gradle.join("core");
+        fs::create_dir_all(sub.join("src/main/java")).unwrap();
+        fs::write(gradle.join("settings.gradle.kts"), "").unwrap();
+        fs::write(sub.join("build.gradle.kts"), "").unwrap();
+        assert_eq!(find_project_root(&sub.join("src/main/java/Core.java")), gradle);
+    }
+
     #[test]
     fn find_project_root_finds_git_dir() {
         let dir = TempDir::new().unwrap();
@@ -267,6 +710,28 @@ mod tests {
         assert_eq!(found, root);
     }
 
+    #[test]
+    fn tree_fingerprint_tracks_files_and_ignores_skipped() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("app.py"), "x = 1").unwrap();
+        fs::write(root.join("lib.py"), "y = 2").unwrap();
+        let app = root.join("app.py");
+        let fingerprint = || tree_fingerprint(root, &[app.as_path()]).unwrap();
+        let original = fingerprint();
+
+        fs::create_dir(root.join("__pycache__")).unwrap();
+        fs::write(root.join("__pycache__").join("app.pyc"), "bytes").unwrap();
+        assert_eq!(fingerprint(), original);
+
+        // Content-hashed: rewriting the same bytes keeps the fingerprint
+        fs::write(&app, "x = 1").unwrap();
+        assert_eq!(fingerprint(), original);
+
+        fs::write(root.join("lib.py"), "y = 22").unwrap();
+        assert_ne!(fingerprint(), original);
+    }
+
     #[test]
     fn should_skip_filters_correctly() {
         assert!(should_skip(".git"));
@@ -0,0 +1,136 @@
+//! Line coverage reports, used to skip mutants no test can reach.
+//!
+//! Supported formats, detected from content:
+//! - coverage.py XML (`coverage xml`, Cobertura)
+//! - coverage.py JSON (`coverage json`)
+//! - lcov (`cargo llvm-cov --lcov`, jest/istanbul `lcov.info`, `coverage lcov`)
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Hit counts per line, per file in the report.
+#[derive(Debug, Default)]
+pub struct CoverageReport {
+    files: BTreeMap<PathBuf, BTreeMap<usize, u64>>,
+}
+
+impl CoverageReport {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read coverage file {}: {}", path.display(), e))?;
+        Self::parse(&content)
+            .map_err(|e| format!("Failed to parse coverage file {}: {}", path.display(), e))
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let trimmed = content.trim_start();
+        let report = if trimmed.starts_with('<') {
+            parse_cobertura(content)?
+        } else if trimmed.starts_with('{') {
+            parse_coverage_json(content)?
+        } else {
+            parse_lcov(content)?
+        };
+        if report.files.is_empty() {
+            return Err("no file entries found".to_string());
+        }
+        Ok(report)
+    }
+
+    /// Lines the report measured for `source`, or `None` if the report has
+    /// no entry for it. Report paths are matched as a component-wise suffix
+    /// of `source`, so relative paths in the report work.
+    pub fn lines_for(&self, source: &Path) -> Option<&BTreeMap<usize, u64>> {
+        self.files
+            .iter()
+            .filter(|(path, _)| source.ends_with(path))
+            .max_by_key(|(path, _)| path.components().count())
+            .map(|(_, lines)| lines)
+    }
+
+    /// Lines of `source` the report measured with zero hits. Lines the
+    /// report doesn't mention (blank lines, continuation lines of a
+    /// multi-line statement) are not considered uncovered.
+    pub fn uncovered_lines(&self, source: &Path) -> Option<BTreeSet<usize>> {
+        self.lines_for(source).map(|lines| {
+            lines
+                .iter()
+                .filter(|(_, hits)| **hits == 0)
+                .map(|(line, _)| *line)
+                .collect()
+        })
+    }
+
+    fn record(&mut self, file: &str, line: usize, hits: u64) {
+        let entry = self
+            .files
+            .entry(PathBuf::from(file.trim_start_matches("./")))
+            .or_default()
+            .entry(line)
+            .or_insert(0);
+        // The same file can appear in several records (one per test binary)
+        *entry = (*entry).max(hits);
+    }
+}
+
+fn parse_cobertura(content: &str) -> Result<CoverageReport, String> {
+    let doc = roxmltree::Document::parse(content).map_err(|e| e.to_string())?;
+    let mut report = CoverageReport::default();
+    for class in doc.descendants().filter(|n| n.has_tag_name("class")) {
+        let Some(filename) = class.attribute("filename") else {
+            continue;
+        };
+        for line in class.descendants().filter(|n| n.has_tag_name("line")) {
+            let number = line.attribute("number").and_then(|v| v.parse().ok());
+            let hits = line.attribute("hits").and_then(|v| v.parse().ok());
+            if let (Some(number), Some(hits)) = (number, hits) {
+                report.record(filename, number, hits);
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn parse_coverage_json(content: &str) -> Result<CoverageReport, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let files = value
+        .get("files")
+        .and_then(|f| f.as_object())
+        .ok_or("expected a coverage.py JSON report with a \"files\" object")?;
+
+    let mut report = CoverageReport::default();
+    for (filename, data) in files {
+        for (key, hits) in [("executed_lines", 1), ("missing_lines", 0)] {
+            let lines = data.get(key).and_then(|l| l.as_array());
+            for line in lines.into_iter().flatten().filter_map(|l| l.as_u64()) {
+                report.record(filename, line as usize, hits);
+            }
+        }
+    }
+    Ok(report)
+}
+
+fn parse_lcov(content: &str) -> Result<CoverageReport, String> {
+    let mut report = CoverageReport::default();
+    let mut current: Option<&str> = None;
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(file) = line.strip_prefix("SF:") {
+            current = Some(file);
+        } else if line == "end_of_record" {
+            current = None;
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            let Some(file) = current else {
+                return Err("DA record outside of an SF section".to_string());
+            };
+            let mut parts = data.split(',');
+            let number = parts.next().and_then(|v| v.parse().ok());
+            let hits = parts.next().and_then(|v| v.parse().ok());
+            match (number, hits) {
+                (Some(number), Some(hits)) => report.record(file, number, hits),
+                _ => return Err(format!("malformed line record: {}", line)),
+            }
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,123 @@
+//! User-defined mutation operators for `--operators FILE`.
+//!
+//! A JSON list of operators, applied alongside the built-ins wherever
+//! discovery walks (the same functions, test-code and logging rules):
+//!
+//! ```json
+//! [
+//!   { "name": "cents", "languages": ["python"], "kinds": ["integer"], "match": "^100$", "replacement": "10" },
+//!   { "name": "round_down", "match": "^round$", "replacement": "floor" }
+//! ]
+//! ```
+//!
+//! - `name`: what the mutants are reported as, like a built-in's `arith`.
+//! - `kinds`: tree-sitter node kinds to try (`integer`, `call`, ...).
+//!   Default: every leaf token (identifiers, literals, operators).
+//! - `match`: regex the node's text must contain; its first match is
+//!   replaced. Default: the whole text.
+//! - `replacement`: what the match becomes; `$1` and `${name}` insert
+//!   capture groups.
+//! - `languages`: python, rust, js, ts, tsx. Default: all of them.
+
+#[cfg(feature = "runtime")]
+use std::path::Path;
+
+use regex::Regex;
+use serde::Deserialize;
+use tree_sitter::Node;
+
+use crate::mutants::Mutation;
+use crate::mutation_parser::node_text;
+use crate::Language;
+
+#[derive(Debug, Clone)]
+pub struct CustomOperator {
+    pub name: String,
+    pub languages: Vec<Language>,
+    pub kinds: Vec<String>,
+    pub pattern: Regex,
+    pub replacement: String,
+}
+
+/// An operator as written in the file, before its regex is compiled.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OperatorDef {
+    name: String,
+    #[serde(default)]
+    languages: Vec<Language>,
+    #[serde(default)]
+    kinds: Vec<String>,
+    #[serde(rename = "match", default)]
+    pattern: Option<String>,
+    replacement: String,
+}
+
+#[cfg(feature = "runtime")]
+pub fn load(path: &Path) -> Result<Vec<CustomOperator>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read operators file {}: {}", path.display(), e))?;
+    parse(&content).map_err(|e| format!("Invalid operators file {}: {}", path.display(), e))
+}
+
+pub fn parse(content: &str) -> Result<Vec<CustomOperator>, String> {
+    let defs: Vec<OperatorDef> = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    defs.into_iter().map(CustomOperator::compile).collect()
+}
+
+impl CustomOperator {
+    fn compile(def: OperatorDef) -> Result<Self, String> {
+        if def.name.trim().is_empty() {
+            return Err("every operator needs a name".to_string());
+        }
+        if def.kinds.is_empty() && def.pattern.is_none() {
+            return Err(format!("operator '{}' needs kinds, match or both", def.name));
+        }
+        let pattern = Regex::new(def.pattern.as_deref().unwrap_or(r"(?s)\A.*\z"))
+            .map_err(|e| format!("operator '{}' has an invalid match: {}", def.name, e))?;
+        Ok(CustomOperator {
+            name: def.name,
+            languages: def.languages,
+            kinds: def.kinds,
+            pattern,
+            replacement: def.replacement,
+        })
+    }
+
+    pub fn applies_to(&self, language: Language) -> bool {
+        self.languages.is_empty() || self.languages.contains(&language)
+    }
+
+    /// The mutant this operator makes of `node`, if its kind and text match
+    /// and the replacement changes anything.
+    pub fn mutate(&self, node: Node, source: &str) -> Option<Mutation> {
+        let kind_matches = if self.kinds.is_empty() {
+            node.child_count() == 0
+        } else {
+            self.kinds.iter().any(|k| k == node.kind())
+        };
+        if !kind_matches {
+            return None;
+        }
+        let text = node_text(node, source);
+        if !self.pattern.is_match(text) {
+            return None;
+        }
+        let replacement = self.pattern.replacen(text, 1, self.replacement.as_str());
+        if replacement == text {
+            return None;
+        }
+        Some(Mutation {
+            line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: self.name.clone(),
+            original: text.to_string(),
+            replacement: replacement.into_owned(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        })
+    }
+}
@@ -0,0 +1,421 @@
+//! `mutator daemon`: serve run/show/status over a Unix socket.
+//!
+//! Requests and responses are newline-delimited JSON-RPC 2.0. Engines are
+//! kept between requests, keyed by their config, so a repeated `run` syncs
+//! the existing isolated copy (`MutatorEngine::reload`) instead of copying
+//! the tree again, and reuses the discovered mutations while the source is
+//! unchanged. Connections are served one at a time.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::copy_tree::CopyMode;
+use crate::engine::{self, EngineConfig, EngineError, MutatorEngine};
+use crate::mutants::Mutation;
+use crate::runner::ResourceLimits;
+use crate::test_adapters::{self, Runner, TestArgStyle};
+use crate::{history, schema, signals, state, test_discovery, Language};
+
+/// Engines kept warm at once; the least recently added is dropped first.
+const MAX_ENGINES: usize = 8;
+
+pub fn socket_path() -> PathBuf {
+    socket_path_in(&std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
+pub fn socket_path_in(dir: &Path) -> PathBuf {
+    dir.join(".mutator").join("daemon.sock")
+}
+
+#[derive(Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// `run` parameters; names and defaults match the `run` flags.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RunParams {
+    file: PathBuf,
+    /// Unsaved buffer to mutate in place of `file`'s contents.
+    #[serde(default)]
+    source_text: Option<String>,
+    #[serde(default)]
+    language: Option<Language>,
+    #[serde(default)]
+    test: Option<PathBuf>,
+    #[serde(default)]
+    function: Option<String>,
+    #[serde(default)]
+    test_cmd: Option<String>,
+    #[serde(default)]
+    timeout_mult: Option<f64>,
+    #[serde(default)]
+    max_timeout: Option<u64>,
+    #[serde(default)]
+    session: Option<String>,
+    #[serde(default)]
+    in_place: bool,
+    #[serde(default)]
+    cwd: Option<PathBuf>,
+    #[serde(default)]
+    coverage: Option<PathBuf>,
+    #[serde(default)]
+    include_tests: bool,
+    #[serde(default)]
+    include_toplevel: bool,
+    #[serde(default)]
+    skip_decorated: Vec<String>,
+    #[serde(default)]
+    test_map: Option<PathBuf>,
+    #[serde(default)]
+    test_filter: Option<String>,
+    #[serde(default)]
+    copy_mode: CopyMode,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    env_file: Option<PathBuf>,
+    #[serde(default)]
+    max_memory_mb: Option<u64>,
+    #[serde(default)]
+    cpu_limit: Option<u64>,
+    #[serde(default)]
+    full_results: bool,
+    #[serde(default)]
+    enable: Vec<String>,
+    #[serde(default)]
+    operators: Option<PathBuf>,
+    #[serde(default)]
+    runner: Option<Runner>,
+    #[serde(default)]
+    test_arg_style: TestArgStyle,
+    #[serde(default)]
+    baseline_retries: u32,
+    #[serde(default)]
+    verify_survivors: bool,
+    #[serde(default)]
+    timeout_retry: Option<f64>,
+    #[serde(default)]
+    no_early_exit: bool,
+    #[serde(default)]
+    precheck: bool,
+    #[serde(default)]
+    max_sessions: Option<usize>,
+    #[serde(default)]
+    context_lines: Option<usize>,
+    #[serde(default)]
+    no_baseline_cache: bool,
+    #[serde(default)]
+    shuffle: Option<u64>,
+    #[serde(default)]
+    prioritize: bool,
+}
+
+impl RunParams {
+    fn into_config(self) -> Result<EngineConfig, EngineError> {
+        let test = match self.test {
+            Some(test) => test,
+            None => test_discovery::find_test_file(&self.file)?,
+        };
+        if self.source_text.is_some() && self.in_place {
+            return Err(EngineError::SourceTextInPlace);
+        }
+        let mut config = EngineConfig::new(self.file, test);
+        config.source_text = self.source_text;
+        config.language = self.language;
+        config.function = self.function;
+        config.test_cmd = self
+            .test_cmd
+            .unwrap_or_else(|| test_adapters::detect_test_cmd(&config.source_file, config.language));
+        if let Some(mult) = self.timeout_mult {
+            config.timeout_mult = mult;
+        }
+        config.max_timeout_secs = self.max_timeout;
+        config.session = self.session;
+        config.in_place = self.in_place;
+        config.cwd = self.cwd;
+        config.coverage = self.coverage;
+        config.include_tests = self.include_tests;
+        config.include_toplevel = self.include_toplevel;
+        config.skip_decorated = self.skip_decorated;
+        config.test_map = self.test_map;
+        config.test_filter = self.test_filter;
+        config.copy_mode = self.copy_mode;
+        config.env = self.env.into_iter().collect();
+        config.env_file = self.env_file;
+        config.limits = ResourceLimits { max_memory_mb: self.max_memory_mb, cpu_seconds: self.cpu_limit };
+        config.enable = self.enable;
+        config.operators = self.operators;
+        config.runner = self.runner;
+        config.test_arg_style = self.test_arg_style;
+        config.baseline_retries = self.baseline_retries;
+        config.verify_survivors = self.verify_survivors;
+        if let Some(mult) = self.timeout_retry {
+            config.timeout_retry = mult;
+        }
+        config.early_exit = !self.no_early_exit;
+        config.precheck = self.precheck;
+        config.max_sessions = self.max_sessions;
+        if let Some(lines) = self.context_lines {
+            config.context_lines = lines;
+        }
+        config.baseline_cache = !self.no_baseline_cache;
+        config.shuffle = self.shuffle;
+        config.prioritize = self.prioritize;
+        Ok(config)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShowParams {
+    #[serde(rename = "ref", default)]
+    mutant_ref: Option<String>,
+    /// With `line` instead of `ref`, like `show --line [FILE:]LINE`.
+    #[serde(default)]
+    file: Option<String>,
+    #[serde(default)]
+    line: Option<usize>,
+    #[serde(default)]
+    near: Option<usize>,
+    #[serde(default)]
+    session: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StatusParams {
+    #[serde(default)]
+    session: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    const PARSE: i64 = -32700;
+    const METHOD_NOT_FOUND: i64 = -32601;
+    const INVALID_PARAMS: i64 = -32602;
+    /// Engine and lookup failures; `data.exit_code` is what `mutator run`
+    /// would have exited with.
+    const FAILED: i64 = -32000;
+
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError { code, message: message.into(), data: None }
+    }
+
+    fn failed(message: impl Into<String>, exit_code: i32) -> Self {
+        RpcError { data: Some(json!({ "exit_code": exit_code })), ..Self::new(Self::FAILED, message) }
+    }
+}
+
+impl From<EngineError> for RpcError {
+    fn from(e: EngineError) -> Self {
+        RpcError::failed(e.to_string(), e.exit_code())
+    }
+}
+
+struct Warm {
+    engine: MutatorEngine,
+    /// Source the mutations were discovered from.
+    source: String,
+    mutations: Vec<Mutation>,
+}
+
+#[derive(Default)]
+pub struct Daemon {
+    engines: Vec<Warm>,
+}
+
+impl Daemon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle one request line. Returns the response line and whether the
+    /// request asked the daemon to shut down.
+    pub fn handle_line(&mut self, line: &str) -> (String, bool) {
+        let (id, outcome, shutdown) = match serde_json::from_str::<Request>(line) {
+            Err(e) => (Value::Null, Err(RpcError::new(RpcError::PARSE, e.to_string())), false),
+            Ok(req) => {
+                let shutdown = req.method == "shutdown";
+                let outcome = self.call(&req.method, req.params);
+                (req.id, outcome, shutdown)
+            }
+        };
+        let response = match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => {
+                let mut error = json!({ "code": e.code, "message": e.message });
+                if let Some(data) = e.data {
+                    error["data"] = data;
+                }
+                json!({ "jsonrpc": "2.0", "id": id, "error": error })
+            }
+        };
+        (response.to_string(), shutdown)
+    }
+
+    /// Handle one `run`, `show`, `status` or `shutdown` call. `params` as in
+    /// a request; the result is the response's `result`.
+    pub fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "run" => self.run(params_as(params)?),
+            "show" => show(params_as(params)?),
+            "status" => {
+                let params: StatusParams = if params.is_null() { StatusParams::default() } else { params_as(params)? };
+                status(params)
+            }
+            "shutdown" => Ok(Value::Null),
+            _ => Err(RpcError::new(RpcError::METHOD_NOT_FOUND, format!("Unknown method '{}'", method))),
+        }
+    }
+
+    fn run(&mut self, params: RunParams) -> Result<Value, RpcError> {
+        let full_results = params.full_results;
+        let config = params.into_config()?;
+        // A new buffer for the same file reuses its engine
+        let same_run = |c: &EngineConfig| EngineConfig { source_text: None, ..c.clone() } == EngineConfig { source_text: None, ..config.clone() };
+        let index = match self.engines.iter().position(|w| same_run(w.engine.config())) {
+            Some(i) => {
+                self.engines[i].engine.set_source_text(config.source_text.clone());
+                self.engines[i].engine.reload()?;
+                i
+            }
+            None => {
+                let engine = MutatorEngine::new(config.clone())?;
+                if self.engines.len() == MAX_ENGINES {
+                    self.engines.remove(0);
+                }
+                self.engines.push(Warm { engine, source: String::new(), mutations: Vec::new() });
+                self.engines.len() - 1
+            }
+        };
+        let warm = &mut self.engines[index];
+        if warm.source != warm.engine.source() || warm.mutations.is_empty() {
+            warm.mutations = warm.engine.discover();
+            warm.source = warm.engine.source().to_string();
+        }
+
+        let results = if warm.mutations.is_empty() {
+            Vec::new()
+        } else {
+            signals::track_run(&config.source_file, config.session.as_deref());
+            if config.in_place {
+                signals::track_in_place(warm.engine.source_path());
+            }
+            // Not `signals::cleanup` on failure: that would delete the warm copy
+            let run = warm.engine.run_all_with(&warm.mutations, signals::record);
+            signals::untrack();
+            run?
+        };
+
+        let mut result = engine::summarize(&results, &config.source_file);
+        if full_results {
+            result.all_mutants = engine::all_mutants(&results, &config.source_file);
+        }
+        state::save_last_run(&result, config.session.as_deref());
+        history::record(
+            &result,
+            &config.source_file,
+            config.function.as_deref(),
+            config.session.as_deref(),
+        );
+        Ok(schema::to_value(&result))
+    }
+}
+
+fn params_as<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(RpcError::INVALID_PARAMS, e.to_string()))
+}
+
+fn show(params: ShowParams) -> Result<Value, RpcError> {
+    let last_run = load(params.session.as_deref())?;
+    match (params.mutant_ref, params.line) {
+        (Some(mutant_ref), None) => match last_run.find_ref(&mutant_ref) {
+            Some(m) => Ok(schema::to_value(m)),
+            None => Err(RpcError::failed(format!("Mutant {} not found", mutant_ref), 2)),
+        },
+        (None, Some(line)) => {
+            if last_run.all_mutants.is_empty() {
+                return Err(RpcError::failed("The last run didn't record every mutant. Re-run with full_results.", 2));
+            }
+            let near = params.near.unwrap_or(2);
+            Ok(schema::to_value(&last_run.mutants_near(params.file.as_deref(), line, near)))
+        }
+        _ => Err(RpcError::new(RpcError::INVALID_PARAMS, "Pass exactly one of ref and line")),
+    }
+}
+
+fn status(params: StatusParams) -> Result<Value, RpcError> {
+    let last_run = load(params.session.as_deref())?;
+    Ok(schema::to_value(&last_run))
+}
+
+fn load(session: Option<&str>) -> Result<state::RunResult, RpcError> {
+    state::load_last_run(session).ok_or_else(|| RpcError::failed("No previous run found", 2))
+}
+
+/// Listen on `path` until a `shutdown` request. A socket file left by a
+/// daemon that is no longer running is replaced.
+pub fn serve(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(format!("A daemon is already listening on {}", path.display()));
+        }
+        std::fs::remove_file(path).map_err(|e| format!("Cannot remove stale socket {}: {}", path.display(), e))?;
+    }
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let listener = UnixListener::bind(path).map_err(|e| format!("Cannot listen on {}: {}", path.display(), e))?;
+
+    let mut daemon = Daemon::new();
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if serve_connection(&mut daemon, stream) {
+            break;
+        }
+    }
+    let _ = std::fs::remove_file(path);
+    Ok(())
+}
+
+/// Returns true on `shutdown`.
+fn serve_connection(daemon: &mut Daemon, stream: UnixStream) -> bool {
+    let Ok(mut writer) = stream.try_clone() else {
+        return false;
+    };
+    for line in BufReader::new(stream).lines() {
+        let Ok(line) = line else {
+            return false;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (response, shutdown) = daemon.handle_line(&line);
+        if writeln!(writer, "{}", response).is_err() {
+            return shutdown;
+        }
+        if shutdown {
+            return true;
+        }
+    }
+    false
+}
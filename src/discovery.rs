@@ -0,0 +1,79 @@
+//! Mutation discovery for source text: the parsers, operators and dedupe,
+//! with no filesystem access or processes. This is all a build without the
+//! `runtime` feature has, e.g. for wasm32 sandboxes that preview mutations;
+//! `MutatorEngine` and `mutator list` call it with the source they read.
+
+use std::path::Path;
+
+use crate::custom_operators::CustomOperator;
+use crate::mutants::{self, Mutation};
+use crate::mutation_parser::ParseOptions;
+use crate::{equivalence, parser_java, parser_js, Language};
+
+/// What discovery covers besides the functions' own code.
+#[derive(Debug, Clone, Copy)]
+pub struct DiscoverOptions<'a> {
+    pub include_tests: bool,
+    /// Module-level code; only when no function is named.
+    pub include_toplevel: bool,
+    /// Opt-in operators (`operators::OPT_IN`); other names are ignored.
+    pub enable: &'a [String],
+    /// Custom operators, already narrowed to the language.
+    pub operators: &'a [CustomOperator],
+    pub skip_decorated: &'a [String],
+    pub context_lines: usize,
+}
+
+impl Default for DiscoverOptions<'_> {
+    fn default() -> Self {
+        DiscoverOptions {
+            include_tests: false,
+            include_toplevel: false,
+            enable: &[],
+            operators: &[],
+            skip_decorated: &[],
+            context_lines: mutants::DEFAULT_CONTEXT_LINES,
+        }
+    }
+}
+
+/// The mutations a run of `source` would test, deduplicated and in
+/// canonical order. `path` is only looked at by name, to tell JS/TS and
+/// Java test files apart; the file needn't exist.
+pub fn discover(
+    lang: &Language,
+    source: &str,
+    function: Option<&str>,
+    options: &DiscoverOptions,
+    path: &Path,
+) -> Vec<Mutation> {
+    let is_test_file = match lang {
+        Language::JavaScript | Language::TypeScript | Language::Tsx => parser_js::is_test_file(path),
+        Language::Java => parser_java::is_test_file(path),
+        Language::Python | Language::Rust => false,
+    };
+    // A whole test file is test code unless a function is named
+    if is_test_file && function.is_none() && !options.include_tests {
+        return Vec::new();
+    }
+    let parser = lang.parser();
+    let parse_options = ParseOptions {
+        include_tests: options.include_tests,
+        string_mut: options.enable.iter().any(|op| op == "string_mut"),
+        collection_method: options.enable.iter().any(|op| op == "collection_method"),
+        skip_decorated: options.skip_decorated,
+        operators: options.operators,
+    };
+    let mut mutations = parser.discover(source, function, &parse_options);
+    if options.include_toplevel && function.is_none() {
+        mutations.extend(parser.toplevel(source, &parse_options));
+    }
+    // The parsers record the default amount of context
+    if options.context_lines != mutants::DEFAULT_CONTEXT_LINES {
+        mutants::assign_context(&mut mutations, source, options.context_lines);
+    }
+    // Dedupe first, so the operator a parser found first keeps an edit
+    let mut mutations = equivalence::dedupe(mutations);
+    mutants::sort_canonical(&mut mutations);
+    mutations
+}
@@ -0,0 +1,1283 @@
+//! Embeddable mutation testing workflow.
+//!
+//! `MutatorEngine` owns everything the CLI used to do inline: path
+//! resolution, language detection, discovery, baseline, and mutant
+//! execution. The `mutator` binary is a thin wrapper around it.
+
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::baseline_cache::{self, CachedBaseline};
+use crate::copy_tree::CopyMode;
+use crate::coverage::CoverageReport;
+use crate::custom_operators::{self, CustomOperator};
+use crate::discovery::{self, DiscoverOptions};
+use crate::mutants::{FunctionSpan, MutantResult, MutantStatus, Mutation};
+use crate::mutation_parser::ParseOptions;
+use crate::runner::{self, AdaptiveTimeout, BaselineResult, CommandLine, IsolatedContext, ResourceLimits, TestInvocation};
+use crate::precheck::{self, Precheck};
+use crate::sessions::{self, SessionLock};
+use crate::test_adapters::{Runner, TestArgStyle};
+use crate::state::{FunctionScore, MutantOutcome, RunResult, SurvivedMutant};
+use crate::{copy_tree, env_file, equivalence, mutants, operators, priority, safety, test_map, Language};
+
+/// Inputs for a mutation testing run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineConfig {
+    pub source_file: PathBuf,
+    pub test_file: PathBuf,
+    /// Source to mutate instead of what's on disk at `source_file`, such
+    /// as an editor's unsaved buffer. It is written to the isolated copy at
+    /// `source_file`'s path, which needn't exist.
+    pub source_text: Option<String>,
+    /// Language of `source_file`; detected from its extension when unset.
+    pub language: Option<Language>,
+    pub function: Option<String>,
+    pub test_cmd: String,
+    pub timeout_mult: f64,
+    /// Hard cap on the per-mutant timeout, in seconds.
+    pub max_timeout_secs: Option<u64>,
+    pub session: Option<String>,
+    pub in_place: bool,
+    /// Directory the test command runs in. Defaults to the test file's
+    /// package (`copy_tree::find_package_dir`), else the source file's.
+    /// In isolated mode it's the same directory inside the copy.
+    pub cwd: Option<PathBuf>,
+    /// Coverage report; mutants on lines it marks unexecuted are reported
+    /// as `Uncovered` without running tests.
+    pub coverage: Option<PathBuf>,
+    /// Also mutate test code (Rust `#[test]` fns and `#[cfg(test)]` modules,
+    /// JS/TS test callbacks and test files).
+    pub include_tests: bool,
+    /// Also mutate module-level code: constants, lookup tables, top-level
+    /// conditionals. Only when no `function` is set.
+    pub include_toplevel: bool,
+    /// Python decorators (`cached`, `functools.cache`) whose functions are
+    /// left out unless named by `function`. `@overload` and
+    /// `@abstractmethod` stubs are always left out.
+    pub skip_decorated: Vec<String>,
+    /// JSON map of function name -> test names. With `function` set, only
+    /// the mapped tests run (see `test_map`).
+    pub test_map: Option<PathBuf>,
+    /// Runner test filter (`pytest -k`, `jest -t`, `cargo test NAME`) for
+    /// baseline and mutant runs. Takes precedence over `test_map`.
+    pub test_filter: Option<String>,
+    /// How the project tree is copied in isolated mode.
+    pub copy_mode: CopyMode,
+    /// Extra environment for every test run (`--env`). Overrides `env_file`.
+    pub env: Vec<(String, String)>,
+    /// Dotenv-style file of extra environment (see `env_file`).
+    pub env_file: Option<PathBuf>,
+    /// Memory and CPU limits for every test run.
+    pub limits: ResourceLimits,
+    /// Test runner; detected from `test_cmd` and the project when unset.
+    pub runner: Option<Runner>,
+    /// How the test file is passed to the test command.
+    pub test_arg_style: TestArgStyle,
+    /// Opt-in operators to generate as well (`operators::OPT_IN`).
+    pub enable: Vec<String>,
+    /// JSON file of user-defined operators (see `custom_operators`).
+    pub operators: Option<PathBuf>,
+    /// Extra baseline runs; if any disagree the run aborts with `FlakyTests`.
+    pub baseline_retries: u32,
+    /// Re-run each survivor once and count it as killed if the re-run fails.
+    pub verify_survivors: bool,
+    /// Re-run each timed-out mutant once, after the others, with its
+    /// timeout multiplied by this, and take that result if it finishes.
+    /// 1 or less turns the retry off.
+    pub timeout_retry: f64,
+    /// Stop a mutant's run at the first failure its output shows.
+    pub early_exit: bool,
+    /// Compile or type-check each mutant before its test run and count
+    /// failures as unviable (see `precheck`).
+    pub precheck: bool,
+    /// Refuse to start when this many sessions are already running in the
+    /// project (see `sessions`).
+    pub max_sessions: Option<usize>,
+    /// Source lines of context recorded either side of each mutation.
+    pub context_lines: usize,
+    /// Reuse a cached baseline when nothing it depends on changed (see
+    /// `baseline_cache`).
+    pub baseline_cache: bool,
+    /// Run mutants in an order shuffled with this seed (`--shuffle`).
+    /// Results are still returned in discovery order.
+    pub shuffle: Option<u64>,
+    /// Run recently changed and weakly tested lines first (see
+    /// `priority`). Overrides `shuffle`.
+    pub prioritize: bool,
+}
+
+impl EngineConfig {
+    /// Config with the CLI defaults: pytest, 3x timeout, isolated mode.
+    pub fn new(source_file: impl Into<PathBuf>, test_file: impl Into<PathBuf>) -> Self {
+        EngineConfig {
+            source_file: source_file.into(),
+            test_file: test_file.into(),
+            source_text: None,
+            language: None,
+            function: None,
+            test_cmd: "pytest".to_string(),
+            timeout_mult: 3.0,
+            max_timeout_secs: None,
+            session: None,
+            in_place: false,
+            cwd: None,
+            coverage: None,
+            include_tests: false,
+            include_toplevel: false,
+            skip_decorated: Vec::new(),
+            test_map: None,
+            test_filter: None,
+            copy_mode: CopyMode::Copy,
+            env: Vec::new(),
+            env_file: None,
+            limits: ResourceLimits::default(),
+            runner: None,
+            test_arg_style: TestArgStyle::Auto,
+            enable: Vec::new(),
+            operators: None,
+            baseline_retries: 0,
+            verify_survivors: false,
+            timeout_retry: 2.0,
+            early_exit: true,
+            precheck: false,
+            max_sessions: None,
+            context_lines: mutants::DEFAULT_CONTEXT_LINES,
+            baseline_cache: true,
+            shuffle: None,
+            prioritize: false,
+        }
+    }
+}
+
+/// Commands a run would spawn; see `MutatorEngine::command_plan`.
+#[derive(Debug, Serialize)]
+pub struct CommandPlan {
+    /// Up-front `cargo test --no-run` build, for cargo targets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build: Option<CommandLine>,
+    pub baseline: CommandLine,
+    /// Run once per mutant, with `MUTATOR_MUTANT_ID` set to its ID.
+    pub mutant: CommandLine,
+    /// `--precheck` command run on each mutant first.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precheck: Option<String>,
+    /// Project root copied to a temp dir for the run, unless `in_place`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isolated_from: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum EngineError {
+    /// A backup from an interrupted in-place run was found and restored.
+    RecoveredInterruptedRun,
+    SourceNotFound(PathBuf),
+    TestNotFound(PathBuf),
+    /// `cwd` isn't a directory.
+    CwdNotFound(PathBuf),
+    /// `--test` was omitted and no test file matches the source by convention.
+    NoTestFile(PathBuf),
+    AmbiguousTestFile { source: PathBuf, candidates: Vec<PathBuf> },
+    ReadFailed(PathBuf, std::io::Error),
+    UnsupportedLanguage(PathBuf),
+    FunctionNotFound { name: String, available: Vec<String> },
+    Setup(String),
+    BaselineFailed(String),
+    /// The unmutated tests passed on some baseline runs and failed on others.
+    FlakyTests(String),
+    /// The baseline selected no tests, so every mutant would survive.
+    NoTests(String),
+    Coverage(String),
+    TestMap(String),
+    Env(String),
+    UnknownOperator(String),
+    /// The `operators` file couldn't be read or parsed.
+    Operators(String),
+    /// `max_sessions` sessions are already running in the project.
+    TooManySessions(String),
+    /// The source file was edited after its mutations were discovered.
+    SourceChanged(String),
+    /// `source_text` was given for an in-place run, which has no copy to
+    /// write it to.
+    SourceTextInPlace,
+}
+
+impl EngineError {
+    /// CLI exit code: 2 for usage errors, 3 for environment/setup failures.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            EngineError::SourceNotFound(_)
+            | EngineError::TestNotFound(_)
+            | EngineError::CwdNotFound(_)
+            | EngineError::NoTestFile(_)
+            | EngineError::AmbiguousTestFile { .. }
+            | EngineError::UnsupportedLanguage(_)
+            | EngineError::FunctionNotFound { .. }
+            | EngineError::Coverage(_)
+            | EngineError::TestMap(_)
+            | EngineError::Env(_)
+            | EngineError::UnknownOperator(_)
+            | EngineError::Operators(_)
+            | EngineError::SourceTextInPlace => 2,
+            EngineError::RecoveredInterruptedRun
+            | EngineError::ReadFailed(..)
+            | EngineError::Setup(_)
+            | EngineError::BaselineFailed(_)
+            | EngineError::FlakyTests(_)
+            | EngineError::NoTests(_)
+            | EngineError::TooManySessions(_)
+            | EngineError::SourceChanged(_) => 3,
+        }
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EngineError::RecoveredInterruptedRun => write!(
+                f,
+                "Recovered source file from a previously interrupted run. Re-run to continue."
+            ),
+            EngineError::SourceNotFound(p) => write!(
+                f,
+                "Source file not found: {}. Check the path and try again.",
+                p.display()
+            ),
+            EngineError::TestNotFound(p) => write!(
+                f,
+                "Test file not found: {}. Pass --test <path> with a valid test file.",
+                p.display()
+            ),
+            EngineError::CwdNotFound(p) => write!(
+                f,
+                "Working directory not found: {}. Pass --cwd <dir> with an existing directory.",
+                p.display()
+            ),
+            EngineError::NoTestFile(p) => write!(
+                f,
+                "No test file found for {}. Looked for test_<name>.py, <name>_test.py, <name>.test.ts, \
+                 tests/<name>.rs and an inline #[cfg(test)] module. Pass --test <path>.",
+                p.display()
+            ),
+            EngineError::AmbiguousTestFile { source, candidates } => write!(
+                f,
+                "Several test files match {}: {}. Pass --test <path> to pick one.",
+                source.display(),
+                candidates.iter().map(|c| c.display().to_string()).collect::<Vec<_>>().join(", ")
+            ),
+            EngineError::ReadFailed(p, e) => write!(f, "Failed to read {}: {}", p.display(), e),
+            EngineError::UnsupportedLanguage(p) => write!(
+                f,
+                "Unsupported file type: {}. Supported: .py, .rs, .js, .ts, .tsx, .jsx, .java; pass --language for other names",
+                p.display()
+            ),
+            EngineError::FunctionNotFound { name, available } => write!(
+                f,
+                "Function '{}' not found. Available: {}",
+                name,
+                available.join(", ")
+            ),
+            EngineError::Setup(e) => write!(f, "Failed to set up isolated environment: {}", e),
+            EngineError::BaselineFailed(output) => write!(
+                f,
+                "Tests fail before mutation. Fix failing tests first.\n{}",
+                output
+            ),
+            EngineError::FlakyTests(output) => write!(
+                f,
+                "Tests are flaky: the unmutated suite both passed and failed. Fix or skip the flaky tests first.\n{}",
+                output
+            ),
+            EngineError::NoTests(output) => write!(
+                f,
+                "The test command ran no tests, so every mutant would survive. Check the test file, --test-filter or --test-map, and the runner's test discovery settings.\n{}",
+                output
+            ),
+            EngineError::UnknownOperator(name) => write!(
+                f,
+                "Unknown operator '{}' for --enable. Opt-in operators: {}",
+                name,
+                operators::OPT_IN.join(", ")
+            ),
+            EngineError::SourceTextInPlace => write!(
+                f,
+                "Source from stdin or --source-text can't be mutated in place: it is written to the isolated copy. Drop --in-place."
+            ),
+            EngineError::Coverage(e)
+            | EngineError::TestMap(e)
+            | EngineError::Env(e)
+            | EngineError::Operators(e)
+            | EngineError::TooManySessions(e)
+            | EngineError::SourceChanged(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
+
+pub struct MutatorEngine {
+    config: EngineConfig,
+    language: Language,
+    abs_source: PathBuf,
+    abs_test: PathBuf,
+    working_dir: PathBuf,
+    resolved_cmd: String,
+    source: String,
+    /// `runner::source_hash` of `source`, checked before each mutant.
+    source_hash: u64,
+    isolated: Option<IsolatedContext>,
+    /// Lockfile marking this run as active, taken by the first baseline.
+    session_lock: Option<SessionLock>,
+    timeout: Option<AdaptiveTimeout>,
+    uncovered_lines: BTreeSet<usize>,
+    /// The `operators` file's operators for `language`.
+    custom_operators: Vec<CustomOperator>,
+    test_filters: Vec<String>,
+    /// Shared `CARGO_TARGET_DIR` for cargo-run Rust targets.
+    cargo_target_dir: Option<PathBuf>,
+    /// `env_file` followed by `env`, so later entries win.
+    env: Vec<(String, String)>,
+    runner: Runner,
+    /// Check run on each mutant before its tests, set up by the baseline
+    /// when `precheck` is on.
+    precheck: Option<Precheck>,
+}
+
+impl MutatorEngine {
+    /// Resolve paths, read the source and validate the function scope.
+    pub fn new(config: EngineConfig) -> Result<Self, EngineError> {
+        let (abs_source, abs_test, working_dir, resolved_cmd) =
+            runner::resolve_paths(&config.source_file, &config.test_file, &config.test_cmd);
+        let (working_dir, resolved_cmd) = match test_working_dir(&config, &abs_source, &abs_test) {
+            Some(dir) => {
+                let resolved_cmd = runner::resolve_test_cmd(&config.test_cmd, &dir);
+                (dir, resolved_cmd)
+            }
+            None => (working_dir, resolved_cmd),
+        };
+        if config.cwd.is_some() && !working_dir.is_dir() {
+            return Err(EngineError::CwdNotFound(working_dir));
+        }
+
+        // Legacy: recover from a previously interrupted in-place run
+        if let Some(bak_path) = safety::check_interrupted_run(&abs_source) {
+            if safety::restore_from_backup(&abs_source, &bak_path).is_ok() {
+                return Err(EngineError::RecoveredInterruptedRun);
+            }
+        }
+
+        if config.source_text.is_some() && config.in_place {
+            return Err(EngineError::SourceTextInPlace);
+        }
+        if config.source_text.is_none() && !abs_source.exists() {
+            return Err(EngineError::SourceNotFound(abs_source));
+        }
+        if !abs_test.exists() {
+            return Err(EngineError::TestNotFound(abs_test));
+        }
+
+        let source = match &config.source_text {
+            Some(text) => text.clone(),
+            None => std::fs::read_to_string(&abs_source).map_err(|e| EngineError::ReadFailed(abs_source.clone(), e))?,
+        };
+
+        let language = config
+            .language
+            .or_else(|| crate::detect_language(&abs_source))
+            .ok_or_else(|| EngineError::UnsupportedLanguage(abs_source.clone()))?;
+
+        check_function(&language, &source, config.function.as_deref())?;
+        check_enabled(&config.enable)?;
+        let uncovered_lines = load_uncovered(&config, &abs_source)?;
+        let custom_operators = load_operators(config.operators.as_deref(), language)?;
+
+        let test_filters = match (&config.test_filter, &config.test_map, &config.function) {
+            (Some(expr), _, _) => vec![expr.clone()],
+            (None, Some(path), Some(fn_name)) => {
+                let map = test_map::load(path).map_err(EngineError::TestMap)?;
+                map.get(fn_name).cloned().unwrap_or_default()
+            }
+            _ => Vec::new(),
+        };
+
+        let mut env = match &config.env_file {
+            Some(path) => env_file::load(path).map_err(EngineError::Env)?,
+            None => Vec::new(),
+        };
+        env.extend(config.env.iter().cloned());
+
+        let project_root = copy_tree::find_project_root(&abs_source);
+        let runner = config
+            .runner
+            .unwrap_or_else(|| Runner::detect(&config.test_cmd, &language, &project_root));
+        let cargo_target_dir = (matches!(language, Language::Rust) && runner.is_cargo())
+            .then(|| runner::cargo_target_dir(&project_root, config.session.as_deref()));
+        log::info!(
+            "Test command `{}` for {} in {} ({:?} runner)",
+            resolved_cmd,
+            abs_test.display(),
+            working_dir.display(),
+            runner
+        );
+
+        Ok(MutatorEngine {
+            config,
+            language,
+            abs_source,
+            abs_test,
+            working_dir,
+            resolved_cmd,
+            source_hash: runner::source_hash(&source),
+            source,
+            isolated: None,
+            session_lock: None,
+            timeout: None,
+            uncovered_lines,
+            custom_operators,
+            test_filters,
+            cargo_target_dir,
+            env,
+            runner,
+            precheck: None,
+        })
+    }
+
+    /// Pick up edits made since the last run: re-read the source, the
+    /// coverage report and the operators file, bring the isolated copy up to date without copying
+    /// the whole tree again, and re-run the baseline before the next mutant.
+    pub fn reload(&mut self) -> Result<(), EngineError> {
+        let source = match &self.config.source_text {
+            Some(text) => text.clone(),
+            None => std::fs::read_to_string(&self.abs_source)
+                .map_err(|e| EngineError::ReadFailed(self.abs_source.clone(), e))?,
+        };
+        check_function(&self.language, &source, self.config.function.as_deref())?;
+        self.uncovered_lines = load_uncovered(&self.config, &self.abs_source)?;
+        self.custom_operators = load_operators(self.config.operators.as_deref(), self.language)?;
+
+        if let Some(ref ctx) = self.isolated {
+            let project_root = copy_tree::find_project_root(&self.abs_source);
+            copy_tree::sync_tree(
+                &project_root,
+                &ctx.copy_result,
+                &self.abs_source,
+                &self.abs_test,
+                self.config.copy_mode,
+            )
+            .map_err(|e| EngineError::Setup(format!("Failed to refresh project copy: {}", e)))?;
+        }
+        self.source_hash = runner::source_hash(&source);
+        self.source = source;
+        self.write_source_text()?;
+        self.timeout = None;
+        Ok(())
+    }
+
+    /// Swap in a new `source_text`, e.g. the buffer after an edit. Takes
+    /// effect on the next `reload`.
+    pub fn set_source_text(&mut self, text: Option<String>) {
+        self.config.source_text = text;
+    }
+
+    /// Write `source_text` over the isolated copy's source file.
+    fn write_source_text(&self) -> Result<(), EngineError> {
+        let (Some(text), Some(ctx)) = (&self.config.source_text, &self.isolated) else {
+            return Ok(());
+        };
+        let path = &ctx.copy_result.source_file;
+        path.parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| std::fs::write(path, text))
+            .map_err(|e| EngineError::Setup(format!("Failed to write source to {}: {}", path.display(), e)))
+    }
+
+    pub fn config(&self) -> &EngineConfig {
+        &self.config
+    }
+
+    pub fn language(&self) -> &Language {
+        &self.language
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Absolute path of the original source file.
+    pub fn source_path(&self) -> &Path {
+        &self.abs_source
+    }
+
+    /// Root of the isolated copy, once the baseline has created it.
+    pub fn temp_dir(&self) -> Option<&Path> {
+        self.isolated.as_ref().map(|ctx| ctx._temp_dir.path())
+    }
+
+    /// Timeout for the next mutant, available once the baseline has run.
+    /// It adapts to the run times of survivors (see `AdaptiveTimeout`).
+    pub fn timeout_ms(&self) -> Option<u64> {
+        self.timeout.as_ref().map(AdaptiveTimeout::timeout_ms)
+    }
+
+    /// Whether the coverage report says no test executes this mutation's line.
+    pub fn is_uncovered(&self, mutation: &Mutation) -> bool {
+        self.uncovered_lines.contains(&mutation.line)
+    }
+
+    /// Find all mutations in the source, scoped to the configured function.
+    pub fn discover(&self) -> Vec<Mutation> {
+        let options = DiscoverOptions {
+            include_tests: self.config.include_tests,
+            include_toplevel: self.config.include_toplevel,
+            enable: &self.config.enable,
+            operators: &self.custom_operators,
+            skip_decorated: &self.config.skip_decorated,
+            context_lines: self.config.context_lines,
+        };
+        let mutations = discovery::discover(
+            &self.language,
+            &self.source,
+            self.config.function.as_deref(),
+            &options,
+            &self.abs_source,
+        );
+        log::info!(
+            "Discovered {} mutations in {}{}",
+            mutations.len(),
+            self.abs_source.display(),
+            self.config.function.as_deref().map(|f| format!(" (function {})", f)).unwrap_or_default()
+        );
+        mutations
+    }
+
+    /// Take the session lock and, in isolated mode, copy the project tree.
+    /// `baseline` does this itself when it hasn't been done; callers that
+    /// must clean up the copy on interrupt call it first, so `temp_dir` is
+    /// known before the (slow) build and baseline runs.
+    pub fn prepare(&mut self) -> Result<(), EngineError> {
+        let session_id = match &self.session_lock {
+            Some(lock) => lock.info().session.clone(),
+            None => {
+                let session_id = self.config.session.clone().unwrap_or_else(generate_session_id);
+                let dir = sessions::sessions_dir(&copy_tree::find_project_root(&self.abs_source));
+                let lock = sessions::acquire(&dir, &session_id, self.config.max_sessions)
+                    .map_err(EngineError::TooManySessions)?;
+                self.session_lock = Some(lock);
+                session_id
+            }
+        };
+        if !self.config.in_place && self.isolated.is_none() {
+            let mut ctx = runner::prepare_isolated_with(
+                &self.abs_source,
+                &self.abs_test,
+                &self.config.test_cmd,
+                &session_id,
+                self.config.copy_mode,
+            )
+            .map_err(EngineError::Setup)?;
+            // The same directory inside the copy. One outside the copied
+            // tree stays where it is if it was given, else the copy's root
+            let project_root = copy_tree::find_project_root(&self.abs_source);
+            match self.working_dir.strip_prefix(&project_root) {
+                Ok(rel) => ctx.working_dir = ctx.copy_result.root.join(rel),
+                Err(_) if self.config.cwd.is_some() => ctx.working_dir = self.working_dir.clone(),
+                Err(_) => {}
+            }
+            if let Some(lock) = self.session_lock.as_mut() {
+                // Best effort: without it a crashed run's tree is left for the OS
+                let _ = lock.set_temp_dir(ctx._temp_dir.path());
+            }
+            self.isolated = Some(ctx);
+            self.write_source_text()?;
+        }
+        Ok(())
+    }
+
+    /// Run the unmutated test suite, `1 + baseline_retries` times. In
+    /// isolated mode this also copies the project tree, unless `prepare`
+    /// already did. Returns the slowest baseline duration in milliseconds.
+    pub fn baseline(&mut self) -> Result<u64, EngineError> {
+        self.prepare()?;
+
+        if let Some(ref dir) = self.cargo_target_dir {
+            // Best effort: without a seed the first build is just slower
+            if let Ok(true) = runner::seed_cargo_target_dir(&copy_tree::find_project_root(&self.abs_source), dir) {
+                log::info!("Seeded {} from the project's target dir", dir.display());
+            }
+            // Build once up front so the timed baseline below costs what a
+            // mutant does: an incremental rebuild of this crate plus the run.
+            if let BaselineResult::Failed(output) = runner::run_baseline_with(&self.invocation(&["--no-run"])) {
+                return Err(EngineError::BaselineFailed(output));
+            }
+            let _ = std::fs::write(self.mutated_file(), &self.source);
+        }
+
+        if self.config.precheck && self.precheck.is_none() {
+            let env = self.invocation(&[]).env;
+            if let Some(precheck) = Precheck::for_file(&self.language, self.mutated_file(), &env) {
+                log::info!("Precheck: {}", precheck.describe());
+                precheck.run(None).map_err(|output| {
+                    EngineError::Setup(format!(
+                        "--precheck: `{}` fails on the unmutated source, so every mutant would look unviable:\n{}",
+                        precheck.describe(),
+                        output
+                    ))
+                })?;
+                self.precheck = Some(precheck);
+            }
+        }
+
+        let invocation = self.invocation(self.runner.adapter().baseline_args());
+        let cache = match self.config.baseline_cache {
+            true => self.baseline_cache_key(&invocation),
+            false => None,
+        };
+        if let Some(hit) = cache.as_ref().and_then(|(path, key)| baseline_cache::lookup(path, key)) {
+            log::info!("Baseline cache hit: passed={} in {}ms", hit.passed, hit.duration_ms);
+            if !hit.passed {
+                return Err(EngineError::BaselineFailed(format!(
+                    "{}\n(cached: nothing changed since this baseline failed; --no-baseline-cache runs it again)",
+                    hit.output.unwrap_or_default()
+                )));
+            }
+            self.set_timeout(hit.duration_ms);
+            return Ok(hit.duration_ms);
+        }
+
+        let runs = 1 + self.config.baseline_retries as usize;
+        let mut durations = Vec::new();
+        let mut failures = Vec::new();
+        for run in 1..=runs {
+            match runner::run_baseline_with(&invocation) {
+                BaselineResult::Ok { duration_ms } => durations.push(duration_ms),
+                BaselineResult::Failed(output) => failures.push((run, output)),
+                BaselineResult::NoTests(output) => return Err(EngineError::NoTests(output)),
+            }
+        }
+        if durations.is_empty() {
+            let output = failures.swap_remove(0).1;
+            if let Some((path, key)) = cache {
+                baseline_cache::store(&path, CachedBaseline { key, passed: false, duration_ms: 0, output: Some(output.clone()) });
+            }
+            return Err(EngineError::BaselineFailed(output));
+        }
+        if !failures.is_empty() {
+            let outputs: Vec<_> =
+                failures.iter().map(|(run, output)| format!("--- run {} of {} failed ---\n{}", run, runs, output)).collect();
+            return Err(EngineError::FlakyTests(outputs.join("\n")));
+        }
+        let duration_ms = durations.into_iter().max().unwrap_or_default();
+        if let Some((path, key)) = cache {
+            baseline_cache::store(&path, CachedBaseline { key, passed: true, duration_ms, output: None });
+        }
+        self.set_timeout(duration_ms);
+        Ok(duration_ms)
+    }
+
+    fn set_timeout(&mut self, baseline_ms: u64) {
+        let cap_ms = self.config.max_timeout_secs.map(|secs| secs * 1000);
+        self.timeout = Some(AdaptiveTimeout::new(baseline_ms, self.config.timeout_mult, cap_ms));
+    }
+
+    /// Cache file and key for the baseline `invocation` runs, or `None` when
+    /// the project can't be fingerprinted. Paths inside the isolated copy
+    /// change every session, so the key is built from the originals.
+    fn baseline_cache_key(&self, invocation: &TestInvocation) -> Option<(PathBuf, String)> {
+        let root = copy_tree::find_project_root(&self.abs_source);
+        let tree = copy_tree::tree_fingerprint(&root, &[&self.abs_source, &self.abs_test]).ok()?;
+        let test = std::fs::read(&self.abs_test).ok()?;
+        let mut hasher = DefaultHasher::new();
+        (&self.config.test_cmd, &self.abs_test, &self.working_dir, &invocation.extra_args, &self.env).hash(&mut hasher);
+        format!(
+            "{:?} {:?} {:?} {}",
+            self.runner, self.config.test_arg_style, self.config.limits, self.config.baseline_retries
+        )
+        .hash(&mut hasher);
+        (test, tree, &self.config.source_text).hash(&mut hasher);
+        Some((baseline_cache::cache_path(&root), format!("{:016x}", hasher.finish())))
+    }
+
+    /// Run a single mutant, running the baseline first if needed.
+    /// The source is restored afterwards.
+    pub fn run_one(&mut self, mutation: &Mutation) -> Result<MutantResult, EngineError> {
+        let mut results = self.run_all(std::slice::from_ref(mutation))?;
+        Ok(results.remove(0))
+    }
+
+    /// Run every mutation, running the baseline first if needed.
+    pub fn run_all(&mut self, mutations: &[Mutation]) -> Result<Vec<MutantResult>, EngineError> {
+        self.run_all_with(mutations, |_, _| {})
+    }
+
+    /// Like `run_all`, calling `on_result` with the mutant's index in
+    /// `mutations` as each one finishes. Results arrive in run order, which
+    /// `--shuffle`, `--prioritize` and timeout retries make differ from it.
+    /// In in-place mode a `.mutator.bak` backup exists for the duration of
+    /// the run so an interrupted run can be recovered.
+    pub fn run_all_with<F: FnMut(usize, &MutantResult)>(
+        &mut self,
+        mutations: &[Mutation],
+        mut on_result: F,
+    ) -> Result<Vec<MutantResult>, EngineError> {
+        self.ensure_baseline()?;
+        let invocation = self
+            .invocation(self.runner.adapter().mutation_args())
+            .with_early_exit(self.config.early_exit);
+        let source_file = self.mutated_file().to_path_buf();
+
+        if self.isolated.is_none() {
+            safety::write_backup(&source_file)
+                .map_err(|e| EngineError::Setup(format!("Failed to write backup: {}", e)))?;
+        }
+
+        let order = self.run_order(mutations);
+        let mut results = Vec::with_capacity(mutations.len());
+        // Timed-out mutants, by position in `results`, with their timeout
+        let mut second_chance = Vec::new();
+        for &index in &order {
+            let mutation = &mutations[index];
+            self.check_unchanged(&source_file)?;
+            if self.is_uncovered(mutation) {
+                let result = uncovered_result(&self.source, mutation);
+                on_result(index, &result);
+                results.push(result);
+                continue;
+            }
+
+            let timeout_ms = self.timeout_ms().unwrap_or_default();
+            let unviable = self
+                .precheck
+                .as_ref()
+                .and_then(|check| precheck::check_mutant(check, &source_file, &self.source, mutation, timeout_ms));
+            if let Some(result) = unviable {
+                let _ = std::fs::write(&source_file, &self.source);
+                runner::clear_pycache_for(&source_file);
+                on_result(index, &result);
+                results.push(result);
+                continue;
+            }
+            let result = self.test_mutant(&invocation, &source_file, mutation, timeout_ms);
+            if result.status == MutantStatus::Timeout && self.retry_timeout_ms(timeout_ms).is_some() {
+                // Reported once the retry settles it
+                second_chance.push((results.len(), timeout_ms));
+            } else {
+                on_result(index, &result);
+            }
+            results.push(result);
+        }
+
+        // A mutant that only slowed the tests down (a loop bound moved)
+        // finishes given more time; an infinite loop times out again
+        for (position, timeout_ms) in second_chance {
+            let retry_ms = self.retry_timeout_ms(timeout_ms).unwrap_or(timeout_ms);
+            let mutation = &mutations[order[position]];
+            self.check_unchanged(&source_file)?;
+            log::info!("Retrying timed-out mutant at line {} with a {}ms timeout", mutation.line, retry_ms);
+            let retry = self.test_mutant(&invocation, &source_file, mutation, retry_ms);
+            if retry.status != MutantStatus::Timeout {
+                results[position] = retry;
+            }
+            on_result(order[position], &results[position]);
+        }
+
+        if self.isolated.is_none() {
+            let _ = std::fs::remove_file(safety::backup_path(&source_file));
+        }
+
+        let mut ordered: Vec<_> = order.into_iter().zip(results).collect();
+        ordered.sort_by_key(|(index, _)| *index);
+        Ok(ordered.into_iter().map(|(_, result)| result).collect())
+    }
+
+    /// `verify_source` before a mutant is written, as `SourceChanged`. In
+    /// place, the backup is dropped too: it predates the edit, so
+    /// recovering from it would undo it.
+    fn check_unchanged(&self, source_file: &Path) -> Result<(), EngineError> {
+        self.verify_source().map_err(|e| {
+            if self.isolated.is_none() {
+                let _ = std::fs::remove_file(safety::backup_path(source_file));
+            }
+            EngineError::SourceChanged(e)
+        })
+    }
+
+    /// Run one mutant's tests, then restore the source. Survivors are
+    /// re-run with `verify_survivors`, count towards the adaptive timeout,
+    /// and are checked for equivalence.
+    fn test_mutant(
+        &mut self,
+        invocation: &TestInvocation,
+        source_file: &Path,
+        mutation: &Mutation,
+        timeout_ms: u64,
+    ) -> MutantResult {
+        let mut result = runner::run_mutant(invocation, source_file, &self.source, mutation, timeout_ms);
+        if result.status == MutantStatus::Survived && self.config.verify_survivors {
+            // A flaky test that happened to pass; the re-run decides
+            let rerun = runner::run_mutant(invocation, source_file, &self.source, mutation, timeout_ms);
+            if rerun.status == MutantStatus::Killed {
+                result = rerun;
+            }
+        }
+        if result.status == MutantStatus::Survived {
+            if let Some(timeout) = self.timeout.as_mut() {
+                timeout.record(result.duration_ms);
+            }
+        }
+        if result.status == MutantStatus::Survived
+            && equivalence::is_suspected_equivalent(&self.language, &self.source, mutation)
+        {
+            result.status = MutantStatus::SuspectedEquivalent;
+        }
+
+        // Restore the original before anything else sees the file
+        let _ = std::fs::write(source_file, &self.source);
+        runner::clear_pycache_for(source_file);
+        result
+    }
+
+    /// The second-chance timeout for a mutant that timed out after
+    /// `timeout_ms`, or `None` if retries are off or `max_timeout_secs`
+    /// leaves no room for a longer one.
+    fn retry_timeout_ms(&self, timeout_ms: u64) -> Option<u64> {
+        let retry_ms = (timeout_ms as f64 * self.config.timeout_retry) as u64;
+        let retry_ms = self.config.max_timeout_secs.map_or(retry_ms, |secs| retry_ms.min(secs * 1000));
+        (retry_ms > timeout_ms).then_some(retry_ms)
+    }
+
+    /// Indices into `mutations` in the order they run: `--prioritize`,
+    /// `--shuffle`, or as given.
+    pub fn run_order(&self, mutations: &[Mutation]) -> Vec<usize> {
+        if !self.config.prioritize {
+            return mutants::run_order(mutations.len(), self.config.shuffle);
+        }
+        let blame = priority::blame_times(&self.abs_source);
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let last_run = priority::last_run(&cwd, &self.config.source_file);
+        log::info!(
+            "Prioritizing: git blame for {} lines, {}",
+            blame.len(),
+            if last_run.is_some() { "with a previous run" } else { "no previous run" }
+        );
+        priority::run_order(mutations, &blame, last_run.as_ref())
+    }
+
+    fn ensure_baseline(&mut self) -> Result<(), EngineError> {
+        if self.timeout.is_none() {
+            self.baseline()?;
+        }
+        Ok(())
+    }
+
+    /// Check that neither the original source nor the isolated copy changed
+    /// since the source was read for discovery.
+    fn verify_source(&self) -> Result<(), String> {
+        // A buffer's file on disk is free to change
+        if self.config.source_text.is_none() {
+            runner::verify_source_hash(&self.abs_source, self.source_hash)?;
+        }
+        if self.isolated.is_some() {
+            runner::verify_source_hash(self.mutated_file(), self.source_hash)?;
+        }
+        Ok(())
+    }
+
+    /// The file that actually gets mutated: the copy in isolated mode,
+    /// the original in in-place mode.
+    fn mutated_file(&self) -> &Path {
+        match &self.isolated {
+            Some(ctx) => &ctx.copy_result.source_file,
+            None => &self.abs_source,
+        }
+    }
+
+    /// Tests selected for this run, from `--test-filter` or the test map.
+    /// Empty means the whole test file runs.
+    pub fn test_filters(&self) -> &[String] {
+        &self.test_filters
+    }
+
+    fn invocation(&self, extra_args: &[&str]) -> TestInvocation {
+        let adapter = self.runner.adapter();
+        let names = match self.config.test_filter {
+            Some(_) => self.test_filters.clone(),
+            None => self.test_filters.iter().map(|name| adapter.test_name(name)).collect(),
+        };
+        // Maven and Gradle select a test class rather than take the file
+        let filters = match self.config.test_arg_style {
+            TestArgStyle::Auto => adapter.class_args(&self.abs_test, &names),
+            _ => None,
+        }
+        .unwrap_or_else(|| adapter.filter_args(&names));
+        // Cargo target selection must precede the `--` in `extra_args`. An
+        // explicit --test-arg-style replaces it.
+        let targets = match (self.cargo_target_dir.as_ref(), self.config.test_arg_style) {
+            (Some(_), TestArgStyle::Auto) => {
+                let mut args = runner::cargo_package_args(&self.abs_source);
+                args.extend(runner::cargo_target_args(&self.abs_test));
+                args
+            }
+            _ => Vec::new(),
+        };
+        let args: Vec<&str> = targets
+            .iter()
+            .map(String::as_str)
+            .chain(extra_args.iter().copied())
+            .chain(filters.iter().map(String::as_str))
+            .collect();
+        let invocation = match &self.isolated {
+            Some(ctx) => ctx.invocation(&args),
+            None => TestInvocation::new(
+                &self.resolved_cmd,
+                &self.abs_test,
+                &self.working_dir,
+                &args,
+            ),
+        };
+        let invocation = invocation
+            .with_limits(self.config.limits)
+            .with_runner(self.runner)
+            .with_test_arg_style(self.config.test_arg_style);
+        let invocation = self
+            .env
+            .iter()
+            .fold(invocation, |inv, (key, value)| inv.with_env(key, value.as_str()));
+        match &self.cargo_target_dir {
+            Some(dir) => invocation.with_env("CARGO_TARGET_DIR", dir.to_string_lossy()),
+            None => invocation,
+        }
+    }
+
+    /// The commands a run would spawn, without running anything (`run
+    /// --print-cmd`). Before the baseline there's no isolated copy yet, so
+    /// paths are the original tree's; a real run uses the same paths inside
+    /// the copy.
+    pub fn command_plan(&self) -> CommandPlan {
+        let adapter = self.runner.adapter();
+        let mutant = self.invocation(adapter.mutation_args()).with_early_exit(self.config.early_exit);
+        let mut mutant = mutant.command();
+        mutant.args(adapter.report_args(Path::new("<report>")));
+        mutant.env("MUTATOR_MUTANT_ID", "<mutant id>");
+        let env = self.invocation(&[]).env;
+        CommandPlan {
+            build: self.cargo_target_dir.as_ref().map(|_| CommandLine::of(&self.invocation(&["--no-run"]).command())),
+            baseline: CommandLine::of(&self.invocation(adapter.baseline_args()).command()),
+            mutant: CommandLine::of(&mutant),
+            precheck: match self.config.precheck {
+                true => Precheck::for_file(&self.language, &self.abs_source, &env).map(|p| p.describe()),
+                false => None,
+            },
+            isolated_from: match self.config.in_place {
+                true => None,
+                false => Some(copy_tree::find_project_root(&self.abs_source)),
+            },
+        }
+    }
+
+    /// The `--shuffle` seed, if mutants run in shuffled order.
+    pub fn shuffle_seed(&self) -> Option<u64> {
+        self.config.shuffle
+    }
+
+    /// The runner from `--runner`, or the detected one.
+    pub fn runner(&self) -> Runner {
+        self.runner
+    }
+
+    /// `CARGO_TARGET_DIR` used for cargo test runs, if the target is Rust
+    /// run through cargo.
+    pub fn cargo_target_dir(&self) -> Option<&Path> {
+        self.cargo_target_dir.as_deref()
+    }
+}
+
+fn uncovered_result(source: &str, mutation: &Mutation) -> MutantResult {
+    let Some(mutated) = runner::apply_mutation(source, mutation) else {
+        return runner::stale_result(mutation);
+    };
+    MutantResult {
+        mutation: mutation.clone(),
+        status: MutantStatus::Uncovered,
+        duration_ms: 0,
+        diff: runner::generate_diff(source, &mutated),
+        output_snippet: None,
+        killed_by: None,
+    }
+}
+
+/// Aggregate mutant results into a `RunResult`, assigning `m1`, `m2`, ...
+/// refs to survivors, `t1`, `t2`, ... to timeouts, `u1`, `u2`, ... to
+/// uncovered, `e1`, `e2`, ... to suspected-equivalent and `r1`, `r2`, ...
+/// to resource-limited mutants in discovery order.
+pub fn summarize(results: &[MutantResult], display_file: &Path) -> RunResult {
+    let survived: Vec<_> = results
+        .iter()
+        .filter(|r| r.status == MutantStatus::Survived)
+        .collect();
+    let timed_out: Vec<_> = results
+        .iter()
+        .filter(|r| r.status == MutantStatus::Timeout)
+        .collect();
+    let uncovered: Vec<_> = results
+        .iter()
+        .filter(|r| r.status == MutantStatus::Uncovered)
+        .collect();
+    let equivalent: Vec<_> = results
+        .iter()
+        .filter(|r| r.status == MutantStatus::SuspectedEquivalent)
+        .collect();
+    let resource_exceeded: Vec<_> = results
+        .iter()
+        .filter(|r| r.status == MutantStatus::ResourceExceeded)
+        .collect();
+    let killed = results.iter().filter(|r| r.status == MutantStatus::Killed).count();
+    let unviable = results.iter().filter(|r| r.status == MutantStatus::Unviable).count();
+    let total = results.len();
+    let testable = total - unviable - equivalent.len();
+    let score = if testable > 0 {
+        killed as f64 / testable as f64
+    } else {
+        1.0
+    };
+
+    let functions = scores_by(results, |r| r.mutation.function.as_deref());
+    let operators = scores_by(results, |r| Some(&r.mutation.operator));
+
+    let mut kills_by_test = BTreeMap::new();
+    for name in results.iter().filter_map(|r| r.killed_by.as_ref()) {
+        *kills_by_test.entry(name.clone()).or_insert(0) += 1;
+    }
+
+    let display_str = display_file.display().to_string();
+    let survived_details = mutant_details(&survived, "m", &display_str);
+    let timeout_details = mutant_details(&timed_out, "t", &display_str);
+    let uncovered_details = mutant_details(&uncovered, "u", &display_str);
+    let equivalent_details = mutant_details(&equivalent, "e", &display_str);
+    let resource_details = mutant_details(&resource_exceeded, "r", &display_str);
+
+    RunResult {
+        score,
+        total,
+        killed,
+        survived: survived_details.len(),
+        timeout: timeout_details.len(),
+        unviable,
+        uncovered: uncovered_details.len(),
+        equivalent: equivalent_details.len(),
+        resource_exceeded: resource_details.len(),
+        duration_ms: results.iter().map(|r| r.duration_ms).sum(),
+        survived_mutants: survived_details,
+        timeout_mutants: timeout_details,
+        uncovered_mutants: uncovered_details,
+        equivalent_mutants: equivalent_details,
+        resource_exceeded_mutants: resource_details,
+        interrupted: false,
+        kills_by_test,
+        functions,
+        operators,
+        all_mutants: Vec::new(),
+    }
+}
+
+/// Status counts and score per `key`; results without a key are skipped.
+fn scores_by<'a>(
+    results: &'a [MutantResult],
+    key: impl Fn(&'a MutantResult) -> Option<&'a str>,
+) -> BTreeMap<String, FunctionScore> {
+    let mut scores: BTreeMap<String, FunctionScore> = BTreeMap::new();
+    for r in results {
+        let Some(name) = key(r) else {
+            continue;
+        };
+        let f = scores.entry(name.to_string()).or_default();
+        f.total += 1;
+        match r.status {
+            MutantStatus::Killed => f.killed += 1,
+            MutantStatus::Survived => f.survived += 1,
+            MutantStatus::Timeout => f.timeout += 1,
+            MutantStatus::Unviable => f.unviable += 1,
+            MutantStatus::Uncovered => f.uncovered += 1,
+            MutantStatus::SuspectedEquivalent => f.equivalent += 1,
+            MutantStatus::ResourceExceeded => f.resource_exceeded += 1,
+        }
+    }
+    for f in scores.values_mut() {
+        let testable = f.testable();
+        f.score = if testable > 0 { f.killed as f64 / testable as f64 } else { 1.0 };
+    }
+    scores
+}
+
+/// Every result as a `MutantOutcome`, with the refs `summarize` assigns.
+pub fn all_mutants(results: &[MutantResult], display_file: &Path) -> Vec<MutantOutcome> {
+    let file = display_file.display().to_string();
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    results
+        .iter()
+        .map(|r| {
+            let prefix = match r.status {
+                MutantStatus::Survived => Some("m"),
+                MutantStatus::Timeout => Some("t"),
+                MutantStatus::Uncovered => Some("u"),
+                MutantStatus::SuspectedEquivalent => Some("e"),
+                MutantStatus::ResourceExceeded => Some("r"),
+                MutantStatus::Killed | MutantStatus::Unviable => None,
+            };
+            let ref_id = prefix.map(|p| {
+                let n = counts.entry(p).or_insert(0);
+                *n += 1;
+                format!("{}{}", p, n)
+            });
+            let m = &r.mutation;
+            MutantOutcome {
+                ref_id,
+                file: file.clone(),
+                line: m.line,
+                column: m.column,
+                function: m.function.clone(),
+                operator: m.operator.clone(),
+                original: m.original.clone(),
+                replacement: m.replacement.clone(),
+                status: r.status.clone(),
+                duration_ms: r.duration_ms,
+                killed_by: r.killed_by.clone(),
+            }
+        })
+        .collect()
+}
+
+fn mutant_details(results: &[&MutantResult], ref_prefix: &str, file: &str) -> Vec<SurvivedMutant> {
+    results
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let m = &r.mutation;
+            SurvivedMutant {
+                ref_id: format!("{}{}", ref_prefix, i + 1),
+                file: file.to_string(),
+                line: m.line,
+                column: m.column,
+                function: m.function.clone(),
+                operator: m.operator.clone(),
+                original: m.original.clone(),
+                replacement: m.replacement.clone(),
+                diff: r.diff.clone(),
+                context_before: m.context_before.clone(),
+                context_after: m.context_after.clone(),
+                output_snippet: r.output_snippet.clone(),
+            }
+        })
+        .collect()
+}
+
+pub fn discover_mutations(lang: &Language, source: &str, function: Option<&str>) -> Vec<Mutation> {
+    lang.parser().discover(source, function, &ParseOptions::default())
+}
+
+/// Mutations `run` would generate for `source_file`, without a test file,
+/// temp tree or baseline. Backs `mutator list`. `language` overrides the
+/// one the extension implies; `options.operators` may cover any language
+/// (see `load_custom_operators`) and are narrowed to this one.
+pub fn list_mutations(
+    source_file: &Path,
+    language: Option<Language>,
+    function: Option<&str>,
+    options: &DiscoverOptions,
+) -> Result<Vec<Mutation>, EngineError> {
+    if !source_file.exists() {
+        return Err(EngineError::SourceNotFound(source_file.to_path_buf()));
+    }
+    let source = std::fs::read_to_string(source_file)
+        .map_err(|e| EngineError::ReadFailed(source_file.to_path_buf(), e))?;
+    let language = language
+        .or_else(|| crate::detect_language(source_file))
+        .ok_or_else(|| EngineError::UnsupportedLanguage(source_file.to_path_buf()))?;
+
+    check_function(&language, &source, function)?;
+    check_enabled(options.enable)?;
+    let operators: Vec<_> = options.operators.iter().filter(|op| op.applies_to(language)).cloned().collect();
+    let options = DiscoverOptions { operators: &operators, ..*options };
+    Ok(discovery::discover(&language, &source, function, &options, source_file))
+}
+
+/// Where the test command runs, per `EngineConfig::cwd`: the given
+/// directory (relative to the current one), else the test file's package,
+/// else the source file's. `None` keeps the source file's directory.
+fn test_working_dir(config: &EngineConfig, abs_source: &Path, abs_test: &Path) -> Option<PathBuf> {
+    match &config.cwd {
+        Some(dir) if dir.is_absolute() => Some(dir.clone()),
+        Some(dir) => Some(std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")).join(dir)),
+        None => copy_tree::find_package_dir(abs_test).or_else(|| copy_tree::find_package_dir(abs_source)),
+    }
+}
+
+fn check_function(lang: &Language, source: &str, function: Option<&str>) -> Result<(), EngineError> {
+    if let Some(fn_name) = function {
+        let available = list_functions(lang, source);
+        if !available.iter().any(|n| names_function(n, fn_name)) {
+            return Err(EngineError::FunctionNotFound {
+                name: fn_name.to_string(),
+                available,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Whether `listed` (as `list_functions` gives it, qualified for methods)
+/// is the function `query` names: exactly, or by its unqualified name.
+fn names_function(listed: &str, query: &str) -> bool {
+    listed == query || listed.rsplit(['.', ':']).next() == Some(query)
+}
+
+fn check_enabled(enable: &[String]) -> Result<(), EngineError> {
+    match enable.iter().find(|op| !operators::OPT_IN.contains(&op.as_str())) {
+        Some(op) => Err(EngineError::UnknownOperator(op.clone())),
+        None => Ok(()),
+    }
+}
+
+/// The operators in the `path` file that apply to `language`.
+fn load_operators(path: Option<&Path>, language: Language) -> Result<Vec<CustomOperator>, EngineError> {
+    let Some(path) = path else {
+        return Ok(Vec::new());
+    };
+    let operators = load_custom_operators(path)?;
+    Ok(operators.into_iter().filter(|op| op.applies_to(language)).collect())
+}
+
+/// Every operator in a `custom_operators` file (`--operators`).
+pub fn load_custom_operators(path: &Path) -> Result<Vec<CustomOperator>, EngineError> {
+    custom_operators::load(path).map_err(EngineError::Operators)
+}
+
+fn load_uncovered(config: &EngineConfig, abs_source: &Path) -> Result<BTreeSet<usize>, EngineError> {
+    let Some(ref path) = config.coverage else {
+        return Ok(BTreeSet::new());
+    };
+    let report = CoverageReport::load(path).map_err(EngineError::Coverage)?;
+    report.uncovered_lines(abs_source).ok_or_else(|| {
+        EngineError::Coverage(format!(
+            "Coverage report {} has no data for {}. Check the report covers this file.",
+            path.display(),
+            abs_source.display()
+        ))
+    })
+}
+
+/// Byte spans of every function in `source`.
+pub fn function_spans(lang: &Language, source: &str) -> Vec<FunctionSpan> {
+    lang.parser().function_spans(source)
+}
+
+pub fn list_functions(lang: &Language, source: &str) -> Vec<String> {
+    lang.parser().list_functions(source)
+}
+
+fn generate_session_id() -> String {
+    format!("{:08x}", fastrand::u32(..))
+}
@@ -0,0 +1,56 @@
+//! Extra test environment for `--env KEY=VALUE` and `--env-file`.
+//!
+//! The file is dotenv-style: one `KEY=VALUE` per line, blank lines and `#`
+//! comments ignored, an optional `export ` prefix, and values optionally
+//! wrapped in single or double quotes (no escapes or interpolation):
+//!
+//! ```text
+//! # .env.test
+//! DATABASE_URL=postgres://localhost/app_test
+//! export SECRET_KEY="not-a-secret"
+//! ```
+
+use std::path::Path;
+
+pub type Env = Vec<(String, String)>;
+
+pub fn load(path: &Path) -> Result<Env, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read env file {}: {}", path.display(), e))?;
+    parse(&content).map_err(|e| format!("Invalid env file {}: {}", path.display(), e))
+}
+
+pub fn parse(content: &str) -> Result<Env, String> {
+    let mut env = Env::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = parse_pair(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        env.push((key, unquote(value.trim()).to_string()));
+    }
+    Ok(env)
+}
+
+/// Split a `KEY=VALUE` pair. The value is kept verbatim.
+pub fn parse_pair(pair: &str) -> Result<(String, String), String> {
+    let (key, value) = pair
+        .split_once('=')
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", pair))?;
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("invalid variable name '{}'", key));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote).and_then(|v| v.strip_suffix(quote)) {
+            return inner;
+        }
+    }
+    value
+}
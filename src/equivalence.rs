@@ -0,0 +1,124 @@
+//! Duplicate and equivalent mutant detection.
+//!
+//! `dedupe` drops mutants that can't tell tests anything: the same edit
+//! generated twice, or a replacement identical to the original.
+//! `is_suspected_equivalent` recognizes binary-operator mutants that can't
+//! change behavior (`x * 1` -> `x / 1`, `a <= a` -> `a >= a`) and argument
+//! swaps in calls to symmetric functions (`max(a, b)` -> `max(b, a)`).
+//! Survivors it matches are reported as `SuspectedEquivalent` and left out
+//! of the score.
+
+use std::collections::HashSet;
+
+use tree_sitter::{Node, Parser};
+
+use crate::mutants::Mutation;
+use crate::Language;
+
+/// Drop no-op mutants and repeats of the same edit, keeping the first.
+pub fn dedupe(mutations: Vec<Mutation>) -> Vec<Mutation> {
+    let mut seen = HashSet::new();
+    mutations
+        .into_iter()
+        .filter(|m| m.original != m.replacement)
+        .filter(|m| seen.insert((m.start_byte, m.end_byte, m.replacement.clone())))
+        .collect()
+}
+
+/// Whether swapping this binary operator provably can't change the result
+/// for the operands at hand.
+pub fn is_suspected_equivalent(lang: &Language, source: &str, mutation: &Mutation) -> bool {
+    let mut parser = Parser::new();
+    if parser.set_language(&grammar(lang)).is_err() {
+        return false;
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return false;
+    };
+    let Some(op) = tree
+        .root_node()
+        .descendant_for_byte_range(mutation.start_byte, mutation.end_byte)
+    else {
+        return false;
+    };
+    if mutation.operator == "arg_swap" {
+        return symmetric_call(op, source);
+    }
+    if op.start_byte() != mutation.start_byte || op.end_byte() != mutation.end_byte {
+        return false;
+    }
+    let Some(expr) = op.parent() else {
+        return false;
+    };
+    let (Some(left), Some(right)) = (operand(expr, "left", source), operand(expr, "right", source)) else {
+        return false;
+    };
+    equivalent_swap(mutation.original.trim(), mutation.replacement.trim(), left, right)
+}
+
+fn equivalent_swap(original: &str, replacement: &str, left: &str, right: &str) -> bool {
+    if left == right {
+        // With identical operands each group evaluates the same
+        const SAME_OPERAND_GROUPS: &[&[&str]] = &[
+            &["<=", ">=", "==", "===", "is"],
+            &["<", ">", "!=", "!==", "is not"],
+            &["-", "%", "^"],
+            &["/", "//"],
+            &["and", "or", "&&", "||", "&", "|"],
+        ];
+        if SAME_OPERAND_GROUPS
+            .iter()
+            .any(|g| g.contains(&original) && g.contains(&replacement))
+        {
+            return true;
+        }
+    }
+    // Identity right operand: x * 1 == x / 1 == x ** 1, x + 0 == x - 0
+    let pair = |a: &str, b: &str| (original == a && replacement == b) || (original == b && replacement == a);
+    match right {
+        "1" | "1.0" => pair("*", "/") || pair("**", "*") || pair("//", "/"),
+        "0" | "0.0" => pair("+", "-") || pair("|", "^") || pair("<<", ">>"),
+        _ => false,
+    }
+}
+
+/// Functions whose result doesn't depend on the order of two arguments.
+const SYMMETRIC_FUNCTIONS: &[&str] = &["max", "min", "hypot", "gcd", "lcm", "isclose"];
+
+/// Whether `args` (the smallest node around the swapped arguments: the
+/// argument list) belongs to a call of a symmetric function.
+fn symmetric_call(args: Node, source: &str) -> bool {
+    let Some(callee) = args.parent().and_then(|call| call.child_by_field_name("function")) else {
+        return false;
+    };
+    let callee = source.get(callee.byte_range()).unwrap_or_default();
+    let name = callee.rsplit(['.', ':']).next().unwrap_or(callee);
+    SYMMETRIC_FUNCTIONS.contains(&name)
+}
+
+/// Operand text by field name, falling back to the first/last named child
+/// (Python's `comparison_operator` has no `left`/`right` fields).
+fn operand<'a>(expr: Node, field: &str, source: &'a str) -> Option<&'a str> {
+    let node = expr.child_by_field_name(field).or_else(|| {
+        let count = expr.named_child_count();
+        if count < 2 {
+            return None;
+        }
+        match field {
+            "left" => expr.named_child(0),
+            _ => expr.named_child(count - 1),
+        }
+    })?;
+    source.get(node.byte_range()).map(str::trim)
+}
+
+fn grammar(lang: &Language) -> tree_sitter::Language {
+    match lang {
+        Language::Python => tree_sitter_python::LANGUAGE.into(),
+        Language::Rust => tree_sitter_rust::LANGUAGE.into(),
+        Language::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+        Language::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        Language::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+        Language::Java => tree_sitter_java::LANGUAGE.into(),
+    }
+}
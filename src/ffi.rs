@@ -0,0 +1,176 @@
+//! C ABI for calling discovery in-process from non-Rust agent frameworks
+//! (`ffi` feature), instead of spawning `mutator list` per call. Build the
+//! shared library with
+//! `cargo rustc --release --lib --features ffi --crate-type cdylib`;
+//! `include/mutator.h` declares the functions.
+//!
+//! Requests and responses are JSON strings. Failures come back as
+//! `{"error": "..."}` rather than a null pointer. Every returned string
+//! must be released with `mutator_free`.
+//!
+//! `mutator_discover` takes the source text and what `mutator list` would:
+//!
+//! ```json
+//! { "source": "def add(a, b):\n    return a + b\n", "path": "app.py",
+//!   "function": "add", "enable": ["string_mut"], "operators": [] }
+//! ```
+//!
+//! and returns `{"mutations": [...]}`, each as in `list --json`. `path`
+//! only picks the language (unless `language` is given) and tells JS/TS
+//! test files apart; it needn't exist. `operators` is a list of custom
+//! operators as in an `--operators` file.
+//!
+//! `mutator_apply` takes `{"source": "...", "mutation": {...}}` with a
+//! mutation from `mutator_discover`, and returns
+//! `{"mutated": "...", "diff": "..."}`.
+
+use std::ffi::{c_char, CStr, CString};
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::discovery::{self, DiscoverOptions};
+use crate::mutants::{self, Mutation};
+use crate::{custom_operators, operators, runner, Language};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DiscoverRequest {
+    source: String,
+    #[serde(default)]
+    path: PathBuf,
+    #[serde(default)]
+    language: Option<Language>,
+    #[serde(default)]
+    function: Option<String>,
+    #[serde(default)]
+    include_tests: bool,
+    #[serde(default)]
+    include_toplevel: bool,
+    #[serde(default)]
+    enable: Vec<String>,
+    #[serde(default)]
+    skip_decorated: Vec<String>,
+    #[serde(default)]
+    operators: Option<Value>,
+    #[serde(default)]
+    context_lines: Option<usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ApplyRequest {
+    source: String,
+    mutation: Mutation,
+}
+
+/// `mutator_discover` on a Rust string.
+pub fn discover_json(request: &str) -> String {
+    respond(discover(request))
+}
+
+/// `mutator_apply` on a Rust string.
+pub fn apply_json(request: &str) -> String {
+    respond(apply(request))
+}
+
+fn discover(request: &str) -> Result<Value, String> {
+    let request: DiscoverRequest = serde_json::from_str(request).map_err(|e| format!("Invalid request: {}", e))?;
+    let language = request
+        .language
+        .or_else(|| crate::detect_language(&request.path))
+        .ok_or("Unknown language: pass language, or a path with a supported extension")?;
+    if let Some(op) = request.enable.iter().find(|op| !operators::OPT_IN.contains(&op.as_str())) {
+        return Err(format!("Unknown operator '{}' in enable. Opt-in operators: {}", op, operators::OPT_IN.join(", ")));
+    }
+    let custom = match &request.operators {
+        Some(ops) => custom_operators::parse(&ops.to_string()).map_err(|e| format!("Invalid operators: {}", e))?,
+        None => Vec::new(),
+    };
+    let custom: Vec<_> = custom.into_iter().filter(|op| op.applies_to(language)).collect();
+    let options = DiscoverOptions {
+        include_tests: request.include_tests,
+        include_toplevel: request.include_toplevel,
+        enable: &request.enable,
+        operators: &custom,
+        skip_decorated: &request.skip_decorated,
+        context_lines: request.context_lines.unwrap_or(mutants::DEFAULT_CONTEXT_LINES),
+    };
+    let mutations =
+        discovery::discover(&language, &request.source, request.function.as_deref(), &options, &request.path);
+    Ok(json!({ "mutations": mutations }))
+}
+
+fn apply(request: &str) -> Result<Value, String> {
+    let request: ApplyRequest = serde_json::from_str(request).map_err(|e| format!("Invalid request: {}", e))?;
+    let mutated = runner::apply_mutation(&request.source, &request.mutation).ok_or_else(|| {
+        format!(
+            "Stale mutation: bytes {}..{} don't contain {:?}",
+            request.mutation.start_byte, request.mutation.end_byte, request.mutation.original
+        )
+    })?;
+    let diff = runner::generate_diff(&request.source, &mutated);
+    Ok(json!({ "mutated": mutated, "diff": diff }))
+}
+
+fn respond(result: Result<Value, String>) -> String {
+    result.unwrap_or_else(|error| json!({ "error": error })).to_string()
+}
+
+/// Run `handler` on the C string at `request` and hand back its response
+/// as a string the caller frees with `mutator_free`.
+///
+/// # Safety
+///
+/// `request` must be null or point to a NUL-terminated string.
+unsafe fn call(request: *const c_char, handler: fn(&str) -> String) -> *mut c_char {
+    let response = if request.is_null() {
+        respond(Err("Request is a null pointer".to_string()))
+    } else {
+        // SAFETY: non-null and NUL-terminated per the caller's contract
+        match unsafe { CStr::from_ptr(request) }.to_str() {
+            Ok(request) => handler(request),
+            Err(_) => respond(Err("Request is not valid UTF-8".to_string())),
+        }
+    };
+    // serde_json escapes NUL, so the response has none inside
+    CString::new(response).expect("JSON has no NUL bytes").into_raw()
+}
+
+/// Discover mutations; see the module docs for the request format.
+///
+/// # Safety
+///
+/// `request` must be null or point to a NUL-terminated UTF-8 string. The
+/// result must be released with `mutator_free`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mutator_discover(request: *const c_char) -> *mut c_char {
+    // SAFETY: forwarded from the caller
+    unsafe { call(request, discover_json) }
+}
+
+/// Apply one mutation to a source text and diff it; see the module docs.
+///
+/// # Safety
+///
+/// As for `mutator_discover`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mutator_apply(request: *const c_char) -> *mut c_char {
+    // SAFETY: forwarded from the caller
+    unsafe { call(request, apply_json) }
+}
+
+/// Release a string returned by `mutator_discover` or `mutator_apply`.
+///
+/// # Safety
+///
+/// `response` must be null or a pointer those functions returned, not
+/// already freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn mutator_free(response: *mut c_char) {
+    if !response.is_null() {
+        // SAFETY: allocated by `CString::into_raw` in `call`
+        drop(unsafe { CString::from_raw(response) });
+    }
+}
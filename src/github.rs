@@ -0,0 +1,67 @@
+//! GitHub Actions output (`--format github`): a `::warning` workflow
+//! command per survivor and uncovered mutant, which the runner turns into
+//! inline annotations on the PR diff, and the markdown summary appended to
+//! `$GITHUB_STEP_SUMMARY` when it's set.
+
+use std::io::Write;
+use std::path::Path;
+
+use crate::markdown;
+use crate::state::{RunResult, SurvivedMutant};
+
+/// One workflow command per survived, then uncovered, mutant.
+pub fn to_annotations(result: &RunResult) -> String {
+    let mut out = String::new();
+    for m in &result.survived_mutants {
+        out.push_str(&annotation(m, "Survived mutant"));
+    }
+    for m in &result.uncovered_mutants {
+        out.push_str(&annotation(m, "Uncovered mutant"));
+    }
+    out
+}
+
+fn annotation(m: &SurvivedMutant, kind: &str) -> String {
+    let message = format!(
+        "{}: '{}' → '{}' ({}, @{}). {}",
+        kind,
+        m.original,
+        m.replacement,
+        m.operator,
+        m.ref_id,
+        markdown::assertion_hint(&m.operator)
+    );
+    format!(
+        "::warning file={},line={},col={},title={}::{}\n",
+        escape_property(&m.file),
+        m.line,
+        m.column,
+        escape_property(&format!("mutator: {}", m.operator)),
+        escape_data(&message)
+    )
+}
+
+/// Workflow command messages end at a newline; `%` starts an escape.
+fn escape_data(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Property values also end at `,` and `:`.
+fn escape_property(text: &str) -> String {
+    escape_data(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// Append the markdown summary to `$GITHUB_STEP_SUMMARY`, if set. Outside
+/// Actions this does nothing.
+pub fn write_step_summary(result: &RunResult) -> std::io::Result<()> {
+    match std::env::var_os("GITHUB_STEP_SUMMARY") {
+        Some(path) if !path.is_empty() => append_step_summary(Path::new(&path), result),
+        _ => Ok(()),
+    }
+}
+
+/// Append the markdown summary to the step summary file at `path`.
+pub fn append_step_summary(path: &Path, result: &RunResult) -> std::io::Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", markdown::to_markdown(result))
+}
@@ -0,0 +1,71 @@
+//! GitLab Code Quality report (`--format gitlab-codequality`): the JSON
+//! array merge request widgets read from a `codequality` artifact. Each
+//! survived mutant is a `major` issue and each uncovered one `minor`.
+//!
+//! GitLab matches issues between the source and target branch by
+//! fingerprint, so it's built from what the mutant is (file, function,
+//! operator, original and replacement text) rather than where: editing
+//! lines above a survivor doesn't make it a new issue. Identical mutants
+//! in one function are told apart by their order.
+
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use crate::markdown;
+use crate::state::{RunResult, SurvivedMutant};
+
+pub fn to_codequality(result: &RunResult) -> Value {
+    let mut seen = HashMap::new();
+    let survived = result.survived_mutants.iter().map(|m| (m, "major", "Survived mutant"));
+    let uncovered = result.uncovered_mutants.iter().map(|m| (m, "minor", "Uncovered mutant"));
+    let issues: Vec<Value> = survived
+        .chain(uncovered)
+        .map(|(m, severity, kind)| {
+            let key = identity(m);
+            let nth = seen.entry(key.clone()).or_insert(0);
+            *nth += 1;
+            json!({
+                "type": "issue",
+                "check_name": format!("mutator/{}", m.operator),
+                "description": format!(
+                    "{}: `{}` -> `{}` ({}, @{}). {}",
+                    kind,
+                    m.original,
+                    m.replacement,
+                    m.operator,
+                    m.ref_id,
+                    markdown::assertion_hint(&m.operator)
+                ),
+                "categories": ["Bug Risk"],
+                "severity": severity,
+                "fingerprint": fingerprint(&format!("{}\0{}", key, nth)),
+                "location": {
+                    "path": m.file.replace('\\', "/"),
+                    "lines": { "begin": m.line },
+                },
+            })
+        })
+        .collect();
+    Value::Array(issues)
+}
+
+fn identity(m: &SurvivedMutant) -> String {
+    [
+        m.file.replace('\\', "/").as_str(),
+        m.function.as_deref().unwrap_or_default(),
+        &m.operator,
+        &m.original,
+        &m.replacement,
+    ]
+    .join("\0")
+}
+
+/// FNV-1a, as hex. `DefaultHasher` output may change between Rust
+/// releases, and fingerprints are compared across pipelines.
+fn fingerprint(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,202 @@
+//! Append-only run history (`.mutator/history.jsonl`) for `mutator history`.
+//!
+//! One JSON object per line, written after every completed run, so score
+//! trends survive across sessions and `status` only ever shows the latest.
+//! Each run's full `RunResult` is kept beside it in `.mutator/runs/` for
+//! `mutator compare`.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::state::{self, RunResult};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// UTC, RFC 3339 (`2025-01-31T12:00:00Z`)
+    pub timestamp: String,
+    pub file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    pub score: f64,
+    pub total: usize,
+    pub killed: usize,
+    pub survived: usize,
+    pub timeout: usize,
+    pub unviable: usize,
+    #[serde(default)]
+    pub uncovered: usize,
+    #[serde(default)]
+    pub equivalent: usize,
+    pub duration_ms: u64,
+    /// Snapshot of the full result in `.mutator/runs/`; absent for runs
+    /// recorded before snapshots were kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn new(result: &RunResult, file: &Path, function: Option<&str>, session: Option<&str>) -> Self {
+        HistoryEntry {
+            timestamp: now_utc(),
+            file: file.display().to_string(),
+            function: function.map(String::from),
+            session: session.map(String::from),
+            score: result.score,
+            total: result.total,
+            killed: result.killed,
+            survived: result.survived,
+            timeout: result.timeout,
+            unviable: result.unviable,
+            uncovered: result.uncovered,
+            equivalent: result.equivalent,
+            duration_ms: result.duration_ms,
+            run: None,
+        }
+    }
+}
+
+/// An entry with its score change since the previous run of the same
+/// file and function.
+#[derive(Debug, Serialize)]
+pub struct TrendPoint {
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+    pub delta: Option<f64>,
+}
+
+pub fn history_path() -> PathBuf {
+    history_path_in(&std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
+}
+
+pub fn history_path_in(dir: &Path) -> PathBuf {
+    dir.join(".mutator").join("history.jsonl")
+}
+
+pub fn runs_dir_in(dir: &Path) -> PathBuf {
+    dir.join(".mutator").join("runs")
+}
+
+pub fn append(entry: &HistoryEntry) {
+    append_to(&history_path(), entry);
+}
+
+/// Snapshot `result` and append its entry to the history in the CWD.
+pub fn record(result: &RunResult, file: &Path, function: Option<&str>, session: Option<&str>) {
+    record_in(&std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")), result, file, function, session);
+}
+
+pub fn record_in(dir: &Path, result: &RunResult, file: &Path, function: Option<&str>, session: Option<&str>) {
+    let mut entry = HistoryEntry::new(result, file, function, session);
+    let runs_dir = runs_dir_in(dir);
+    let name = format!("{}-{:08x}.json", entry.timestamp.replace(':', ""), fastrand::u32(..));
+    if std::fs::create_dir_all(&runs_dir).is_ok() {
+        state::save_to_path(result, &runs_dir.join(&name));
+        entry.run = Some(name);
+    }
+    append_to(&history_path_in(dir), &entry);
+}
+
+/// The snapshot `entry` points at, if it was kept and still parses.
+pub fn load_run(dir: &Path, entry: &HistoryEntry) -> Option<RunResult> {
+    state::load_from_path(&runs_dir_in(dir).join(entry.run.as_ref()?))
+}
+
+pub fn append_to(path: &Path, entry: &HistoryEntry) {
+    let Ok(line) = serde_json::to_string(entry) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// All entries in file order. Lines that don't parse (e.g. a write cut
+/// short by a crash) are skipped.
+pub fn load_from(path: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path)
+        .map(|data| {
+            data.lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Attach score deltas, optionally keeping only runs on `file` (matched as a
+/// path suffix, so `app.py` matches `src/app.py`).
+pub fn trend(entries: Vec<HistoryEntry>, file: Option<&Path>) -> Vec<TrendPoint> {
+    let mut points: Vec<TrendPoint> = Vec::new();
+    for entry in entries {
+        if let Some(file) = file {
+            if !Path::new(&entry.file).ends_with(file) {
+                continue;
+            }
+        }
+        let delta = points
+            .iter()
+            .rev()
+            .find(|p| p.entry.file == entry.file && p.entry.function == entry.function)
+            .map(|p| entry.score - p.entry.score);
+        points.push(TrendPoint { entry, delta });
+    }
+    points
+}
+
+/// Runs of the same file and function as the latest matching entry, oldest
+/// first, so `series[len - 1 - n]` is `n` runs back. `file` matches as a path
+/// suffix; `session`, when given, must match exactly.
+pub fn series(entries: Vec<HistoryEntry>, file: Option<&Path>, session: Option<&str>) -> Vec<HistoryEntry> {
+    let matching: Vec<_> = entries
+        .into_iter()
+        .filter(|e| file.is_none_or(|f| Path::new(&e.file).ends_with(f)))
+        .filter(|e| session.is_none_or(|s| e.session.as_deref() == Some(s)))
+        .collect();
+    let Some(latest) = matching.last() else {
+        return Vec::new();
+    };
+    let (file, function) = (latest.file.clone(), latest.function.clone());
+    matching
+        .into_iter()
+        .filter(|e| e.file == file && e.function == function)
+        .collect()
+}
+
+fn now_utc() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format_utc(secs)
+}
+
+/// Format a Unix timestamp as RFC 3339 UTC.
+pub fn format_utc(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+    // Civil-from-days (Howard Hinnant), valid for the proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
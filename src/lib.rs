@@ -1,30 +1,196 @@
+#[cfg(feature = "runtime")]
+pub mod baseline_cache;
+#[cfg(feature = "runtime")]
+pub mod compare;
+#[cfg(feature = "runtime")]
 pub mod copy_tree;
+#[cfg(feature = "runtime")]
+pub mod coverage;
+pub mod custom_operators;
+#[cfg(all(unix, feature = "runtime"))]
+pub mod daemon;
+pub mod discovery;
+#[cfg(feature = "runtime")]
+pub mod engine;
+#[cfg(feature = "runtime")]
+pub mod env_file;
+pub mod equivalence;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "runtime")]
+pub mod github;
+#[cfg(feature = "runtime")]
+pub mod gitlab;
+#[cfg(feature = "runtime")]
+pub mod history;
+#[cfg(feature = "runtime")]
+pub mod markdown;
 pub mod mutants;
+pub mod mutation_parser;
+#[cfg(all(unix, feature = "node"))]
+pub mod node;
 pub mod operators;
 pub mod parser;
+pub mod parser_java;
 pub mod parser_js;
 pub mod parser_rust;
+#[cfg(feature = "runtime")]
+pub mod precheck;
+#[cfg(feature = "runtime")]
+pub mod priority;
+#[cfg(feature = "runtime")]
 pub mod runner;
+#[cfg(feature = "runtime")]
 pub mod output;
+#[cfg(feature = "runtime")]
 pub mod safety;
+#[cfg(feature = "runtime")]
+pub mod sarif;
+#[cfg(feature = "runtime")]
+pub mod schema;
+#[cfg(feature = "runtime")]
+pub mod sessions;
+#[cfg(feature = "runtime")]
+pub mod signals;
+#[cfg(feature = "runtime")]
 pub mod state;
+#[cfg(feature = "runtime")]
+pub mod suggest;
+#[cfg(feature = "runtime")]
+pub mod test_adapters;
+#[cfg(feature = "runtime")]
+pub mod test_discovery;
+#[cfg(feature = "runtime")]
+pub mod test_map;
+#[cfg(feature = "runtime")]
+pub mod watch;
+#[cfg(feature = "runtime")]
+pub mod xml_report;
+#[cfg(feature = "tui")]
+pub mod tui;
 
+#[cfg(feature = "runtime")]
+pub use engine::{EngineConfig, EngineError, MutatorEngine};
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use mutation_parser::MutationParser;
+#[cfg(feature = "runtime")]
+use test_adapters::{Runner, TestDefaults};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Language {
+    #[serde(rename = "python", alias = "py")]
     Python,
+    #[serde(rename = "rust", alias = "rs")]
     Rust,
+    #[serde(rename = "js", alias = "javascript")]
     JavaScript,
+    #[serde(rename = "ts", alias = "typescript")]
     TypeScript,
+    #[serde(rename = "tsx", alias = "jsx")]
     Tsx,
+    #[serde(rename = "java")]
+    Java,
 }
 
-pub fn detect_language(path: &std::path::Path) -> Option<Language> {
-    match path.extension()?.to_str()? {
-        "py" => Some(Language::Python),
-        "rs" => Some(Language::Rust),
-        "js" | "mjs" | "cjs" => Some(Language::JavaScript),
-        "ts" | "mts" | "cts" => Some(Language::TypeScript),
-        "tsx" | "jsx" => Some(Language::Tsx),
-        _ => None,
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::Python,
+        Language::Rust,
+        Language::JavaScript,
+        Language::TypeScript,
+        Language::Tsx,
+        Language::Java,
+    ];
+
+    /// Name for `--language`, as `Display` prints it.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::Python => "python",
+            Language::Rust => "rust",
+            Language::JavaScript => "js",
+            Language::TypeScript => "ts",
+            Language::Tsx => "tsx",
+            Language::Java => "java",
+        }
+    }
+
+    /// The language files ending in `.ext` are written in.
+    pub fn from_extension(ext: &str) -> Option<Language> {
+        match ext {
+            "py" => Some(Language::Python),
+            "rs" => Some(Language::Rust),
+            "js" | "mjs" | "cjs" => Some(Language::JavaScript),
+            "ts" | "mts" | "cts" => Some(Language::TypeScript),
+            "tsx" | "jsx" => Some(Language::Tsx),
+            "java" => Some(Language::Java),
+            _ => None,
+        }
+    }
+
+    /// Runner and test command when neither `--test-cmd` nor the project
+    /// (a virtualenv, `package.json`, a Gradle build) says otherwise.
+    #[cfg(feature = "runtime")]
+    pub fn test_defaults(self) -> TestDefaults {
+        let (runner, test_cmd) = match self {
+            Language::Python => (Runner::Pytest, "pytest"),
+            Language::Rust => (Runner::CargoTest, "cargo test"),
+            Language::JavaScript | Language::TypeScript | Language::Tsx => (Runner::Jest, "npx jest"),
+            Language::Java => (Runner::Maven, "mvn -q test"),
+        };
+        TestDefaults { runner, test_cmd }
+    }
+
+    /// The parser that discovers this language's mutations.
+    pub fn parser(self) -> Box<dyn MutationParser> {
+        match self {
+            Language::Python => Box::new(parser::PythonParser),
+            Language::Rust => Box::new(parser_rust::RustParser),
+            Language::JavaScript => Box::new(parser_js::JsParser(parser_js::JsDialect::JavaScript)),
+            Language::TypeScript => Box::new(parser_js::JsParser(parser_js::JsDialect::TypeScript)),
+            Language::Tsx => Box::new(parser_js::JsParser(parser_js::JsDialect::Tsx)),
+            Language::Java => Box::new(parser_java::JavaParser),
+        }
     }
+
+    /// Other names `FromStr` accepts: the usual file extension or the long
+    /// name.
+    fn alias(self) -> &'static str {
+        match self {
+            Language::Python => "py",
+            Language::Rust => "rs",
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::Tsx => "jsx",
+            Language::Java => "java",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_ascii_lowercase();
+        Language::ALL.into_iter().find(|l| l.name() == name || l.alias() == name).ok_or_else(|| {
+            let names: Vec<_> = Language::ALL.iter().map(|l| l.name()).collect();
+            format!("unknown language '{}' (expected {})", s, names.join(", "))
+        })
+    }
+}
+
+/// The language of `path`, from its extension.
+pub fn detect_language(path: &std::path::Path) -> Option<Language> {
+    Language::from_extension(path.extension()?.to_str()?)
 }
 
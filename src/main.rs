@@ -1,63 +1,332 @@
+use mutator::compare;
+use mutator::copy_tree::CopyMode;
+use mutator::discovery::DiscoverOptions;
+use mutator::engine::{self, EngineConfig, EngineError, MutatorEngine};
+use mutator::env_file;
+use mutator::github;
+use mutator::gitlab;
+use mutator::history;
+use mutator::markdown;
 use mutator::mutants;
-use mutator::parser;
-use mutator::parser_js;
-use mutator::parser_rust;
-use mutator::runner;
+use mutator::operators;
 use mutator::output;
-use mutator::safety;
+use mutator::runner::ResourceLimits;
+use mutator::sarif;
+use mutator::schema;
+use mutator::signals;
 use mutator::state;
+use mutator::suggest;
+use mutator::test_adapters::{self, Runner, TestArgStyle};
+use mutator::test_discovery;
+use mutator::watch;
+use mutator::xml_report;
+use mutator::Language;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 
-use clap::{Parser, Subcommand};
+use clap::builder::PossibleValuesParser;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(name = "mutator", version, about = "Mutation testing for AI agents")]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Log what mutator does to stderr: -v for discovery, commands, copies and the baseline, -vv for every mutant run
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    /// Write the log to this file instead of stderr (at -vv detail unless -v is given)
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Sarif,
+    Markdown,
+    Github,
+    GitlabCodequality,
+}
+
+/// When a completed run exits 1.
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum FailOn {
+    /// Any survived or uncovered mutant
+    AnySurvivor,
+    /// Mutation score below --fail-under
+    Threshold,
+    /// Never; only setup and usage errors are non-zero
+    Never,
+}
+
+/// `--json` is shorthand for `--format json`.
+fn resolve_format(json: bool, format: Format) -> Format {
+    if json { Format::Json } else { format }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Source file to mutate, or - to read it from stdin (with --stdin-path)
+    file: PathBuf,
+    /// Path the source read from stdin is mutated as: it is written there in the isolated copy, and its language and test file are found from it. The file needn't exist
+    #[arg(long, value_name = "PATH", required_if_eq("file", "-"))]
+    stdin_path: Option<PathBuf>,
+    /// Mutate this text instead of FILE's contents on disk, e.g. an editor's unsaved buffer. FILE needn't exist
+    #[arg(long, value_name = "TEXT")]
+    source_text: Option<String>,
+    /// Source language: python, rust, js, ts, tsx or java (default: from the file extension). For extensionless scripts and unusual extensions
+    #[arg(long, value_name = "LANG")]
+    language: Option<Language>,
+    /// Test file to run against mutations (default: found by naming convention, e.g. test_<name>.py, <name>.test.ts, tests/<name>.rs)
+    #[arg(short, long)]
+    test: Option<PathBuf>,
+    /// Function name to scope mutations to (recommended)
+    #[arg(short, long)]
+    function: Option<String>,
+    /// Output JSON instead of human-readable text
+    #[arg(long)]
+    json: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value = "text")]
+    format: Format,
+    /// Exit code only, no output
+    #[arg(short, long)]
+    quiet: bool,
+    /// Only mutate lines changed in git
+    #[arg(long)]
+    in_diff: bool,
+    /// Test command (default: detected from the project: venv pytest, package.json runner, cargo test)
+    #[arg(long)]
+    test_cmd: Option<String>,
+    /// Timeout multiplier for test runs (default: 3x baseline)
+    #[arg(long, default_value = "3")]
+    timeout_mult: f64,
+    /// Upper bound on the per-mutant timeout, in seconds (the timeout otherwise adapts to survivor run times)
+    #[arg(long, value_name = "SECONDS")]
+    max_timeout: Option<u64>,
+    /// Session ID for isolation (default: auto-generated). Agents should pass their own.
+    #[arg(long)]
+    session: Option<String>,
+    /// Mutate source in-place instead of copying to temp dir (unsafe for concurrent use)
+    #[arg(long)]
+    in_place: bool,
+    /// Directory to run the test command in (default: the test file's package, the nearest dir with package.json, pyproject.toml or a Cargo.toml [package]; else the source's)
+    #[arg(long, value_name = "DIR")]
+    cwd: Option<PathBuf>,
+    /// Coverage report (coverage.py XML/JSON or lcov); mutants on unexecuted lines are marked uncovered without running tests
+    #[arg(long, value_name = "FILE")]
+    coverage: Option<PathBuf>,
+    /// Also mutate test code (Rust #[test]/#[cfg(test)], JS/TS describe/it/test callbacks and test files)
+    #[arg(long)]
+    include_tests: bool,
+    /// Also mutate module-level code (constants, lookup tables, top-level conditionals; not `if __name__ == "__main__"`). Ignored with -f
+    #[arg(long)]
+    include_toplevel: bool,
+    /// Skip Python functions with any of these decorators, e.g. cached,property (a function named with -f is mutated anyway). @overload and @abstractmethod stubs are always skipped
+    #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+    skip_decorated: Vec<String>,
+    /// JSON map of function name -> test names; with -f, only the mapped tests run
+    #[arg(long, value_name = "FILE")]
+    test_map: Option<PathBuf>,
+    /// Only run matching tests: forwarded as pytest -k, jest/vitest -t, or cargo test NAME
+    #[arg(long, value_name = "EXPR")]
+    test_filter: Option<String>,
+    /// Minimum mutation score (0.0-1.0); exit 1 only below it. Implies --fail-on threshold
+    #[arg(long, value_name = "SCORE", value_parser = parse_score, required_if_eq("fail_on", "threshold"))]
+    fail_under: Option<f64>,
+    /// When to exit 1 (default: any-survivor, or threshold if --fail-under is set)
+    #[arg(long, value_enum)]
+    fail_on: Option<FailOn>,
+    /// How to copy the project tree: copy, reflink (copy-on-write clones where supported) or link (clones, else hard links)
+    #[arg(long, value_name = "MODE", default_value = "copy")]
+    copy_mode: CopyMode,
+    /// Extra environment for test runs (repeatable); overrides --env-file
+    #[arg(long = "env", value_name = "KEY=VALUE", value_parser = env_file::parse_pair)]
+    env: Vec<(String, String)>,
+    /// Dotenv-style file of extra environment for test runs
+    #[arg(long, value_name = "FILE")]
+    env_file: Option<PathBuf>,
+    /// Address-space limit per test process, in MiB; runs that hit it are marked ResourceExceeded (Unix)
+    #[arg(long, value_name = "MB")]
+    max_memory_mb: Option<u64>,
+    /// CPU-time limit per test process, in seconds; runs that hit it are marked ResourceExceeded (Unix)
+    #[arg(long, value_name = "SECONDS")]
+    cpu_limit: Option<u64>,
+    /// Include every mutant's outcome (status, duration, killing test) as all_mutants in the JSON output
+    #[arg(long)]
+    full_results: bool,
+    /// Test runner: pytest, unittest, jest, vitest, mocha, cargo-test, cargo-nextest, go-test, maven or gradle (default: detected from --test-cmd and the project)
+    #[arg(long, value_name = "RUNNER")]
+    runner: Option<Runner>,
+    /// How the test file is passed to the test command: auto (as the runner expects), path, relative (to the working directory) or none
+    #[arg(long, value_name = "STYLE", default_value = "auto")]
+    test_arg_style: TestArgStyle,
+    /// Don't pass the test file to the test command; for commands that select their own tests (same as --test-arg-style none)
+    #[arg(long, conflicts_with = "test_arg_style")]
+    no_test_arg: bool,
+    /// Also generate an opt-in operator (repeatable): string_mut (Python string literals), collection_method (min/max, sorted, push, slice, first/last swaps)
+    #[arg(long, value_name = "OPERATOR", value_parser = PossibleValuesParser::new(operators::OPT_IN))]
+    enable: Vec<String>,
+    /// JSON file of custom operators (node kinds and/or a regex over node text, a replacement and a name), applied alongside the built-ins
+    #[arg(long, value_name = "FILE")]
+    operators: Option<PathBuf>,
+    /// Run the baseline N more times and abort with a flaky-tests error if the runs disagree
+    #[arg(long, value_name = "N", default_value = "0")]
+    baseline_retries: u32,
+    /// Re-run each surviving mutant once; it counts as killed if the re-run fails
+    #[arg(long)]
+    verify_survivors: bool,
+    /// Re-run each timed-out mutant once, after the others, with its timeout times MULT; 0 turns this off
+    #[arg(long, value_name = "MULT", default_value = "2")]
+    timeout_retry: f64,
+    /// Let each mutant's test run finish instead of stopping it at the first failure in its output
+    #[arg(long)]
+    no_early_exit: bool,
+    /// Print the resolved baseline and per-mutant commands (program, args, cwd, env) and exit without running them
+    #[arg(long)]
+    print_cmd: bool,
+    /// Compile or type-check each mutant before running tests (py_compile, cargo check, tsc --noEmit, node --check); failures count as unviable
+    #[arg(long)]
+    precheck: bool,
+    /// Refuse to start while this many other runs are active in the project (default: no limit)
+    #[arg(long, value_name = "N")]
+    max_sessions: Option<usize>,
+    /// Source lines of context shown either side of each survivor (0 for none)
+    #[arg(long, value_name = "N", default_value_t = mutants::DEFAULT_CONTEXT_LINES)]
+    context_lines: usize,
+    /// Always run the baseline, even if an identical one is cached in .mutator/baseline-cache.json
+    #[arg(long)]
+    no_baseline_cache: bool,
+    /// Run mutants in a random order, reproducible with the same SEED (default: a new seed, printed on stderr). Results are still listed in source order
+    #[arg(long, value_name = "SEED", num_args = 0..=1)]
+    shuffle: Option<Option<u64>>,
+    /// Run the most useful mutants first: lines changed most recently (git blame), then lines with the fewest kills in the last recorded run, then condition flips before other operators
+    #[arg(long, conflicts_with = "shuffle")]
+    prioritize: bool,
+}
+
+fn parse_score(value: &str) -> Result<f64, String> {
+    let score: f64 = value.parse().map_err(|_| format!("'{}' is not a number", value))?;
+    if (0.0..=1.0).contains(&score) {
+        Ok(score)
+    } else {
+        Err(format!("score must be between 0.0 and 1.0, got {}", value))
+    }
+}
+
+/// `42` or `app.py:42`.
+fn parse_location(value: &str) -> Result<(Option<String>, usize), String> {
+    let (file, line) = match value.rsplit_once(':') {
+        Some((file, line)) => (Some(file.to_string()), line),
+        None => (None, value),
+    };
+    match line.parse() {
+        Ok(line) if line > 0 => Ok((file, line)),
+        _ => Err(format!("expected LINE or FILE:LINE with a line number from 1, got '{}'", value)),
+    }
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Run mutation testing on a source file
-    Run {
+    Run(RunArgs),
+    /// Run mutation testing, then re-run whenever the source or test file changes
+    Watch(RunArgs),
+    /// Run mutation testing in an interactive terminal UI (same options as `run`; output flags are ignored)
+    #[cfg(feature = "tui")]
+    Tui(RunArgs),
+    /// Serve run/show/status as JSON-RPC over a Unix socket, keeping the project copy warm between runs
+    #[cfg(unix)]
+    Daemon {
+        /// Socket path (default: .mutator/daemon.sock)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+    },
+    /// List the mutations a run would test, without running any tests
+    List {
         /// Source file to mutate
         file: PathBuf,
-        /// Test file to run against mutations
-        #[arg(short, long)]
-        test: PathBuf,
-        /// Function name to scope mutations to (recommended)
+        /// Source language: python, rust, js, ts, tsx or java (default: from the file extension)
+        #[arg(long, value_name = "LANG")]
+        language: Option<Language>,
+        /// Function name to scope mutations to
         #[arg(short, long)]
         function: Option<String>,
-        /// Output JSON instead of human-readable text
+        /// Also list mutations in test code (Rust #[test]/#[cfg(test)], JS/TS describe/it/test callbacks and test files)
+        #[arg(long)]
+        include_tests: bool,
+        /// Also list mutations in module-level code (constants, lookup tables, top-level conditionals). Ignored with -f
+        #[arg(long)]
+        include_toplevel: bool,
+        /// Skip Python functions with any of these decorators, e.g. cached,property. Ignored with -f
+        #[arg(long, value_name = "NAMES", value_delimiter = ',')]
+        skip_decorated: Vec<String>,
+        /// Also list an opt-in operator's mutations (repeatable): string_mut, collection_method
+        #[arg(long, value_name = "OPERATOR", value_parser = PossibleValuesParser::new(operators::OPT_IN))]
+        enable: Vec<String>,
+        /// JSON file of custom operators to list as well
+        #[arg(long, value_name = "FILE")]
+        operators: Option<PathBuf>,
+        /// Output JSON
         #[arg(long)]
         json: bool,
-        /// Exit code only, no output
-        #[arg(short, long)]
-        quiet: bool,
-        /// Only mutate lines changed in git
+    },
+    /// Show details for a survived (@m1), timed-out (@t1), uncovered (@u1), suspected-equivalent (@e1) or resource-limited (@r1) mutant by ref, or every mutant near a line with --line
+    Show {
+        /// Mutant ref (e.g. @m1, m1, @t1, @u1, @e1 or @r1)
+        #[arg(name = "ref", required_unless_present = "line")]
+        mutant_ref: Option<String>,
+        /// List every mutant (any status) at or near this line of the last --full-results run, e.g. 42 or app.py:42
+        #[arg(long, value_name = "[FILE:]LINE", value_parser = parse_location, conflicts_with = "ref")]
+        line: Option<(Option<String>, usize)>,
+        /// With --line, how many lines either side count as near
+        #[arg(long, value_name = "LINES", default_value = "2", requires = "line")]
+        near: usize,
+        /// Output JSON
         #[arg(long)]
-        in_diff: bool,
-        /// Test command override (default: pytest)
-        #[arg(long, default_value = "pytest")]
-        test_cmd: String,
-        /// Timeout multiplier for test runs (default: 3x baseline)
-        #[arg(long, default_value = "3")]
-        timeout_mult: f64,
-        /// Session ID for isolation (default: auto-generated). Agents should pass their own.
+        json: bool,
+        /// Read the last run of this session (as passed to `run --session`)
         #[arg(long)]
         session: Option<String>,
-        /// Mutate source in-place instead of copying to temp dir (unsafe for concurrent use)
-        #[arg(long)]
-        in_place: bool,
     },
-    /// Show details for a survived mutant by ref
-    Show {
-        /// Mutant ref (e.g. @m1 or m1)
+    /// Suggest a test for each survivor (or just REF): inputs at the mutated boundary and a test skeleton
+    DiffTests {
+        /// Only this mutant (e.g. @m1); any ref `show` accepts
         #[arg(name = "ref")]
-        mutant_ref: String,
+        mutant_ref: Option<String>,
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+        /// Read the last run of this session (as passed to `run --session`)
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Score trend across past runs
+    History {
+        /// Only runs on this source file
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Output JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare two recorded runs: newly killed and newly surviving mutants, score and per-function changes
+    Compare {
+        /// Earlier run: ~N for N runs before the latest, or a saved run JSON file (default: ~1)
+        #[arg(default_value = "~1")]
+        before: String,
+        /// Later run, as for BEFORE (default: ~0, the latest)
+        #[arg(default_value = "~0")]
+        after: String,
+        /// Only consider runs on this source file
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Only consider runs of this session (as passed to `run --session`)
+        #[arg(long)]
+        session: Option<String>,
         /// Output JSON
         #[arg(long)]
         json: bool,
@@ -67,344 +336,627 @@ enum Commands {
         /// Output JSON
         #[arg(long)]
         json: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: Format,
+        /// Read the last run of this session (as passed to `run --session`)
+        #[arg(long)]
+        session: Option<String>,
+    },
+    /// Write the last run's survived and uncovered mutants as JUnit or Checkstyle XML for CI dashboards
+    #[command(group = clap::ArgGroup::new("reports").required(true).multiple(true))]
+    Report {
+        /// JUnit XML: one failing test case per mutant (classname = file, name = ref)
+        #[arg(long, value_name = "PATH", group = "reports")]
+        junit: Option<PathBuf>,
+        /// Checkstyle XML: one warning per mutant on its line
+        #[arg(long, value_name = "PATH", group = "reports")]
+        checkstyle: Option<PathBuf>,
+        /// Read the last run of this session (as passed to `run --session`)
+        #[arg(long)]
+        session: Option<String>,
     },
 }
 
 fn main() {
     let cli = Cli::parse();
+    if let Err(e) = init_logging(cli.verbose, cli.log_file.as_deref()) {
+        output::print_error(&e);
+        process::exit(2);
+    }
 
     let exit_code = match cli.command {
-        Commands::Run {
-            file,
-            test,
-            function,
-            json,
-            quiet,
-            in_diff,
-            test_cmd,
-            timeout_mult,
-            session,
-            in_place,
-        } => cmd_run(file, test, function, json, quiet, in_diff, test_cmd, timeout_mult, session, in_place),
-        Commands::Show { mutant_ref, json } => cmd_show(mutant_ref, json),
-        Commands::Status { json } => cmd_status(json),
+        Commands::Run(args) => with_stdin_source(args, cmd_run),
+        Commands::Watch(args) => with_stdin_source(args, cmd_watch),
+        #[cfg(feature = "tui")]
+        Commands::Tui(args) => with_stdin_source(args, cmd_tui),
+        #[cfg(unix)]
+        Commands::Daemon { socket } => cmd_daemon(socket),
+        Commands::List { file, language, function, include_tests, include_toplevel, skip_decorated, enable, operators, json } => {
+            let options = DiscoverOptions {
+                include_tests,
+                include_toplevel,
+                enable: &enable,
+                skip_decorated: &skip_decorated,
+                ..Default::default()
+            };
+            cmd_list(file, language, function.as_deref(), options, operators.as_deref(), json)
+        }
+        Commands::Show { mutant_ref: Some(mutant_ref), json, session, .. } => {
+            cmd_show(mutant_ref, json, session.as_deref())
+        }
+        Commands::Show { line, near, json, session, .. } => {
+            let (file, line) = line.expect("clap requires a ref or --line");
+            cmd_show_near(file.as_deref(), line, near, json, session.as_deref())
+        }
+        Commands::DiffTests { mutant_ref, json, session } => {
+            cmd_diff_tests(mutant_ref.as_deref(), json, session.as_deref())
+        }
+        Commands::History { file, json } => cmd_history(file, json),
+        Commands::Compare { before, after, file, session, json } => {
+            cmd_compare(&before, &after, file.as_deref(), session.as_deref(), json)
+        }
+        Commands::Status { json, format, session } => {
+            cmd_status(resolve_format(json, format), session.as_deref())
+        }
+        Commands::Report { junit, checkstyle, session } => {
+            cmd_report(junit.as_deref(), checkstyle.as_deref(), session.as_deref())
+        }
     };
 
     process::exit(exit_code);
 }
 
-fn generate_session_id() -> String {
-    format!("{:08x}", fastrand::u32(..))
+/// Set up `log` output for -v/-vv and --log-file. Only mutator's own
+/// records are shown; `RUST_LOG` can still override.
+fn init_logging(verbose: u8, log_file: Option<&Path>) -> Result<(), String> {
+    let level = match (verbose, log_file) {
+        (0, None) => log::LevelFilter::Warn,
+        (1, _) => log::LevelFilter::Info,
+        (0, Some(_)) | (2, _) => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    let mut builder = env_logger::Builder::new();
+    builder.filter_level(log::LevelFilter::Warn).filter_module("mutator", level).parse_default_env();
+    if let Some(path) = log_file {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open log file {}: {}", path.display(), e))?;
+        builder.target(env_logger::Target::Pipe(Box::new(file))).write_style(env_logger::WriteStyle::Never);
+    }
+    builder.try_init().map_err(|e| e.to_string())
 }
 
-fn cmd_run(
-    file: PathBuf,
-    test: PathBuf,
-    function: Option<String>,
-    json_mode: bool,
-    quiet: bool,
-    _in_diff: bool,
-    test_cmd: String,
-    timeout_mult: f64,
-    session: Option<String>,
-    in_place: bool,
-) -> i32 {
-    let (abs_file, abs_test, _working_dir, resolved_cmd) =
-        runner::resolve_paths(&file, &test, &test_cmd);
-
-    // Legacy: recover from a previously interrupted in-place run
-    if let Some(bak_path) = safety::check_interrupted_run(&abs_file) {
-        if safety::restore_from_backup(&abs_file, &bak_path).is_ok() {
-            output::print_error(
-                "Recovered source file from a previously interrupted run. Re-run to continue."
-            );
-            return 3;
+/// For a `-` source, read the source text from stdin and mutate it as
+/// `--stdin-path`.
+fn with_stdin_source(mut args: RunArgs, cmd: fn(RunArgs) -> i32) -> i32 {
+    if args.file == Path::new("-") {
+        match std::io::read_to_string(std::io::stdin()) {
+            Ok(text) => {
+                args.source_text = Some(text);
+                // Required by clap when the file is -
+                args.file = args.stdin_path.take().unwrap_or_default();
+            }
+            Err(e) => {
+                output::print_error(&format!("Failed to read source from stdin: {}", e));
+                return 3;
+            }
         }
     }
+    cmd(args)
+}
 
-    if !abs_file.exists() {
-        output::print_error(&format!(
-            "Source file not found: {}. Check the path and try again.",
-            abs_file.display()
-        ));
-        return 2;
+fn cmd_run(args: RunArgs) -> i32 {
+    let report = report_for(&args);
+    let file = args.file.clone();
+    let in_place = args.in_place;
+    let print_cmd = args.print_cmd;
+
+    let mut engine = match engine_config(args).and_then(MutatorEngine::new) {
+        Ok(e) => e,
+        Err(e) => {
+            output::print_error(&e.to_string());
+            return e.exit_code();
+        }
+    };
+
+    let mutations = engine.discover();
+    if print_cmd {
+        let plan = engine.command_plan();
+        match report.format {
+            Format::Json => println!("{}", serde_json::to_string_pretty(&plan).unwrap_or_default()),
+            _ => output::print_command_plan(&plan, mutations.len()),
+        }
+        return 0;
     }
-    if !abs_test.exists() {
-        output::print_error(&format!(
-            "Test file not found: {}. Pass --test <path> with a valid test file.",
-            abs_test.display()
-        ));
-        return 2;
+    if mutations.is_empty() {
+        if !report.quiet && !print_machine_readable(&engine::summarize(&[], &file), report.format) {
+            output::print_success("No mutable code found.");
+        }
+        return 0;
+    }
+
+    if let Err(e) = signals::install() {
+        output::print_error(&e);
+    }
+    signals::track_run(&file, report.session.as_deref());
+    if in_place {
+        signals::track_in_place(engine.source_path());
+    }
+
+    if let Err(e) = engine.prepare() {
+        signals::cleanup();
+        output::print_error(&e.to_string());
+        return e.exit_code();
+    }
+    // Tracked before the baseline, so an interrupted build or baseline run
+    // still removes the copy
+    if let Some(dir) = engine.temp_dir() {
+        signals::track_temp_dir(dir);
+    }
+    if let Err(e) = engine.baseline() {
+        signals::cleanup();
+        output::print_error(&e.to_string());
+        return e.exit_code();
     }
 
-    let source = match std::fs::read_to_string(&abs_file) {
-        Ok(s) => s,
+    if let Some(seed) = engine.shuffle_seed() {
+        if !report.quiet {
+            output::print_note(&format!("Running mutants in shuffled order (--shuffle {} repeats it)", seed));
+        }
+    }
+    let mut progress = (report.format == Format::Text && !report.quiet).then(|| output::Progress::new(mutations.len()));
+    let run = engine.run_all_with(&mutations, |i, r| {
+        signals::record(i, r);
+        if let Some(progress) = progress.as_mut() {
+            progress.update(r);
+        }
+    });
+    if let Some(progress) = &progress {
+        progress.finish();
+    }
+    let results = match run {
+        Ok(r) => r,
         Err(e) => {
-            output::print_error(&format!("Failed to read {}: {}", abs_file.display(), e));
-            return 3;
+            signals::cleanup();
+            output::print_error(&e.to_string());
+            return e.exit_code();
         }
     };
+    signals::untrack();
 
-    let lang = match mutator::detect_language(&abs_file) {
-        Some(l) => l,
-        None => {
-            output::print_error(&format!(
-                "Unsupported file type: {}. Supported: .py, .rs, .js, .ts, .tsx, .jsx",
-                abs_file.display()
-            ));
-            return 2;
+    finalize_results(&results, &file, &report)
+}
+
+fn report_for(args: &RunArgs) -> Report {
+    Report {
+        format: resolve_format(args.json, args.format),
+        quiet: args.quiet,
+        fail_on: args
+            .fail_on
+            .unwrap_or(if args.fail_under.is_some() { FailOn::Threshold } else { FailOn::AnySurvivor }),
+        fail_under: args.fail_under.unwrap_or(1.0),
+        function: args.function.clone(),
+        session: args.session.clone(),
+        full_results: args.full_results,
+    }
+}
+
+fn engine_config(args: RunArgs) -> Result<EngineConfig, EngineError> {
+    let test_file = match args.test {
+        Some(test) => test,
+        None => test_discovery::find_test_file(&args.file)?,
+    };
+    let test_cmd = args.test_cmd.unwrap_or_else(|| test_adapters::detect_test_cmd(&args.file, args.language));
+    Ok(EngineConfig {
+        source_file: args.file,
+        test_file,
+        source_text: args.source_text,
+        language: args.language,
+        function: args.function,
+        test_cmd,
+        timeout_mult: args.timeout_mult,
+        max_timeout_secs: args.max_timeout,
+        session: args.session,
+        in_place: args.in_place,
+        cwd: args.cwd,
+        coverage: args.coverage,
+        include_tests: args.include_tests,
+        include_toplevel: args.include_toplevel,
+        skip_decorated: args.skip_decorated,
+        test_map: args.test_map,
+        test_filter: args.test_filter,
+        copy_mode: args.copy_mode,
+        env: args.env,
+        env_file: args.env_file,
+        limits: ResourceLimits { max_memory_mb: args.max_memory_mb, cpu_seconds: args.cpu_limit },
+        runner: args.runner,
+        test_arg_style: if args.no_test_arg { TestArgStyle::None } else { args.test_arg_style },
+        enable: args.enable,
+        operators: args.operators,
+        baseline_retries: args.baseline_retries,
+        verify_survivors: args.verify_survivors,
+        timeout_retry: args.timeout_retry,
+        early_exit: !args.no_early_exit,
+        precheck: args.precheck,
+        max_sessions: args.max_sessions,
+        context_lines: args.context_lines,
+        baseline_cache: !args.no_baseline_cache,
+        shuffle: args.shuffle.map(|seed| seed.unwrap_or_else(|| fastrand::u32(..).into())),
+        prioritize: args.prioritize,
+    })
+}
+
+fn cmd_watch(args: RunArgs) -> i32 {
+    let report = report_for(&args);
+    let config = match engine_config(args) {
+        Ok(config) => config,
+        Err(e) => {
+            output::print_error(&e.to_string());
+            return e.exit_code();
         }
     };
+    if let Err(e) = MutatorEngine::new(config.clone()) {
+        output::print_error(&e.to_string());
+        return e.exit_code();
+    }
+    let watcher = match watch::Watcher::new(&[&config.source_file, &config.test_file]) {
+        Ok(w) => w,
+        Err(e) => {
+            output::print_error(&e);
+            return 3;
+        }
+    };
+    if let Err(e) = signals::install() {
+        output::print_error(&e);
+    }
 
-    if let Some(ref fn_name) = function {
-        let available = match lang {
-            mutator::Language::Python => parser::list_functions(&source),
-            mutator::Language::Rust => parser_rust::list_functions(&source),
-            mutator::Language::JavaScript => parser_js::list_functions(&source, parser_js::JsDialect::JavaScript),
-            mutator::Language::TypeScript => parser_js::list_functions(&source, parser_js::JsDialect::TypeScript),
-            mutator::Language::Tsx => parser_js::list_functions(&source, parser_js::JsDialect::Tsx),
-        };
-        if !available.iter().any(|n| n == fn_name) {
-            output::print_error(&format!(
-                "Function '{}' not found. Available: {}",
-                fn_name,
-                available.join(", ")
-            ));
-            return 2;
+    let mut cache = watch::ResultCache::new();
+    let contents = || {
+        [&config.source_file, &config.test_file].map(|p| std::fs::read(p).unwrap_or_default())
+    };
+    loop {
+        let started = contents();
+        watch_cycle(&config, &report, &mut cache);
+        if report.format == Format::Text && !report.quiet {
+            println!(
+                "\nWatching {} and {} (Ctrl-C to stop)",
+                config.source_file.display(),
+                config.test_file.display()
+            );
+        }
+        // Compare contents rather than trusting events: in-place runs write
+        // the source themselves, and edits made mid-run must still trigger.
+        while contents() == started {
+            if watcher.wait(std::time::Duration::from_millis(200)).is_empty() {
+                output::print_error("File watcher stopped.");
+                return 3;
+            }
         }
     }
+}
 
-    let mutations = match lang {
-        mutator::Language::Python => parser::discover_mutations(&source, function.as_deref()),
-        mutator::Language::Rust => parser_rust::discover_mutations(&source, function.as_deref()),
-        mutator::Language::JavaScript => parser_js::discover_mutations(&source, function.as_deref(), parser_js::JsDialect::JavaScript),
-        mutator::Language::TypeScript => parser_js::discover_mutations(&source, function.as_deref(), parser_js::JsDialect::TypeScript),
-        mutator::Language::Tsx => parser_js::discover_mutations(&source, function.as_deref(), parser_js::JsDialect::Tsx),
+/// One `watch` iteration: run the mutants whose cached result no longer
+/// applies, streaming each result, then report like `run`.
+fn watch_cycle(config: &EngineConfig, report: &Report, cache: &mut watch::ResultCache) {
+    let mut engine = match MutatorEngine::new(config.clone()) {
+        Ok(e) => e,
+        Err(e) => {
+            output::print_error(&e.to_string());
+            return;
+        }
     };
+    let test_source = std::fs::read_to_string(&config.test_file).unwrap_or_default();
+    let mutations = engine.discover();
     if mutations.is_empty() {
-        if !quiet {
-            if json_mode {
-                let result = state::RunResult {
-                    score: 1.0,
-                    total: 0,
-                    killed: 0,
-                    survived: 0,
-                    timeout: 0,
-                    unviable: 0,
-                    duration_ms: 0,
-                    survived_mutants: vec![],
-                };
-                println!("{}", serde_json::to_string(&result).unwrap());
-            } else {
-                output::print_success("No mutable code found.");
-            }
+        if report.format == Format::Text && !report.quiet {
+            output::print_success("No mutable code found.");
         }
-        return 0;
+        return;
     }
 
-    let (baseline_args, mutation_args): (Vec<&str>, Vec<&str>) = match lang {
-        mutator::Language::Python => (
-            vec!["-x", "-q", "--tb=short", "--no-header"],
-            vec!["-x", "-q", "--tb=no", "--no-header", "-p", "no:cacheprovider"],
-        ),
-        mutator::Language::Rust => (
-            vec!["--", "--test-threads=1"],
-            vec!["--", "--test-threads=1"],
-        ),
-        mutator::Language::JavaScript | mutator::Language::TypeScript | mutator::Language::Tsx => (
-            vec!["--bail"],
-            vec!["--bail"],
-        ),
+    let stream = |r: &mutator::mutants::MutantResult, reused: bool| {
+        if report.quiet {
+            return;
+        }
+        match report.format {
+            Format::Text => output::print_mutant_result(r, &config.source_file, reused),
+            Format::Json => {
+                let m = &r.mutation;
+                let event = serde_json::json!({
+                    "schema_version": schema::SCHEMA_VERSION,
+                    "event": "mutant",
+                    "line": m.line,
+                    "column": m.column,
+                    "operator": m.operator,
+                    "original": m.original,
+                    "replacement": m.replacement,
+                    "function": m.function,
+                    "status": r.status,
+                    "reused": reused,
+                });
+                println!("{}", event);
+            }
+            Format::Sarif | Format::Markdown | Format::Github | Format::GitlabCodequality => {}
+        }
     };
 
-    if in_place {
-        return run_in_place(
-            &abs_file, &abs_test, &source, &mutations, &resolved_cmd,
-            &_working_dir, &baseline_args, &mutation_args,
-            timeout_mult, json_mode, quiet, &file,
-        );
+    let cached = cache.lookup(engine.language(), engine.source(), &test_source, &mutations);
+    let to_run: Vec<_> = mutations
+        .iter()
+        .zip(&cached)
+        .filter(|(_, c)| c.is_none())
+        .map(|(m, _)| m.clone())
+        .collect();
+    for r in cached.iter().flatten() {
+        stream(r, true);
+    }
+
+    let mut fresh = Vec::new();
+    if !to_run.is_empty() {
+        signals::track_run(&config.source_file, report.session.as_deref());
+        if config.in_place {
+            signals::track_in_place(engine.source_path());
+        }
+        if let Err(e) = engine.baseline() {
+            signals::cleanup();
+            output::print_error(&e.to_string());
+            return;
+        }
+        if let Some(dir) = engine.temp_dir() {
+            signals::track_temp_dir(dir);
+        }
+        let run = engine.run_all_with(&to_run, |i, r| {
+            signals::record(i, r);
+            stream(r, false);
+        });
+        fresh = match run {
+            Ok(r) => r,
+            Err(e) => {
+                signals::cleanup();
+                output::print_error(&e.to_string());
+                return;
+            }
+        };
+        signals::untrack();
     }
 
-    // Default: isolated tree-copy mode
-    let session_id = session.unwrap_or_else(generate_session_id);
+    let mut fresh = fresh.into_iter();
+    let results: Vec<_> = cached
+        .into_iter()
+        .filter_map(|c| c.or_else(|| fresh.next()))
+        .collect();
+    cache.store(engine.language(), engine.source(), &test_source, &results);
+    finalize_results(&results, &config.source_file, report);
+}
+
+#[cfg(feature = "tui")]
+fn cmd_tui(args: RunArgs) -> i32 {
+    use mutator::tui::{self, TuiExit};
+    use std::io::IsTerminal;
 
-    let ctx = match runner::prepare_isolated(&abs_file, &abs_test, &test_cmd, &session_id) {
-        Ok(c) => c,
+    if !std::io::stdout().is_terminal() {
+        output::print_error("mutator tui needs a terminal; use `mutator run` instead.");
+        return 2;
+    }
+    // The summary is printed as text once the UI closes
+    let report = Report { format: Format::Text, quiet: false, ..report_for(&args) };
+    let file = args.file.clone();
+    let in_place = args.in_place;
+
+    let engine = match engine_config(args).and_then(MutatorEngine::new) {
+        Ok(e) => e,
         Err(e) => {
-            output::print_error(&format!("Failed to set up isolated environment: {}", e));
-            return 3;
+            output::print_error(&e.to_string());
+            return e.exit_code();
         }
     };
+    let mutations = engine.discover();
+    if mutations.is_empty() {
+        output::print_success("No mutable code found.");
+        return 0;
+    }
 
-    let baseline = runner::run_baseline(
-        &ctx.resolved_cmd,
-        &ctx.copy_result.test_file,
-        &ctx.copy_result.root,
-        &baseline_args,
-    );
-    match baseline {
-        runner::BaselineResult::Failed(stderr) => {
-            output::print_error(&format!(
-                "Tests fail before mutation. Fix failing tests first.\n{}",
-                stderr
-            ));
-            3
-        }
-        runner::BaselineResult::Ok { duration_ms } => {
-            let timeout_ms = (duration_ms as f64 * timeout_mult) as u64 + 2000;
-
-            let results = runner::run_mutations_isolated(
-                &ctx,
-                &source,
-                &mutations,
-                timeout_ms,
-                &mutation_args,
-            );
+    if let Err(e) = signals::install() {
+        output::print_error(&e);
+    }
+    signals::track_run(&file, report.session.as_deref());
+    if in_place {
+        signals::track_in_place(engine.source_path());
+    }
 
-            finalize_results(&results, &mutations, &file, json_mode, quiet)
+    match tui::run(engine, mutations, &file.display().to_string()) {
+        Ok(TuiExit::Completed(results)) => {
+            signals::untrack();
+            finalize_results(&results, &file, &report)
+        }
+        Ok(TuiExit::Failed(e)) => {
+            signals::cleanup();
+            output::print_error(&e.to_string());
+            e.exit_code()
+        }
+        // Raw mode turns Ctrl-C into a key press, so quitting mid-run takes
+        // the same path as the signal handler.
+        Ok(TuiExit::Interrupted) => signals::interrupt(),
+        Err(e) => {
+            output::print_error(&format!("Terminal error: {}", e));
+            signals::interrupt()
         }
     }
 }
 
-/// Legacy in-place mutation mode (--in-place flag)
-fn run_in_place(
-    abs_file: &std::path::Path,
-    abs_test: &std::path::Path,
-    source: &str,
-    mutations: &[mutator::mutants::Mutation],
-    resolved_cmd: &str,
-    working_dir: &std::path::Path,
-    baseline_args: &[&str],
-    mutation_args: &[&str],
-    timeout_mult: f64,
-    json_mode: bool,
-    quiet: bool,
-    display_file: &std::path::Path,
-) -> i32 {
-    let baseline = runner::run_baseline(resolved_cmd, abs_test, working_dir, baseline_args);
-    match baseline {
-        runner::BaselineResult::Failed(stderr) => {
-            output::print_error(&format!(
-                "Tests fail before mutation. Fix failing tests first.\n{}",
-                stderr
-            ));
-            3
+/// Print `result` in a machine-readable format. Returns false for text
+/// output, which each command renders itself.
+fn print_machine_readable(result: &state::RunResult, format: Format) -> bool {
+    match format {
+        Format::Json => println!("{}", schema::to_json(result)),
+        Format::Sarif => println!("{}", serde_json::to_string_pretty(&sarif::to_sarif(result)).unwrap()),
+        Format::Markdown => print!("{}", markdown::to_markdown(result)),
+        Format::Github => {
+            print!("{}", github::to_annotations(result));
+            if let Err(e) = github::write_step_summary(result) {
+                output::print_note(&format!("Could not write $GITHUB_STEP_SUMMARY: {}", e));
+            }
         }
-        runner::BaselineResult::Ok { duration_ms } => {
-            let timeout_ms = (duration_ms as f64 * timeout_mult) as u64 + 2000;
-
-            // In-place: write backup, mutate original, restore after
-            let backup_content = source.to_string();
-            let results = runner::run_mutations(
-                abs_file,
-                abs_test,
-                source,
-                mutations,
-                resolved_cmd,
-                working_dir,
-                timeout_ms,
-                mutation_args,
-            );
-            // run_mutations already restores original
-            let _ = backup_content; // ensure we have the original
-
-            finalize_results(&results, mutations, display_file, json_mode, quiet)
+        Format::GitlabCodequality => {
+            println!("{}", serde_json::to_string_pretty(&gitlab::to_codequality(result)).unwrap())
         }
+        Format::Text => return false,
     }
+    true
+}
+
+/// How `run` reports and records a completed run.
+struct Report {
+    format: Format,
+    quiet: bool,
+    fail_on: FailOn,
+    fail_under: f64,
+    function: Option<String>,
+    session: Option<String>,
+    full_results: bool,
 }
 
 fn finalize_results(
     results: &[mutator::mutants::MutantResult],
-    _mutations: &[mutator::mutants::Mutation],
     display_file: &std::path::Path,
-    json_mode: bool,
-    quiet: bool,
+    report: &Report,
 ) -> i32 {
-    let survived: Vec<_> = results
-        .iter()
-        .filter(|r| r.status == mutants::MutantStatus::Survived)
-        .collect();
-    let killed = results.iter().filter(|r| r.status == mutants::MutantStatus::Killed).count();
-    let timed_out = results.iter().filter(|r| r.status == mutants::MutantStatus::Timeout).count();
-    let unviable = results.iter().filter(|r| r.status == mutants::MutantStatus::Unviable).count();
-    let total = results.len();
-    let testable = total - unviable;
-    let score = if testable > 0 {
-        killed as f64 / testable as f64
-    } else {
-        1.0
-    };
+    let mut run_result = engine::summarize(results, display_file);
+    if report.full_results {
+        run_result.all_mutants = engine::all_mutants(results, display_file);
+    }
 
-    let display_str = display_file.display().to_string();
-    let survived_details: Vec<state::SurvivedMutant> = survived
-        .iter()
-        .enumerate()
-        .map(|(i, r)| {
-            let m = &r.mutation;
-            state::SurvivedMutant {
-                ref_id: format!("m{}", i + 1),
-                file: display_str.clone(),
-                line: m.line,
-                column: m.column,
-                operator: m.operator.clone(),
-                original: m.original.clone(),
-                replacement: m.replacement.clone(),
-                diff: r.diff.clone(),
-                context_before: m.context_before.clone(),
-                context_after: m.context_after.clone(),
-            }
-        })
-        .collect();
+    state::save_last_run(&run_result, report.session.as_deref());
+    history::record(
+        &run_result,
+        display_file,
+        report.function.as_deref(),
+        report.session.as_deref(),
+    );
 
-    let run_result = state::RunResult {
-        score,
-        total,
-        killed,
-        survived: survived_details.len(),
-        timeout: timed_out,
-        unviable,
-        duration_ms: results.iter().map(|r| r.duration_ms).sum(),
-        survived_mutants: survived_details,
+    let failed = match report.fail_on {
+        FailOn::AnySurvivor => run_result.survived + run_result.uncovered > 0,
+        // Tolerate float noise so 4/5 passes --fail-under 0.8
+        FailOn::Threshold => run_result.score + 1e-9 < report.fail_under,
+        FailOn::Never => false,
     };
+    let exit_code = if failed { 1 } else { 0 };
+    if report.quiet {
+        return exit_code;
+    }
 
-    state::save_last_run(&run_result);
+    if !print_machine_readable(&run_result, report.format) {
+        output::print_run_result(&run_result, display_file);
+    }
 
-    if quiet {
-        return if run_result.survived > 0 { 1 } else { 0 };
+    exit_code
+}
+
+#[cfg(unix)]
+fn cmd_daemon(socket: Option<PathBuf>) -> i32 {
+    use mutator::daemon;
+
+    let path = socket.unwrap_or_else(daemon::socket_path);
+    if let Err(e) = signals::install() {
+        output::print_error(&e);
+    }
+    eprintln!("Listening on {}", path.display());
+    match daemon::serve(&path) {
+        Ok(()) => 0,
+        Err(e) => {
+            output::print_error(&e);
+            3
+        }
     }
+}
+
+fn cmd_list(
+    file: PathBuf,
+    language: Option<Language>,
+    function: Option<&str>,
+    options: DiscoverOptions,
+    operators: Option<&Path>,
+    json_mode: bool,
+) -> i32 {
+    let listed = operators.map(engine::load_custom_operators).transpose().and_then(|custom| {
+        let custom = custom.unwrap_or_default();
+        engine::list_mutations(&file, language, function, &DiscoverOptions { operators: &custom, ..options })
+    });
+    let mutations = match listed {
+        Ok(m) => m,
+        Err(e) => {
+            output::print_error(&e.to_string());
+            return e.exit_code();
+        }
+    };
 
     if json_mode {
-        println!("{}", serde_json::to_string(&run_result).unwrap());
+        let items: Vec<_> = mutations
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "line": m.line,
+                    "column": m.column,
+                    "operator": m.operator,
+                    "original": m.original,
+                    "replacement": m.replacement,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string(&items).unwrap());
     } else {
-        output::print_run_result(&run_result, display_file);
+        output::print_mutation_list(&mutations, &file);
+    }
+    0
+}
+
+fn cmd_show_near(file: Option<&str>, line: usize, near: usize, json_mode: bool, session: Option<&str>) -> i32 {
+    let last_run = match state::load_last_run(session) {
+        Some(r) => r,
+        None => {
+            output::print_error(&no_previous_run(session));
+            return 2;
+        }
+    };
+    if last_run.all_mutants.is_empty() {
+        output::print_error("The last run didn't record every mutant. Re-run with --full-results to look mutants up by line.");
+        return 2;
     }
 
-    if run_result.survived > 0 { 1 } else { 0 }
+    let found = last_run.mutants_near(file, line, near);
+    if json_mode {
+        println!("{}", schema::to_json(&found));
+    } else {
+        output::print_mutants_near(&found);
+    }
+    0
 }
 
-fn cmd_show(mutant_ref: String, json_mode: bool) -> i32 {
+fn cmd_show(mutant_ref: String, json_mode: bool, session: Option<&str>) -> i32 {
     let ref_id = mutant_ref.trim_start_matches('@');
 
-    let last_run = match state::load_last_run() {
+    let last_run = match state::load_last_run(session) {
         Some(r) => r,
         None => {
-            output::print_error("No previous run found. Run `mutator run` first.");
+            output::print_error(&no_previous_run(session));
             return 2;
         }
     };
 
-    let mutant = last_run.survived_mutants.iter().find(|m| m.ref_id == ref_id);
-    match mutant {
+    match last_run.find_ref(ref_id) {
         Some(m) => {
             if json_mode {
-                println!("{}", serde_json::to_string(m).unwrap());
+                println!("{}", schema::to_json(m));
             } else {
                 output::print_mutant_detail(m);
             }
             0
         }
         None => {
-            let valid: Vec<_> = last_run.survived_mutants.iter().map(|m| format!("@{}", m.ref_id)).collect();
+            let valid: Vec<_> = last_run.referenced().map(|m| format!("@{}", m.ref_id)).collect();
             output::print_error(&format!(
                 "Mutant @{} not found. Valid refs: {}",
                 ref_id,
@@ -415,18 +967,143 @@ fn cmd_show(mutant_ref: String, json_mode: bool) -> i32 {
     }
 }
 
-fn cmd_status(json_mode: bool) -> i32 {
-    match state::load_last_run() {
+fn cmd_diff_tests(mutant_ref: Option<&str>, json_mode: bool, session: Option<&str>) -> i32 {
+    let last_run = match state::load_last_run(session) {
+        Some(r) => r,
+        None => {
+            output::print_error(&no_previous_run(session));
+            return 2;
+        }
+    };
+
+    let mutants: Vec<_> = match mutant_ref {
+        Some(r) => match last_run.find_ref(r) {
+            Some(m) => vec![m],
+            None => {
+                output::print_error(&format!("Mutant @{} not found", r.trim_start_matches('@')));
+                return 2;
+            }
+        },
+        None => last_run.survived_mutants.iter().collect(),
+    };
+    let suggestions: Vec<_> = mutants.into_iter().map(suggest::suggest).collect();
+
+    if json_mode {
+        println!("{}", serde_json::to_string(&suggestions).unwrap());
+    } else if suggestions.is_empty() {
+        output::print_success("No survivors in the last run.");
+    } else {
+        for s in &suggestions {
+            output::print_suggestion(s);
+        }
+    }
+    0
+}
+
+fn cmd_report(junit: Option<&Path>, checkstyle: Option<&Path>, session: Option<&str>) -> i32 {
+    let Some(result) = state::load_last_run(session) else {
+        output::print_error(&no_previous_run(session));
+        return 2;
+    };
+    let mutants = match result.survived_mutants.len() + result.uncovered_mutants.len() {
+        1 => "1 mutant".to_string(),
+        n => format!("{} mutants", n),
+    };
+    let reports = [
+        junit.map(|path| (path, xml_report::to_junit(&result))),
+        checkstyle.map(|path| (path, xml_report::to_checkstyle(&result))),
+    ];
+    for (path, xml) in reports.into_iter().flatten() {
+        if let Err(e) = std::fs::write(path, xml) {
+            output::print_error(&format!("Failed to write {}: {}", path.display(), e));
+            return 3;
+        }
+        output::print_success(&format!("Wrote {} to {}", mutants, path.display()));
+    }
+    0
+}
+
+fn no_previous_run(session: Option<&str>) -> String {
+    match session {
+        Some(id) => format!("No previous run found for session '{}'. Run `mutator run --session {}` first.", id, id),
+        None => "No previous run found. Run `mutator run` first.".to_string(),
+    }
+}
+
+fn cmd_history(file: Option<PathBuf>, json_mode: bool) -> i32 {
+    let points = history::trend(history::load_from(&history::history_path()), file.as_deref());
+    if json_mode {
+        println!("{}", serde_json::to_string(&points).unwrap());
+        return 0;
+    }
+    if points.is_empty() {
+        output::print_error("No recorded runs found. Run `mutator run` first.");
+        return 2;
+    }
+    output::print_history(&points);
+    0
+}
+
+fn cmd_compare(before: &str, after: &str, file: Option<&Path>, session: Option<&str>, json_mode: bool) -> i32 {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let series = history::series(history::load_from(&history::history_path_in(&cwd)), file, session);
+    let runs = load_compared_run(before, &series, &cwd).and_then(|b| Ok((b, load_compared_run(after, &series, &cwd)?)));
+    let ((before_label, before), (after_label, after)) = match runs {
+        Ok(runs) => runs,
+        Err(e) => {
+            output::print_error(&e);
+            return 2;
+        }
+    };
+
+    let comparison = compare::compare(&before, &after);
+    if json_mode {
+        println!("{}", serde_json::to_string(&comparison).unwrap());
+    } else {
+        output::print_comparison(&comparison, &before_label, &after_label);
+    }
+    0
+}
+
+/// `~N` (N runs before the latest in `series`) or a saved run JSON file,
+/// with a label for the text output.
+fn load_compared_run(spec: &str, series: &[history::HistoryEntry], cwd: &Path) -> Result<(String, state::RunResult), String> {
+    let Some(back) = spec.strip_prefix('~') else {
+        let result = state::load_from_path(Path::new(spec)).ok_or_else(|| format!("Can't read a run result from {}", spec))?;
+        return Ok((spec.to_string(), result));
+    };
+    let back: usize = back.parse().map_err(|_| format!("Expected ~N or a file, got '{}'", spec))?;
+    let Some(entry) = series.len().checked_sub(back + 1).map(|i| &series[i]) else {
+        return Err(format!(
+            "{} needs {} recorded runs of the same file and function, found {}. Run `mutator run` again, or pass saved run files.",
+            spec,
+            back + 1,
+            series.len()
+        ));
+    };
+    let result = history::load_run(cwd, entry).ok_or_else(|| {
+        format!(
+            "The run at {} has no saved result (recorded by an older version, or .mutator/runs/ was cleared).",
+            entry.timestamp
+        )
+    })?;
+    let label = match &entry.function {
+        Some(f) => format!("{}::{} at {}", entry.file, f, entry.timestamp),
+        None => format!("{} at {}", entry.file, entry.timestamp),
+    };
+    Ok((label, result))
+}
+
+fn cmd_status(format: Format, session: Option<&str>) -> i32 {
+    match state::load_last_run(session) {
         Some(result) => {
-            if json_mode {
-                println!("{}", serde_json::to_string(&result).unwrap());
-            } else {
+            if !print_machine_readable(&result, format) {
                 output::print_status(&result);
             }
             0
         }
         None => {
-            output::print_error("No previous run found. Run `mutator run` first.");
+            output::print_error(&no_previous_run(session));
             2
         }
     }
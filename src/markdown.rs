@@ -0,0 +1,110 @@
+//! Markdown summary (`--format markdown`), meant to be pasted into PR
+//! descriptions or planning notes.
+
+use crate::state::{RunResult, SurvivedMutant};
+
+pub fn to_markdown(result: &RunResult) -> String {
+    let testable = result.testable();
+    let mut out = String::from("## Mutation testing\n\n");
+
+    out.push_str(&format!(
+        "**Score:** {:.1}% ({}/{} killed)",
+        result.score * 100.0,
+        result.killed,
+        testable
+    ));
+    for (count, label) in [
+        (result.survived, "survived"),
+        (result.uncovered, "uncovered"),
+        (result.timeout, "timed out"),
+        (result.resource_exceeded, "over resource limits"),
+        (result.unviable, "unviable"),
+        (result.equivalent, "suspected equivalent"),
+    ] {
+        if count > 0 {
+            out.push_str(&format!(" · {} {}", count, label));
+        }
+    }
+    if result.interrupted {
+        out.push_str(" · *interrupted, partial results*");
+    }
+    out.push('\n');
+
+    if result.survived_mutants.is_empty() && result.uncovered_mutants.is_empty() {
+        return out;
+    }
+
+    out.push_str("\n| Ref | Location | Operator | Change | Suggested assertion |\n");
+    out.push_str("|---|---|---|---|---|\n");
+    for m in &result.survived_mutants {
+        out.push_str(&row(m, assertion_hint(&m.operator)));
+    }
+    for m in &result.uncovered_mutants {
+        out.push_str(&row(m, "Write a test that executes this line first"));
+    }
+    out
+}
+
+fn row(m: &SurvivedMutant, hint: &str) -> String {
+    format!(
+        "| @{} | `{}:{}` | {} | {} → {} | {} |\n",
+        m.ref_id,
+        m.file,
+        m.line,
+        m.operator,
+        code_cell(&m.original),
+        code_cell(&m.replacement),
+        hint
+    )
+}
+
+/// Inline code that survives a table cell: one line, pipes escaped.
+fn code_cell(text: &str) -> String {
+    let single_line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if single_line.is_empty() {
+        return "*(empty)*".to_string();
+    }
+    let escaped = single_line.replace('|', "\\|");
+    if escaped.contains('`') {
+        format!("`` {} ``", escaped)
+    } else {
+        format!("`{}`", escaped)
+    }
+}
+
+/// What kind of assertion would kill a mutant from this operator.
+pub fn assertion_hint(operator: &str) -> &'static str {
+    match operator {
+        "boundary" => "Test the exact boundary value",
+        "len_boundary" => "Test with an empty collection and one of exactly the compared length",
+        "negate_cmp" | "negate_eq" | "negate_is" | "negate_in" => {
+            "Assert results on both sides of the condition"
+        }
+        "logic_flip" => "Cover a case where only one operand is true",
+        "negate_remove" | "bool_flip" => "Assert the behaviour for the opposite boolean",
+        "return_val" | "some_none" | "unwrap_or_default" => "Assert the exact return value",
+        "arith" | "aug_assign" => "Assert a computed value with non-trivial operands",
+        "string_mut" => "Assert the exact string content",
+        "string_method" => "Use input where the prefix and suffix (or the case) differ, and assert the result",
+        "collection_method" => "Assert the collection's exact contents and order, not just its size",
+        "trim_remove" => "Pass input with surrounding whitespace and assert it is stripped",
+        "block_remove" | "arm_remove" | "except_body" => "Assert a side effect of the removed branch",
+        "ternary_swap" => "Assert the result for both branch conditions",
+        "call_remove" => "Assert the call's side effect (state change, write, invalidation)",
+        "filter_remove" | "filter_negate" => "Include items the filter must exclude and assert they are dropped",
+        "render_negate" | "render_remove" => "Render with the condition on and off and assert the element appears only when it should",
+        "if_let_none" => "Cover both the Some and the None case",
+        "negate_matches" | "predicate_swap" => "Assert the result for values on both sides of the check",
+        "range_tweak" => "Assert on the first and last element the range covers",
+        "arg_swap" => "Call with distinct argument values whose order matters",
+        "default_arg" => "Call without the argument and assert the default behaviour",
+        "loop_control" | "loop_zero" => "Assert the loop's effect, including early exits",
+        "raise_remove" | "try_unwrap" => "Assert the error path (e.g. pytest.raises / is_err())",
+        "except_swap" => "Trigger the caught exception and assert how it is handled",
+        "optional_chain" => "Call with a null/undefined receiver",
+        "await_remove" => "Assert on the resolved value, not just the call",
+        "gather_drop" => "Assert on every gathered result or its side effect",
+        "sleep_zero" => "Assert the delay itself (e.g. with a fake clock or a patched asyncio.sleep)",
+        _ => "Add an assertion that distinguishes original from mutant",
+    }
+}
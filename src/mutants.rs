@@ -11,6 +11,71 @@ pub struct Mutation {
     pub replacement: String,
     pub context_before: Vec<String>,
     pub context_after: Vec<String>,
+    /// Innermost named function containing the mutation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+}
+
+impl Mutation {
+    /// `line:column:operator:replacement`, stable across runs while the
+    /// mutated code doesn't move.
+    pub fn id(&self) -> String {
+        format!("{}:{}:{}:{}", self.line, self.column, self.operator, self.replacement)
+    }
+}
+
+/// A named function's byte range, for `assign_functions`.
+pub struct FunctionSpan {
+    pub name: String,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+/// Set `function` on each mutation to the innermost span containing it.
+pub fn assign_functions(mutations: &mut [Mutation], spans: &[FunctionSpan]) {
+    for m in mutations {
+        m.function = spans
+            .iter()
+            .filter(|s| s.start_byte <= m.start_byte && m.end_byte <= s.end_byte)
+            .min_by_key(|s| s.end_byte - s.start_byte)
+            .map(|s| s.name.clone());
+    }
+}
+
+/// Put mutations in the order they are listed, run and given refs: by
+/// line, then operator name, then column and replacement, so the order is
+/// the same whatever order a parser found them in.
+pub fn sort_canonical(mutations: &mut [Mutation]) {
+    mutations.sort_by(|a, b| {
+        (a.line, &a.operator, a.column, &a.replacement).cmp(&(b.line, &b.operator, b.column, &b.replacement))
+    });
+}
+
+/// Run order for `len` mutants: `0..len`, or with `--shuffle` a
+/// permutation fixed by `seed`, so a shuffled run can be repeated.
+pub fn run_order(len: usize, shuffle: Option<u64>) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    if let Some(seed) = shuffle {
+        fastrand::Rng::with_seed(seed).shuffle(&mut order);
+    }
+    order
+}
+
+/// Lines of context kept either side of a mutation unless
+/// `--context-lines` says otherwise.
+pub const DEFAULT_CONTEXT_LINES: usize = 2;
+
+/// Set `context_before`/`context_after` on each mutation to up to
+/// `context_lines` source lines either side of its line.
+pub fn assign_context(mutations: &mut [Mutation], source: &str, context_lines: usize) {
+    let lines: Vec<&str> = source.lines().collect();
+    for m in mutations {
+        let idx = m.line.saturating_sub(1).min(lines.len());
+        let start = idx.saturating_sub(context_lines);
+        let end = (idx + context_lines + 1).min(lines.len());
+        m.context_before = lines[start..idx].iter().map(|s| s.to_string()).collect();
+        m.context_after = lines.get(idx + 1..end).unwrap_or_default().iter().map(|s| s.to_string()).collect();
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -19,6 +84,15 @@ pub enum MutantStatus {
     Survived,
     Timeout,
     Unviable,
+    /// No test executes the mutated line, per the coverage report.
+    /// Not run.
+    Uncovered,
+    /// Survived, but the operator swap can't change the result for these
+    /// operands (see `equivalence`). Not counted in the score.
+    SuspectedEquivalent,
+    /// The test run hit `--max-memory-mb` or `--cpu-limit`. Scored like a
+    /// timeout.
+    ResourceExceeded,
 }
 
 #[derive(Debug, Clone)]
@@ -27,4 +101,9 @@ pub struct MutantResult {
     pub status: MutantStatus,
     pub duration_ms: u64,
     pub diff: String,
+    /// Tail of the test run's combined stdout/stderr. For timeouts this is
+    /// whatever the child wrote before it was killed.
+    pub output_snippet: Option<String>,
+    /// First failing test reported by the runner, for killed mutants.
+    pub killed_by: Option<String>,
 }
@@ -0,0 +1,243 @@
+//! `MutationParser`: the interface the per-language parser modules
+//! (`parser`, `parser_rust`, `parser_js`) share. `Language::parser` picks
+//! the implementation, so callers don't match on the language themselves.
+//!
+//! The parsers also share their tree walks: each describes its language as
+//! a `Grammar` (which nodes define functions, what to mutate in a node,
+//! what to skip) and the functions here do the finding, collecting and
+//! walking, so every language follows the same skip rules.
+
+use tree_sitter::{Node, Parser, Tree};
+
+use crate::custom_operators::CustomOperator;
+use crate::mutants::{self, FunctionSpan, Mutation};
+
+/// What discovery covers besides production functions. Options a language
+/// has no use for are ignored.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions<'a> {
+    /// Also walk test code (Rust `#[test]` fns and `#[cfg(test)]` modules,
+    /// JS/TS test callbacks).
+    pub include_tests: bool,
+    /// Add the opt-in `string_mut` mutants (Python).
+    pub string_mut: bool,
+    /// Add the opt-in `collection_method` mutants (`mutate_collection`).
+    pub collection_method: bool,
+    /// Decorators whose functions are left out unless named (Python).
+    pub skip_decorated: &'a [String],
+    /// `--operators` tried on every node `walk` visits, for this language.
+    pub operators: &'a [CustomOperator],
+}
+
+pub trait MutationParser {
+    /// Mutations in `function`, or in every function when `None`, with
+    /// `function` and the default context filled in.
+    fn discover(&self, source: &str, function: Option<&str>, options: &ParseOptions) -> Vec<Mutation>;
+
+    /// Mutations in module-level code (`--include-toplevel`).
+    fn toplevel(&self, source: &str, options: &ParseOptions) -> Vec<Mutation>;
+
+    /// Byte spans of every function in `source`.
+    fn function_spans(&self, source: &str) -> Vec<FunctionSpan>;
+
+    /// Names `--function` accepts, qualified for methods and nested
+    /// functions.
+    fn list_functions(&self, source: &str) -> Vec<String>;
+}
+
+/// A function definition as the shared walk sees it.
+#[derive(Clone, Copy)]
+pub struct FunctionDef<'a> {
+    /// The node the name belongs to: the definition itself, or the
+    /// declarator of a JS `const f = () => ...`.
+    pub node: Node<'a>,
+    pub name: Node<'a>,
+    /// The code that is mutated and whose span is the function's.
+    pub body: Node<'a>,
+}
+
+/// A language's node kinds and rules, the part of a parser that differs
+/// between languages. The tree walks (`discover`, `find_function`,
+/// `function_spans`, `list_functions`, `walk`) are shared.
+pub trait Grammar {
+    fn language(&self) -> tree_sitter::Language;
+
+    /// The function `node` defines, if it's a named one.
+    fn function_at<'a>(&self, node: Node<'a>, source: &str) -> Option<FunctionDef<'a>>;
+
+    /// `def`'s name qualified by its enclosing types (and functions, where
+    /// the language nests them): `Validator.check`, `Validator::check`.
+    fn qualified_name(&self, def: &FunctionDef, source: &str) -> String;
+
+    /// Push the mutations `node` itself yields. `walk` visits the children.
+    fn mutate_node(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>);
+
+    /// Push the opt-in `collection_method` mutations `node` yields
+    /// (`min` <-> `max`, `push` -> `unshift`, ...). `walk` calls it only
+    /// when they're enabled.
+    fn mutate_collection(&self, _node: Node, _source: &str, _mutations: &mut Vec<Mutation>) {}
+
+    /// Subtrees no walk enters: logging calls, docstrings.
+    fn should_skip(&self, _node: Node, _source: &str) -> bool {
+        false
+    }
+
+    /// Test code, left out of `discover` unless `include_tests` or named.
+    fn is_test_code(&self, _node: Node, _source: &str) -> bool {
+        false
+    }
+
+    /// Functions with no body worth mutating, even when named.
+    fn is_stub(&self, _def: &FunctionDef, _source: &str) -> bool {
+        false
+    }
+
+    /// Functions `discover` leaves out unless named, besides stubs.
+    fn is_skipped(&self, _def: &FunctionDef, _source: &str, _options: &ParseOptions) -> bool {
+        false
+    }
+
+    /// Whether `list_functions` offers `def`.
+    fn is_listed(&self, _def: &FunctionDef, _source: &str) -> bool {
+        true
+    }
+
+    /// Mutate one function's body: `walk` plus any opt-in mutants.
+    fn mutate_function(&self, body: Node, source: &str, options: &ParseOptions, mutations: &mut Vec<Mutation>) {
+        walk(self, body, source, options, mutations);
+    }
+}
+
+pub fn parse<G: Grammar + ?Sized>(grammar: &G, source: &str) -> Tree {
+    let mut parser = Parser::new();
+    parser.set_language(&grammar.language()).expect("Failed to set grammar");
+    parser.parse(source, None).expect("Failed to parse source")
+}
+
+/// Mutations in `function`, or every function but stubs, skipped ones
+/// and test code, with their function and default context set.
+pub fn discover<G: Grammar + ?Sized>(grammar: &G, source: &str, function: Option<&str>, options: &ParseOptions) -> Vec<Mutation> {
+    let tree = parse(grammar, source);
+    let root = tree.root_node();
+
+    let mut mutations = Vec::new();
+    match function {
+        Some(name) => {
+            if let Some(def) = find_function(grammar, root, name, source) {
+                grammar.mutate_function(def.body, source, options, &mut mutations);
+            }
+        }
+        None => collect_functions(grammar, root, source, options, &mut mutations),
+    }
+
+    let mut spans = Vec::new();
+    collect_function_spans(grammar, root, source, &mut spans);
+    mutants::assign_functions(&mut mutations, &spans);
+    mutants::assign_context(&mut mutations, source, mutants::DEFAULT_CONTEXT_LINES);
+    mutations
+}
+
+/// Walk every outermost function; nested ones are part of their parent.
+fn collect_functions<G: Grammar + ?Sized>(
+    grammar: &G,
+    node: Node,
+    source: &str,
+    options: &ParseOptions,
+    mutations: &mut Vec<Mutation>,
+) {
+    if !options.include_tests && grammar.is_test_code(node, source) {
+        return;
+    }
+    if let Some(def) = grammar.function_at(node, source) {
+        if !grammar.is_stub(&def, source) && !grammar.is_skipped(&def, source, options) {
+            grammar.mutate_function(def.body, source, options, mutations);
+        }
+        return;
+    }
+    for child in children(node) {
+        collect_functions(grammar, child, source, options, mutations);
+    }
+}
+
+/// The first function named `name`, plain (`check`) or qualified
+/// (`Validator.check`). Stubs are passed over for the implementation.
+pub fn find_function<'a, G: Grammar + ?Sized>(grammar: &G, node: Node<'a>, name: &str, source: &str) -> Option<FunctionDef<'a>> {
+    if let Some(def) = grammar.function_at(node, source) {
+        let matches = node_text(def.name, source) == name || grammar.qualified_name(&def, source) == name;
+        if matches && !grammar.is_stub(&def, source) {
+            return Some(def);
+        }
+    }
+    children(node).find_map(|child| find_function(grammar, child, name, source))
+}
+
+/// Byte spans of every named function, nested ones included.
+pub fn function_spans<G: Grammar + ?Sized>(grammar: &G, source: &str) -> Vec<FunctionSpan> {
+    let tree = parse(grammar, source);
+    let mut spans = Vec::new();
+    collect_function_spans(grammar, tree.root_node(), source, &mut spans);
+    spans
+}
+
+fn collect_function_spans<G: Grammar + ?Sized>(grammar: &G, node: Node, source: &str, spans: &mut Vec<FunctionSpan>) {
+    if let Some(def) = grammar.function_at(node, source) {
+        spans.push(FunctionSpan {
+            name: grammar.qualified_name(&def, source),
+            start_byte: def.body.start_byte(),
+            end_byte: def.body.end_byte(),
+        });
+    }
+    for child in children(node) {
+        collect_function_spans(grammar, child, source, spans);
+    }
+}
+
+/// Qualified names of every listed function, nested ones included.
+pub fn list_functions<G: Grammar + ?Sized>(grammar: &G, source: &str) -> Vec<String> {
+    let tree = parse(grammar, source);
+    let mut names = Vec::new();
+    collect_function_names(grammar, tree.root_node(), source, &mut names);
+    names
+}
+
+fn collect_function_names<G: Grammar + ?Sized>(grammar: &G, node: Node, source: &str, names: &mut Vec<String>) {
+    if let Some(def) = grammar.function_at(node, source) {
+        if !grammar.is_stub(&def, source) && grammar.is_listed(&def, source) {
+            names.push(grammar.qualified_name(&def, source));
+        }
+    }
+    for child in children(node) {
+        collect_function_names(grammar, child, source, names);
+    }
+}
+
+/// Mutate `node` and everything below it that `should_skip` doesn't
+/// rule out, with the built-in, enabled opt-in and `options.operators`
+/// operators.
+pub fn walk<G: Grammar + ?Sized>(
+    grammar: &G,
+    node: Node,
+    source: &str,
+    options: &ParseOptions,
+    mutations: &mut Vec<Mutation>,
+) {
+    if grammar.should_skip(node, source) {
+        return;
+    }
+    grammar.mutate_node(node, source, mutations);
+    if options.collection_method {
+        grammar.mutate_collection(node, source, mutations);
+    }
+    mutations.extend(options.operators.iter().filter_map(|op| op.mutate(node, source)));
+    for child in children(node) {
+        walk(grammar, child, source, options, mutations);
+    }
+}
+
+fn children(node: Node) -> impl Iterator<Item = Node> {
+    (0..node.child_count()).filter_map(move |i| node.child(i))
+}
+
+pub fn node_text<'a>(node: Node, source: &'a str) -> &'a str {
+    &source[node.start_byte()..node.end_byte()]
+}
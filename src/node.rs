@@ -0,0 +1,104 @@
+//! Node.js bindings (`node` feature, Unix), so JS/TS agent toolchains and
+//! editor extensions can embed the mutator without child processes. Build
+//! the addon with
+//! `cargo rustc --release --lib --features node --crate-type cdylib` and
+//! load the library renamed to `mutator.node`:
+//!
+//! ```js
+//! const { discover, apply, Mutator } = require("./mutator.node");
+//! const { mutations } = JSON.parse(discover(JSON.stringify({ source, path: "app.ts" })));
+//! const { mutated, diff } = JSON.parse(apply(JSON.stringify({ source, mutation: mutations[0] })));
+//!
+//! const mutator = new Mutator();
+//! const result = JSON.parse(await mutator.run(JSON.stringify({ file: "src/app.ts", function: "add" })));
+//! ```
+//!
+//! `discover` and `apply` take the JSON requests the C functions do (see
+//! `ffi`). `Mutator` runs tests like `mutator daemon`: `run`, `show` and
+//! `status` take that method's params and resolve to its result, off the
+//! JS thread, and the instance keeps engines warm between runs. Failures
+//! reject with the message `mutator run` would print.
+
+use std::sync::{Arc, Mutex};
+
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Task};
+use napi_derive::napi;
+use serde_json::Value;
+
+use crate::daemon::Daemon;
+use crate::ffi;
+
+/// Discover mutations in a source text; see `ffi` for the request.
+#[napi]
+pub fn discover(request: String) -> String {
+    ffi::discover_json(&request)
+}
+
+/// Apply one discovered mutation and diff it; see `ffi` for the request.
+#[napi]
+pub fn apply(request: String) -> String {
+    ffi::apply_json(&request)
+}
+
+#[napi]
+pub struct Mutator {
+    daemon: Arc<Mutex<Daemon>>,
+}
+
+#[napi]
+impl Mutator {
+    #[napi(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Mutator { daemon: Arc::new(Mutex::new(Daemon::new())) }
+    }
+
+    /// Mutate and test a file; `params` as for the daemon's `run`.
+    #[napi(ts_return_type = "Promise<string>")]
+    pub fn run(&self, params: String) -> AsyncTask<Call> {
+        self.call("run", params)
+    }
+
+    /// A recorded mutant's details; `params` as for the daemon's `show`.
+    #[napi(ts_return_type = "Promise<string>")]
+    pub fn show(&self, params: String) -> AsyncTask<Call> {
+        self.call("show", params)
+    }
+
+    /// The last run's result; `params` as for the daemon's `status`.
+    #[napi(ts_return_type = "Promise<string>")]
+    pub fn status(&self, params: String) -> AsyncTask<Call> {
+        self.call("status", params)
+    }
+
+    fn call(&self, method: &'static str, params: String) -> AsyncTask<Call> {
+        AsyncTask::new(Call { daemon: Arc::clone(&self.daemon), method, params })
+    }
+}
+
+/// One daemon call, run on the libuv thread pool.
+pub struct Call {
+    daemon: Arc<Mutex<Daemon>>,
+    method: &'static str,
+    params: String,
+}
+
+impl Task for Call {
+    type Output = Value;
+    type JsValue = String;
+
+    fn compute(&mut self) -> napi::Result<Value> {
+        let params = if self.params.trim().is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&self.params).map_err(|e| napi::Error::from_reason(format!("Invalid params: {}", e)))?
+        };
+        let mut daemon = self.daemon.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        daemon.call(self.method, params).map_err(|e| napi::Error::from_reason(e.message))
+    }
+
+    fn resolve(&mut self, _env: Env, output: Value) -> napi::Result<String> {
+        Ok(output.to_string())
+    }
+}
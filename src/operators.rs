@@ -1,5 +1,9 @@
-/// Mutation operator definitions for Python.
-/// Returns (original_pattern, replacement) pairs for a given AST node kind.
+//! Mutation operator definitions for Python.
+//! Returns (original_pattern, replacement) pairs for a given AST node kind.
+//! The `arg_swap` helpers at the end are shared by all parsers.
+
+/// Operators that are off by default and turned on with `--enable`.
+pub const OPT_IN: &[&str] = &["string_mut", "collection_method"];
 
 pub struct MutationOp {
     pub operator_name: &'static str,
@@ -47,6 +51,27 @@ pub fn comparison_mutations(op_text: &str) -> Vec<MutationOp> {
     }
 }
 
+/// Tier 1: Collection-length comparisons (`len(xs) > 0`, `xs.length === 0`,
+/// `v.len() <= n`). The boundary flip is labelled `len_boundary` instead of
+/// `boundary`, since emptiness checks are common and weakly tested, and
+/// equality gets a one-sided variant (`len(xs) == 0` -> `>= 0`).
+/// `length_on_left` says which operand is the length, so that variant
+/// never mirrors into a plain negation.
+pub fn length_boundary(op_text: &str, length_on_left: bool) -> Option<MutationOp> {
+    let replacement = match (op_text, length_on_left) {
+        (">", _) => ">=",
+        (">=", _) => ">",
+        ("<", _) => "<=",
+        ("<=", _) => "<",
+        ("==" | "===", true) => ">=",
+        ("==" | "===", false) => "<=",
+        ("!=" | "!==", true) => "<",
+        ("!=" | "!==", false) => ">",
+        _ => return None,
+    };
+    Some(MutationOp { operator_name: "len_boundary", replacement })
+}
+
 /// Tier 1: Boolean mutations
 pub fn boolean_mutations(text: &str) -> Vec<MutationOp> {
     match text {
@@ -109,8 +134,22 @@ pub fn arithmetic_mutations(op_text: &str) -> Vec<MutationOp> {
     }
 }
 
-/// Tier 2: String literal mutations
+/// Tier 2: Augmented assignment mutations (accumulator loops)
+pub fn augmented_assignment_mutations(op_text: &str) -> Vec<MutationOp> {
+    match op_text {
+        "+=" => vec![MutationOp { operator_name: "aug_assign", replacement: "-=" }],
+        "-=" => vec![MutationOp { operator_name: "aug_assign", replacement: "+=" }],
+        "*=" => vec![MutationOp { operator_name: "aug_assign", replacement: "/=" }],
+        "/=" => vec![MutationOp { operator_name: "aug_assign", replacement: "*=" }],
+        _ => vec![],
+    }
+}
+
+/// Tier 2: String literal mutations. Opt-in (see `OPT_IN`), Python only.
+/// The replacement has no prefix; callers keep the literal's own
+/// (`string_prefix`), so `b"x"` stays bytes.
 pub fn string_mutations(text: &str) -> Vec<MutationOp> {
+    let text = &text[string_prefix(text).len()..];
     if text == "\"\"" || text == "''" {
         vec![MutationOp { operator_name: "string_mut", replacement: "\"mutator_xx\"" }]
     } else {
@@ -118,7 +157,192 @@ pub fn string_mutations(text: &str) -> Vec<MutationOp> {
     }
 }
 
+/// A Python string literal's prefix: `b` in `b"x"`, `rb` in `rb'x'`.
+pub fn string_prefix(text: &str) -> &str {
+    let end = text.find(['"', '\'']).unwrap_or(0);
+    &text[..end]
+}
+
+/// Tier 2: Default parameter value mutations (`def f(flag=True)`).
+/// Only literal defaults; `None` and computed defaults are left alone.
+pub fn default_value_mutations(value: &str) -> Vec<MutationOp> {
+    let trimmed = value.trim();
+    let replacement = match trimmed {
+        "True" => "False",
+        "False" => "True",
+        "0" => "1",
+        "0.0" => "1.0",
+        _ if trimmed.starts_with('"') || trimmed.starts_with('\'') => {
+            return string_mutations(trimmed)
+                .into_iter()
+                .map(|op| MutationOp { operator_name: "default_arg", replacement: op.replacement })
+                .collect();
+        }
+        _ if trimmed.parse::<i64>().is_ok() => "0",
+        _ if trimmed.parse::<f64>().is_ok() => "0.0",
+        _ => return vec![],
+    };
+    vec![MutationOp { operator_name: "default_arg", replacement }]
+}
+
+/// Tier 2: JS/TS parameter and destructuring defaults (`limit = 10` ->
+/// `limit = 11`, `{ strict = true }` -> `strict = false`). Integers are
+/// nudged by one so the default sits just past any boundary it guards.
+pub fn js_default_value_mutation(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    match trimmed {
+        "true" => return Some("false".to_string()),
+        "false" => return Some("true".to_string()),
+        _ => {}
+    }
+    if trimmed.starts_with(['"', '\'']) {
+        return string_mutations(trimmed).first().map(|op| op.replacement.to_string());
+    }
+    trimmed.parse::<i64>().ok().map(|n| (n + 1).to_string())
+}
+
+/// Tier 1: Rust `Option`/`Result` predicates, swapped for their opposite.
+pub fn predicate_swap(method: &str) -> Option<&'static str> {
+    match method {
+        "is_some" => Some("is_none"),
+        "is_none" => Some("is_some"),
+        "is_ok" => Some("is_err"),
+        "is_err" => Some("is_ok"),
+        _ => None,
+    }
+}
+
+/// Tier 2: String methods swapped for their mirror image, in each
+/// language's spelling: `startswith` <-> `endswith`, `upper` <-> `lower`.
+/// Tests that only check a result is truthy don't notice.
+pub fn string_method_swap(method: &str) -> Option<&'static str> {
+    match method {
+        // Python
+        "startswith" => Some("endswith"),
+        "endswith" => Some("startswith"),
+        "upper" => Some("lower"),
+        "lower" => Some("upper"),
+        // JS/TS
+        "startsWith" => Some("endsWith"),
+        "endsWith" => Some("startsWith"),
+        "toUpperCase" => Some("toLowerCase"),
+        "toLowerCase" => Some("toUpperCase"),
+        // Rust
+        "starts_with" => Some("ends_with"),
+        "ends_with" => Some("starts_with"),
+        "to_uppercase" => Some("to_lowercase"),
+        "to_lowercase" => Some("to_uppercase"),
+        _ => None,
+    }
+}
+
+/// Tier 2: Whitespace trimming calls that `trim_remove` drops
+/// (`s.strip()` -> `s`), Python and JS/TS.
+pub fn is_trim_method(method: &str) -> bool {
+    matches!(method, "strip" | "lstrip" | "rstrip" | "trim" | "trimStart" | "trimEnd")
+}
+
+/// Tier 2 (opt-in `collection_method`): `min` <-> `max`, as a builtin,
+/// `Math` method or Rust method/function.
+pub fn min_max_swap(name: &str) -> Option<&'static str> {
+    match name {
+        "min" => Some("max"),
+        "max" => Some("min"),
+        _ => None,
+    }
+}
+
+/// Tier 1: Loop control mutations (`break` <-> `continue`)
+pub fn loop_control_mutations(keyword: &str) -> Vec<MutationOp> {
+    match keyword {
+        "break" => vec![MutationOp { operator_name: "loop_control", replacement: "continue" }],
+        "continue" => vec![MutationOp { operator_name: "loop_control", replacement: "break" }],
+        _ => vec![],
+    }
+}
+
+/// Tier 1: Loop-to-zero-iterations (`while cond:` -> `while False:`)
+pub fn loop_zero_iterations() -> Vec<MutationOp> {
+    vec![MutationOp { operator_name: "loop_zero", replacement: "False" }]
+}
+
+/// Tier 1: Exception handler body mutations. `raise` lets the caught
+/// exception escape; `pass` silently drops the handler's effect.
+pub fn except_body_mutations(body: &str) -> Vec<MutationOp> {
+    match body.trim() {
+        "pass" => vec![MutationOp { operator_name: "except_body", replacement: "raise" }],
+        // A lone `raise`/`raise X` already gets raise_remove, which is the `pass` mutant
+        "raise" => vec![],
+        b if b.starts_with("raise ") && !b.contains('\n') => {
+            vec![MutationOp { operator_name: "except_body", replacement: "raise" }]
+        }
+        _ => vec![
+            MutationOp { operator_name: "except_body", replacement: "raise" },
+            MutationOp { operator_name: "except_body", replacement: "pass" },
+        ],
+    }
+}
+
+/// Tier 1: Raise removal (`raise X` -> `pass`)
+pub fn raise_mutations() -> Vec<MutationOp> {
+    vec![MutationOp { operator_name: "raise_remove", replacement: "pass" }]
+}
+
+/// Tier 2: Swap a caught builtin exception type for a sibling that is not
+/// its parent or child, so the handler stops matching.
+pub fn exception_sibling(name: &str) -> Option<&'static str> {
+    match name {
+        "ValueError" => Some("TypeError"),
+        "TypeError" => Some("ValueError"),
+        "KeyError" => Some("IndexError"),
+        "IndexError" => Some("KeyError"),
+        "AttributeError" => Some("TypeError"),
+        "FileNotFoundError" => Some("PermissionError"),
+        "PermissionError" => Some("FileNotFoundError"),
+        "ZeroDivisionError" => Some("OverflowError"),
+        "OverflowError" => Some("ZeroDivisionError"),
+        "ConnectionError" => Some("TimeoutError"),
+        "TimeoutError" => Some("ConnectionError"),
+        "UnicodeDecodeError" => Some("UnicodeEncodeError"),
+        "UnicodeEncodeError" => Some("UnicodeDecodeError"),
+        _ => None,
+    }
+}
+
 /// Tier 1: Conditional body removal (if block -> pass)
 pub fn conditional_body_removal() -> Vec<MutationOp> {
     vec![MutationOp { operator_name: "block_remove", replacement: "pass" }]
 }
+
+/// Tier 2: Whether the first two call arguments (by node kind) are worth
+/// swapping. Literals of different kinds (`f("a", 1)`) would just make a
+/// type error; anything non-literal might be the same type.
+pub fn swappable_args(first_kind: &str, second_kind: &str) -> bool {
+    match (literal_class(first_kind), literal_class(second_kind)) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+fn literal_class(kind: &str) -> Option<&'static str> {
+    match kind {
+        "string" | "concatenated_string" | "template_string" | "string_literal" | "raw_string_literal" => {
+            Some("string")
+        }
+        "integer" | "float" | "number" | "integer_literal" | "float_literal" => Some("number"),
+        "true" | "false" | "boolean_literal" => Some("bool"),
+        "none" | "null" | "undefined" => Some("null"),
+        "char_literal" => Some("char"),
+        _ => None,
+    }
+}
+
+/// Calls whose argument order is not business logic: logging and printing
+/// (`logger.info(msg, x)`, `printf(fmt, x)`).
+pub fn is_logging_call(callee: &str) -> bool {
+    let name = callee.rsplit(['.', ':']).next().unwrap_or(callee);
+    matches!(
+        name,
+        "print" | "printf" | "log" | "debug" | "info" | "warn" | "warning" | "error" | "critical" | "trace" | "exception"
+    )
+}
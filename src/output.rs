@@ -1,12 +1,23 @@
 use console::Style;
-use crate::state::{RunResult, SurvivedMutant};
+use crate::compare::{ComparedMutant, Comparison};
+use crate::engine::CommandPlan;
+use crate::history::TrendPoint;
+use crate::mutants::{MutantResult, MutantStatus, Mutation};
+use crate::state::{MutantsNear, RunResult, SurvivedMutant};
+use crate::suggest::Suggestion;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 pub fn print_error(msg: &str) {
     let style = Style::new().red().bold();
     eprintln!("{} {}", style.apply_to("✗"), msg);
 }
 
+/// A dim aside on stderr, out of the way of `--json` output.
+pub fn print_note(msg: &str) {
+    eprintln!("{}", Style::new().dim().apply_to(msg));
+}
+
 pub fn print_success(msg: &str) {
     let style = Style::new().green().bold();
     println!("{} {}", style.apply_to("✓"), msg);
@@ -14,9 +25,9 @@ pub fn print_success(msg: &str) {
 
 pub fn print_run_result(result: &RunResult, file: &Path) {
     let score_pct = result.score * 100.0;
-    let testable = result.total - result.unviable;
+    let testable = result.testable();
 
-    if result.survived == 0 {
+    if result.survived == 0 && result.uncovered == 0 {
         let style = Style::new().green().bold();
         println!(
             "{} {}: {} mutants, all killed ({:.1}%) in {:.1}s",
@@ -34,7 +45,7 @@ pub fn print_run_result(result: &RunResult, file: &Path) {
         "{} {}: {} survived / {} testable ({:.1}% killed) in {:.1}s",
         style.apply_to("!"),
         file.display(),
-        result.survived,
+        result.survived + result.uncovered,
         testable,
         score_pct,
         result.duration_ms as f64 / 1000.0,
@@ -44,9 +55,43 @@ pub fn print_run_result(result: &RunResult, file: &Path) {
         let dim = Style::new().dim();
         println!("  {} {} unviable mutants skipped", dim.apply_to("·"), result.unviable);
     }
+    if result.equivalent > 0 {
+        let dim = Style::new().dim();
+        let refs: Vec<_> = result.equivalent_mutants.iter().map(|m| format!("@{}", m.ref_id)).collect();
+        println!(
+            "  {} {} suspected equivalent mutants not scored {}",
+            dim.apply_to("·"),
+            result.equivalent,
+            dim.apply_to(format!("({})", refs.join(", "))),
+        );
+    }
     if result.timeout > 0 {
         let dim = Style::new().dim();
-        println!("  {} {} mutants timed out", dim.apply_to("·"), result.timeout);
+        let refs: Vec<_> = result.timeout_mutants.iter().map(|m| format!("@{}", m.ref_id)).collect();
+        println!(
+            "  {} {} mutants timed out {}",
+            dim.apply_to("·"),
+            result.timeout,
+            dim.apply_to(format!("({})", refs.join(", "))),
+        );
+    }
+
+    if result.resource_exceeded > 0 {
+        let dim = Style::new().dim();
+        let refs: Vec<_> = result.resource_exceeded_mutants.iter().map(|m| format!("@{}", m.ref_id)).collect();
+        println!(
+            "  {} {} mutants exceeded the resource limits {}",
+            dim.apply_to("·"),
+            result.resource_exceeded,
+            dim.apply_to(format!("({})", refs.join(", "))),
+        );
+    }
+
+    if result.functions.len() > 1 {
+        print_function_scores(result);
+    }
+    if result.operators.len() > 1 {
+        print_operator_scores(result);
     }
 
     println!();
@@ -65,6 +110,72 @@ pub fn print_run_result(result: &RunResult, file: &Path) {
             op_style.apply_to(&m.replacement),
         );
     }
+
+    if result.uncovered > 0 {
+        let dim = Style::new().dim();
+        println!();
+        println!(
+            "  {} {} mutants on lines no test executes (write a test that reaches them first):",
+            dim.apply_to("·"),
+            result.uncovered,
+        );
+        for m in &result.uncovered_mutants {
+            println!(
+                "  {} {}:{} {}",
+                Style::new().cyan().apply_to(format!("@{}", m.ref_id)),
+                m.file,
+                m.line,
+                dim.apply_to(format!("[{}]", m.operator)),
+            );
+        }
+    }
+}
+
+/// Per-operator counts, weakest kill rate first, to see which operators
+/// find gaps in this codebase.
+fn print_operator_scores(result: &RunResult) {
+    let dim = Style::new().dim();
+    let mut operators: Vec<_> = result.operators.iter().collect();
+    operators.sort_by(|a, b| a.1.score.total_cmp(&b.1.score).then_with(|| a.0.cmp(b.0)));
+    println!();
+    for (name, o) in operators {
+        let pct = format!("{:>5.1}%", o.score * 100.0);
+        let pct = if o.score < 1.0 {
+            Style::new().yellow().apply_to(pct)
+        } else {
+            Style::new().green().apply_to(pct)
+        };
+        let mut counts = vec![format!("{} generated", o.total), format!("{} killed", o.killed)];
+        if o.survived > 0 {
+            counts.push(format!("{} survived", o.survived));
+        }
+        if o.timeout > 0 {
+            counts.push(format!("{} timed out", o.timeout));
+        }
+        println!("  {} {} {}", pct, name, dim.apply_to(format!("({})", counts.join(", "))));
+    }
+}
+
+/// Per-function kill rates, weakest first.
+fn print_function_scores(result: &RunResult) {
+    let dim = Style::new().dim();
+    let mut functions: Vec<_> = result.functions.iter().collect();
+    functions.sort_by(|a, b| a.1.score.total_cmp(&b.1.score).then_with(|| a.0.cmp(b.0)));
+    println!();
+    for (name, f) in functions {
+        let pct = format!("{:>5.1}%", f.score * 100.0);
+        let pct = if f.score < 1.0 {
+            Style::new().yellow().apply_to(pct)
+        } else {
+            Style::new().green().apply_to(pct)
+        };
+        println!(
+            "  {} {} {}",
+            pct,
+            name,
+            dim.apply_to(format!("({}/{} killed)", f.killed, f.testable())),
+        );
+    }
 }
 
 pub fn print_mutant_detail(m: &SurvivedMutant) {
@@ -99,16 +210,35 @@ pub fn print_mutant_detail(m: &SurvivedMutant) {
     for line in &m.context_after {
         println!("  {}", dim.apply_to(line));
     }
+
+    if let Some(ref snippet) = m.output_snippet {
+        println!();
+        println!("{}", dim.apply_to("Test output:"));
+        for line in snippet.lines() {
+            println!("  {}", dim.apply_to(line));
+        }
+    }
 }
 
 pub fn print_status(result: &RunResult) {
     let score_pct = result.score * 100.0;
-    let testable = result.total - result.unviable;
+    let testable = result.testable();
 
     println!(
         "Last run: {} mutants, {} killed, {} survived ({:.1}% score)",
         testable, result.killed, result.survived, score_pct,
     );
+    if result.uncovered > 0 {
+        let dim = Style::new().dim();
+        println!("  {} {} mutants on uncovered lines", dim.apply_to("·"), result.uncovered);
+    }
+    if result.interrupted {
+        let dim = Style::new().dim();
+        println!("  {} run was interrupted; results are partial", dim.apply_to("·"));
+    }
+    if !result.operators.is_empty() {
+        print_operator_scores(result);
+    }
 
     if result.survived > 0 {
         println!();
@@ -127,3 +257,308 @@ pub fn print_status(result: &RunResult) {
         println!("Use `mutator show @m1` for details on a specific mutant.");
     }
 }
+
+pub fn print_history(points: &[TrendPoint]) {
+    let dim = Style::new().dim();
+    for p in points {
+        let e = &p.entry;
+        let target = match &e.function {
+            Some(f) => format!("{}::{}", e.file, f),
+            None => e.file.clone(),
+        };
+        let delta = match p.delta {
+            Some(d) if d > 0.0 => Style::new().green().apply_to(format!("+{:.1}", d * 100.0)).to_string(),
+            Some(d) if d < 0.0 => Style::new().red().apply_to(format!("{:.1}", d * 100.0)).to_string(),
+            Some(_) => dim.apply_to("=").to_string(),
+            None => String::new(),
+        };
+        println!(
+            "{}  {:>5.1}%  {:>7} killed  {}  {}",
+            dim.apply_to(&e.timestamp),
+            e.score * 100.0,
+            format!("{}/{}", e.killed, e.total - e.unviable - e.equivalent),
+            target,
+            delta,
+        );
+    }
+}
+
+pub fn print_comparison(c: &Comparison, before: &str, after: &str) {
+    let dim = Style::new().dim();
+    let delta = c.score_delta * 100.0;
+    let delta = if delta > 0.0 {
+        Style::new().green().apply_to(format!("+{:.1}", delta))
+    } else if delta < 0.0 {
+        Style::new().red().apply_to(format!("{:.1}", delta))
+    } else {
+        dim.apply_to("=".to_string())
+    };
+    println!("{}", dim.apply_to(format!("{} → {}", before, after)));
+    println!("Score: {:.1}% → {:.1}% ({})", c.score_before * 100.0, c.score_after * 100.0, delta);
+
+    let sections = [
+        ("Newly killed", Style::new().green().bold().apply_to("✓"), &c.newly_killed),
+        ("Newly surviving", Style::new().red().bold().apply_to("✗"), &c.newly_surviving),
+    ];
+    for (title, mark, mutants) in sections {
+        if mutants.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{} ({}):", title, mutants.len());
+        for m in mutants {
+            print_compared_mutant(m, &mark);
+        }
+    }
+
+    if !c.functions.is_empty() {
+        println!();
+        println!("Functions:");
+        for f in &c.functions {
+            let pct = |s: &Option<crate::state::FunctionScore>| match s {
+                Some(s) => format!("{:.1}%", s.score * 100.0),
+                None => "-".to_string(),
+            };
+            let delta = f.score_delta.map(|d| format!(" ({:+.1})", d * 100.0)).unwrap_or_default();
+            println!("  {} {} → {}{}", f.function, pct(&f.before), pct(&f.after), delta);
+        }
+    }
+
+    if c.newly_killed.is_empty() && c.newly_surviving.is_empty() {
+        println!();
+        println!("{}", dim.apply_to("No mutants changed status."));
+    }
+}
+
+fn print_compared_mutant(m: &ComparedMutant, mark: &console::StyledObject<&str>) {
+    let dim = Style::new().dim();
+    let function = m.function.as_deref().map(|f| format!(" in {}", f)).unwrap_or_default();
+    println!(
+        "  {} {}:{} {} → {} {}",
+        mark,
+        m.file,
+        m.line,
+        one_line(&m.original),
+        one_line(&m.replacement),
+        dim.apply_to(format!("[{}]{}", m.operator, function)),
+    );
+}
+
+pub fn print_mutation_list(mutations: &[Mutation], file: &Path) {
+    let dim = Style::new().dim();
+    for m in mutations {
+        println!(
+            "  {}:{}:{} {} {} → {}",
+            file.display(),
+            m.line,
+            m.column,
+            dim.apply_to(format!("[{}]", m.operator)),
+            one_line(&m.original),
+            one_line(&m.replacement),
+        );
+    }
+    println!(
+        "{} mutations in {} (no tests run)",
+        mutations.len(),
+        file.display()
+    );
+}
+
+fn status_label(status: &MutantStatus) -> console::StyledObject<&'static str> {
+    let dim = Style::new().dim();
+    match status {
+        MutantStatus::Killed => Style::new().green().apply_to("killed"),
+        MutantStatus::Survived => Style::new().red().bold().apply_to("survived"),
+        MutantStatus::Timeout => Style::new().yellow().apply_to("timeout"),
+        MutantStatus::Unviable => dim.apply_to("unviable"),
+        MutantStatus::Uncovered => Style::new().magenta().apply_to("uncovered"),
+        MutantStatus::SuspectedEquivalent => dim.apply_to("equivalent?"),
+        MutantStatus::ResourceExceeded => Style::new().yellow().apply_to("limit"),
+    }
+}
+
+/// Live progress line for `run` in human mode: mutants done, running kill
+/// rate and an ETA from the average time per mutant so far. Redrawn in
+/// place on stderr; does nothing when stderr isn't a terminal.
+pub struct Progress {
+    term: console::Term,
+    enabled: bool,
+    total: usize,
+    done: usize,
+    killed: usize,
+    /// Finished mutants that count towards the score (not unviable,
+    /// uncovered or suspected equivalent).
+    scored: usize,
+    started: Instant,
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        let term = console::Term::stderr();
+        let enabled = term.is_term();
+        let progress = Progress { term, enabled, total, done: 0, killed: 0, scored: 0, started: Instant::now() };
+        progress.draw();
+        progress
+    }
+
+    pub fn update(&mut self, result: &MutantResult) {
+        self.done += 1;
+        match result.status {
+            MutantStatus::Killed => {
+                self.killed += 1;
+                self.scored += 1;
+            }
+            MutantStatus::Unviable | MutantStatus::Uncovered | MutantStatus::SuspectedEquivalent => {}
+            _ => self.scored += 1,
+        }
+        self.draw();
+    }
+
+    /// Clear the line before the results are printed.
+    pub fn finish(&self) {
+        if self.enabled {
+            let _ = self.term.clear_line();
+        }
+    }
+
+    fn draw(&self) {
+        if !self.enabled {
+            return;
+        }
+        let line = progress_line(self.done, self.total, self.killed, self.scored, self.started.elapsed());
+        let _ = self.term.clear_line();
+        let _ = self.term.write_str(&Style::new().dim().apply_to(line).to_string());
+    }
+}
+
+/// `  [12/40] 75% killed · ETA 1m 05s`. The ETA assumes the remaining
+/// mutants take as long as the finished ones did on average.
+pub fn progress_line(done: usize, total: usize, killed: usize, scored: usize, elapsed: Duration) -> String {
+    let mut line = format!("  [{}/{}]", done, total);
+    if scored > 0 {
+        line.push_str(&format!(" {:.0}% killed", killed as f64 * 100.0 / scored as f64));
+    }
+    if done > 0 && done < total {
+        let eta = elapsed.mul_f64((total - done) as f64 / done as f64);
+        line.push_str(&format!(" · ETA {}", format_eta(eta)));
+    }
+    line
+}
+
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!("{}h {:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+/// `run --print-cmd`: the commands a run would spawn, one shell line each.
+pub fn print_command_plan(plan: &CommandPlan, mutants: usize) {
+    let bold = Style::new().bold();
+    let dim = Style::new().dim();
+    if let Some(build) = &plan.build {
+        println!("{}", bold.apply_to("Build (once):"));
+        println!("  {}", build.shell());
+    }
+    println!("{}", bold.apply_to("Baseline:"));
+    println!("  {}", plan.baseline.shell());
+    if let Some(precheck) = &plan.precheck {
+        println!("{}", bold.apply_to("Precheck (per mutant):"));
+        println!("  {}", precheck);
+    }
+    println!("{}", bold.apply_to(format!("Per mutant ({}):", mutants)));
+    println!("  {}", plan.mutant.shell());
+    if let Some(root) = &plan.isolated_from {
+        println!(
+            "{}",
+            dim.apply_to(format!(
+                "Runs in a temp copy of {}, with these paths pointing into the copy.",
+                root.display()
+            ))
+        );
+    }
+}
+
+/// One line per finished mutant, for streaming progress in `watch`.
+pub fn print_mutant_result(result: &MutantResult, file: &Path, reused: bool) {
+    let dim = Style::new().dim();
+    let status = status_label(&result.status);
+    let m = &result.mutation;
+    println!(
+        "  {:<11} {}:{}:{} {} {} → {}{}",
+        status,
+        file.display(),
+        m.line,
+        m.column,
+        dim.apply_to(format!("[{}]", m.operator)),
+        one_line(&m.original),
+        one_line(&m.replacement),
+        if reused { dim.apply_to(" (unchanged)").to_string() } else { String::new() },
+    );
+}
+
+/// `show --line`: one line per recorded mutant near the queried line.
+pub fn print_mutants_near(found: &MutantsNear) {
+    let dim = Style::new().dim();
+    let ref_style = Style::new().cyan().bold();
+    let target = match found.file {
+        Some(file) => format!("{}:{}", file, found.line),
+        None => format!("line {}", found.line),
+    };
+    if found.mutants.is_empty() {
+        println!("No mutants within {} lines of {}.", found.near, target);
+        return;
+    }
+    for m in &found.mutants {
+        let mut notes = Vec::new();
+        if let Some(ref_id) = &m.ref_id {
+            notes.push(ref_style.apply_to(format!("@{}", ref_id)).to_string());
+        }
+        if let Some(test) = &m.killed_by {
+            notes.push(dim.apply_to(format!("by {}", test)).to_string());
+        }
+        println!(
+            "  {:<11} {}:{}:{} {} {} → {}  {}",
+            status_label(&m.status),
+            m.file,
+            m.line,
+            m.column,
+            dim.apply_to(format!("[{}]", m.operator)),
+            one_line(&m.original),
+            one_line(&m.replacement),
+            notes.join(" "),
+        );
+    }
+    println!("{} mutants within {} lines of {}", found.mutants.len(), found.near, target);
+}
+
+/// Collapse a multi-line snippet (e.g. a removed block) onto one line.
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+pub fn print_suggestion(s: &Suggestion) {
+    let ref_style = Style::new().cyan().bold();
+    let dim = Style::new().dim();
+
+    println!(
+        "{} {}:{} [{}] {} → {}",
+        ref_style.apply_to(format!("@{}", s.ref_id)),
+        s.file,
+        s.line,
+        s.operator,
+        s.original.trim(),
+        s.replacement.trim(),
+    );
+    println!("  {}", s.hint);
+    for input in &s.inputs {
+        println!("  {} {}", dim.apply_to("try:"), input);
+    }
+    println!();
+    for line in s.skeleton.lines() {
+        println!("    {}", line);
+    }
+    println!();
+}
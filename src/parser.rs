@@ -1,138 +1,272 @@
-use tree_sitter::{Node, Parser};
-use crate::mutants::Mutation;
+use tree_sitter::Node;
+use crate::mutants::{self, FunctionSpan, Mutation};
+use crate::mutation_parser::{self, node_text, FunctionDef, Grammar, MutationParser, ParseOptions};
 use crate::operators;
 
+/// Decorators marking stubs with no body worth mutating; such functions are
+/// always skipped.
+const STUB_DECORATORS: &[&str] = &["overload", "abstractmethod"];
+
 pub fn discover_mutations(source: &str, function_name: Option<&str>) -> Vec<Mutation> {
-    let mut parser = Parser::new();
-    let language = tree_sitter_python::LANGUAGE;
-    parser.set_language(&language.into()).expect("Failed to set Python grammar");
+    discover_mutations_with(source, function_name, false, &[])
+}
+
+/// Like `discover_mutations`, optionally adding the opt-in `string_mut`
+/// mutants (`--enable string_mut`) and skipping functions carrying one of
+/// `skip_decorated` (`--skip-decorated`) unless named explicitly.
+pub fn discover_mutations_with(
+    source: &str,
+    function_name: Option<&str>,
+    string_mut: bool,
+    skip_decorated: &[String],
+) -> Vec<Mutation> {
+    let options = ParseOptions { string_mut, skip_decorated, ..Default::default() };
+    mutation_parser::discover(&PythonParser, source, function_name, &options)
+}
+
+/// Mutations in module-level code (`--include-toplevel`): constants,
+/// dispatch tables, class attributes and top-level conditionals. Function
+/// bodies (see `discover_mutations_with`) and the `if __name__ == "__main__"`
+/// block are left out.
+pub fn toplevel_mutations(source: &str, string_mut: bool) -> Vec<Mutation> {
+    toplevel_mutations_with(source, &ParseOptions { string_mut, ..Default::default() })
+}
 
-    let tree = parser.parse(source, None).expect("Failed to parse source");
+/// Like `toplevel_mutations`, with every `ParseOptions` setting.
+pub fn toplevel_mutations_with(source: &str, options: &ParseOptions) -> Vec<Mutation> {
+    let tree = mutation_parser::parse(&PythonParser, source);
     let root = tree.root_node();
-    let lines: Vec<&str> = source.lines().collect();
 
     let mut mutations = Vec::new();
-
-    match function_name {
-        Some(name) => {
-            // Find the named function and only mutate within its body
-            if let Some(func_node) = find_function(root, name, source) {
-                walk_node(func_node, source, &lines, &mut mutations);
-            }
-        }
-        None => {
-            // Mutate all functions (skip module-level code)
-            collect_all_functions(root, source, &lines, &mut mutations);
-        }
+    mutation_parser::walk(&PythonParser, root, source, options, &mut mutations);
+    if options.string_mut {
+        collect_string_mutations(root, source, &mut mutations);
     }
 
+    let spans = function_spans(source);
+    let mut excluded: Vec<_> = spans.iter().map(|s| s.start_byte..s.end_byte).collect();
+    let mut cursor = root.walk();
+    excluded.extend(root.named_children(&mut cursor).filter(|n| is_main_guard(*n, source)).map(|n| n.byte_range()));
+    mutations.retain(|m| !excluded.iter().any(|r| r.start <= m.start_byte && m.end_byte <= r.end));
+    mutants::assign_context(&mut mutations, source, mutants::DEFAULT_CONTEXT_LINES);
+
     mutations
 }
 
-/// Find a function_definition node by name.
-fn find_function<'a>(node: Node<'a>, name: &str, source: &str) -> Option<Node<'a>> {
-    if node.kind() == "function_definition" {
-        if let Some(name_node) = node.child_by_field_name("name") {
-            if node_text(name_node, source) == name {
-                return Some(node);
-            }
+/// `if __name__ == "__main__":`, either way round and with either quotes.
+fn is_main_guard(node: Node, source: &str) -> bool {
+    if node.kind() != "if_statement" {
+        return false;
+    }
+    let Some(condition) = node.child_by_field_name("condition") else {
+        return false;
+    };
+    let text: String = node_text(condition, source)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '\'' { '"' } else { c })
+        .collect();
+    text == r#"__name__=="__main__""# || text == r#""__main__"==__name__"#
+}
+
+/// Dotted names of a function's decorators, without `@` or call arguments:
+/// `@functools.lru_cache(maxsize=None)` gives `functools.lru_cache`.
+fn decorators<'a>(func: Node<'a>, source: &'a str) -> Vec<&'a str> {
+    let mut names = Vec::new();
+    if let Some(parent) = func.parent().filter(|p| p.kind() == "decorated_definition") {
+        let mut cursor = parent.walk();
+        for decorator in parent.named_children(&mut cursor).filter(|n| n.kind() == "decorator") {
+            let text = node_text(decorator, source).trim_start_matches('@');
+            names.push(text.split('(').next().unwrap_or("").trim());
         }
     }
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            if let Some(found) = find_function(child, name, source) {
-                return Some(found);
+    names
+}
+
+/// `name` matches a decorator by its full dotted name or its last part, so
+/// `cache` matches `@functools.cache` and `setter` matches `@value.setter`.
+fn decorator_matches(decorator: &str, name: &str) -> bool {
+    decorator == name || decorator.rsplit('.').next() == Some(name)
+}
+
+/// `@overload` and `@abstractmethod` stubs.
+fn is_stub(func: Node, source: &str) -> bool {
+    decorators(func, source).into_iter().any(|d| STUB_DECORATORS.iter().any(|s| decorator_matches(d, s)))
+}
+
+/// A function's name prefixed with its enclosing classes and functions:
+/// `Outer.Inner.method`, `outer.inner` for a closure.
+fn qualified_name(func: Node, source: &str) -> String {
+    let mut parts = vec![func.child_by_field_name("name").map_or("", |n| node_text(n, source))];
+    let mut ancestor = func.parent();
+    while let Some(node) = ancestor {
+        if matches!(node.kind(), "class_definition" | "function_definition") {
+            if let Some(name) = node.child_by_field_name("name") {
+                parts.push(node_text(name, source));
             }
         }
+        ancestor = node.parent();
     }
-    None
+    parts.reverse();
+    parts.join(".")
+}
+
+/// Byte spans of every function definition, nested ones included.
+pub fn function_spans(source: &str) -> Vec<FunctionSpan> {
+    mutation_parser::function_spans(&PythonParser, source)
+}
+
+/// List all function names in the source file, nested ones included;
+/// methods are qualified by their class (`Validator.check`) and inner
+/// functions by their parent (`outer.inner`). Dunder methods, `test_`
+/// functions and stubs are left out.
+pub fn list_functions(source: &str) -> Vec<String> {
+    mutation_parser::list_functions(&PythonParser, source)
 }
 
-/// Collect mutations from all function bodies (skip module-level code).
-fn collect_all_functions(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
-    if node.kind() == "function_definition" {
-        walk_node(node, source, lines, mutations);
-        return; // Don't recurse into nested functions twice
+/// `MutationParser` for Python.
+pub struct PythonParser;
+
+impl Grammar for PythonParser {
+    fn language(&self) -> tree_sitter::Language {
+        tree_sitter_python::LANGUAGE.into()
     }
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            collect_all_functions(child, source, lines, mutations);
+
+    fn function_at<'a>(&self, node: Node<'a>, _source: &str) -> Option<FunctionDef<'a>> {
+        if node.kind() != "function_definition" {
+            return None;
         }
+        let name = node.child_by_field_name("name")?;
+        Some(FunctionDef { node, name, body: node })
     }
-}
 
-/// List all function names in the source file.
-pub fn list_functions(source: &str) -> Vec<String> {
-    let mut parser = Parser::new();
-    let language = tree_sitter_python::LANGUAGE;
-    parser.set_language(&language.into()).expect("Failed to set Python grammar");
+    fn qualified_name(&self, def: &FunctionDef, source: &str) -> String {
+        qualified_name(def.node, source)
+    }
 
-    let tree = parser.parse(source, None).expect("Failed to parse source");
-    let root = tree.root_node();
-    let mut names = Vec::new();
-    collect_function_names(root, source, &mut names);
-    names
-}
+    fn mutate_node(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        mutate_node(node, source, mutations);
+    }
 
-fn collect_function_names(node: Node, source: &str, names: &mut Vec<String>) {
-    if node.kind() == "function_definition" {
-        if let Some(name_node) = node.child_by_field_name("name") {
-            let name = node_text(name_node, source);
-            // Skip dunder methods and test functions
-            if !name.starts_with("__") && !name.starts_with("test_") {
-                names.push(name.to_string());
-            }
+    fn mutate_collection(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        if node.kind() == "call" {
+            collect_collection_mutations(node, source, mutations);
         }
     }
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            collect_function_names(child, source, names);
+
+    /// `print` and logging calls, docstrings.
+    fn should_skip(&self, node: Node, source: &str) -> bool {
+        should_skip_node(node, source)
+    }
+
+    fn is_stub(&self, def: &FunctionDef, source: &str) -> bool {
+        is_stub(def.node, source)
+    }
+
+    fn is_skipped(&self, def: &FunctionDef, source: &str, options: &ParseOptions) -> bool {
+        decorators(def.node, source).into_iter().any(|d| options.skip_decorated.iter().any(|s| decorator_matches(d, s)))
+    }
+
+    fn is_listed(&self, def: &FunctionDef, source: &str) -> bool {
+        let name = node_text(def.name, source);
+        !name.starts_with("__") && !name.starts_with("test_")
+    }
+
+    fn mutate_function(&self, body: Node, source: &str, options: &ParseOptions, mutations: &mut Vec<Mutation>) {
+        mutation_parser::walk(self, body, source, options, mutations);
+        if options.string_mut {
+            collect_string_mutations(body, source, mutations);
         }
     }
 }
 
-fn walk_node(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
-    // Skip nodes that are noise for business logic testing
-    if should_skip_node(node, source) {
-        return;
+impl MutationParser for PythonParser {
+    fn discover(&self, source: &str, function: Option<&str>, options: &ParseOptions) -> Vec<Mutation> {
+        mutation_parser::discover(self, source, function, options)
+    }
+
+    fn toplevel(&self, source: &str, options: &ParseOptions) -> Vec<Mutation> {
+        toplevel_mutations_with(source, options)
+    }
+
+    fn function_spans(&self, source: &str) -> Vec<FunctionSpan> {
+        function_spans(source)
     }
 
+    fn list_functions(&self, source: &str) -> Vec<String> {
+        list_functions(source)
+    }
+}
+
+/// The mutations `node` itself yields; `mutation_parser::walk` visits its
+/// children.
+fn mutate_node(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     match node.kind() {
         "comparison_operator" => {
-            collect_comparison_mutations(node, source, lines, mutations);
+            collect_comparison_mutations(node, source, mutations);
         }
         "boolean_operator" => {
-            collect_boolean_operator_mutations(node, source, lines, mutations);
+            collect_boolean_operator_mutations(node, source, mutations);
         }
         "not_operator" => {
-            collect_not_operator_mutations(node, source, lines, mutations);
+            collect_not_operator_mutations(node, source, mutations);
         }
         "binary_operator" => {
-            collect_arithmetic_mutations(node, source, lines, mutations);
+            collect_arithmetic_mutations(node, source, mutations);
+        }
+        "default_parameter" | "typed_default_parameter" => {
+            collect_default_parameter_mutations(node, source, mutations);
+        }
+        "augmented_assignment" => {
+            collect_augmented_assignment_mutations(node, source, mutations);
         }
         "return_statement" => {
-            collect_return_mutations(node, source, lines, mutations);
+            collect_return_mutations(node, source, mutations);
         }
         "true" | "false" => {
-            collect_boolean_literal_mutations(node, source, lines, mutations);
+            collect_boolean_literal_mutations(node, source, mutations);
         }
         "if_statement" => {
-            collect_if_body_mutations(node, source, lines, mutations);
+            collect_if_body_mutations(node, source, mutations);
+        }
+        "for_statement" | "with_statement" => {
+            collect_async_body_mutations(node, source, mutations);
+        }
+        "while_statement" => {
+            collect_while_condition_mutations(node, source, mutations);
+        }
+        "except_clause" => {
+            collect_except_mutations(node, source, mutations);
+        }
+        "raise_statement" => {
+            collect_raise_mutations(node, source, mutations);
+        }
+        "break_statement" | "continue_statement" => {
+            collect_loop_control_mutations(node, source, mutations);
+        }
+        "match_statement" => {
+            collect_match_case_mutations(node, source, mutations);
+        }
+        "conditional_expression" => {
+            collect_ternary_mutations(node, source, mutations);
+        }
+        "call" => {
+            collect_arg_swap_mutations(node, source, mutations);
+            collect_string_method_mutations(node, source, mutations);
+            collect_asyncio_mutations(node, source, mutations);
+        }
+        "expression_statement" => {
+            collect_call_remove_mutations(node, source, mutations);
+        }
+        "if_clause" => {
+            collect_comprehension_filter_mutations(node, source, mutations);
         }
         // String mutations deliberately excluded from defaults.
-        // They mostly test formatting, not business logic.
+        // They mostly test formatting, not business logic; `--enable
+        // string_mut` adds them (see `collect_string_mutations`).
         _ => {}
     }
 
-    let child_count = node.child_count();
-    for i in 0..child_count {
-        if let Some(child) = node.child(i) {
-            walk_node(child, source, lines, mutations);
-        }
-    }
 }
 
 /// Skip nodes that are not business logic: print calls, logging,
@@ -164,23 +298,7 @@ fn should_skip_node(node: Node, source: &str) -> bool {
     false
 }
 
-fn get_context(lines: &[&str], line_idx: usize, range: usize) -> (Vec<String>, Vec<String>) {
-    let start = line_idx.saturating_sub(range);
-    let end = (line_idx + range + 1).min(lines.len());
-    let before: Vec<String> = lines[start..line_idx].iter().map(|s| s.to_string()).collect();
-    let after: Vec<String> = if line_idx + 1 < end {
-        lines[line_idx + 1..end].iter().map(|s| s.to_string()).collect()
-    } else {
-        vec![]
-    };
-    (before, after)
-}
-
-fn node_text<'a>(node: Node<'a>, source: &'a str) -> &'a str {
-    &source[node.start_byte()..node.end_byte()]
-}
-
-fn collect_comparison_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_comparison_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
@@ -197,9 +315,13 @@ fn collect_comparison_mutations(node: Node, source: &str, lines: &[&str], mutati
 
             let line = child.start_position().row + 1;
             let col = child.start_position().column + 1;
-            let (ctx_before, ctx_after) = get_context(lines, child.start_position().row, 2);
 
-            for op in operators::comparison_mutations(&op_str) {
+            let mut ops = operators::comparison_mutations(&op_str);
+            if let Some(length_on_left) = length_side(child, source) {
+                ops.retain(|op| op.operator_name != "boundary");
+                ops.extend(operators::length_boundary(&op_str, length_on_left));
+            }
+            for op in ops {
                 mutations.push(Mutation {
                     line,
                     column: col,
@@ -208,15 +330,35 @@ fn collect_comparison_mutations(node: Node, source: &str, lines: &[&str], mutati
                     operator: op.operator_name.to_string(),
                     original: op_str.clone(),
                     replacement: op.replacement.to_string(),
-                    context_before: ctx_before.clone(),
-                    context_after: ctx_after.clone(),
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    function: None,
                 });
             }
         }
     }
 }
 
-fn collect_boolean_operator_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+/// Whether the operand left (`Some(true)`) or right of comparison operator
+/// `op` is a `len(...)` call.
+fn length_side(op: Node, source: &str) -> Option<bool> {
+    let is_len = |operand: Option<Node>| {
+        operand.is_some_and(|n| {
+            n.kind() == "call"
+                && n.child_by_field_name("function").is_some_and(|f| node_text(f, source) == "len")
+                && n.child_by_field_name("arguments").is_some_and(|a| a.named_child_count() == 1)
+        })
+    };
+    if is_len(op.prev_named_sibling()) {
+        Some(true)
+    } else if is_len(op.next_named_sibling()) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn collect_boolean_operator_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
@@ -224,7 +366,6 @@ fn collect_boolean_operator_mutations(node: Node, source: &str, lines: &[&str],
                 let op_text = node_text(child, source);
                 let line = child.start_position().row + 1;
                 let col = child.start_position().column + 1;
-                let (ctx_before, ctx_after) = get_context(lines, child.start_position().row, 2);
 
                 for op in operators::logical_mutations(op_text) {
                     mutations.push(Mutation {
@@ -235,8 +376,9 @@ fn collect_boolean_operator_mutations(node: Node, source: &str, lines: &[&str],
                         operator: op.operator_name.to_string(),
                         original: op_text.to_string(),
                         replacement: op.replacement.to_string(),
-                        context_before: ctx_before.clone(),
-                        context_after: ctx_after.clone(),
+                        context_before: Vec::new(),
+                        context_after: Vec::new(),
+                        function: None,
                     });
                 }
             }
@@ -244,13 +386,12 @@ fn collect_boolean_operator_mutations(node: Node, source: &str, lines: &[&str],
     }
 }
 
-fn collect_not_operator_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_not_operator_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     if let Some(not_kw) = node.child(0) {
         if not_kw.kind() == "not" {
             if let Some(operand) = node.child(1) {
                 let line = not_kw.start_position().row + 1;
                 let col = not_kw.start_position().column + 1;
-                let (ctx_before, ctx_after) = get_context(lines, not_kw.start_position().row, 2);
                 let operand_text = node_text(operand, source);
 
                 mutations.push(Mutation {
@@ -261,15 +402,16 @@ fn collect_not_operator_mutations(node: Node, source: &str, lines: &[&str], muta
                     operator: "negate_remove".to_string(),
                     original: node_text(node, source).to_string(),
                     replacement: operand_text.to_string(),
-                    context_before: ctx_before,
-                    context_after: ctx_after,
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    function: None,
                 });
             }
         }
     }
 }
 
-fn collect_arithmetic_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_arithmetic_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
@@ -280,7 +422,6 @@ fn collect_arithmetic_mutations(node: Node, source: &str, lines: &[&str], mutati
                 let op_text = node_text(child, source);
                 let line = child.start_position().row + 1;
                 let col = child.start_position().column + 1;
-                let (ctx_before, ctx_after) = get_context(lines, child.start_position().row, 2);
 
                 // Skip string concatenation
                 if kind == "+" {
@@ -300,8 +441,9 @@ fn collect_arithmetic_mutations(node: Node, source: &str, lines: &[&str], mutati
                         operator: op.operator_name.to_string(),
                         original: op_text.to_string(),
                         replacement: op.replacement.to_string(),
-                        context_before: ctx_before.clone(),
-                        context_after: ctx_after.clone(),
+                        context_before: Vec::new(),
+                        context_after: Vec::new(),
+                        function: None,
                     });
                 }
             }
@@ -309,12 +451,68 @@ fn collect_arithmetic_mutations(node: Node, source: &str, lines: &[&str], mutati
     }
 }
 
-fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_default_parameter_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // default_parameter: name "=" value / typed_default_parameter: name ":" type "=" value
+    let Some(value) = node.child_by_field_name("value") else {
+        return;
+    };
+    let text = node_text(value, source);
+    let line = value.start_position().row + 1;
+    let col = value.start_position().column + 1;
+
+    for op in operators::default_value_mutations(text) {
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: value.start_byte(),
+            end_byte: value.end_byte(),
+            operator: op.operator_name.to_string(),
+            original: text.to_string(),
+            replacement: op.replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+fn collect_augmented_assignment_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // augmented_assignment: left operator right
+    let Some(op_node) = node.child_by_field_name("operator") else {
+        return;
+    };
+    // Skip string accumulation (`s += "x"`)
+    if let Some(right) = node.child_by_field_name("right") {
+        if right.kind() == "string" || right.kind() == "concatenated_string" {
+            return;
+        }
+    }
+
+    let op_text = node_text(op_node, source);
+    let line = op_node.start_position().row + 1;
+    let col = op_node.start_position().column + 1;
+
+    for op in operators::augmented_assignment_mutations(op_text) {
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: op_node.start_byte(),
+            end_byte: op_node.end_byte(),
+            operator: op.operator_name.to_string(),
+            original: op_text.to_string(),
+            replacement: op.replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+fn collect_return_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     let child_count = node.child_count();
     if child_count < 2 {
         let line = node.start_position().row + 1;
         let col = node.start_position().column + 1;
-        let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
         mutations.push(Mutation {
             line,
             column: col,
@@ -323,8 +521,9 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
             operator: "return_val".to_string(),
             original: node_text(node, source).to_string(),
             replacement: "return None".to_string(),
-            context_before: ctx_before,
-            context_after: ctx_after,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
         });
         return;
     }
@@ -333,7 +532,6 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
         let expr_text = node_text(expr, source);
         let line = node.start_position().row + 1;
         let col = node.start_position().column + 1;
-        let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
 
         for op in operators::return_mutations(expr_text) {
             mutations.push(Mutation {
@@ -344,16 +542,18 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
                 operator: op.operator_name.to_string(),
                 original: node_text(node, source).to_string(),
                 replacement: op.replacement.to_string(),
-                context_before: ctx_before.clone(),
-                context_after: ctx_after.clone(),
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
             });
         }
     }
 }
 
-fn collect_boolean_literal_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_boolean_literal_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     if let Some(parent) = node.parent() {
-        if parent.kind() == "return_statement" {
+        // Handled by return_val / default_arg
+        if matches!(parent.kind(), "return_statement" | "default_parameter" | "typed_default_parameter") {
             return;
         }
     }
@@ -361,7 +561,6 @@ fn collect_boolean_literal_mutations(node: Node, source: &str, lines: &[&str], m
     let text = node_text(node, source);
     let line = node.start_position().row + 1;
     let col = node.start_position().column + 1;
-    let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
 
     for op in operators::boolean_mutations(text) {
         mutations.push(Mutation {
@@ -372,13 +571,14 @@ fn collect_boolean_literal_mutations(node: Node, source: &str, lines: &[&str], m
             operator: op.operator_name.to_string(),
             original: text.to_string(),
             replacement: op.replacement.to_string(),
-            context_before: ctx_before.clone(),
-            context_after: ctx_after.clone(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
         });
     }
 }
 
-fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_if_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     let child_count = node.child_count();
     for i in 0..child_count {
         if let Some(child) = node.child(i) {
@@ -390,7 +590,6 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
 
                 let line = child.start_position().row + 1;
                 let col = child.start_position().column + 1;
-                let (ctx_before, ctx_after) = get_context(lines, child.start_position().row, 2);
 
                 let indent = " ".repeat(child.start_position().column);
                 let replacement = format!("\n{}pass", indent);
@@ -403,8 +602,9 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
                     operator: "block_remove".to_string(),
                     original: block_text.to_string(),
                     replacement,
-                    context_before: ctx_before,
-                    context_after: ctx_after,
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    function: None,
                 });
 
                 break;
@@ -412,3 +612,556 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
         }
     }
 }
+
+/// `async for` and `async with` bodies -> `pass`, as for `if` bodies.
+/// Async paths are often the least tested.
+fn collect_async_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    if node.child(0).is_some_and(|c| c.kind() == "async") {
+        collect_if_body_mutations(node, source, mutations);
+    }
+}
+
+/// `asyncio.gather(a, b)` with each awaitable dropped in turn
+/// (`gather_drop`), and `asyncio.sleep(n)` -> `asyncio.sleep(0)`
+/// (`sleep_zero`).
+fn collect_asyncio_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(callee), Some(args)) = (node.child_by_field_name("function"), node.child_by_field_name("arguments")) else {
+        return;
+    };
+    let mut cursor = args.walk();
+    let positional: Vec<Node> = args
+        .named_children(&mut cursor)
+        .filter(|a| !matches!(a.kind(), "comment" | "keyword_argument" | "dictionary_splat"))
+        .collect();
+    let mut push = |operator: &str, start_byte: usize, end_byte: usize, at: Node, replacement: &str| {
+        mutations.push(Mutation {
+            line: at.start_position().row + 1,
+            column: at.start_position().column + 1,
+            start_byte,
+            end_byte,
+            operator: operator.to_string(),
+            original: source[start_byte..end_byte].to_string(),
+            replacement: replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    };
+
+    match node_text(callee, source) {
+        "asyncio.gather" | "gather" if positional.len() >= 2 => {
+            for (i, arg) in positional.iter().enumerate() {
+                // Take the separator with it: the next argument's start, or
+                // for the last one, the previous argument's end
+                let (start, end) = match positional.get(i + 1) {
+                    Some(next) => (arg.start_byte(), next.start_byte()),
+                    None => (positional[i - 1].end_byte(), arg.end_byte()),
+                };
+                push("gather_drop", start, end, *arg, "");
+            }
+        }
+        "asyncio.sleep" => {
+            if let Some(delay) = positional.first().filter(|d| !matches!(node_text(**d, source), "0" | "0.0")) {
+                push("sleep_zero", delay.start_byte(), delay.end_byte(), *delay, "0");
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_except_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // except_clause: "except" [value] ":" block
+    let mut cursor = node.walk();
+    let Some(body) = node.children(&mut cursor).find(|c| c.kind() == "block") else {
+        return;
+    };
+    let body_text = node_text(body, source);
+    let line = body.start_position().row + 1;
+    let col = body.start_position().column + 1;
+    // `except E: pass` keeps its body on the `except` line
+    let prefix = if body.start_position().row == node.start_position().row {
+        String::new()
+    } else {
+        format!("\n{}", " ".repeat(body.start_position().column))
+    };
+
+    for op in operators::except_body_mutations(body_text) {
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: body.start_byte(),
+            end_byte: body.end_byte(),
+            operator: op.operator_name.to_string(),
+            original: body_text.to_string(),
+            replacement: format!("{}{}", prefix, op.replacement),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+
+    // Caught types: `except E`, `except E as e`, `except (E1, E2)`
+    let Some(value) = node.child_by_field_name("value") else {
+        return;
+    };
+    let types: Vec<Node> = match value.kind() {
+        "identifier" => vec![value],
+        "as_pattern" => value.named_child(0).filter(|n| n.kind() == "identifier").into_iter().collect(),
+        "tuple" => {
+            let mut cursor = value.walk();
+            value.named_children(&mut cursor).filter(|n| n.kind() == "identifier").collect()
+        }
+        _ => vec![],
+    };
+    let caught: Vec<&str> = types.iter().map(|t| node_text(*t, source)).collect();
+    for ty in &types {
+        let name = node_text(*ty, source);
+        let Some(sibling) = operators::exception_sibling(name) else {
+            continue;
+        };
+        // Swapping to a type the clause already catches changes nothing
+        if caught.contains(&sibling) {
+            continue;
+        }
+        let line = ty.start_position().row + 1;
+        let col = ty.start_position().column + 1;
+
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: ty.start_byte(),
+            end_byte: ty.end_byte(),
+            operator: "except_swap".to_string(),
+            original: name.to_string(),
+            replacement: sibling.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+fn collect_raise_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let text = node_text(node, source);
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    for op in operators::raise_mutations() {
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: op.operator_name.to_string(),
+            original: text.to_string(),
+            replacement: op.replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+fn collect_while_condition_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(cond) = node.child_by_field_name("condition") else {
+        return;
+    };
+    // Literal conditions are already covered by bool_flip
+    if matches!(cond.kind(), "true" | "false") {
+        return;
+    }
+
+    let text = node_text(cond, source);
+    let line = cond.start_position().row + 1;
+    let col = cond.start_position().column + 1;
+
+    for op in operators::loop_zero_iterations() {
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: cond.start_byte(),
+            end_byte: cond.end_byte(),
+            operator: op.operator_name.to_string(),
+            original: text.to_string(),
+            replacement: op.replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+fn collect_loop_control_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let text = node_text(node, source);
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    for op in operators::loop_control_mutations(text) {
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: op.operator_name.to_string(),
+            original: text.to_string(),
+            replacement: op.replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+/// Drop individual `case` clauses from a `match` statement. A match with a
+/// single case is left alone: removing it would leave an empty body.
+fn collect_match_case_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let body = match node.child_by_field_name("body") {
+        Some(b) => b,
+        None => return,
+    };
+    let mut cursor = body.walk();
+    let cases: Vec<Node> = body
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "case_clause")
+        .collect();
+    if cases.len() < 2 {
+        return;
+    }
+
+    for case in cases {
+        let line = case.start_position().row + 1;
+        let col = case.start_position().column + 1;
+
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: case.start_byte(),
+            end_byte: case.end_byte(),
+            operator: "arm_remove".to_string(),
+            original: node_text(case, source).to_string(),
+            replacement: String::new(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+/// Swap the branches of `a if cond else b` -> `b if cond else a`.
+fn collect_ternary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (consequence, alternative) = match (node.named_child(0), node.named_child(2)) {
+        (Some(c), Some(a)) => (c, a),
+        _ => return,
+    };
+
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    let replacement = format!(
+        "{}{}{}",
+        node_text(alternative, source),
+        &source[consequence.end_byte()..alternative.start_byte()],
+        node_text(consequence, source),
+    );
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "ternary_swap".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Swap the first two positional arguments: `f(a, b)` -> `f(b, a)`.
+fn collect_arg_swap_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(callee), Some(args)) = (node.child_by_field_name("function"), node.child_by_field_name("arguments")) else {
+        return;
+    };
+    if operators::is_logging_call(node_text(callee, source)) {
+        return;
+    }
+    let mut cursor = args.walk();
+    let mut positional = args.named_children(&mut cursor).filter(|a| a.kind() != "comment");
+    let (Some(first), Some(second)) = (positional.next(), positional.next()) else {
+        return;
+    };
+    if [first, second]
+        .iter()
+        .any(|a| matches!(a.kind(), "keyword_argument" | "list_splat" | "dictionary_splat"))
+        || !operators::swappable_args(first.kind(), second.kind())
+    {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: first.start_position().row + 1,
+        column: first.start_position().column + 1,
+        start_byte: first.start_byte(),
+        end_byte: second.end_byte(),
+        operator: "arg_swap".to_string(),
+        original: source[first.start_byte()..second.end_byte()].to_string(),
+        replacement: format!(
+            "{}{}{}",
+            node_text(second, source),
+            &source[first.end_byte()..second.start_byte()],
+            node_text(first, source),
+        ),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `.startswith` <-> `.endswith`, `.upper()` <-> `.lower()`, and
+/// `.strip()` dropped (`name.strip()` -> `name`).
+fn collect_string_method_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(callee) = node.child_by_field_name("function").filter(|f| f.kind() == "attribute") else {
+        return;
+    };
+    let (Some(object), Some(method)) = (callee.child_by_field_name("object"), callee.child_by_field_name("attribute")) else {
+        return;
+    };
+    let name = node_text(method, source);
+    if let Some(replacement) = operators::string_method_swap(name) {
+        mutations.push(Mutation {
+            line: method.start_position().row + 1,
+            column: method.start_position().column + 1,
+            start_byte: method.start_byte(),
+            end_byte: method.end_byte(),
+            operator: "string_method".to_string(),
+            original: name.to_string(),
+            replacement: replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    } else if operators::is_trim_method(name) {
+        mutations.push(Mutation {
+            line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: "trim_remove".to_string(),
+            original: node_text(node, source).to_string(),
+            replacement: node_text(object, source).to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+/// Opt-in `collection_method`: `min` <-> `max`, `sorted(xs)` ->
+/// `sorted(xs, reverse=True)`, and a `.pop()` whose value is used reads
+/// without removing (`x = q.pop()` -> `x = q[-1]`). A `.pop()` or
+/// `.append(x)` statement is already dropped by `call_remove`.
+fn collect_collection_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(callee), Some(args)) = (node.child_by_field_name("function"), node.child_by_field_name("arguments")) else {
+        return;
+    };
+    let mut cursor = args.walk();
+    let positional: Vec<Node> = args.named_children(&mut cursor).filter(|a| a.kind() != "comment").collect();
+    let mut push = |target: Node, replacement: String| {
+        mutations.push(Mutation {
+            line: target.start_position().row + 1,
+            column: target.start_position().column + 1,
+            start_byte: target.start_byte(),
+            end_byte: target.end_byte(),
+            operator: "collection_method".to_string(),
+            original: node_text(target, source).to_string(),
+            replacement,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    };
+
+    match callee.kind() {
+        "identifier" => {
+            let name = node_text(callee, source);
+            if let Some(swapped) = operators::min_max_swap(name) {
+                push(callee, swapped.to_string());
+            } else if name == "sorted" && !positional.iter().any(|a| is_keyword(*a, "reverse", source)) {
+                // Append after the last argument so comments and a trailing
+                // comma inside the parentheses stay where they are
+                if let Some(last) = positional.last() {
+                    push(*last, format!("{}, reverse=True", node_text(*last, source)));
+                }
+            }
+        }
+        "attribute" => {
+            let (Some(object), Some(method)) = (callee.child_by_field_name("object"), callee.child_by_field_name("attribute")) else {
+                return;
+            };
+            let used = node.parent().is_some_and(|p| p.kind() != "expression_statement");
+            if node_text(method, source) == "pop" && used && positional.len() <= 1 {
+                let index = positional.first().map_or("-1", |i| node_text(*i, source));
+                push(node, format!("{}[{}]", node_text(object, source), index));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_keyword(arg: Node, name: &str, source: &str) -> bool {
+    arg.kind() == "keyword_argument" && arg.child_by_field_name("name").is_some_and(|n| node_text(n, source) == name)
+}
+
+/// Drop a call made for its side effect: `self.save()` -> `pass`.
+fn collect_call_remove_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(call) = node.named_child(0).filter(|c| c.kind() == "call" && node.named_child_count() == 1) else {
+        return;
+    };
+    let Some(callee) = call.child_by_field_name("function") else {
+        return;
+    };
+    if operators::is_logging_call(node_text(callee, source)) || is_sole_statement_of_removed_block(node) {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "call_remove".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: "pass".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Whether `statement` is all of an `if` or `async` body, which
+/// `block_remove` already replaces with `pass`.
+fn is_sole_statement_of_removed_block(statement: Node) -> bool {
+    let Some(block) = statement.parent().filter(|b| b.kind() == "block") else {
+        return false;
+    };
+    let mut cursor = block.walk();
+    if block.named_children(&mut cursor).filter(|s| s.kind() != "comment").count() != 1 {
+        return false;
+    }
+    block.parent().is_some_and(|owner| match owner.kind() {
+        "if_statement" => owner.child_by_field_name("consequence") == Some(block),
+        "for_statement" | "with_statement" => owner.child(0).is_some_and(|c| c.kind() == "async"),
+        _ => false,
+    })
+}
+
+/// Comprehension filters: `[x for x in xs if x > 0]` -> drop the `if`, or
+/// `if not (x > 0)`.
+fn collect_comprehension_filter_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(cond) = node.named_child(0) else {
+        return;
+    };
+    let cond_text = node_text(cond, source);
+    // Drop the clause along with the whitespace before it
+    let start = source[..node.start_byte()].trim_end().len();
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: start,
+        end_byte: node.end_byte(),
+        operator: "filter_remove".to_string(),
+        original: source[start..node.end_byte()].to_string(),
+        replacement: String::new(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+    let negated = match cond.kind() {
+        "not_operator" => return, // negate_remove already covers `if not x`
+        "identifier" | "attribute" | "call" | "subscript" | "parenthesized_expression" => {
+            format!("not {}", cond_text)
+        }
+        _ => format!("not ({})", cond_text),
+    };
+    mutations.push(Mutation {
+        line,
+        column: cond.start_position().column + 1,
+        start_byte: cond.start_byte(),
+        end_byte: cond.end_byte(),
+        operator: "filter_negate".to_string(),
+        original: cond_text.to_string(),
+        replacement: negated,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Opt-in string mutations: literals assigned to a name or used as a dict
+/// value (`status = "active"`, `{"mode": "fast"}`), keeping their prefix
+/// (`b"x"` -> `b""`). An f-string loses its literal text but keeps its
+/// expressions (`f"{base}/api"` -> `f"{base}"`). Docstrings and anything
+/// inside a print/log call are formatting, not values, and are left alone.
+fn collect_string_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    if should_skip_node(node, source) {
+        return;
+    }
+    if node.kind() == "call"
+        && node
+            .child_by_field_name("function")
+            .is_some_and(|f| operators::is_logging_call(node_text(f, source)))
+    {
+        return;
+    }
+
+    let value = match node.kind() {
+        "assignment" => node.child_by_field_name("right"),
+        "pair" => node.child_by_field_name("value"),
+        _ => None,
+    };
+    if let Some(value) = value.filter(|v| v.kind() == "string") {
+        let text = node_text(value, source);
+        let mut push = |operator: &str, replacement: String| {
+            mutations.push(Mutation {
+                line: value.start_position().row + 1,
+                column: value.start_position().column + 1,
+                start_byte: value.start_byte(),
+                end_byte: value.end_byte(),
+                operator: operator.to_string(),
+                original: text.to_string(),
+                replacement,
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
+            });
+        };
+        let mut cursor = value.walk();
+        if value.named_children(&mut cursor).any(|c| c.kind() == "interpolation") {
+            let mut cursor = value.walk();
+            let kept: String = value
+                .children(&mut cursor)
+                .filter(|c| matches!(c.kind(), "string_start" | "interpolation" | "string_end"))
+                .map(|c| node_text(c, source))
+                .collect();
+            if kept != text {
+                push("string_mut", kept);
+            }
+        } else {
+            let prefix = operators::string_prefix(text);
+            for op in operators::string_mutations(text) {
+                push(op.operator_name, format!("{}{}", prefix, op.replacement));
+            }
+        }
+    }
+
+    let child_count = node.child_count();
+    for i in 0..child_count {
+        if let Some(child) = node.child(i) {
+            collect_string_mutations(child, source, mutations);
+        }
+    }
+}
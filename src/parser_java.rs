@@ -0,0 +1,695 @@
+use std::path::Path;
+
+use tree_sitter::Node;
+use crate::mutants::{self, FunctionSpan, Mutation};
+use crate::mutation_parser::{self, node_text, FunctionDef, Grammar, MutationParser, ParseOptions};
+use crate::operators::{self, MutationOp};
+
+/// Discover mutations in production code. JUnit test methods and
+/// fixtures (`@Test`, `@BeforeEach`, ...) are skipped unless named
+/// explicitly.
+pub fn discover_mutations(source: &str, function_name: Option<&str>) -> Vec<Mutation> {
+    discover_mutations_with(source, function_name, false)
+}
+
+/// Like `discover_mutations`, optionally walking into test code too.
+pub fn discover_mutations_with(source: &str, function_name: Option<&str>, include_tests: bool) -> Vec<Mutation> {
+    let options = ParseOptions { include_tests, ..Default::default() };
+    mutation_parser::discover(&JavaParser, source, function_name, &options)
+}
+
+/// Mutations in field initializers outside methods (`--include-toplevel`):
+/// `static final int LIMIT = 10;`, lookup tables, default flags.
+pub fn toplevel_mutations(source: &str) -> Vec<Mutation> {
+    toplevel_mutations_with(source, &ParseOptions::default())
+}
+
+/// Like `toplevel_mutations`, with every `ParseOptions` setting.
+pub fn toplevel_mutations_with(source: &str, options: &ParseOptions) -> Vec<Mutation> {
+    let tree = mutation_parser::parse(&JavaParser, source);
+    let mut mutations = Vec::new();
+    collect_field_initializers(tree.root_node(), source, options, &mut mutations);
+    mutants::assign_context(&mut mutations, source, mutants::DEFAULT_CONTEXT_LINES);
+
+    mutations
+}
+
+fn collect_field_initializers(node: Node, source: &str, options: &ParseOptions, mutations: &mut Vec<Mutation>) {
+    match node.kind() {
+        "method_declaration" | "constructor_declaration" | "lambda_expression" => return,
+        "field_declaration" | "constant_declaration" => {
+            mutation_parser::walk(&JavaParser, node, source, options, mutations);
+            return;
+        }
+        _ => {}
+    }
+    let count = node.child_count();
+    for i in 0..count {
+        if let Some(child) = node.child(i) {
+            collect_field_initializers(child, source, options, mutations);
+        }
+    }
+}
+
+/// Byte spans of every method and constructor, nested classes' included.
+pub fn function_spans(source: &str) -> Vec<FunctionSpan> {
+    mutation_parser::function_spans(&JavaParser, source)
+}
+
+pub fn list_functions(source: &str) -> Vec<String> {
+    mutation_parser::list_functions(&JavaParser, source)
+}
+
+/// Test sources by naming convention: `FooTest.java`, `FooTests.java`,
+/// `TestFoo.java`, or anything under `src/test/`.
+pub fn is_test_file(path: &Path) -> bool {
+    let in_test_tree = path
+        .components()
+        .collect::<Vec<_>>()
+        .windows(2)
+        .any(|w| w[0].as_os_str() == "src" && w[1].as_os_str() == "test");
+    let stem = path.file_stem().and_then(|n| n.to_str()).unwrap_or("");
+    let test_prefixed = stem.strip_prefix("Test").is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()));
+    in_test_tree || stem.ends_with("Test") || stem.ends_with("Tests") || test_prefixed
+}
+
+/// `MutationParser` for Java.
+pub struct JavaParser;
+
+impl Grammar for JavaParser {
+    fn language(&self) -> tree_sitter::Language {
+        tree_sitter_java::LANGUAGE.into()
+    }
+
+    /// Methods and constructors with a body; abstract and interface
+    /// methods have nothing to mutate.
+    fn function_at<'a>(&self, node: Node<'a>, _source: &str) -> Option<FunctionDef<'a>> {
+        if !matches!(node.kind(), "method_declaration" | "constructor_declaration") {
+            return None;
+        }
+        let name = node.child_by_field_name("name")?;
+        node.child_by_field_name("body")?;
+        Some(FunctionDef { node, name, body: node })
+    }
+
+    fn qualified_name(&self, def: &FunctionDef, source: &str) -> String {
+        qualified_name(def.node, def.name, source)
+    }
+
+    fn mutate_node(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        mutate_node(node, source, mutations);
+    }
+
+    fn mutate_collection(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        if node.kind() == "method_invocation" {
+            collect_collection_mutations(node, source, mutations);
+        }
+    }
+
+    /// `System.out`/`System.err` printing and logger calls.
+    fn should_skip(&self, node: Node, source: &str) -> bool {
+        should_skip_node(node, source)
+    }
+
+    /// JUnit test methods and lifecycle fixtures.
+    fn is_test_code(&self, node: Node, source: &str) -> bool {
+        node.kind() == "method_declaration" && is_test_method(node, source)
+    }
+
+    /// Not constructors nor `test*` helpers.
+    fn is_listed(&self, def: &FunctionDef, source: &str) -> bool {
+        def.node.kind() == "method_declaration" && !node_text(def.name, source).starts_with("test")
+    }
+}
+
+impl MutationParser for JavaParser {
+    fn discover(&self, source: &str, function: Option<&str>, options: &ParseOptions) -> Vec<Mutation> {
+        mutation_parser::discover(self, source, function, options)
+    }
+
+    fn toplevel(&self, source: &str, options: &ParseOptions) -> Vec<Mutation> {
+        toplevel_mutations_with(source, options)
+    }
+
+    fn function_spans(&self, source: &str) -> Vec<FunctionSpan> {
+        function_spans(source)
+    }
+
+    fn list_functions(&self, source: &str) -> Vec<String> {
+        list_functions(source)
+    }
+}
+
+/// A method's name prefixed with its enclosing types: `Outer.Inner.check`.
+/// Anonymous classes add nothing.
+fn qualified_name(node: Node, name_node: Node, source: &str) -> String {
+    let mut parts = vec![node_text(name_node, source)];
+    let mut ancestor = node.parent();
+    while let Some(n) = ancestor {
+        if matches!(
+            n.kind(),
+            "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration"
+        ) {
+            if let Some(name) = n.child_by_field_name("name") {
+                parts.push(node_text(name, source));
+            }
+        }
+        ancestor = n.parent();
+    }
+    parts.reverse();
+    parts.join(".")
+}
+
+/// JUnit 4 and 5 annotations marking tests and their fixtures.
+const TEST_ANNOTATIONS: &[&str] = &[
+    "Test",
+    "ParameterizedTest",
+    "RepeatedTest",
+    "TestFactory",
+    "TestTemplate",
+    "BeforeEach",
+    "AfterEach",
+    "BeforeAll",
+    "AfterAll",
+    "Before",
+    "After",
+    "BeforeClass",
+    "AfterClass",
+];
+
+/// Whether a method carries a test or fixture annotation, plain
+/// (`@Test`) or qualified (`@org.junit.jupiter.api.Test`).
+fn is_test_method(node: Node, source: &str) -> bool {
+    let mut cursor = node.walk();
+    let Some(modifiers) = node.children(&mut cursor).find(|c| c.kind() == "modifiers") else {
+        return false;
+    };
+    let mut cursor = modifiers.walk();
+    let annotations: Vec<Node> = modifiers
+        .children(&mut cursor)
+        .filter(|c| matches!(c.kind(), "marker_annotation" | "annotation"))
+        .collect();
+    annotations.iter().filter_map(|a| a.child_by_field_name("name")).any(|name| {
+        let name = node_text(name, source);
+        TEST_ANNOTATIONS.contains(&name.rsplit('.').next().unwrap_or(name))
+    })
+}
+
+/// The mutations `node` itself yields; `mutation_parser::walk` visits its
+/// children.
+fn mutate_node(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    match node.kind() {
+        "binary_expression" => {
+            collect_binary_mutations(node, source, mutations);
+        }
+        "unary_expression" => {
+            collect_unary_mutations(node, source, mutations);
+        }
+        "assignment_expression" => {
+            collect_aug_assign_mutations(node, source, mutations);
+        }
+        "return_statement" => {
+            collect_return_mutations(node, source, mutations);
+        }
+        "true" | "false" => {
+            collect_boolean_mutations(node, source, mutations);
+        }
+        "if_statement" => {
+            collect_if_body_mutations(node, source, mutations);
+        }
+        "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement" => {
+            collect_loop_body_mutations(node, source, mutations);
+            if node.kind() == "while_statement" {
+                collect_while_condition_mutations(node, source, mutations);
+            }
+        }
+        "break_statement" | "continue_statement" => {
+            collect_loop_control_mutations(node, source, mutations);
+        }
+        "ternary_expression" => {
+            collect_ternary_mutations(node, source, mutations);
+        }
+        "method_invocation" => {
+            collect_arg_swap_mutations(node, source, mutations);
+            collect_string_method_mutations(node, source, mutations);
+        }
+        "expression_statement" => {
+            collect_call_remove_mutations(node, source, mutations);
+        }
+        _ => {}
+    }
+}
+
+fn should_skip_node(node: Node, source: &str) -> bool {
+    if node.kind() != "method_invocation" {
+        return false;
+    }
+    let object = node.child_by_field_name("object").map_or("", |o| node_text(o, source));
+    if object == "System.out" || object == "System.err" {
+        return true;
+    }
+    let name = node.child_by_field_name("name").map_or("", |n| node_text(n, source));
+    // `logger.info(...)`, `LOG.warn(...)`; a bare `error(...)` may be ours
+    !object.is_empty() && operators::is_logging_call(name)
+}
+
+fn push_op(node: Node, source: &str, op: MutationOp, mutations: &mut Vec<Mutation>) {
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: op.operator_name.to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: op.replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn logical_mutations(op: &str) -> Vec<MutationOp> {
+    match op {
+        "&&" => vec![MutationOp { operator_name: "logic_flip", replacement: "||" }],
+        "||" => vec![MutationOp { operator_name: "logic_flip", replacement: "&&" }],
+        _ => vec![],
+    }
+}
+
+fn collect_binary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(op_node) = node.child_by_field_name("operator") else {
+        return;
+    };
+    let op_text = node_text(op_node, source);
+    let ops = match op_text {
+        ">" | ">=" | "<" | "<=" | "==" | "!=" => {
+            let mut ops = operators::comparison_mutations(op_text);
+            if let Some(length_on_left) = length_side(node, source) {
+                ops.retain(|op| op.operator_name != "boundary");
+                ops.extend(operators::length_boundary(op_text, length_on_left));
+            }
+            ops
+        }
+        "&&" | "||" => logical_mutations(op_text),
+        "+" | "-" | "*" | "/" | "%" => {
+            // Skip string concatenation
+            let is_string = |field| node.child_by_field_name(field).is_some_and(|n| n.kind() == "string_literal");
+            if op_text == "+" && (is_string("left") || is_string("right")) {
+                return;
+            }
+            operators::arithmetic_mutations(op_text)
+        }
+        _ => vec![],
+    };
+    for op in ops {
+        push_op(op_node, source, op, mutations);
+    }
+}
+
+/// Whether the left (`Some(true)`) or right operand of binary expression
+/// `node` is a `.size()`/`.length()` call or an array's `.length`.
+fn length_side(node: Node, source: &str) -> Option<bool> {
+    let is_length = |field| {
+        node.child_by_field_name(field).is_some_and(|n| match n.kind() {
+            "method_invocation" => {
+                n.child_by_field_name("arguments").is_some_and(|a| a.named_child_count() == 0)
+                    && n.child_by_field_name("name")
+                        .is_some_and(|name| matches!(node_text(name, source), "size" | "length"))
+            }
+            "field_access" => n.child_by_field_name("field").is_some_and(|f| node_text(f, source) == "length"),
+            _ => false,
+        })
+    };
+    if is_length("left") {
+        Some(true)
+    } else if is_length("right") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn collect_unary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(op_node), Some(operand)) = (node.child_by_field_name("operator"), node.child_by_field_name("operand")) else {
+        return;
+    };
+    if op_node.kind() != "!" {
+        return;
+    }
+    mutations.push(Mutation {
+        line: op_node.start_position().row + 1,
+        column: op_node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "negate_remove".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: node_text(operand, source).to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `total += x` -> `total -= x`.
+fn collect_aug_assign_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(op_node) = node.child_by_field_name("operator") else {
+        return;
+    };
+    for op in operators::augmented_assignment_mutations(node_text(op_node, source)) {
+        push_op(op_node, source, op, mutations);
+    }
+}
+
+fn collect_return_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // return_statement: "return" [expression] ";" -- a bare `return;` has
+    // no value to replace
+    let Some(expr) = node.named_child(0) else {
+        return;
+    };
+    let expr_text = node_text(expr, source).trim();
+    let Some(replacement) = return_value_replacement(expr_text, declared_return_type(node, source)) else {
+        return;
+    };
+
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "return_val".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: format!("return {};", replacement),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// The declared return type of the method `node` returns from. Lambdas
+/// have none.
+fn declared_return_type<'a>(node: Node, source: &'a str) -> Option<&'a str> {
+    let mut ancestor = node.parent();
+    while let Some(n) = ancestor {
+        match n.kind() {
+            "method_declaration" => return n.child_by_field_name("type").map(|t| node_text(t, source).trim()),
+            "lambda_expression" | "constructor_declaration" => return None,
+            _ => ancestor = n.parent(),
+        }
+    }
+    None
+}
+
+/// Replacement value for a returned expression, picked from the method's
+/// declared type so the mutant still compiles. `None` when the value is
+/// already the one we'd return.
+fn return_value_replacement(expr_text: &str, return_type: Option<&str>) -> Option<String> {
+    let ty = return_type?;
+    let qualified = ty.split('<').next().unwrap_or(ty).trim();
+    let base = qualified.rsplit('.').next().unwrap_or(qualified);
+    // `0` for a `long`, `0.0f` for a `float`: compare by value, not text
+    let number = |zero: &str, one: &str| {
+        Some(if numeric_literal(expr_text) == Some(0.0) { one } else { zero }.to_string())
+    };
+    // `List.of()` needs the import the file has; a qualified type may have none
+    let util = |name: &str, call: &str| {
+        if qualified == name { format!("{}.{}", name, call) } else { format!("java.util.{}.{}", name, call) }
+    };
+    let value = match base {
+        "boolean" | "Boolean" => match expr_text {
+            "true" => "false".to_string(),
+            "false" => "true".to_string(),
+            _ => format!("!({})", expr_text),
+        },
+        "int" | "short" | "byte" | "Integer" | "Short" | "Byte" => return number("0", "1"),
+        "long" | "Long" => return number("0L", "1L"),
+        "double" | "Double" => return number("0.0", "1.0"),
+        "float" | "Float" => return number("0.0f", "1.0f"),
+        "char" | "Character" => return None,
+        "String" | "CharSequence" => "\"\"".to_string(),
+        "Optional" => util("Optional", "empty()"),
+        "List" | "Collection" | "Iterable" => util("List", "of()"),
+        "Set" => util("Set", "of()"),
+        "Map" => util("Map", "of()"),
+        // Arrays, generics and other references
+        _ => "null".to_string(),
+    };
+    (value != expr_text).then_some(value)
+}
+
+/// The value of a decimal or hex number literal (`1_000L`, `0.5f`, `0x0`).
+fn numeric_literal(text: &str) -> Option<f64> {
+    let text = text.replace('_', "");
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        let hex = hex.trim_end_matches(['l', 'L']);
+        return i64::from_str_radix(hex, 16).ok().map(|n| n as f64);
+    }
+    text.trim_end_matches(['l', 'L', 'f', 'F', 'd', 'D']).parse().ok()
+}
+
+fn collect_boolean_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // Returned literals are handled by return_val
+    if node.parent().is_some_and(|p| p.kind() == "return_statement") {
+        return;
+    }
+    let replacement = match node_text(node, source) {
+        "true" => "false",
+        "false" => "true",
+        _ => return,
+    };
+    push_op(node, source, MutationOp { operator_name: "bool_flip", replacement }, mutations);
+}
+
+fn collect_if_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // if_statement: condition, consequence, alternative -- the alternative
+    // is the else statement itself; an `else if` is a nested if_statement
+    for field in ["consequence", "alternative"] {
+        if let Some(branch) = node.child_by_field_name(field).filter(|b| b.kind() == "block") {
+            add_block_remove_mutation(branch, source, mutations);
+        }
+    }
+}
+
+fn collect_loop_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    if let Some(body) = node.child_by_field_name("body").filter(|b| b.kind() == "block") {
+        add_block_remove_mutation(body, source, mutations);
+    }
+}
+
+/// `while (cond)` -> `while (false)`: the loop body never runs.
+fn collect_while_condition_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // parenthesized_expression: "(" expr ")"
+    let Some(inner) = node.child_by_field_name("condition").and_then(|c| c.named_child(0)) else {
+        return;
+    };
+    // Literal conditions are already covered by bool_flip, and javac
+    // rejects the unreachable body of `while (false)` anyway
+    if matches!(inner.kind(), "true" | "false") {
+        return;
+    }
+    push_op(inner, source, MutationOp { operator_name: "loop_zero", replacement: "false" }, mutations);
+}
+
+/// `break` <-> `continue`. Only the keyword is replaced so labels survive.
+fn collect_loop_control_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // A `break` that exits a switch has no loop to `continue`
+    if !innermost_breakable_is_loop(node) {
+        return;
+    }
+    let Some(keyword) = node.child(0) else {
+        return;
+    };
+    for op in operators::loop_control_mutations(keyword.kind()) {
+        push_op(keyword, source, op, mutations);
+    }
+}
+
+fn innermost_breakable_is_loop(node: Node) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        match n.kind() {
+            "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement" => return true,
+            "switch_block" => return node.kind() == "continue_statement",
+            "method_declaration" | "constructor_declaration" | "lambda_expression" => return false,
+            _ => current = n.parent(),
+        }
+    }
+    false
+}
+
+/// Swap the branches of `cond ? a : b` -> `cond ? b : a`.
+fn collect_ternary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(consequence), Some(alternative)) =
+        (node.child_by_field_name("consequence"), node.child_by_field_name("alternative"))
+    else {
+        return;
+    };
+
+    mutations.push(Mutation {
+        line: consequence.start_position().row + 1,
+        column: consequence.start_position().column + 1,
+        start_byte: consequence.start_byte(),
+        end_byte: alternative.end_byte(),
+        operator: "ternary_swap".to_string(),
+        original: source[consequence.start_byte()..alternative.end_byte()].to_string(),
+        replacement: format!(
+            "{}{}{}",
+            node_text(alternative, source),
+            &source[consequence.end_byte()..alternative.start_byte()],
+            node_text(consequence, source),
+        ),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn add_block_remove_mutation(block: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    if block.named_child_count() == 0 {
+        return;
+    }
+    push_op(block, source, MutationOp { operator_name: "block_remove", replacement: "{}" }, mutations);
+}
+
+/// `.startsWith` <-> `.endsWith`, `.toUpperCase()` <-> `.toLowerCase()`,
+/// and `.trim()`/`.strip()` dropped (`name.trim()` -> `name`).
+fn collect_string_method_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(object), Some(method)) = (node.child_by_field_name("object"), node.child_by_field_name("name")) else {
+        return;
+    };
+    let name = node_text(method, source);
+    if let Some(replacement) = operators::string_method_swap(name) {
+        push_op(method, source, MutationOp { operator_name: "string_method", replacement }, mutations);
+    } else if operators::is_trim_method(name) || name == "stripLeading" || name == "stripTrailing" {
+        let no_args = node.child_by_field_name("arguments").is_some_and(|a| a.named_child_count() == 0);
+        if !no_args {
+            return;
+        }
+        mutations.push(Mutation {
+            line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: "trim_remove".to_string(),
+            original: node_text(node, source).to_string(),
+            replacement: node_text(object, source).to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+/// Opt-in `collection_method`: `Math.min` <-> `Math.max`.
+fn collect_collection_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(object), Some(method)) = (node.child_by_field_name("object"), node.child_by_field_name("name")) else {
+        return;
+    };
+    if node_text(object, source) != "Math" {
+        return;
+    }
+    if let Some(replacement) = operators::min_max_swap(node_text(method, source)) {
+        push_op(method, source, MutationOp { operator_name: "collection_method", replacement }, mutations);
+    }
+}
+
+/// Swap the first two arguments: `f(a, b)` -> `f(b, a)`.
+fn collect_arg_swap_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(method), Some(args)) = (node.child_by_field_name("name"), node.child_by_field_name("arguments")) else {
+        return;
+    };
+    if operators::is_logging_call(node_text(method, source)) {
+        return;
+    }
+    let mut cursor = args.walk();
+    let mut positional = args.named_children(&mut cursor).filter(|a| !a.kind().contains("comment"));
+    let (Some(first), Some(second)) = (positional.next(), positional.next()) else {
+        return;
+    };
+    if !operators::swappable_args(literal_kind(first), literal_kind(second)) {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: first.start_position().row + 1,
+        column: first.start_position().column + 1,
+        start_byte: first.start_byte(),
+        end_byte: second.end_byte(),
+        operator: "arg_swap".to_string(),
+        original: source[first.start_byte()..second.end_byte()].to_string(),
+        replacement: format!(
+            "{}{}{}",
+            node_text(second, source),
+            &source[first.end_byte()..second.start_byte()],
+            node_text(first, source),
+        ),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Java literal kinds under the names `operators::swappable_args` knows.
+fn literal_kind(node: Node) -> &'static str {
+    match node.kind() {
+        "string_literal" => "string_literal",
+        "decimal_integer_literal" | "hex_integer_literal" | "octal_integer_literal" | "binary_integer_literal" => {
+            "integer_literal"
+        }
+        "decimal_floating_point_literal" | "hex_floating_point_literal" => "float_literal",
+        "true" => "true",
+        "false" => "false",
+        "null_literal" => "null",
+        "character_literal" => "char_literal",
+        _ => "expression",
+    }
+}
+
+/// Whether `statement` is all of an `if`/`else` or loop body, which
+/// `block_remove` already empties.
+fn is_sole_statement_of_removed_block(statement: Node) -> bool {
+    let Some(block) = statement.parent().filter(|b| b.kind() == "block") else {
+        return false;
+    };
+    let mut cursor = block.walk();
+    if block.named_children(&mut cursor).filter(|s| !s.kind().contains("comment")).count() != 1 {
+        return false;
+    }
+    block.parent().is_some_and(|owner| match owner.kind() {
+        "if_statement" => {
+            owner.child_by_field_name("consequence") == Some(block) || owner.child_by_field_name("alternative") == Some(block)
+        }
+        "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement" => {
+            owner.child_by_field_name("body") == Some(block)
+        }
+        _ => false,
+    })
+}
+
+/// Drop a call made for its side effect: `list.add(x);` -> `;`.
+fn collect_call_remove_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(call) = node.named_child(0).filter(|c| c.kind() == "method_invocation") else {
+        return;
+    };
+    let Some(method) = call.child_by_field_name("name") else {
+        return;
+    };
+    if should_skip_node(call, source)
+        || operators::is_logging_call(node_text(method, source))
+        || is_sole_statement_of_removed_block(node)
+    {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "call_remove".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: ";".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
@@ -1,236 +1,290 @@
-use tree_sitter::{Node, Parser};
-use crate::mutants::Mutation;
+use std::path::Path;
 
-#[derive(Clone, Copy)]
+use tree_sitter::Node;
+use crate::mutants::{self, FunctionSpan, Mutation};
+use crate::mutation_parser::{self, node_text, FunctionDef, Grammar, MutationParser, ParseOptions};
+use crate::operators;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JsDialect {
     JavaScript,
     TypeScript,
     Tsx,
 }
 
+/// Discover mutations in production code. Test definitions (`describe`,
+/// `it`, `test` callbacks and hooks) are skipped unless named explicitly.
 pub fn discover_mutations(source: &str, function_name: Option<&str>, dialect: JsDialect) -> Vec<Mutation> {
-    let mut parser = Parser::new();
-    let language = match dialect {
-        JsDialect::JavaScript => tree_sitter_javascript::LANGUAGE,
-        JsDialect::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
-        JsDialect::Tsx => tree_sitter_typescript::LANGUAGE_TSX,
-    };
-    parser.set_language(&language.into()).expect("Failed to set JS/TS grammar");
+    discover_mutations_with(source, function_name, dialect, false)
+}
 
-    let tree = parser.parse(source, None).expect("Failed to parse JS/TS source");
+/// Like `discover_mutations`, optionally walking into test code too.
+pub fn discover_mutations_with(
+    source: &str,
+    function_name: Option<&str>,
+    dialect: JsDialect,
+    include_tests: bool,
+) -> Vec<Mutation> {
+    let options = ParseOptions { include_tests, ..Default::default() };
+    mutation_parser::discover(&JsParser(dialect), source, function_name, &options)
+}
+
+/// Mutations in module-level code (`--include-toplevel`): constants, lookup
+/// tables, class field initializers and top-level conditionals. Function
+/// and method bodies (see `discover_mutations_with`) and test calls are left
+/// out.
+pub fn toplevel_mutations(source: &str, dialect: JsDialect) -> Vec<Mutation> {
+    toplevel_mutations_with(source, dialect, &ParseOptions::default())
+}
+
+/// Like `toplevel_mutations`, with every `ParseOptions` setting.
+pub fn toplevel_mutations_with(source: &str, dialect: JsDialect, options: &ParseOptions) -> Vec<Mutation> {
+    let tree = mutation_parser::parse(&JsParser(dialect), source);
     let root = tree.root_node();
-    let lines: Vec<&str> = source.lines().collect();
 
     let mut mutations = Vec::new();
+    mutation_parser::walk(&JsParser(dialect), root, source, options, &mut mutations);
 
-    match function_name {
-        Some(name) => {
-            if let Some(func_node) = find_function(root, name, source) {
-                walk_node(func_node, source, &lines, &mut mutations);
-            }
-        }
-        None => {
-            collect_all_functions(root, source, &lines, &mut mutations);
+    let mut excluded = Vec::new();
+    collect_function_ranges(root, source, &mut excluded);
+    mutations.retain(|m| !excluded.iter().any(|r| r.start <= m.start_byte && m.end_byte <= r.end));
+    mutants::assign_context(&mut mutations, source, mutants::DEFAULT_CONTEXT_LINES);
+
+    mutations
+}
+
+/// Byte ranges of every function-like node (named or not) and test call.
+fn collect_function_ranges(node: Node, source: &str, ranges: &mut Vec<std::ops::Range<usize>>) {
+    let kind = node.kind();
+    if is_function_node(kind)
+        || matches!(kind, "function_declaration" | "generator_function_declaration" | "method_definition")
+        || is_test_call(node, source)
+    {
+        ranges.push(node.byte_range());
+        return;
+    }
+    let count = node.child_count();
+    for i in 0..count {
+        if let Some(child) = node.child(i) {
+            collect_function_ranges(child, source, ranges);
         }
     }
+}
 
-    mutations
+/// Byte spans of every function, method and function-valued declarator.
+pub fn function_spans(source: &str, dialect: JsDialect) -> Vec<FunctionSpan> {
+    mutation_parser::function_spans(&JsParser(dialect), source)
 }
 
 pub fn list_functions(source: &str, dialect: JsDialect) -> Vec<String> {
-    let mut parser = Parser::new();
-    let language = match dialect {
-        JsDialect::JavaScript => tree_sitter_javascript::LANGUAGE,
-        JsDialect::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
-        JsDialect::Tsx => tree_sitter_typescript::LANGUAGE_TSX,
-    };
-    parser.set_language(&language.into()).expect("Failed to set JS/TS grammar");
-
-    let tree = parser.parse(source, None).expect("Failed to parse JS/TS source");
-    let root = tree.root_node();
-    let mut names = Vec::new();
-    collect_function_names(root, source, &mut names);
-    names
+    mutation_parser::list_functions(&JsParser(dialect), source)
 }
 
-fn find_function<'a>(node: Node<'a>, name: &str, source: &str) -> Option<Node<'a>> {
-    match node.kind() {
-        // function foo() {}
-        "function_declaration" | "generator_function_declaration" => {
-            if let Some(name_node) = node.child_by_field_name("name") {
-                if node_text(name_node, source) == name {
-                    return Some(node);
-                }
-            }
-        }
-        // class { foo() {} }
-        "method_definition" => {
-            if let Some(name_node) = node.child_by_field_name("name") {
-                if node_text(name_node, source) == name {
-                    return Some(node);
-                }
-            }
+/// `MutationParser` for one JavaScript/TypeScript dialect.
+pub struct JsParser(pub JsDialect);
+
+impl Grammar for JsParser {
+    fn language(&self) -> tree_sitter::Language {
+        match self.0 {
+            JsDialect::JavaScript => tree_sitter_javascript::LANGUAGE,
+            JsDialect::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT,
+            JsDialect::Tsx => tree_sitter_typescript::LANGUAGE_TSX,
         }
-        // const foo = () => {} or const foo = function() {}
-        "lexical_declaration" | "variable_declaration" => {
-            let count = node.child_count();
-            for i in 0..count {
-                if let Some(declarator) = node.child(i) {
-                    if declarator.kind() == "variable_declarator" {
-                        if let Some(name_node) = declarator.child_by_field_name("name") {
-                            if node_text(name_node, source) == name {
-                                if let Some(value) = declarator.child_by_field_name("value") {
-                                    if is_function_node(value.kind()) {
-                                        return Some(node);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        .into()
+    }
+
+    /// `function foo() {}`, `class Foo { bar() {} }` and
+    /// `const foo = () => {}`; the latter's body is the function value.
+    fn function_at<'a>(&self, node: Node<'a>, _source: &str) -> Option<FunctionDef<'a>> {
+        match node.kind() {
+            "function_declaration" | "generator_function_declaration" | "method_definition" => {
+                let name = node.child_by_field_name("name")?;
+                Some(FunctionDef { node, name, body: node })
             }
-        }
-        // export function foo() {} or export default function foo() {}
-        "export_statement" => {
-            let count = node.child_count();
-            for i in 0..count {
-                if let Some(child) = node.child(i) {
-                    if let Some(found) = find_function(child, name, source) {
-                        return Some(found);
-                    }
-                }
+            "variable_declarator" => {
+                let name = node.child_by_field_name("name")?;
+                let body = node.child_by_field_name("value").filter(|v| is_function_node(v.kind()))?;
+                Some(FunctionDef { node, name, body })
             }
+            _ => None,
         }
-        _ => {}
     }
 
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            if let Some(found) = find_function(child, name, source) {
-                return Some(found);
-            }
+    fn qualified_name(&self, def: &FunctionDef, source: &str) -> String {
+        qualified_name(def.node, def.name, source)
+    }
+
+    fn mutate_node(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        mutate_node(node, source, mutations);
+    }
+
+    fn mutate_collection(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        if node.kind() == "call_expression" {
+            collect_collection_mutations(node, source, mutations);
         }
     }
-    None
+
+    /// `console` calls and directives like `'use strict'`.
+    fn should_skip(&self, node: Node, source: &str) -> bool {
+        should_skip_node(node, source)
+    }
+
+    fn is_test_code(&self, node: Node, source: &str) -> bool {
+        is_test_call(node, source)
+    }
+
+    /// Not constructors, `test*` or `_private` helpers, nor anything
+    /// declared inside a test callback.
+    fn is_listed(&self, def: &FunctionDef, source: &str) -> bool {
+        let name = node_text(def.name, source);
+        let shown = match def.node.kind() {
+            "method_definition" => !name.starts_with("test") && name != "constructor",
+            _ => !name.starts_with("test") && !name.starts_with('_'),
+        };
+        shown && !std::iter::successors(def.node.parent(), |n| n.parent()).any(|n| is_test_call(n, source))
+    }
+}
+
+impl MutationParser for JsParser {
+    fn discover(&self, source: &str, function: Option<&str>, options: &ParseOptions) -> Vec<Mutation> {
+        mutation_parser::discover(self, source, function, options)
+    }
+
+    fn toplevel(&self, source: &str, options: &ParseOptions) -> Vec<Mutation> {
+        toplevel_mutations_with(source, self.0, options)
+    }
+
+    fn function_spans(&self, source: &str) -> Vec<FunctionSpan> {
+        function_spans(source, self.0)
+    }
+
+    fn list_functions(&self, source: &str) -> Vec<String> {
+        list_functions(source, self.0)
+    }
 }
 
 fn is_function_node(kind: &str) -> bool {
     matches!(kind, "arrow_function" | "function" | "generator_function")
 }
 
-fn collect_all_functions(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
-    match node.kind() {
-        "function_declaration" | "generator_function_declaration" | "method_definition" => {
-            walk_node(node, source, lines, mutations);
-            return;
-        }
-        "lexical_declaration" | "variable_declaration" => {
-            let count = node.child_count();
-            for i in 0..count {
-                if let Some(declarator) = node.child(i) {
-                    if declarator.kind() == "variable_declarator" {
-                        if let Some(value) = declarator.child_by_field_name("value") {
-                            if is_function_node(value.kind()) {
-                                walk_node(value, source, lines, mutations);
-                                return;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        _ => {}
+/// Jest/Vitest/Mocha test definitions and hooks, including modifiers and
+/// table forms: `it.only(...)`, `describe.each(table)(...)`.
+fn is_test_call(node: Node, source: &str) -> bool {
+    if node.kind() != "call_expression" {
+        return false;
     }
-
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            collect_all_functions(child, source, lines, mutations);
-        }
+    let mut callee = node.child_by_field_name("function");
+    while let Some(inner) = callee.filter(|c| c.kind() == "call_expression") {
+        callee = inner.child_by_field_name("function");
     }
+    let Some(callee) = callee else {
+        return false;
+    };
+    let name = node_text(callee, source).split('.').next().unwrap_or("");
+    matches!(
+        name,
+        "describe"
+            | "fdescribe"
+            | "xdescribe"
+            | "suite"
+            | "it"
+            | "fit"
+            | "xit"
+            | "test"
+            | "xtest"
+            | "beforeEach"
+            | "afterEach"
+            | "beforeAll"
+            | "afterAll"
+    )
 }
 
-fn collect_function_names(node: Node, source: &str, names: &mut Vec<String>) {
-    match node.kind() {
-        "function_declaration" | "generator_function_declaration" => {
-            if let Some(name_node) = node.child_by_field_name("name") {
-                let name = node_text(name_node, source);
-                if !name.starts_with("test") && !name.starts_with("_") {
-                    names.push(name.to_string());
-                }
-            }
-        }
-        "method_definition" => {
-            if let Some(name_node) = node.child_by_field_name("name") {
-                let name = node_text(name_node, source);
-                if !name.starts_with("test") && name != "constructor" {
-                    names.push(name.to_string());
-                }
-            }
-        }
-        "lexical_declaration" | "variable_declaration" => {
-            let count = node.child_count();
-            for i in 0..count {
-                if let Some(declarator) = node.child(i) {
-                    if declarator.kind() == "variable_declarator" {
-                        if let Some(value) = declarator.child_by_field_name("value") {
-                            if is_function_node(value.kind()) {
-                                if let Some(name_node) = declarator.child_by_field_name("name") {
-                                    let name = node_text(name_node, source);
-                                    if !name.starts_with("test") && !name.starts_with("_") {
-                                        names.push(name.to_string());
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        _ => {}
-    }
+/// Test files by naming convention: `*.test.ts`, `*.spec.js`, or anything
+/// under `__tests__/`.
+pub fn is_test_file(path: &Path) -> bool {
+    let in_tests_dir = path.components().any(|c| c.as_os_str() == "__tests__");
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    in_tests_dir || name.contains(".test.") || name.contains(".spec.")
+}
 
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            collect_function_names(child, source, names);
+/// A function's name prefixed with its enclosing classes and named
+/// functions: `Outer.Inner.method`, `outer.inner` for a closure declared
+/// in `outer`. `node` is the declaration, method or declarator owning
+/// `name_node`; anonymous classes and callbacks add nothing.
+fn qualified_name(node: Node, name_node: Node, source: &str) -> String {
+    let mut parts = vec![node_text(name_node, source)];
+    let mut ancestor = node.parent();
+    while let Some(n) = ancestor {
+        let name = match n.kind() {
+            "class_declaration" | "abstract_class_declaration" | "class" | "method_definition"
+            | "function_declaration" | "generator_function_declaration" => n.child_by_field_name("name"),
+            "variable_declarator" => n
+                .child_by_field_name("value")
+                .filter(|v| is_function_node(v.kind()))
+                .and(n.child_by_field_name("name")),
+            _ => None,
+        };
+        if let Some(name) = name {
+            parts.push(node_text(name, source));
         }
+        ancestor = n.parent();
     }
+    parts.reverse();
+    parts.join(".")
 }
 
-fn walk_node(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
-    if should_skip_node(node, source) {
-        return;
-    }
-
+/// The mutations `node` itself yields; `mutation_parser::walk` visits its
+/// children.
+fn mutate_node(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     match node.kind() {
         "binary_expression" => {
-            collect_binary_mutations(node, source, lines, mutations);
+            collect_binary_mutations(node, source, mutations);
+            collect_conditional_render_mutations(node, source, mutations);
         }
         "unary_expression" => {
-            collect_unary_mutations(node, source, lines, mutations);
+            collect_unary_mutations(node, source, mutations);
         }
         "return_statement" => {
-            collect_return_mutations(node, source, lines, mutations);
+            collect_return_mutations(node, source, mutations);
         }
         "true" | "false" => {
-            collect_boolean_mutations(node, source, lines, mutations);
+            collect_boolean_mutations(node, source, mutations);
         }
         "if_statement" => {
-            collect_if_body_mutations(node, source, lines, mutations);
+            collect_if_body_mutations(node, source, mutations);
         }
         "for_statement" | "for_in_statement" | "while_statement" => {
-            collect_loop_body_mutations(node, source, lines, mutations);
+            collect_loop_body_mutations(node, source, mutations);
+            if node.kind() == "while_statement" {
+                collect_while_condition_mutations(node, source, mutations);
+            }
         }
-        _ => {}
-    }
-
-    let child_count = node.child_count();
-    for i in 0..child_count {
-        if let Some(child) = node.child(i) {
-            walk_node(child, source, lines, mutations);
+        "break_statement" | "continue_statement" => {
+            collect_loop_control_mutations(node, source, mutations);
+        }
+        "ternary_expression" => {
+            collect_ternary_mutations(node, source, mutations);
+        }
+        "switch_case" | "switch_default" => {
+            collect_switch_case_mutations(node, source, mutations);
+        }
+        "await_expression" => {
+            collect_await_mutations(node, source, mutations);
+        }
+        "optional_chain" => {
+            collect_optional_chain_mutations(node, source, mutations);
+        }
+        "call_expression" => {
+            collect_arg_swap_mutations(node, source, mutations);
+            collect_string_method_mutations(node, source, mutations);
+        }
+        "assignment_pattern" | "object_assignment_pattern" | "required_parameter" | "optional_parameter" => {
+            collect_default_value_mutations(node, source, mutations);
         }
+        "expression_statement" => {
+            collect_call_remove_mutations(node, source, mutations);
+        }
+        _ => {}
     }
 }
 
@@ -259,22 +313,6 @@ fn should_skip_node(node: Node, source: &str) -> bool {
     false
 }
 
-fn get_context(lines: &[&str], line_idx: usize, range: usize) -> (Vec<String>, Vec<String>) {
-    let start = line_idx.saturating_sub(range);
-    let end = (line_idx + range + 1).min(lines.len());
-    let before: Vec<String> = lines[start..line_idx].iter().map(|s| s.to_string()).collect();
-    let after: Vec<String> = if line_idx + 1 < end {
-        lines[line_idx + 1..end].iter().map(|s| s.to_string()).collect()
-    } else {
-        vec![]
-    };
-    (before, after)
-}
-
-fn node_text<'a>(node: Node<'a>, source: &'a str) -> &'a str {
-    &source[node.start_byte()..node.end_byte()]
-}
-
 struct JsMutationOp {
     operator_name: &'static str,
     replacement: &'static str,
@@ -335,12 +373,22 @@ fn arithmetic_mutations(op: &str) -> Vec<JsMutationOp> {
     }
 }
 
-fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_binary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     if let Some(op_node) = node.child_by_field_name("operator") {
         let op_text = node_text(op_node, source);
 
         let ops: Vec<JsMutationOp> = match op_text {
-            ">" | ">=" | "<" | "<=" | "==" | "!=" | "===" | "!==" => comparison_mutations(op_text),
+            ">" | ">=" | "<" | "<=" | "==" | "!=" | "===" | "!==" => {
+                let mut ops = comparison_mutations(op_text);
+                if let Some(length_on_left) = length_side(node, source) {
+                    ops.retain(|op| op.operator_name != "boundary");
+                    ops.extend(operators::length_boundary(op_text, length_on_left).map(|op| JsMutationOp {
+                        operator_name: op.operator_name,
+                        replacement: op.replacement,
+                    }));
+                }
+                ops
+            }
             "&&" | "||" | "??" => logical_mutations(op_text),
             "+" | "-" | "*" | "/" | "%" | "**" => {
                 // Skip string concatenation
@@ -362,7 +410,6 @@ fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations:
 
         let line = op_node.start_position().row + 1;
         let col = op_node.start_position().column + 1;
-        let (ctx_before, ctx_after) = get_context(lines, op_node.start_position().row, 2);
 
         for op in ops {
             mutations.push(Mutation {
@@ -373,20 +420,38 @@ fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations:
                 operator: op.operator_name.to_string(),
                 original: op_text.to_string(),
                 replacement: op.replacement.to_string(),
-                context_before: ctx_before.clone(),
-                context_after: ctx_after.clone(),
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
             });
         }
     }
 }
 
-fn collect_unary_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+/// Whether the left (`Some(true)`) or right operand of binary expression
+/// `node` is a `.length` read.
+fn length_side(node: Node, source: &str) -> Option<bool> {
+    let is_length = |field| {
+        node.child_by_field_name(field).is_some_and(|n| {
+            n.kind() == "member_expression"
+                && n.child_by_field_name("property").is_some_and(|p| node_text(p, source) == "length")
+        })
+    };
+    if is_length("left") {
+        Some(true)
+    } else if is_length("right") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn collect_unary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     if let Some(op_node) = node.child_by_field_name("operator") {
         if op_node.kind() == "!" {
             if let Some(operand) = node.child_by_field_name("argument") {
                 let line = op_node.start_position().row + 1;
                 let col = op_node.start_position().column + 1;
-                let (ctx_before, ctx_after) = get_context(lines, op_node.start_position().row, 2);
 
                 mutations.push(Mutation {
                     line,
@@ -396,15 +461,16 @@ fn collect_unary_mutations(node: Node, source: &str, lines: &[&str], mutations:
                     operator: "negate_remove".to_string(),
                     original: node_text(node, source).to_string(),
                     replacement: node_text(operand, source).to_string(),
-                    context_before: ctx_before,
-                    context_after: ctx_after,
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    function: None,
                 });
             }
         }
     }
 }
 
-fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_return_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // return_statement children: "return" [expression] [";"]
     let mut expr = None;
     let child_count = node.child_count();
@@ -419,7 +485,6 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
 
     let line = node.start_position().row + 1;
     let col = node.start_position().column + 1;
-    let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
 
     let expr = match expr {
         Some(e) => e,
@@ -433,8 +498,9 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
                 operator: "return_val".to_string(),
                 original: node_text(node, source).to_string(),
                 replacement: "return undefined;".to_string(),
-                context_before: ctx_before,
-                context_after: ctx_after,
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
             });
             return;
         }
@@ -442,7 +508,53 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
 
     let expr_text = node_text(expr, source).trim();
 
-    let replacement = if expr_text == "true" {
+    // A declared TypeScript return type beats guessing from the expression
+    let typed = declared_return_type(node, source).map_or(TypedReturn::Unknown, |ty| typed_return(&ty, expr_text));
+    match typed {
+        TypedReturn::Replace(replacement) => {
+            mutations.push(Mutation {
+                line,
+                column: col,
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                operator: "return_val".to_string(),
+                original: node_text(node, source).to_string(),
+                replacement,
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
+            });
+            return;
+        }
+        TypedReturn::Skip => return,
+        TypedReturn::Unknown => {}
+    }
+
+    // Keep the promise contract intact: resolve with null instead of
+    // returning a non-promise
+    if expr_text.starts_with("Promise.resolve(") {
+        if expr_text == "Promise.resolve()" || expr_text == "Promise.resolve(null)" {
+            return;
+        }
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: "return_val".to_string(),
+            original: node_text(node, source).to_string(),
+            replacement: "return Promise.resolve(null);".to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+        return;
+    }
+
+    let replacement = if expr.kind() == "await_expression" {
+        // `return await foo()` resolves to null in the async caller
+        "return null;"
+    } else if expr_text == "true" {
         "return false;"
     } else if expr_text == "false" {
         "return true;"
@@ -472,12 +584,88 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
         operator: "return_val".to_string(),
         original: node_text(node, source).to_string(),
         replacement: replacement.to_string(),
-        context_before: ctx_before,
-        context_after: ctx_after,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
     });
 }
 
-fn collect_boolean_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+/// Return replacement picked from the function's declared return type.
+enum TypedReturn {
+    Replace(String),
+    /// The only type-valid replacement is the value already returned.
+    Skip,
+    /// No annotation, or one we can't build a value for: fall back to the
+    /// expression's shape.
+    Unknown,
+}
+
+/// The return type annotation of the function `ret` returns from, without
+/// the `:`. An `async` function's `Promise<T>` gives `T`.
+fn declared_return_type(ret: Node, source: &str) -> Option<String> {
+    let mut ancestor = ret.parent();
+    let func = loop {
+        let n = ancestor?;
+        if matches!(
+            n.kind(),
+            "function_declaration"
+                | "generator_function_declaration"
+                | "method_definition"
+                | "arrow_function"
+                | "function_expression"
+                | "function"
+                | "generator_function"
+        ) {
+            break n;
+        }
+        ancestor = n.parent();
+    };
+    // Generators return an iterator, whatever each `return` yields
+    if func.kind().contains("generator") {
+        return None;
+    }
+    let annotation = func.child_by_field_name("return_type")?;
+    let ty = node_text(annotation, source).trim_start_matches(':').trim();
+    let mut cursor = func.walk();
+    let is_async = func.children(&mut cursor).any(|c| c.kind() == "async");
+    match ty.strip_prefix("Promise<").and_then(|t| t.strip_suffix('>')) {
+        Some(inner) if is_async => Some(inner.trim().to_string()),
+        _ if is_async => None,
+        _ => Some(ty.to_string()),
+    }
+}
+
+fn typed_return(ty: &str, expr_text: &str) -> TypedReturn {
+    let replace = |r: &str| TypedReturn::Replace(format!("return {};", r));
+    if ty.ends_with("[]") || ty.starts_with("Array<") || ty.starts_with("ReadonlyArray<") {
+        return if expr_text == "[]" { TypedReturn::Skip } else { replace("[]") };
+    }
+    let alternatives: Vec<_> = ty.split('|').map(str::trim).collect();
+    if alternatives.len() > 1 {
+        // `T | null` and `T | undefined` take the empty alternative
+        return match ["null", "undefined"].into_iter().find(|e| alternatives.contains(e)) {
+            Some(empty) if expr_text == empty => TypedReturn::Unknown,
+            Some(empty) => replace(empty),
+            None => TypedReturn::Unknown,
+        };
+    }
+    match ty {
+        "number" => replace(if expr_text == "0" { "1" } else { "0" }),
+        "bigint" => replace(if expr_text == "0n" { "1n" } else { "0n" }),
+        "string" if expr_text == "\"\"" || expr_text == "''" || expr_text == "``" => TypedReturn::Skip,
+        "string" => replace("\"\""),
+        "boolean" => match expr_text {
+            "true" => replace("false"),
+            "false" => replace("true"),
+            _ => replace(&format!("!({})", expr_text)),
+        },
+        // Type predicates: `(x): x is Foo`
+        _ if ty.contains(" is ") => replace(&format!("!({})", expr_text)),
+        _ => TypedReturn::Unknown,
+    }
+}
+
+fn collect_boolean_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // Skip if inside a return (handled by return_mutations)
     if let Some(parent) = node.parent() {
         if parent.kind() == "return_statement" {
@@ -488,7 +676,6 @@ fn collect_boolean_mutations(node: Node, source: &str, lines: &[&str], mutations
     let text = node_text(node, source);
     let line = node.start_position().row + 1;
     let col = node.start_position().column + 1;
-    let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
 
     let replacement = match text {
         "true" => "false",
@@ -504,16 +691,17 @@ fn collect_boolean_mutations(node: Node, source: &str, lines: &[&str], mutations
         operator: "bool_flip".to_string(),
         original: text.to_string(),
         replacement: replacement.to_string(),
-        context_before: ctx_before,
-        context_after: ctx_after,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
     });
 }
 
-fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_if_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // if_statement has: condition, consequence (statement_block), alternative (else_clause)
     if let Some(consequence) = node.child_by_field_name("consequence") {
         if consequence.kind() == "statement_block" {
-            add_block_remove_mutation(consequence, source, lines, mutations);
+            add_block_remove_mutation(consequence, source, mutations);
         }
     }
 
@@ -524,7 +712,7 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
             for i in 0..count {
                 if let Some(child) = alternative.child(i) {
                     if child.kind() == "statement_block" {
-                        add_block_remove_mutation(child, source, lines, mutations);
+                        add_block_remove_mutation(child, source, mutations);
                     }
                     // else if is a nested if_statement, handled by recursion in walk_node
                 }
@@ -533,15 +721,213 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
     }
 }
 
-fn collect_loop_body_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_loop_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     if let Some(body) = node.child_by_field_name("body") {
         if body.kind() == "statement_block" {
-            add_block_remove_mutation(body, source, lines, mutations);
+            add_block_remove_mutation(body, source, mutations);
         }
     }
 }
 
-fn add_block_remove_mutation(block: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+/// `while (cond)` -> `while (false)`: the loop body never runs.
+fn collect_while_condition_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(cond) = node.child_by_field_name("condition") else {
+        return;
+    };
+    // parenthesized_expression: "(" expr ")"
+    let Some(inner) = cond.named_child(0) else {
+        return;
+    };
+    // Literal conditions are already covered by bool_flip
+    if matches!(inner.kind(), "true" | "false") {
+        return;
+    }
+
+    let line = inner.start_position().row + 1;
+    let col = inner.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: inner.start_byte(),
+        end_byte: inner.end_byte(),
+        operator: "loop_zero".to_string(),
+        original: node_text(inner, source).to_string(),
+        replacement: "false".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `break` <-> `continue`. Only the keyword is replaced so labels survive.
+fn collect_loop_control_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // A `break` that exits a switch has no loop to `continue`
+    if !innermost_breakable_is_loop(node) {
+        return;
+    }
+    let Some(keyword) = node.child(0) else {
+        return;
+    };
+    let replacement = match keyword.kind() {
+        "break" => "continue",
+        "continue" => "break",
+        _ => return,
+    };
+
+    let line = keyword.start_position().row + 1;
+    let col = keyword.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: keyword.start_byte(),
+        end_byte: keyword.end_byte(),
+        operator: "loop_control".to_string(),
+        original: node_text(keyword, source).to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn innermost_breakable_is_loop(node: Node) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        match n.kind() {
+            "for_statement" | "for_in_statement" | "while_statement" | "do_statement" => return true,
+            "switch_statement" => return node.kind() == "continue_statement",
+            "function_declaration" | "function_expression" | "function" | "arrow_function"
+            | "method_definition" | "generator_function_declaration" => return false,
+            _ => current = n.parent(),
+        }
+    }
+    false
+}
+
+/// Swap the branches of `cond ? a : b` -> `cond ? b : a`.
+fn collect_ternary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (consequence, alternative) = match (
+        node.child_by_field_name("consequence"),
+        node.child_by_field_name("alternative"),
+    ) {
+        (Some(c), Some(a)) => (c, a),
+        _ => return,
+    };
+
+    let line = consequence.start_position().row + 1;
+    let col = consequence.start_position().column + 1;
+
+    let replacement = format!(
+        "{}{}{}",
+        node_text(alternative, source),
+        &source[consequence.end_byte()..alternative.start_byte()],
+        node_text(consequence, source),
+    );
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: consequence.start_byte(),
+        end_byte: alternative.end_byte(),
+        operator: "ternary_swap".to_string(),
+        original: source[consequence.start_byte()..alternative.end_byte()].to_string(),
+        replacement,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `await expr` -> `expr`: the caller gets the pending promise instead of
+/// its value, which only tests that check the resolved result will notice.
+fn collect_await_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // `return await x` and `return x` behave the same outside try blocks
+    if let Some(parent) = node.parent() {
+        if parent.kind() == "return_statement" {
+            return;
+        }
+    }
+    let Some(argument) = node.named_child(0) else {
+        return;
+    };
+
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: node.start_byte(),
+        end_byte: argument.start_byte(),
+        operator: "await_remove".to_string(),
+        original: source[node.start_byte()..argument.start_byte()].to_string(),
+        replacement: String::new(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Drop the null guard: `a?.b` -> `a.b`, `a?.[i]` -> `a[i]`, `f?.()` -> `f()`.
+fn collect_optional_chain_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let replacement = match node.parent().map(|p| p.kind()) {
+        Some("member_expression") => ".",
+        Some("subscript_expression") | Some("call_expression") => "",
+        _ => return,
+    };
+
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "optional_chain".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Empty a `case`/`default` body. The body is replaced with `break;` rather
+/// than nothing so the mutant doesn't silently fall through into the next case.
+fn collect_switch_case_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let mut cursor = node.walk();
+    let body: Vec<Node> = node.children_by_field_name("body", &mut cursor).collect();
+    let (first, last) = match (body.first(), body.last()) {
+        (Some(f), Some(l)) => (*f, *l),
+        _ => return, // fallthrough label with no statements
+    };
+
+    let original = &source[first.start_byte()..last.end_byte()];
+    if original.trim() == "break;" {
+        return;
+    }
+
+    let line = first.start_position().row + 1;
+    let col = first.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: first.start_byte(),
+        end_byte: last.end_byte(),
+        operator: "arm_remove".to_string(),
+        original: original.to_string(),
+        replacement: "break;".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn add_block_remove_mutation(block: Node, source: &str, mutations: &mut Vec<Mutation>) {
     let block_text = node_text(block, source);
     if block_text.trim() == "{}" {
         return;
@@ -549,7 +935,6 @@ fn add_block_remove_mutation(block: Node, source: &str, lines: &[&str], mutation
 
     let line = block.start_position().row + 1;
     let col = block.start_position().column + 1;
-    let (ctx_before, ctx_after) = get_context(lines, block.start_position().row, 2);
 
     mutations.push(Mutation {
         line,
@@ -559,7 +944,269 @@ fn add_block_remove_mutation(block: Node, source: &str, lines: &[&str], mutation
         operator: "block_remove".to_string(),
         original: block_text.to_string(),
         replacement: "{}".to_string(),
-        context_before: ctx_before,
-        context_after: ctx_after,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `.startsWith` <-> `.endsWith`, `.toUpperCase()` <-> `.toLowerCase()`,
+/// and `.trim()` dropped (`name.trim()` -> `name`).
+fn collect_string_method_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(callee) = node.child_by_field_name("function").filter(|f| f.kind() == "member_expression") else {
+        return;
+    };
+    let (Some(object), Some(method)) = (callee.child_by_field_name("object"), callee.child_by_field_name("property")) else {
+        return;
+    };
+    let name = node_text(method, source);
+    if let Some(replacement) = operators::string_method_swap(name) {
+        mutations.push(Mutation {
+            line: method.start_position().row + 1,
+            column: method.start_position().column + 1,
+            start_byte: method.start_byte(),
+            end_byte: method.end_byte(),
+            operator: "string_method".to_string(),
+            original: name.to_string(),
+            replacement: replacement.to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    } else if operators::is_trim_method(name) {
+        mutations.push(Mutation {
+            line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+            operator: "trim_remove".to_string(),
+            original: node_text(node, source).to_string(),
+            replacement: node_text(object, source).to_string(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+/// Opt-in `collection_method`: `Math.min` <-> `Math.max`, `.push` ->
+/// `.unshift`, and `.slice(a, b)` off by one at either end for integer
+/// literal bounds (`slice(0, 3)` -> `slice(1, 3)` / `slice(0, 2)`).
+fn collect_collection_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(callee) = node.child_by_field_name("function").filter(|f| f.kind() == "member_expression") else {
+        return;
+    };
+    let (Some(object), Some(method)) = (callee.child_by_field_name("object"), callee.child_by_field_name("property")) else {
+        return;
+    };
+    let mut push = |target: Node, replacement: String| {
+        mutations.push(Mutation {
+            line: target.start_position().row + 1,
+            column: target.start_position().column + 1,
+            start_byte: target.start_byte(),
+            end_byte: target.end_byte(),
+            operator: "collection_method".to_string(),
+            original: node_text(target, source).to_string(),
+            replacement,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    };
+
+    let name = node_text(method, source);
+    match name {
+        "min" | "max" if node_text(object, source) == "Math" => {
+            push(method, operators::min_max_swap(name).unwrap_or(name).to_string());
+        }
+        "push" => push(method, "unshift".to_string()),
+        "slice" => {
+            let Some(args) = node.child_by_field_name("arguments") else {
+                return;
+            };
+            let mut cursor = args.walk();
+            let bounds: Vec<Node> = args.named_children(&mut cursor).filter(|a| a.kind() != "comment").collect();
+            let literal = |n: &Node| n.kind() == "number" && node_text(*n, source).parse::<u64>().is_ok();
+            if let Some(start) = bounds.first().filter(|n| literal(n)) {
+                let n: u64 = node_text(*start, source).parse().unwrap_or_default();
+                push(*start, (n + 1).to_string());
+            }
+            if let Some(end) = bounds.get(1).filter(|n| literal(n)) {
+                let n: u64 = node_text(*end, source).parse().unwrap_or_default();
+                if n > 0 {
+                    push(*end, (n - 1).to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Swap the first two arguments: `f(a, b)` -> `f(b, a)`.
+fn collect_arg_swap_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(callee), Some(args)) = (node.child_by_field_name("function"), node.child_by_field_name("arguments")) else {
+        return;
+    };
+    if operators::is_logging_call(node_text(callee, source)) {
+        return;
+    }
+    let mut cursor = args.walk();
+    let mut positional = args.named_children(&mut cursor).filter(|a| !a.kind().contains("comment"));
+    let (Some(first), Some(second)) = (positional.next(), positional.next()) else {
+        return;
+    };
+    if first.kind() == "spread_element"
+        || second.kind() == "spread_element"
+        || !operators::swappable_args(first.kind(), second.kind())
+    {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: first.start_position().row + 1,
+        column: first.start_position().column + 1,
+        start_byte: first.start_byte(),
+        end_byte: second.end_byte(),
+        operator: "arg_swap".to_string(),
+        original: source[first.start_byte()..second.end_byte()].to_string(),
+        replacement: format!(
+            "{}{}{}",
+            node_text(second, source),
+            &source[first.end_byte()..second.start_byte()],
+            node_text(first, source),
+        ),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Drop a call made for its side effect: `list.push(x);` -> `;`.
+fn collect_call_remove_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(call) = node.named_child(0).filter(|c| c.kind() == "call_expression") else {
+        return;
+    };
+    let Some(callee) = call.child_by_field_name("function") else {
+        return;
+    };
+    // `super(...)` is required in derived constructors
+    let callee = node_text(callee, source);
+    if callee == "super" || operators::is_logging_call(callee) || is_sole_statement_of_removed_block(node) {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "call_remove".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: ";".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Whether `statement` is all of an `if`/`else` or loop body, which
+/// `block_remove` already empties.
+fn is_sole_statement_of_removed_block(statement: Node) -> bool {
+    let Some(block) = statement.parent().filter(|b| b.kind() == "statement_block") else {
+        return false;
+    };
+    let mut cursor = block.walk();
+    if block.named_children(&mut cursor).filter(|s| s.kind() != "comment").count() != 1 {
+        return false;
+    }
+    block.parent().is_some_and(|owner| match owner.kind() {
+        "if_statement" => owner.child_by_field_name("consequence") == Some(block),
+        "else_clause" => true,
+        "for_statement" | "for_in_statement" | "while_statement" => owner.child_by_field_name("body") == Some(block),
+        _ => false,
+    })
+}
+
+/// Parameter and destructuring defaults: `function f(limit = 10)`,
+/// `const { retries = 3 } = opts`, TS `limit: number = 10`.
+fn collect_default_value_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(value) = node.child_by_field_name("right").or_else(|| node.child_by_field_name("value")) else {
+        return;
+    };
+    let text = node_text(value, source);
+    let Some(replacement) = operators::js_default_value_mutation(text) else {
+        return;
+    };
+    mutations.push(Mutation {
+        line: value.start_position().row + 1,
+        column: value.start_position().column + 1,
+        start_byte: value.start_byte(),
+        end_byte: value.end_byte(),
+        operator: "default_arg".to_string(),
+        original: text.to_string(),
+        replacement,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
     });
 }
+
+/// Conditional rendering (`.tsx`/`.jsx`): `cond && <Badge/>` ->
+/// `!cond && <Badge/>`, or `null` so the element never renders. Only the
+/// condition and the expression are touched, never the markup itself.
+fn collect_conditional_render_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(op), Some(left), Some(right)) = (
+        node.child_by_field_name("operator"),
+        node.child_by_field_name("left"),
+        node.child_by_field_name("right"),
+    ) else {
+        return;
+    };
+    if node_text(op, source) != "&&" || !is_jsx(right) {
+        return;
+    }
+
+    let line = node.start_position().row + 1;
+    let cond = node_text(left, source);
+    // `!cond && ...` is already covered by negate_remove
+    if !(left.kind() == "unary_expression" && cond.starts_with('!')) {
+        let negated = match left.kind() {
+            "identifier" | "member_expression" | "call_expression" | "parenthesized_expression" => {
+                format!("!{}", cond)
+            }
+            _ => format!("!({})", cond),
+        };
+        mutations.push(Mutation {
+            line,
+            column: left.start_position().column + 1,
+            start_byte: left.start_byte(),
+            end_byte: left.end_byte(),
+            operator: "render_negate".to_string(),
+            original: cond.to_string(),
+            replacement: negated,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+    mutations.push(Mutation {
+        line,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "render_remove".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: "null".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn is_jsx(node: Node) -> bool {
+    match node.kind() {
+        "jsx_element" | "jsx_self_closing_element" => true,
+        "parenthesized_expression" => node.named_child(0).is_some_and(is_jsx),
+        _ => false,
+    }
+}
@@ -1,119 +1,280 @@
-use tree_sitter::{Node, Parser};
-use crate::mutants::Mutation;
+use tree_sitter::Node;
+use crate::mutants::{self, FunctionSpan, Mutation};
+use crate::mutation_parser::{self, node_text, FunctionDef, Grammar, MutationParser, ParseOptions};
+use crate::operators;
 
+/// Discover mutations in production code. `#[test]` functions and
+/// `#[cfg(test)]` modules are skipped unless named explicitly.
 pub fn discover_mutations(source: &str, function_name: Option<&str>) -> Vec<Mutation> {
-    let mut parser = Parser::new();
-    let language = tree_sitter_rust::LANGUAGE;
-    parser.set_language(&language.into()).expect("Failed to set Rust grammar");
+    discover_mutations_with(source, function_name, false)
+}
 
-    let tree = parser.parse(source, None).expect("Failed to parse Rust source");
-    let root = tree.root_node();
-    let lines: Vec<&str> = source.lines().collect();
+/// Like `discover_mutations`, optionally walking into test code too.
+pub fn discover_mutations_with(source: &str, function_name: Option<&str>, include_tests: bool) -> Vec<Mutation> {
+    let options = ParseOptions { include_tests, ..Default::default() };
+    mutation_parser::discover(&RustParser, source, function_name, &options)
+}
 
-    let mut mutations = Vec::new();
+/// Mutations in `const` and `static` initializers outside functions
+/// (`--include-toplevel`), associated consts included. Items in test code
+/// are skipped unless `include_tests`.
+pub fn toplevel_mutations(source: &str, include_tests: bool) -> Vec<Mutation> {
+    toplevel_mutations_with(source, &ParseOptions { include_tests, ..Default::default() })
+}
 
-    match function_name {
-        Some(name) => {
-            if let Some(func_node) = find_function(root, name, source) {
-                walk_node(func_node, source, &lines, &mut mutations);
-            }
-        }
-        None => {
-            collect_all_functions(root, source, &lines, &mut mutations);
-        }
-    }
+/// Like `toplevel_mutations`, with every `ParseOptions` setting.
+pub fn toplevel_mutations_with(source: &str, options: &ParseOptions) -> Vec<Mutation> {
+    let tree = mutation_parser::parse(&RustParser, source);
+    let mut mutations = Vec::new();
+    collect_const_items(tree.root_node(), source, options, &mut mutations);
+    mutants::assign_context(&mut mutations, source, mutants::DEFAULT_CONTEXT_LINES);
 
     mutations
 }
 
-fn find_function<'a>(node: Node<'a>, name: &str, source: &str) -> Option<Node<'a>> {
-    if node.kind() == "function_item" {
-        if let Some(name_node) = node.child_by_field_name("name") {
-            if node_text(name_node, source) == name {
-                return Some(node);
+fn collect_const_items(node: Node, source: &str, options: &ParseOptions, mutations: &mut Vec<Mutation>) {
+    match node.kind() {
+        "function_item" => return,
+        "mod_item" if !options.include_tests && is_test_item(node, source) => return,
+        "const_item" | "static_item" => {
+            if options.include_tests || !is_test_item(node, source) {
+                mutation_parser::walk(&RustParser, node, source, options, mutations);
             }
+            return;
         }
+        _ => {}
     }
     let count = node.child_count();
     for i in 0..count {
         if let Some(child) = node.child(i) {
-            if let Some(found) = find_function(child, name, source) {
-                return Some(found);
+            collect_const_items(child, source, options, mutations);
+        }
+    }
+}
+
+/// A function's name prefixed with the type of its `impl` block or its
+/// trait, `Type::method`. Generics and module paths are dropped, so
+/// `impl<T> fmt::Debug for Wrapper<T>` gives `Wrapper::fmt`.
+fn qualified_name(func: Node, source: &str) -> String {
+    let name = func.child_by_field_name("name").map_or("", |n| node_text(n, source));
+    let mut ancestor = func.parent();
+    while let Some(node) = ancestor {
+        let owner = match node.kind() {
+            "impl_item" => node.child_by_field_name("type"),
+            "trait_item" => node.child_by_field_name("name"),
+            // A function nested in a method isn't the type's
+            "function_item" => break,
+            _ => None,
+        };
+        if let Some(owner) = owner {
+            let owner = node_text(owner, source).split('<').next().unwrap_or("").trim();
+            let owner = owner.rsplit("::").next().unwrap_or(owner);
+            return format!("{}::{}", owner, name);
+        }
+        ancestor = node.parent();
+    }
+    name.to_string()
+}
+
+/// Whether an item carries a test attribute (`#[test]`, `#[tokio::test]`,
+/// `#[cfg(test)]`, ...). Attributes are preceding siblings in the tree.
+fn is_test_item(node: Node, source: &str) -> bool {
+    let mut prev = node.prev_named_sibling();
+    while let Some(sibling) = prev {
+        match sibling.kind() {
+            "attribute_item" => {
+                if is_test_attribute(node_text(sibling, source)) {
+                    return true;
+                }
             }
+            "line_comment" | "block_comment" => {}
+            _ => break,
         }
+        prev = sibling.prev_named_sibling();
     }
-    None
+    false
 }
 
-fn collect_all_functions(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
-    if node.kind() == "function_item" {
-        walk_node(node, source, lines, mutations);
-        return;
+fn is_test_attribute(attr: &str) -> bool {
+    let inner = attr
+        .trim()
+        .trim_start_matches("#[")
+        .trim_end_matches(']')
+        .trim();
+    let path = inner.split('(').next().unwrap_or("").trim();
+    if path == "cfg" {
+        let predicate = inner["cfg".len()..].trim();
+        let predicate = predicate.strip_prefix('(').and_then(|p| p.strip_suffix(')')).unwrap_or("");
+        return cfg_requires_test(predicate);
     }
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            collect_all_functions(child, source, lines, mutations);
+    path == "test" || path.ends_with("::test") || path == "rstest" || path == "bench"
+}
+
+/// Whether a `cfg` predicate only holds in test builds: `test`,
+/// `all(test, feature = "x")`, `any(test, all(test, unix))`. `not(test)`
+/// and anything else that can hold outside tests is production code.
+fn cfg_requires_test(predicate: &str) -> bool {
+    let predicate = predicate.trim();
+    if predicate == "test" {
+        return true;
+    }
+    let Some((op, args)) = predicate.split_once('(') else {
+        return false;
+    };
+    let Some(args) = args.trim_end().strip_suffix(')') else {
+        return false;
+    };
+    let args = split_cfg_args(args);
+    match op.trim() {
+        "all" => args.iter().any(|a| cfg_requires_test(a)),
+        "any" => !args.is_empty() && args.iter().all(|a| cfg_requires_test(a)),
+        _ => false,
+    }
+}
+
+/// Split `a, all(b, c), d = "x,y"` at its top-level commas.
+fn split_cfg_args(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut quoted, mut start) = (0usize, false, 0);
+    for (i, c) in args.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            ',' if !quoted && depth == 0 => {
+                parts.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
         }
     }
+    parts.push(&args[start..]);
+    parts.into_iter().map(str::trim).filter(|p| !p.is_empty()).collect()
 }
 
-pub fn list_functions(source: &str) -> Vec<String> {
-    let mut parser = Parser::new();
-    let language = tree_sitter_rust::LANGUAGE;
-    parser.set_language(&language.into()).expect("Failed to set Rust grammar");
+/// Byte spans of every function item, nested ones included.
+pub fn function_spans(source: &str) -> Vec<FunctionSpan> {
+    mutation_parser::function_spans(&RustParser, source)
+}
 
-    let tree = parser.parse(source, None).expect("Failed to parse Rust source");
-    let root = tree.root_node();
-    let mut names = Vec::new();
-    collect_function_names(root, source, &mut names);
-    names
+pub fn list_functions(source: &str) -> Vec<String> {
+    mutation_parser::list_functions(&RustParser, source)
 }
 
-fn collect_function_names(node: Node, source: &str, names: &mut Vec<String>) {
-    if node.kind() == "function_item" {
-        if let Some(name_node) = node.child_by_field_name("name") {
-            let name = node_text(name_node, source);
-            names.push(name.to_string());
+/// `MutationParser` for Rust.
+pub struct RustParser;
+
+impl Grammar for RustParser {
+    fn language(&self) -> tree_sitter::Language {
+        tree_sitter_rust::LANGUAGE.into()
+    }
+
+    fn function_at<'a>(&self, node: Node<'a>, _source: &str) -> Option<FunctionDef<'a>> {
+        if node.kind() != "function_item" {
+            return None;
         }
+        let name = node.child_by_field_name("name")?;
+        Some(FunctionDef { node, name, body: node })
     }
-    let count = node.child_count();
-    for i in 0..count {
-        if let Some(child) = node.child(i) {
-            collect_function_names(child, source, names);
+
+    fn qualified_name(&self, def: &FunctionDef, source: &str) -> String {
+        qualified_name(def.node, source)
+    }
+
+    fn mutate_node(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        mutate_node(node, source, mutations);
+    }
+
+    fn mutate_collection(&self, node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+        if node.kind() == "call_expression" {
+            collect_collection_mutations(node, source, mutations);
         }
     }
+
+    /// Printing and logging macros.
+    fn should_skip(&self, node: Node, source: &str) -> bool {
+        should_skip_node(node, source)
+    }
+
+    /// `#[test]` functions and `#[cfg(test)]` modules.
+    fn is_test_code(&self, node: Node, source: &str) -> bool {
+        matches!(node.kind(), "function_item" | "mod_item") && is_test_item(node, source)
+    }
 }
 
-fn walk_node(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
-    if should_skip_node(node, source) {
-        return;
+impl MutationParser for RustParser {
+    fn discover(&self, source: &str, function: Option<&str>, options: &ParseOptions) -> Vec<Mutation> {
+        mutation_parser::discover(self, source, function, options)
+    }
+
+    fn toplevel(&self, source: &str, options: &ParseOptions) -> Vec<Mutation> {
+        toplevel_mutations_with(source, options)
+    }
+
+    fn function_spans(&self, source: &str) -> Vec<FunctionSpan> {
+        function_spans(source)
     }
 
+    fn list_functions(&self, source: &str) -> Vec<String> {
+        list_functions(source)
+    }
+}
+
+/// The mutations `node` itself yields; `mutation_parser::walk` visits its
+/// children.
+fn mutate_node(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     match node.kind() {
+        "function_item" => {
+            collect_tail_return_mutations(node, source, mutations);
+        }
         "binary_expression" => {
-            collect_binary_mutations(node, source, lines, mutations);
+            collect_binary_mutations(node, source, mutations);
         }
         "unary_expression" => {
-            collect_unary_mutations(node, source, lines, mutations);
+            collect_unary_mutations(node, source, mutations);
         }
         "return_expression" => {
-            collect_return_mutations(node, source, lines, mutations);
+            collect_return_mutations(node, source, mutations);
         }
         "boolean_literal" => {
-            collect_boolean_mutations(node, source, lines, mutations);
+            collect_boolean_mutations(node, source, mutations);
         }
         "if_expression" => {
-            collect_if_body_mutations(node, source, lines, mutations);
+            collect_if_body_mutations(node, source, mutations);
+            collect_if_else_swap_mutations(node, source, mutations);
         }
-        _ => {}
-    }
-
-    let child_count = node.child_count();
-    for i in 0..child_count {
-        if let Some(child) = node.child(i) {
-            walk_node(child, source, lines, mutations);
+        "match_expression" => {
+            collect_match_arm_mutations(node, source, mutations);
+        }
+        "while_expression" => {
+            collect_while_condition_mutations(node, source, mutations);
+        }
+        "break_expression" | "continue_expression" => {
+            collect_loop_control_mutations(node, source, mutations);
+        }
+        "try_expression" => {
+            collect_try_mutations(node, source, mutations);
+        }
+        "call_expression" => {
+            collect_some_mutations(node, source, mutations);
+            collect_unwrap_or_mutations(node, source, mutations);
+            collect_arg_swap_mutations(node, source, mutations);
+            collect_predicate_swap_mutations(node, source, mutations);
+            collect_string_method_mutations(node, source, mutations);
+        }
+        "range_expression" => {
+            collect_range_mutations(node, source, mutations);
+        }
+        "let_condition" => {
+            collect_if_let_mutations(node, source, mutations);
         }
+        "macro_invocation" => {
+            collect_matches_mutations(node, source, mutations);
+        }
+        "expression_statement" => {
+            collect_call_remove_mutations(node, source, mutations);
+        }
+        _ => {}
     }
 }
 
@@ -140,22 +301,6 @@ fn should_skip_node(node: Node, source: &str) -> bool {
     false
 }
 
-fn get_context(lines: &[&str], line_idx: usize, range: usize) -> (Vec<String>, Vec<String>) {
-    let start = line_idx.saturating_sub(range);
-    let end = (line_idx + range + 1).min(lines.len());
-    let before: Vec<String> = lines[start..line_idx].iter().map(|s| s.to_string()).collect();
-    let after: Vec<String> = if line_idx + 1 < end {
-        lines[line_idx + 1..end].iter().map(|s| s.to_string()).collect()
-    } else {
-        vec![]
-    };
-    (before, after)
-}
-
-fn node_text<'a>(node: Node<'a>, source: &'a str) -> &'a str {
-    &source[node.start_byte()..node.end_byte()]
-}
-
 struct RustMutationOp {
     operator_name: &'static str,
     replacement: &'static str,
@@ -208,7 +353,7 @@ fn arithmetic_mutations(op: &str) -> Vec<RustMutationOp> {
     }
 }
 
-fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_binary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // binary_expression: left operator right
     let child_count = node.child_count();
     for i in 0..child_count {
@@ -217,7 +362,17 @@ fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations:
             let op_text = node_text(child, source);
 
             let ops: Vec<RustMutationOp> = match kind {
-                ">" | ">=" | "<" | "<=" | "==" | "!=" => comparison_mutations(op_text),
+                ">" | ">=" | "<" | "<=" | "==" | "!=" => {
+                    let mut ops = comparison_mutations(op_text);
+                    if let Some(length_on_left) = length_side(node, source) {
+                        ops.retain(|op| op.operator_name != "boundary");
+                        ops.extend(operators::length_boundary(op_text, length_on_left).map(|op| RustMutationOp {
+                            operator_name: op.operator_name,
+                            replacement: op.replacement,
+                        }));
+                    }
+                    ops
+                }
                 "&&" | "||" => logical_mutations(op_text),
                 "+" | "-" | "*" | "/" | "%" => arithmetic_mutations(op_text),
                 _ => vec![],
@@ -229,7 +384,6 @@ fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations:
 
             let line = child.start_position().row + 1;
             let col = child.start_position().column + 1;
-            let (ctx_before, ctx_after) = get_context(lines, child.start_position().row, 2);
 
             for op in ops {
                 mutations.push(Mutation {
@@ -240,22 +394,44 @@ fn collect_binary_mutations(node: Node, source: &str, lines: &[&str], mutations:
                     operator: op.operator_name.to_string(),
                     original: op_text.to_string(),
                     replacement: op.replacement.to_string(),
-                    context_before: ctx_before.clone(),
-                    context_after: ctx_after.clone(),
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    function: None,
                 });
             }
         }
     }
 }
 
-fn collect_unary_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+/// Whether the left (`Some(true)`) or right operand of binary expression
+/// `node` is a `.len()` call.
+fn length_side(node: Node, source: &str) -> Option<bool> {
+    let is_len = |field| {
+        node.child_by_field_name(field).is_some_and(|n| {
+            n.kind() == "call_expression"
+                && n.child_by_field_name("arguments").is_some_and(|a| a.named_child_count() == 0)
+                && n.child_by_field_name("function").is_some_and(|f| {
+                    f.kind() == "field_expression"
+                        && f.child_by_field_name("field").is_some_and(|name| node_text(name, source) == "len")
+                })
+        })
+    };
+    if is_len("left") {
+        Some(true)
+    } else if is_len("right") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn collect_unary_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // unary_expression: ! operand
     if let Some(op_node) = node.child(0) {
         if op_node.kind() == "!" {
             if let Some(operand) = node.child(1) {
                 let line = op_node.start_position().row + 1;
                 let col = op_node.start_position().column + 1;
-                let (ctx_before, ctx_after) = get_context(lines, op_node.start_position().row, 2);
 
                 mutations.push(Mutation {
                     line,
@@ -265,15 +441,16 @@ fn collect_unary_mutations(node: Node, source: &str, lines: &[&str], mutations:
                     operator: "negate_remove".to_string(),
                     original: node_text(node, source).to_string(),
                     replacement: node_text(operand, source).to_string(),
-                    context_before: ctx_before,
-                    context_after: ctx_after,
+                    context_before: Vec::new(),
+                    context_after: Vec::new(),
+                    function: None,
                 });
             }
         }
     }
 }
 
-fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_return_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // return_expression: "return" expr?
     // In Rust, the last expression in a block is an implicit return,
     // but explicit `return` statements are return_expression nodes.
@@ -284,24 +461,10 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
         let expr_text = node_text(expr, source).trim();
         let line = node.start_position().row + 1;
         let col = node.start_position().column + 1;
-        let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
-
-        let replacement = if expr_text == "true" {
-            "return false"
-        } else if expr_text == "false" {
-            "return true"
-        } else if expr_text == "None" || expr_text == "()" {
-            return; // No useful mutation for unit return
-        } else if expr_text == "0" {
-            "return 1"
-        } else if expr_text.starts_with('"') {
-            "return \"\".to_string()"
-        } else if expr_text.starts_with("vec!") || expr_text.starts_with("Vec::") {
-            "return vec![]"
-        } else if expr_text == "Ok(())" {
+
+        let return_type = declared_return_type(node, source);
+        let Some(replacement) = return_value_replacement(expr_text, return_type) else {
             return;
-        } else {
-            "return Default::default()"
         };
 
         mutations.push(Mutation {
@@ -311,14 +474,536 @@ fn collect_return_mutations(node: Node, source: &str, lines: &[&str], mutations:
             end_byte: node.end_byte(),
             operator: "return_val".to_string(),
             original: node_text(node, source).to_string(),
-            replacement: replacement.to_string(),
-            context_before: ctx_before,
-            context_after: ctx_after,
+            replacement: format!("return {}", replacement),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+/// The `->` type of the function or closure `node` returns from, if it
+/// declares one.
+fn declared_return_type<'a>(node: Node<'a>, source: &'a str) -> Option<&'a str> {
+    let mut ancestor = node.parent();
+    while let Some(n) = ancestor {
+        if matches!(n.kind(), "function_item" | "closure_expression") {
+            return n.child_by_field_name("return_type").map(|t| node_text(t, source).trim());
+        }
+        ancestor = n.parent();
+    }
+    None
+}
+
+/// Replacement value for a returned expression, or `None` when there is no
+/// useful mutation (unit-like returns, values already minimal, or types
+/// with no value we can build). The declared return type picks the value
+/// when there is one; otherwise only literals are replaced.
+fn return_value_replacement(expr_text: &str, return_type: Option<&str>) -> Option<String> {
+    if expr_text == "true" {
+        return Some("false".to_string());
+    } else if expr_text == "false" {
+        return Some("true".to_string());
+    } else if expr_text == "None" || expr_text == "()" || expr_text == "Ok(())"
+        // some_none already covers `Some(x)`
+        || expr_text.starts_with("Some(")
+    {
+        return None;
+    }
+    let empty_or = |empties: &[&str], value: &str| (!empties.contains(&expr_text)).then(|| value.to_string());
+    match return_type.map(strip_generics) {
+        Some("bool") => Some(format!("!({})", expr_text)),
+        Some("Option") => Some("None".to_string()),
+        // No `Err` value can be built without knowing the error type
+        Some("Result") => None,
+        Some("Vec") => empty_or(&["vec![]", "Vec::new()"], "vec![]"),
+        Some("String") => empty_or(&["String::new()", "\"\".to_string()", "String::from(\"\")"], "String::new()"),
+        Some("&str" | "&'static str") => empty_or(&["\"\""], "\"\""),
+        Some(ty) if INTEGER_TYPES.contains(&ty) => Some(if expr_text == "0" { "1" } else { "0" }.to_string()),
+        Some("f32" | "f64") => Some(if expr_text == "0.0" { "1.0" } else { "0.0" }.to_string()),
+        Some(_) => None,
+        // Closures without an annotation: only literals have a known type
+        None if expr_text == "0" => Some("1".to_string()),
+        None if expr_text.starts_with('"') => Some("\"\".to_string()".to_string()),
+        None if expr_text.starts_with("vec!") => Some("vec![]".to_string()),
+        None => None,
+    }
+}
+
+const INTEGER_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// `Option<T>` -> `Option`, `std::io::Result<()>` -> `Result`; other types
+/// as written.
+fn strip_generics(ty: &str) -> &str {
+    let base = ty.split('<').next().unwrap_or(ty).trim();
+    if base.starts_with('&') {
+        return ty;
+    }
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+fn collect_tail_return_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // function_item: the body's trailing expression is an implicit return.
+    // Unit functions have nothing worth replacing.
+    match node.child_by_field_name("return_type") {
+        Some(ret) if node_text(ret, source).trim() != "()" => {}
+        _ => return,
+    }
+    if let Some(body) = node.child_by_field_name("body") {
+        collect_block_tail_mutations(body, source, mutations);
+    }
+}
+
+fn collect_block_tail_mutations(block: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    if let Some(tail) = tail_expression(block) {
+        collect_value_mutation(tail, source, mutations);
+    }
+}
+
+/// Mutate a value-producing expression in tail position. Branching
+/// expressions are followed into each branch's own tail.
+fn collect_value_mutation(expr: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    match expr.kind() {
+        "block" | "unsafe_block" => collect_block_tail_mutations(expr, source, mutations),
+        "if_expression" => {
+            if let Some(consequence) = expr.child_by_field_name("consequence") {
+                collect_block_tail_mutations(consequence, source, mutations);
+            }
+            if let Some(alt) = expr.child_by_field_name("alternative") {
+                // else_clause wraps either a block or a nested if_expression
+                if let Some(inner) = alt.named_child(0) {
+                    collect_value_mutation(inner, source, mutations);
+                }
+            }
+        }
+        "match_expression" => {
+            let Some(body) = expr.child_by_field_name("body") else {
+                return;
+            };
+            let mut cursor = body.walk();
+            for arm in body.named_children(&mut cursor).filter(|c| c.kind() == "match_arm") {
+                if let Some(value) = arm.child_by_field_name("value") {
+                    collect_value_mutation(value, source, mutations);
+                }
+            }
+        }
+        // Already mutated by other operators, or not a plain value
+        "boolean_literal" | "return_expression" | "unit_expression" | "loop_expression"
+        | "while_expression" | "for_expression" | "break_expression" | "continue_expression" => {}
+        "macro_invocation" if should_skip_node(expr, source) => {}
+        _ => {
+            let text = node_text(expr, source);
+            let return_type = declared_return_type(expr, source);
+            let Some(replacement) = return_value_replacement(text.trim(), return_type) else {
+                return;
+            };
+            let line = expr.start_position().row + 1;
+            let col = expr.start_position().column + 1;
+
+            mutations.push(Mutation {
+                line,
+                column: col,
+                start_byte: expr.start_byte(),
+                end_byte: expr.end_byte(),
+                operator: "return_val".to_string(),
+                original: text.to_string(),
+                replacement: replacement.to_string(),
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
+            });
+        }
+    }
+}
+
+fn collect_while_condition_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // while_expression: "while" condition body -- `while let` is left alone
+    let Some(cond) = node.child_by_field_name("condition") else {
+        return;
+    };
+    if matches!(cond.kind(), "let_condition" | "let_chain" | "boolean_literal") {
+        return;
+    }
+
+    let line = cond.start_position().row + 1;
+    let col = cond.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: cond.start_byte(),
+        end_byte: cond.end_byte(),
+        operator: "loop_zero".to_string(),
+        original: node_text(cond, source).to_string(),
+        replacement: "false".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn collect_loop_control_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // `break value` has no `continue` equivalent
+    let mut cursor = node.walk();
+    if node.named_children(&mut cursor).any(|c| c.kind() != "label") {
+        return;
+    }
+    let Some(keyword) = node.child(0) else {
+        return;
+    };
+    let replacement = match keyword.kind() {
+        "break" => "continue",
+        "continue" => "break",
+        _ => return,
+    };
+
+    let line = keyword.start_position().row + 1;
+    let col = keyword.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: keyword.start_byte(),
+        end_byte: keyword.end_byte(),
+        operator: "loop_control".to_string(),
+        original: node_text(keyword, source).to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn collect_try_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // try_expression: expr "?" -- unwrapping instead of propagating means an
+    // untested error path panics rather than returning the error
+    let Some(question) = node.child(node.child_count().saturating_sub(1)) else {
+        return;
+    };
+    if question.kind() != "?" {
+        return;
+    }
+    let line = question.start_position().row + 1;
+    let col = question.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: question.start_byte(),
+        end_byte: question.end_byte(),
+        operator: "try_unwrap".to_string(),
+        original: node_text(question, source).to_string(),
+        replacement: ".unwrap()".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+fn collect_some_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // call_expression: Some(x) -> None
+    let is_some = node
+        .child_by_field_name("function")
+        .is_some_and(|f| node_text(f, source) == "Some");
+    let single_arg = node
+        .child_by_field_name("arguments")
+        .is_some_and(|a| a.named_child_count() == 1);
+    if !is_some || !single_arg {
+        return;
+    }
+    let line = node.start_position().row + 1;
+    let col = node.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "some_none".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: "None".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `if let Some(x) = e` / `while let Some(x) = e` -> `... = None`: the
+/// branch is never taken.
+fn collect_if_let_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(pattern), Some(value)) = (node.child_by_field_name("pattern"), node.child_by_field_name("value")) else {
+        return;
+    };
+    let is_some_pattern = pattern.kind() == "tuple_struct_pattern"
+        && pattern
+            .child_by_field_name("type")
+            .is_some_and(|t| node_text(t, source) == "Some");
+    if !is_some_pattern || node_text(value, source) == "None" {
+        return;
+    }
+    mutations.push(Mutation {
+        line: value.start_position().row + 1,
+        column: value.start_position().column + 1,
+        start_byte: value.start_byte(),
+        end_byte: value.end_byte(),
+        operator: "if_let_none".to_string(),
+        original: node_text(value, source).to_string(),
+        replacement: "None".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `matches!(x, P)` -> `!matches!(x, P)`.
+fn collect_matches_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let is_matches = node
+        .child_by_field_name("macro")
+        .is_some_and(|m| node_text(m, source) == "matches");
+    // `!matches!(..)` is already covered by negate_remove
+    let negated = node
+        .parent()
+        .is_some_and(|p| p.kind() == "unary_expression" && node_text(p, source).starts_with('!'));
+    if !is_matches || negated {
+        return;
+    }
+    let text = node_text(node, source);
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "negate_matches".to_string(),
+        original: text.to_string(),
+        replacement: format!("!{}", text),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `.is_some()` <-> `.is_none()`, `.is_ok()` <-> `.is_err()`.
+fn collect_predicate_swap_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(field) = node
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "field_expression")
+        .and_then(|f| f.child_by_field_name("field"))
+    else {
+        return;
+    };
+    let no_args = node
+        .child_by_field_name("arguments")
+        .is_some_and(|a| a.named_child_count() == 0);
+    let name = node_text(field, source);
+    let Some(replacement) = operators::predicate_swap(name).filter(|_| no_args) else {
+        return;
+    };
+    mutations.push(Mutation {
+        line: field.start_position().row + 1,
+        column: field.start_position().column + 1,
+        start_byte: field.start_byte(),
+        end_byte: field.end_byte(),
+        operator: "predicate_swap".to_string(),
+        original: name.to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// `.starts_with` <-> `.ends_with`, `.to_uppercase()` <-> `.to_lowercase()`.
+fn collect_string_method_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(field) = node
+        .child_by_field_name("function")
+        .filter(|f| f.kind() == "field_expression")
+        .and_then(|f| f.child_by_field_name("field"))
+    else {
+        return;
+    };
+    let name = node_text(field, source);
+    let Some(replacement) = operators::string_method_swap(name) else {
+        return;
+    };
+    mutations.push(Mutation {
+        line: field.start_position().row + 1,
+        column: field.start_position().column + 1,
+        start_byte: field.start_byte(),
+        end_byte: field.end_byte(),
+        operator: "string_method".to_string(),
+        original: name.to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Opt-in `collection_method`: `.first()` <-> `.last()`, and `min` <->
+/// `max` as a method (`a.min(b)`, `iter.max()`) or function
+/// (`cmp::min(a, b)`).
+fn collect_collection_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(callee) = node.child_by_field_name("function") else {
+        return;
+    };
+    let no_args = node
+        .child_by_field_name("arguments")
+        .is_some_and(|a| a.named_child_count() == 0);
+    let (target, replacement) = match callee.kind() {
+        "field_expression" => {
+            let Some(field) = callee.child_by_field_name("field") else {
+                return;
+            };
+            let replacement = match node_text(field, source) {
+                "first" if no_args => Some("last"),
+                "last" if no_args => Some("first"),
+                name => operators::min_max_swap(name),
+            };
+            (field, replacement)
+        }
+        "identifier" => (callee, operators::min_max_swap(node_text(callee, source))),
+        "scoped_identifier" => {
+            let Some(name) = callee.child_by_field_name("name") else {
+                return;
+            };
+            (name, operators::min_max_swap(node_text(name, source)))
+        }
+        _ => return,
+    };
+    let Some(replacement) = replacement else {
+        return;
+    };
+    mutations.push(Mutation {
+        line: target.start_position().row + 1,
+        column: target.start_position().column + 1,
+        start_byte: target.start_byte(),
+        end_byte: target.end_byte(),
+        operator: "collection_method".to_string(),
+        original: node_text(target, source).to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Ranges: `0..n` <-> `0..=n`, and the start moved up by one (`0..n` ->
+/// `1..n`), the off-by-one that `boundary` can't reach in range loops.
+fn collect_range_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+    let Some(op_index) = children.iter().position(|c| matches!(c.kind(), ".." | "..=")) else {
+        return;
+    };
+    let op = children[op_index];
+    let start = children[..op_index].iter().find(|c| c.is_named());
+    let end = children[op_index + 1..].iter().find(|c| c.is_named());
+    let mut push = |target: Node, replacement: String| {
+        mutations.push(Mutation {
+            line: target.start_position().row + 1,
+            column: target.start_position().column + 1,
+            start_byte: target.start_byte(),
+            end_byte: target.end_byte(),
+            operator: "range_tweak".to_string(),
+            original: node_text(target, source).to_string(),
+            replacement,
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
         });
+    };
+
+    if end.is_some() {
+        let flipped = if op.kind() == ".." { "..=" } else { ".." };
+        push(op, flipped.to_string());
+    }
+    if let Some(&start) = start {
+        let text = node_text(start, source);
+        let bumped = match text.parse::<i64>() {
+            Ok(n) => (n + 1).to_string(),
+            Err(_) if matches!(start.kind(), "identifier" | "field_expression" | "call_expression") => {
+                format!("{} + 1", text)
+            }
+            Err(_) => format!("({}) + 1", text),
+        };
+        push(start, bumped);
     }
 }
 
-fn collect_boolean_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+/// Fallbacks that already equal the type's default; swapping them for
+/// `unwrap_or_default()` would be an equivalent mutant.
+const DEFAULT_LIKE: &[&str] = &[
+    "0", "0.0", "false", "\"\"", "String::new()", "Vec::new()", "vec![]", "Default::default()",
+];
+
+fn collect_unwrap_or_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    // call_expression: recv.unwrap_or(a) -> recv.unwrap_or_default()
+    let Some(function) = node.child_by_field_name("function") else {
+        return;
+    };
+    if function.kind() != "field_expression" {
+        return;
+    }
+    let Some(field) = function.child_by_field_name("field") else {
+        return;
+    };
+    if node_text(field, source) != "unwrap_or" {
+        return;
+    }
+    let Some(args) = node.child_by_field_name("arguments") else {
+        return;
+    };
+    match args.named_child(0) {
+        Some(arg) if args.named_child_count() == 1 => {
+            if DEFAULT_LIKE.contains(&node_text(arg, source).trim()) {
+                return;
+            }
+        }
+        _ => return,
+    }
+
+    let line = field.start_position().row + 1;
+    let col = field.start_position().column + 1;
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: field.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "unwrap_or_default".to_string(),
+        original: source[field.start_byte()..node.end_byte()].to_string(),
+        replacement: "unwrap_or_default()".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// The trailing expression of a block (its value), if it has one.
+fn tail_expression(block: Node) -> Option<Node> {
+    let mut cursor = block.walk();
+    let last = block
+        .named_children(&mut cursor)
+        .filter(|c| !c.kind().ends_with("comment"))
+        .last()?;
+    match last.kind() {
+        // `if`/`match`/blocks in tail position parse as a statement
+        // without a trailing semicolon
+        "expression_statement" => {
+            let terminated = last
+                .child(last.child_count().saturating_sub(1))
+                .is_some_and(|c| c.kind() == ";");
+            if terminated { None } else { last.named_child(0) }
+        }
+        "let_declaration" | "empty_statement" => None,
+        kind if kind.ends_with("_item") => None,
+        _ => Some(last),
+    }
+}
+
+fn collect_boolean_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // Skip if inside a return (handled by return_mutations)
     if let Some(parent) = node.parent() {
         if parent.kind() == "return_expression" {
@@ -329,7 +1014,6 @@ fn collect_boolean_mutations(node: Node, source: &str, lines: &[&str], mutations
     let text = node_text(node, source);
     let line = node.start_position().row + 1;
     let col = node.start_position().column + 1;
-    let (ctx_before, ctx_after) = get_context(lines, node.start_position().row, 2);
 
     let replacement = match text {
         "true" => "false",
@@ -345,12 +1029,13 @@ fn collect_boolean_mutations(node: Node, source: &str, lines: &[&str], mutations
         operator: "bool_flip".to_string(),
         original: text.to_string(),
         replacement: replacement.to_string(),
-        context_before: ctx_before,
-        context_after: ctx_after,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
     });
 }
 
-fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations: &mut Vec<Mutation>) {
+fn collect_if_body_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
     // if_expression: "if" condition consequence [else_clause]
     if let Some(consequence) = node.child_by_field_name("consequence") {
         if consequence.kind() == "block" {
@@ -361,7 +1046,6 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
 
             let line = consequence.start_position().row + 1;
             let col = consequence.start_position().column + 1;
-            let (ctx_before, ctx_after) = get_context(lines, consequence.start_position().row, 2);
 
             mutations.push(Mutation {
                 line,
@@ -371,9 +1055,187 @@ fn collect_if_body_mutations(node: Node, source: &str, lines: &[&str], mutations
                 operator: "block_remove".to_string(),
                 original: block_text.to_string(),
                 replacement: "{}".to_string(),
-                context_before: ctx_before,
-                context_after: ctx_after,
+                context_before: Vec::new(),
+                context_after: Vec::new(),
+                function: None,
             });
         }
     }
 }
+
+/// Swap the branches of `if cond { a } else { b }` -> `if cond { b } else { a }`.
+/// `else if` chains are handled when the walker reaches the inner `if`.
+fn collect_if_else_swap_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let consequence = match node.child_by_field_name("consequence") {
+        Some(c) if c.kind() == "block" => c,
+        _ => return,
+    };
+    let alternative = match node
+        .child_by_field_name("alternative")
+        .and_then(|else_clause| else_clause.named_child(0))
+    {
+        Some(a) if a.kind() == "block" => a,
+        _ => return,
+    };
+
+    let line = consequence.start_position().row + 1;
+    let col = consequence.start_position().column + 1;
+
+    let replacement = format!(
+        "{}{}{}",
+        node_text(alternative, source),
+        &source[consequence.end_byte()..alternative.start_byte()],
+        node_text(consequence, source),
+    );
+
+    mutations.push(Mutation {
+        line,
+        column: col,
+        start_byte: consequence.start_byte(),
+        end_byte: alternative.end_byte(),
+        operator: "ternary_swap".to_string(),
+        original: source[consequence.start_byte()..alternative.end_byte()].to_string(),
+        replacement,
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Remove individual arms from a `match`. Only done when an unguarded
+/// catch-all arm (`_` or a bare binding) exists, so the match stays
+/// exhaustive and the mutant compiles. The catch-all itself is kept.
+fn collect_match_arm_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let body = match node.child_by_field_name("body") {
+        Some(b) => b,
+        None => return,
+    };
+    let mut cursor = body.walk();
+    let arms: Vec<Node> = body
+        .children(&mut cursor)
+        .filter(|c| c.kind() == "match_arm")
+        .collect();
+    if arms.len() < 2 || !arms.iter().any(|a| is_catch_all_arm(*a, source)) {
+        return;
+    }
+
+    for arm in arms {
+        if is_catch_all_arm(arm, source) {
+            continue;
+        }
+        let line = arm.start_position().row + 1;
+        let col = arm.start_position().column + 1;
+
+        mutations.push(Mutation {
+            line,
+            column: col,
+            start_byte: arm.start_byte(),
+            end_byte: arm.end_byte(),
+            operator: "arm_remove".to_string(),
+            original: node_text(arm, source).to_string(),
+            replacement: String::new(),
+            context_before: Vec::new(),
+            context_after: Vec::new(),
+            function: None,
+        });
+    }
+}
+
+fn is_catch_all_arm(arm: Node, source: &str) -> bool {
+    let pattern = match arm.child_by_field_name("pattern") {
+        Some(p) => p,
+        None => return false,
+    };
+    if pattern.child_by_field_name("condition").is_some() {
+        return false;
+    }
+    match pattern.named_child(0) {
+        // A bare lowercase identifier is a binding; `None` etc. are also identifiers
+        Some(inner) if inner.kind() == "identifier" => {
+            node_text(inner, source).starts_with(|c: char| c.is_lowercase() || c == '_')
+        }
+        Some(inner) => node_text(inner, source) == "_",
+        None => node_text(pattern, source).trim() == "_",
+    }
+}
+
+/// Swap the first two arguments: `f(a, b)` -> `f(b, a)`.
+fn collect_arg_swap_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let (Some(callee), Some(args)) = (node.child_by_field_name("function"), node.child_by_field_name("arguments")) else {
+        return;
+    };
+    if operators::is_logging_call(node_text(callee, source)) {
+        return;
+    }
+    let mut cursor = args.walk();
+    let mut positional = args.named_children(&mut cursor).filter(|a| !a.kind().contains("comment"));
+    let (Some(first), Some(second)) = (positional.next(), positional.next()) else {
+        return;
+    };
+    if !operators::swappable_args(first.kind(), second.kind()) {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: first.start_position().row + 1,
+        column: first.start_position().column + 1,
+        start_byte: first.start_byte(),
+        end_byte: second.end_byte(),
+        operator: "arg_swap".to_string(),
+        original: source[first.start_byte()..second.end_byte()].to_string(),
+        replacement: format!(
+            "{}{}{}",
+            node_text(second, source),
+            &source[first.end_byte()..second.start_byte()],
+            node_text(first, source),
+        ),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
+
+/// Whether `statement` is all of an `if` body, which `block_remove`
+/// already empties.
+fn is_sole_statement_of_if_body(statement: Node) -> bool {
+    let Some(block) = statement.parent().filter(|b| b.kind() == "block") else {
+        return false;
+    };
+    let mut cursor = block.walk();
+    if block.named_children(&mut cursor).filter(|s| !s.kind().ends_with("comment")).count() != 1 {
+        return false;
+    }
+    block
+        .parent()
+        .is_some_and(|owner| owner.kind() == "if_expression" && owner.child_by_field_name("consequence") == Some(block))
+}
+
+/// Drop a call made for its side effect: `cache.invalidate(&key);` -> `;`.
+fn collect_call_remove_mutations(node: Node, source: &str, mutations: &mut Vec<Mutation>) {
+    let Some(call) = node.named_child(0).filter(|c| c.kind() == "call_expression") else {
+        return;
+    };
+    let Some(callee) = call.child_by_field_name("function") else {
+        return;
+    };
+    // Without the `;` the call is the block's value, not a statement
+    let terminated = node
+        .child(node.child_count().saturating_sub(1))
+        .is_some_and(|c| c.kind() == ";");
+    if !terminated || operators::is_logging_call(node_text(callee, source)) || is_sole_statement_of_if_body(node) {
+        return;
+    }
+
+    mutations.push(Mutation {
+        line: node.start_position().row + 1,
+        column: node.start_position().column + 1,
+        start_byte: node.start_byte(),
+        end_byte: node.end_byte(),
+        operator: "call_remove".to_string(),
+        original: node_text(node, source).to_string(),
+        replacement: ";".to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    });
+}
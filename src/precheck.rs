@@ -0,0 +1,134 @@
+//! `--precheck`: compile or type-check each mutant before its test run.
+//!
+//! A mutant the compiler rejects can never be killed by a test, so it is
+//! classified `Unviable` after a `cargo check`, `tsc --noEmit` or
+//! `py_compile` instead of a full test-suite run. The check runs once on
+//! the unmutated source first; if that fails every mutant would look
+//! unviable, so the run stops.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::mutants::{MutantResult, MutantStatus, Mutation};
+use crate::runner::{self, OutputCapture};
+use crate::signals;
+use crate::Language;
+
+/// A resolved check command for one mutated file.
+#[derive(Debug, Clone)]
+pub struct Precheck {
+    program: String,
+    args: Vec<String>,
+    working_dir: PathBuf,
+    env: Vec<(String, String)>,
+}
+
+impl Precheck {
+    /// The check for `file`, the file that gets mutated:
+    ///
+    /// - Python: `python3 -m py_compile <file>`
+    /// - Rust: `cargo check --tests` in the crate holding `file`
+    /// - TypeScript/TSX: `npx --no-install tsc --noEmit -p <dir>` with the
+    ///   nearest `tsconfig.json`
+    /// - JavaScript: `node --check <file>`
+    ///
+    /// `None` when there's nothing to run: no `Cargo.toml` or `tsconfig.json`
+    /// above the file, or JSX, which `node --check` can't parse.
+    pub fn for_file(language: &Language, file: &Path, env: &[(String, String)]) -> Option<Precheck> {
+        let dir = file.parent().unwrap_or(Path::new("."));
+        let path = file.to_string_lossy().into_owned();
+        let (program, args, working_dir): (&str, Vec<String>, PathBuf) = match language {
+            Language::Python => ("python3", vec!["-m".into(), "py_compile".into(), path], dir.to_path_buf()),
+            Language::Rust => {
+                let krate = nearest_with(dir, "Cargo.toml")?;
+                ("cargo", vec!["check".into(), "--tests".into(), "--quiet".into()], krate)
+            }
+            Language::TypeScript | Language::Tsx if file.extension().is_some_and(|e| e != "jsx") => {
+                let project = nearest_with(dir, "tsconfig.json")?;
+                let args = ["--no-install", "tsc", "--noEmit", "-p", "."].map(String::from).to_vec();
+                ("npx", args, project)
+            }
+            Language::JavaScript => ("node", vec!["--check".into(), path], dir.to_path_buf()),
+            _ => return None,
+        };
+        Some(Precheck { program: program.to_string(), args, working_dir, env: env.to_vec() })
+    }
+
+    /// The command line, for messages.
+    pub fn describe(&self) -> String {
+        std::iter::once(self.program.as_str()).chain(self.args.iter().map(String::as_str)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Run the check. `Err` carries its output when it fails. A check that
+    /// outlives `timeout` is stopped and counts as passing: the test run
+    /// then decides.
+    pub fn run(&self, timeout: Option<Duration>) -> Result<(), String> {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .current_dir(&self.working_dir)
+            .envs(self.env.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        runner::own_process_group(&mut command);
+        log::debug!("Precheck: {:?}", command);
+        let mut child = command.spawn().map_err(|e| format!("Failed to run {}: {}", self.describe(), e))?;
+        signals::track_child(child.id());
+        let mut stdout = OutputCapture::spawn(child.stdout.take());
+        let mut stderr = OutputCapture::spawn(child.stderr.take());
+        let start = Instant::now();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) if timeout.is_some_and(|t| start.elapsed() > t) => {
+                    runner::kill_process_group(&mut child);
+                    let _ = child.wait();
+                    break None;
+                }
+                Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+                Err(_) => break None,
+            }
+        };
+        signals::untrack_child();
+        stdout.finish();
+        stderr.finish();
+        match status {
+            Some(status) if !status.success() => {
+                Err(runner::output_snippet(&stdout.snapshot(), &stderr.snapshot()).unwrap_or_default())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Write `mutation` to `source_file` and run `precheck` on it. Returns the
+/// `Unviable` result if the check fails, `None` if the mutant should go on
+/// to its test run. The mutated content is left on disk, as with
+/// `runner::run_mutant`.
+pub fn check_mutant(
+    precheck: &Precheck,
+    source_file: &Path,
+    original_source: &str,
+    mutation: &Mutation,
+    timeout_ms: u64,
+) -> Option<MutantResult> {
+    let mutated = runner::apply_mutation(original_source, mutation)?;
+    std::fs::write(source_file, &mutated).ok()?;
+    let start = Instant::now();
+    let output = precheck.run(Some(Duration::from_millis(timeout_ms))).err()?;
+    Some(MutantResult {
+        mutation: mutation.clone(),
+        status: MutantStatus::Unviable,
+        duration_ms: start.elapsed().as_millis() as u64,
+        diff: runner::generate_diff(original_source, &mutated),
+        output_snippet: Some(output),
+        killed_by: None,
+    })
+}
+
+/// The nearest of `dir` and its ancestors containing `name`.
+fn nearest_with(dir: &Path, name: &str) -> Option<PathBuf> {
+    dir.ancestors().find(|d| d.join(name).is_file()).map(Path::to_path_buf)
+}
@@ -0,0 +1,134 @@
+//! `run --prioritize`: run the mutants most likely to matter first, so a
+//! run cut short (Ctrl-C, a CI time limit) has already tried them.
+//!
+//! Mutants are ordered by, in turn:
+//!
+//! 1. how recently their line changed, per `git blame` (uncommitted lines
+//!    count as changed now);
+//! 2. how many mutants were killed on the line in the last recorded run of
+//!    the file, fewest first;
+//! 3. operator: condition flips (`boundary`, `negate_*`, ...) before the
+//!    rest, since a surviving one usually means an untested branch;
+//! 4. the default order.
+//!
+//! Outside a git checkout, or with no recorded run, that criterion ties.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+use crate::history;
+use crate::mutants::{MutantStatus, Mutation};
+use crate::state::RunResult;
+
+/// Operators that flip a condition; they run before the others.
+pub const CONDITION_OPERATORS: &[&str] = &[
+    "boundary",
+    "len_boundary",
+    "negate_cmp",
+    "negate_eq",
+    "negate_is",
+    "negate_in",
+    "negate_matches",
+    "negate_remove",
+    "logic_flip",
+    "bool_flip",
+    "true",
+    "false",
+    "filter_negate",
+    "predicate_swap",
+];
+
+/// Run order for `mutations` (indices into it). `blame` maps line numbers
+/// to their last change (Unix time), `last_run` is the file's previous
+/// result.
+pub fn run_order(mutations: &[Mutation], blame: &HashMap<usize, u64>, last_run: Option<&RunResult>) -> Vec<usize> {
+    let kills = last_run.map(LineKills::new);
+    let mut order: Vec<usize> = (0..mutations.len()).collect();
+    // Stable, so ties keep the default order
+    order.sort_by_key(|&i| {
+        let m = &mutations[i];
+        (
+            std::cmp::Reverse(blame.get(&m.line).copied().unwrap_or(0)),
+            kills.as_ref().map_or(0, |k| k.on(m.line)),
+            !CONDITION_OPERATORS.contains(&m.operator.as_str()),
+        )
+    });
+    order
+}
+
+/// Kills per line in a recorded run.
+struct LineKills {
+    killed: HashMap<usize, usize>,
+    /// Lines with a survivor, timeout or other referenced mutant.
+    referenced: Vec<usize>,
+    /// Killed mutants were recorded one by one (`--full-results`).
+    full: bool,
+}
+
+impl LineKills {
+    fn new(run: &RunResult) -> Self {
+        let mut killed = HashMap::new();
+        for m in run.all_mutants.iter().filter(|m| m.status == MutantStatus::Killed) {
+            *killed.entry(m.line).or_insert(0) += 1;
+        }
+        LineKills { killed, referenced: run.referenced().map(|m| m.line).collect(), full: !run.all_mutants.is_empty() }
+    }
+
+    /// Without `--full-results` only survivors are recorded, so any other
+    /// line that had mutants is assumed to have had a kill.
+    fn on(&self, line: usize) -> usize {
+        match self.killed.get(&line) {
+            Some(&n) => n,
+            None if self.full || self.referenced.contains(&line) => 0,
+            None => 1,
+        }
+    }
+}
+
+/// When each line of `file` last changed, from `git blame`. Empty if the
+/// file isn't in a git checkout or git isn't installed.
+pub fn blame_times(file: &Path) -> HashMap<usize, u64> {
+    let dir = file.parent().unwrap_or(Path::new("."));
+    let output = Command::new("git")
+        .args(["blame", "--line-porcelain", "--"])
+        .arg(file)
+        .current_dir(dir)
+        .output();
+    match output {
+        Ok(o) if o.status.success() => parse_blame(&String::from_utf8_lossy(&o.stdout)),
+        _ => HashMap::new(),
+    }
+}
+
+/// `git blame --line-porcelain`: a `<sha> <orig> <final> [<count>]` header
+/// per line, then its commit's fields, `author-time` among them.
+fn parse_blame(output: &str) -> HashMap<usize, u64> {
+    let mut times = HashMap::new();
+    let mut line = None;
+    for row in output.lines() {
+        let mut fields = row.split(' ');
+        let first = fields.next().unwrap_or_default();
+        if first.len() == 40 && first.bytes().all(|b| b.is_ascii_hexdigit()) {
+            line = fields.nth(1).and_then(|n| n.parse::<usize>().ok());
+        } else if let (Some(n), Some(time)) = (line, row.strip_prefix("author-time ")) {
+            if let Ok(time) = time.parse() {
+                times.insert(n, time);
+            }
+        }
+    }
+    times
+}
+
+/// The latest run of `file` recorded in the history under `dir`, if its
+/// snapshot was kept.
+pub fn last_run(dir: &Path, file: &Path) -> Option<RunResult> {
+    history::load_from(&history::history_path_in(dir))
+        .iter()
+        .rev()
+        .filter(|entry| {
+            let recorded = Path::new(&entry.file);
+            recorded.ends_with(file) || file.ends_with(recorded)
+        })
+        .find_map(|entry| history::load_run(dir, entry))
+}
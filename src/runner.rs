@@ -1,21 +1,308 @@
-use std::path::{Path, PathBuf};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
 use std::time::Instant;
 
-use crate::copy_tree::{self, CopyResult};
+use crate::copy_tree::{self, CopyMode, CopyResult};
+use crate::{signals, state};
+use crate::test_adapters::{ReportOutcome, Runner, TestArgStyle};
 use crate::mutants::{Mutation, MutantResult, MutantStatus};
 
 pub enum BaselineResult {
     Ok { duration_ms: u64 },
     Failed(String),
+    /// The command ran, but selected no tests (`TestAdapter::ran_no_tests`);
+    /// carries its output.
+    NoTests(String),
+}
+
+/// Per-mutant timeout that follows how long surviving mutants actually
+/// take. It starts at `baseline * mult + 2s`; once `MIN_SAMPLES` survivors
+/// have run, it becomes the p95 of the last `WINDOW` survivor durations
+/// times `mult`, plus 2s. Only survivors count: they run the whole suite
+/// like the baseline did, while killed mutants stop at the first failure
+/// and would pull the timeout below a full run. `cap_ms` bounds it either way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdaptiveTimeout {
+    baseline_ms: u64,
+    mult: f64,
+    cap_ms: Option<u64>,
+    recent: VecDeque<u64>,
+}
+
+impl AdaptiveTimeout {
+    pub const WINDOW: usize = 20;
+    pub const MIN_SAMPLES: usize = 5;
+    const GRACE_MS: u64 = 2000;
+
+    pub fn new(baseline_ms: u64, mult: f64, cap_ms: Option<u64>) -> Self {
+        AdaptiveTimeout { baseline_ms, mult, cap_ms, recent: VecDeque::with_capacity(Self::WINDOW) }
+    }
+
+    /// Record a survivor's run time.
+    pub fn record(&mut self, duration_ms: u64) {
+        if self.recent.len() == Self::WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(duration_ms);
+    }
+
+    pub fn timeout_ms(&self) -> u64 {
+        let reference = if self.recent.len() < Self::MIN_SAMPLES {
+            self.baseline_ms
+        } else {
+            let mut sorted: Vec<u64> = self.recent.iter().copied().collect();
+            sorted.sort_unstable();
+            sorted[(sorted.len() * 95).div_ceil(100) - 1]
+        };
+        let timeout = (reference as f64 * self.mult) as u64 + Self::GRACE_MS;
+        self.cap_ms.map_or(timeout, |cap| timeout.min(cap))
+    }
 }
 
 pub struct IsolatedContext {
     pub copy_result: CopyResult,
     pub resolved_cmd: String,
+    /// Where tests run: the copy's root unless the caller moves it.
+    pub working_dir: PathBuf,
     pub _temp_dir: tempfile::TempDir,
 }
 
+impl IsolatedContext {
+    /// Test invocation rooted in the copied tree.
+    pub fn invocation(&self, extra_args: &[&str]) -> TestInvocation {
+        TestInvocation::new(
+            &self.resolved_cmd,
+            &self.copy_result.test_file,
+            &self.working_dir,
+            extra_args,
+        )
+    }
+}
+
+/// Per-process limits for test runs, applied as rlimits on Unix (not
+/// enforced elsewhere). Each child of the test process gets the same limits.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceLimits {
+    /// Address space (`RLIMIT_AS`), in MiB.
+    pub max_memory_mb: Option<u64>,
+    /// CPU time (`RLIMIT_CPU`), in seconds.
+    pub cpu_seconds: Option<u64>,
+}
+
+/// Output that means an allocation failed: Python, Rust, C/C++, Node, Go.
+const OUT_OF_MEMORY: &[&str] = &[
+    "MemoryError",
+    "memory allocation of",
+    "Cannot allocate memory",
+    "std::bad_alloc",
+    "JavaScript heap out of memory",
+    "out of memory",
+];
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_memory_mb.is_none() && self.cpu_seconds.is_none()
+    }
+
+    /// Whether a failed run with this exit status and output ran into the
+    /// limits rather than failing a test.
+    pub fn exceeded(&self, status: std::process::ExitStatus, output: &str) -> bool {
+        #[cfg(unix)]
+        if self.cpu_seconds.is_some() {
+            use std::os::unix::process::ExitStatusExt;
+            // SIGXCPU at the soft limit, SIGKILL at the hard one
+            if matches!(status.signal(), Some(libc::SIGXCPU) | Some(libc::SIGKILL)) {
+                return true;
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = status;
+        self.max_memory_mb.is_some() && OUT_OF_MEMORY.iter().any(|marker| output.contains(marker))
+    }
+
+    #[cfg(unix)]
+    fn apply(&self, cmd: &mut Command) {
+        use std::os::unix::process::CommandExt;
+        if self.is_empty() {
+            return;
+        }
+        let limits = *self;
+        let set = |resource, soft: u64, hard: u64| {
+            let limit = libc::rlimit { rlim_cur: soft as libc::rlim_t, rlim_max: hard as libc::rlim_t };
+            // SAFETY: setrlimit is async-signal-safe and `limit` outlives the call
+            if unsafe { libc::setrlimit(resource, &limit) } == 0 {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error())
+            }
+        };
+        // SAFETY: the closure only calls setrlimit, which is safe between
+        // fork and exec
+        unsafe {
+            cmd.pre_exec(move || {
+                if let Some(mb) = limits.max_memory_mb {
+                    let bytes = mb.saturating_mul(1024 * 1024);
+                    set(libc::RLIMIT_AS, bytes, bytes)?;
+                }
+                if let Some(secs) = limits.cpu_seconds {
+                    set(libc::RLIMIT_CPU, secs, secs + 1)?;
+                }
+                Ok(())
+            });
+        }
+    }
+}
+
+/// A fully resolved test command: program, target test file, working directory
+/// and the extra runner arguments appended after the test file.
+#[derive(Debug, Clone)]
+pub struct TestInvocation {
+    pub test_cmd: String,
+    pub test_file: PathBuf,
+    pub working_dir: PathBuf,
+    pub extra_args: Vec<String>,
+    /// Extra environment for the test process (e.g. `CARGO_TARGET_DIR`).
+    pub env: Vec<(String, String)>,
+    pub limits: ResourceLimits,
+    /// How the command is built and its output read. Detected from
+    /// `test_cmd` by `new`, pytest if it names no known runner.
+    pub runner: Runner,
+    /// Stop a mutant run as soon as its output shows a failed test
+    /// (`TestAdapter::reports_failure`) instead of waiting for the exit.
+    pub early_exit: bool,
+    /// Whether and how `test_file` goes on the command line.
+    pub test_arg_style: TestArgStyle,
+}
+
+impl TestInvocation {
+    pub fn new(test_cmd: &str, test_file: &Path, working_dir: &Path, extra_args: &[&str]) -> Self {
+        TestInvocation {
+            test_cmd: test_cmd.to_string(),
+            test_file: test_file.to_path_buf(),
+            working_dir: working_dir.to_path_buf(),
+            extra_args: extra_args.iter().map(|s| s.to_string()).collect(),
+            env: Vec::new(),
+            limits: ResourceLimits::default(),
+            runner: Runner::from_command(test_cmd).unwrap_or(Runner::Pytest),
+            early_exit: false,
+            test_arg_style: TestArgStyle::Auto,
+        }
+    }
+
+    pub fn with_env(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.env.push((key.to_string(), value.into()));
+        self
+    }
+
+    pub fn with_limits(mut self, limits: ResourceLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn with_runner(mut self, runner: Runner) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    pub fn with_early_exit(mut self, early_exit: bool) -> Self {
+        self.early_exit = early_exit;
+        self
+    }
+
+    pub fn with_test_arg_style(mut self, style: TestArgStyle) -> Self {
+        self.test_arg_style = style;
+        self
+    }
+
+    /// Build the `Command` for this invocation. Stdio is left to the caller.
+    /// `MUTATOR_ACTIVE=1` is always set so tests can tell they run under
+    /// mutator.
+    pub fn command(&self) -> Command {
+        let (program, first_args) = parse_test_cmd(&self.test_cmd);
+        let mut cmd = Command::new(&program);
+        for arg in &first_args {
+            cmd.arg(arg);
+        }
+        match self.test_arg_style {
+            TestArgStyle::Auto if self.runner.adapter().passes_test_file() => {
+                cmd.arg(&self.test_file);
+            }
+            TestArgStyle::Path => {
+                cmd.arg(&self.test_file);
+            }
+            TestArgStyle::Relative => {
+                cmd.arg(relative_to(&self.test_file, &self.working_dir));
+            }
+            _ => {}
+        }
+        for arg in &self.extra_args {
+            cmd.arg(arg);
+        }
+        cmd.current_dir(&self.working_dir)
+            .env("OBJC_DISABLE_INITIALIZE_FORK_SAFETY", "YES")
+            .env("MUTATOR_ACTIVE", "1");
+        for (key, value) in &self.env {
+            cmd.env(key, value);
+        }
+        #[cfg(unix)]
+        self.limits.apply(&mut cmd);
+        cmd
+    }
+}
+
+/// `path` relative to `base`, with `..` where it lies outside it. Both are
+/// absolute; `path` is returned as is if they share no root.
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let (path_parts, base_parts): (Vec<_>, Vec<_>) = (path.components().collect(), base.components().collect());
+    let common = path_parts.iter().zip(&base_parts).take_while(|(a, b)| a == b).count();
+    if common == 0 {
+        return path.to_path_buf();
+    }
+    let mut relative: PathBuf = base_parts[common..].iter().map(|_| Component::ParentDir).collect();
+    relative.extend(&path_parts[common..]);
+    relative
+}
+
+/// A command as it would be spawned, for `run --print-cmd`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandLine {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: Option<PathBuf>,
+    /// Variables set on top of the inherited environment.
+    pub env: Vec<(String, String)>,
+}
+
+impl CommandLine {
+    pub fn of(command: &Command) -> Self {
+        let text = |s: &std::ffi::OsStr| s.to_string_lossy().into_owned();
+        CommandLine {
+            program: text(command.get_program()),
+            args: command.get_args().map(text).collect(),
+            cwd: command.get_current_dir().map(Path::to_path_buf),
+            env: command.get_envs().filter_map(|(k, v)| Some((text(k), text(v?)))).collect(),
+        }
+    }
+
+    /// One shell line: `cd <cwd> && KEY=value program args...`, quoted for sh.
+    pub fn shell(&self) -> String {
+        let quote = |s: &str| shlex::try_quote(s).map(|q| q.into_owned()).unwrap_or_else(|_| s.to_string());
+        let mut parts = Vec::new();
+        if let Some(cwd) = &self.cwd {
+            parts.push(format!("cd {} &&", quote(&cwd.to_string_lossy())));
+        }
+        parts.extend(self.env.iter().map(|(k, v)| format!("{}={}", k, quote(v))));
+        parts.push(quote(&self.program));
+        parts.extend(self.args.iter().map(|a| quote(a)));
+        parts.join(" ")
+    }
+}
+
 /// Resolve all paths to absolute. This is critical for flat project layouts
 /// where source, tests, and venv all live in the same directory. We never
 /// copy files elsewhere (unlike mutmut's mutants/ dir approach), so imports
@@ -49,16 +336,40 @@ pub fn resolve_paths(
     (abs_source, abs_test, working_dir, resolved_cmd)
 }
 
+/// `test_cmd` with a relative program resolved as `resolve_paths` does,
+/// for tests run in `working_dir` instead of the source's directory.
+pub fn resolve_test_cmd(test_cmd: &str, working_dir: &Path) -> String {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    resolve_cmd(test_cmd, working_dir, &cwd)
+}
+
+/// Split a test command into program and arguments with POSIX shell
+/// quoting rules, so `pytest -k "adds and subtracts"` keeps the expression
+/// as one argument. No expansion or redirection happens. A command with
+/// unbalanced quotes falls back to splitting on whitespace.
 pub fn parse_test_cmd(cmd: &str) -> (String, Vec<String>) {
-    let parts: Vec<&str> = cmd.split_whitespace().collect();
-    if parts.len() > 1 {
-        (parts[0].to_string(), parts[1..].iter().map(|s| s.to_string()).collect())
-    } else {
-        (cmd.to_string(), vec![])
+    let mut parts = shlex::split(cmd)
+        .unwrap_or_else(|| cmd.split_whitespace().map(String::from).collect())
+        .into_iter();
+    match parts.next() {
+        Some(program) => (program, parts.collect()),
+        None => (cmd.to_string(), vec![]),
     }
 }
 
+/// Resolve the program of `cmd` to an absolute path if it is a relative
+/// path that exists, keeping its arguments (re-quoted) as they were.
 fn resolve_cmd(cmd: &str, working_dir: &Path, cwd: &Path) -> String {
+    let (program, args) = parse_test_cmd(cmd);
+    let resolved = resolve_program(&program, working_dir, cwd);
+    if resolved == program {
+        return cmd.to_string();
+    }
+    let words = std::iter::once(resolved.as_str()).chain(args.iter().map(String::as_str));
+    shlex::try_join(words).unwrap_or_else(|_| cmd.to_string())
+}
+
+fn resolve_program(cmd: &str, working_dir: &Path, cwd: &Path) -> String {
     let p = Path::new(cmd);
     if p.is_absolute() {
         return cmd.to_string();
@@ -78,40 +389,143 @@ fn resolve_cmd(cmd: &str, working_dir: &Path, cwd: &Path) -> String {
     cmd.to_string()
 }
 
-pub fn run_baseline(test_cmd: &str, test_file: &Path, working_dir: &Path, extra_args: &[&str]) -> BaselineResult {
-    let start = Instant::now();
-    let (program, first_args) = parse_test_cmd(test_cmd);
-    let mut cmd = Command::new(&program);
-    for arg in &first_args {
-        cmd.arg(arg);
+/// Cargo target selection for `test_file`: `--test NAME` for an integration
+/// test (`tests/NAME.rs` or `tests/NAME/main.rs`), so only that binary is
+/// rebuilt and run per mutant. Unit tests live in the crate's own targets,
+/// so nothing is selected for them.
+pub fn cargo_target_args(test_file: &Path) -> Vec<String> {
+    if test_file.extension().is_none_or(|ext| ext != "rs") {
+        return Vec::new();
     }
-    // For non-cargo commands, pass test file as arg
-    if !test_cmd.contains("cargo") {
-        cmd.arg(test_file);
+    let parent = test_file.parent();
+    let name = if parent.and_then(|p| p.file_name()).is_some_and(|n| n == "tests") {
+        test_file.file_stem()
+    } else if test_file.file_name().is_some_and(|n| n == "main.rs")
+        && parent.and_then(|p| p.parent()).and_then(|p| p.file_name()).is_some_and(|n| n == "tests")
+    {
+        parent.and_then(|p| p.file_name())
+    } else {
+        None
+    };
+    match name {
+        Some(name) => vec!["--test".to_string(), name.to_string_lossy().to_string()],
+        None => Vec::new(),
+    }
+}
+
+/// Package selection for a `source_file` in a Cargo workspace member:
+/// `-p NAME`, so cargo builds and tests that crate instead of the whole
+/// workspace. Empty for a crate outside a workspace or whose manifest
+/// has no readable name.
+pub fn cargo_package_args(source_file: &Path) -> Vec<String> {
+    let Some(krate) = copy_tree::find_package_dir(source_file) else {
+        return Vec::new();
+    };
+    if copy_tree::find_cargo_workspace(&krate).is_none() {
+        return Vec::new();
     }
-    for arg in extra_args {
-        cmd.arg(arg);
+    let manifest = std::fs::read_to_string(krate.join("Cargo.toml")).unwrap_or_default();
+    match cargo_package_name(&manifest) {
+        Some(name) => vec!["-p".to_string(), name],
+        None => Vec::new(),
     }
-    let output = cmd
-        .current_dir(working_dir)
-        .env("OBJC_DISABLE_INITIALIZE_FORK_SAFETY", "YES")
-        .output();
+}
+
+/// `name` under `[package]` in a Cargo manifest.
+fn cargo_package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if let Some(value) = line.strip_prefix("name").filter(|_| in_package) {
+            if let Some(value) = value.trim_start().strip_prefix('=') {
+                return Some(value.trim().trim_matches(['"', '\'']).to_string()).filter(|name| !name.is_empty());
+            }
+        }
+    }
+    None
+}
+
+/// Target dir for cargo runs against `project_root`. It lives outside the
+/// isolated copy so compiled dependencies survive across runs, and is keyed
+/// by session so concurrent agents don't wait on each other's build lock.
+pub fn cargo_target_dir(project_root: &Path, session: Option<&str>) -> PathBuf {
+    let base = cargo_target_base(project_root);
+    let session = session.map(state::file_safe).unwrap_or_else(|| "default".to_string());
+    base.join("mutator").join(session)
+}
+
+/// The project's own target dir: `$CARGO_TARGET_DIR`, else `target/`.
+pub fn cargo_target_base(project_root: &Path) -> PathBuf {
+    std::env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project_root.join("target"))
+}
+
+/// Seed a new session target dir from the project's own debug build so the
+/// first run reuses its compiled dependencies. Only `deps`, `build` and
+/// `.fingerprint` are taken; `incremental` is large and only helps the
+/// workspace crates, which rebuild anyway at their new path. Artifacts are
+/// cloned where supported and copied otherwise, never hard-linked, since
+/// cargo rewrites them. Returns false if `session_dir` already exists or
+/// there is no build to seed from.
+pub fn seed_cargo_target_dir(project_root: &Path, session_dir: &Path) -> std::io::Result<bool> {
+    let debug = cargo_target_base(project_root).join("debug");
+    if session_dir.exists() || !debug.join(".fingerprint").is_dir() {
+        return Ok(false);
+    }
+    let seeded = ["deps", "build", ".fingerprint"].iter().try_for_each(|sub| {
+        let src = debug.join(sub);
+        if src.is_dir() {
+            copy_tree::clone_dir(&src, &session_dir.join("debug").join(sub), CopyMode::Reflink)
+        } else {
+            Ok(())
+        }
+    });
+    if let Err(e) = seeded {
+        // A half-copied artifact with an intact fingerprint would not rebuild
+        let _ = std::fs::remove_dir_all(session_dir);
+        return Err(e);
+    }
+    Ok(true)
+}
+
+pub fn run_baseline(test_cmd: &str, test_file: &Path, working_dir: &Path, extra_args: &[&str]) -> BaselineResult {
+    run_baseline_with(&TestInvocation::new(test_cmd, test_file, working_dir, extra_args))
+}
+
+pub fn run_baseline_with(invocation: &TestInvocation) -> BaselineResult {
+    let start = Instant::now();
+    let mut command = invocation.command();
+    log::info!("Baseline: {:?}", command);
+    let output = command.output();
 
     match output {
         Ok(o) => {
             let duration_ms = start.elapsed().as_millis() as u64;
-            if o.status.success() {
+            log::info!("Baseline finished with {} in {}ms", o.status, duration_ms);
+            let stderr = String::from_utf8_lossy(&o.stderr).to_string();
+            let stdout = String::from_utf8_lossy(&o.stdout).to_string();
+            let output = format!("{}\n{}", stdout, stderr);
+            if invocation.runner.adapter().ran_no_tests(o.status.code(), &output) {
+                BaselineResult::NoTests(output)
+            } else if o.status.success() {
                 BaselineResult::Ok { duration_ms }
             } else {
-                let stderr = String::from_utf8_lossy(&o.stderr).to_string();
-                let stdout = String::from_utf8_lossy(&o.stdout).to_string();
-                BaselineResult::Failed(format!("{}\n{}", stdout, stderr))
+                if !invocation.limits.is_empty() && invocation.limits.exceeded(o.status, &output) {
+                    return BaselineResult::Failed(format!(
+                        "The unmutated tests exceed --max-memory-mb/--cpu-limit; raise the limits.\n{}",
+                        output
+                    ));
+                }
+                BaselineResult::Failed(output)
             }
         }
-        Err(e) => BaselineResult::Failed(format!("Failed to run {}: {}", test_cmd, e)),
+        Err(e) => BaselineResult::Failed(format!("Failed to run {}: {}", invocation.test_cmd, e)),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run_mutations(
     source_file: &Path,
     test_file: &Path,
@@ -122,103 +536,285 @@ pub fn run_mutations(
     timeout_ms: u64,
     extra_args: &[&str],
 ) -> Vec<MutantResult> {
+    let invocation = TestInvocation::new(test_cmd, test_file, working_dir, extra_args);
     let mut results = Vec::with_capacity(mutations.len());
 
     for mutation in mutations {
-        let mutated = apply_mutation(original_source, mutation);
-        let diff = generate_diff(original_source, &mutated);
-
-        if std::fs::write(source_file, &mutated).is_err() {
-            results.push(MutantResult {
-                mutation: mutation.clone(),
-                status: MutantStatus::Unviable,
-                duration_ms: 0,
-                diff,
-            });
-            continue;
-        }
+        results.push(run_mutant(&invocation, source_file, original_source, mutation, timeout_ms));
+    }
 
-        let start = Instant::now();
-        let timeout = std::time::Duration::from_millis(timeout_ms);
+    // ALWAYS restore original source, even on panic
+    let _ = std::fs::write(source_file, original_source);
+    // Restore pycache validity
+    clear_pycache(source_file);
 
-        clear_pycache(source_file);
+    results
+}
 
-        let (program, first_args) = parse_test_cmd(test_cmd);
-        let mut cmd = Command::new(&program);
-        for arg in &first_args {
-            cmd.arg(arg);
-        }
-        if !test_cmd.contains("cargo") {
-            cmd.arg(test_file);
-        }
-        for arg in extra_args {
-            cmd.arg(arg);
-        }
-        let child = cmd
-            .current_dir(working_dir)
-            .env("OBJC_DISABLE_INITIALIZE_FORK_SAFETY", "YES")
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .spawn();
-
-        let status = match child {
-            Ok(mut child) => {
-                loop {
-                    match child.try_wait() {
-                        Ok(Some(exit_status)) => {
-                            let stderr = child
-                                .stderr
-                                .take()
-                                .and_then(|mut s| {
-                                    let mut buf = String::new();
-                                    std::io::Read::read_to_string(&mut s, &mut buf).ok()?;
-                                    Some(buf)
-                                })
-                                .unwrap_or_default();
-
-                            if exit_status.success() {
-                                break MutantStatus::Survived;
-                            } else if stderr.contains("SyntaxError")
-                                || stderr.contains("IndentationError")
-                                || stderr.contains("ImportError")
-                                || stderr.contains("ModuleNotFoundError")
-                            {
-                                break MutantStatus::Unviable;
-                            } else {
-                                break MutantStatus::Killed;
+/// Start the test process in a process group of its own (Unix), so a
+/// timeout can kill everything it spawned: pytest-xdist workers, the
+/// processes behind `npm test` wrapper scripts, and so on.
+pub(crate) fn own_process_group(command: &mut Command) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+    #[cfg(not(unix))]
+    let _ = command;
+}
+
+/// Kill `child` and, on Unix, the rest of its process group.
+pub(crate) fn kill_process_group(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    signals::kill_group(child.id());
+    let _ = child.kill();
+}
+
+/// Apply a single mutation to `source_file`, run the tests and classify the outcome.
+/// The tests see the mutant's `Mutation::id` as `MUTATOR_MUTANT_ID`.
+/// The mutated content is left on disk; callers are responsible for restoring it.
+/// A mutation whose offsets don't fit `original_source` is `Unviable` and not run.
+pub fn run_mutant(
+    invocation: &TestInvocation,
+    source_file: &Path,
+    original_source: &str,
+    mutation: &Mutation,
+    timeout_ms: u64,
+) -> MutantResult {
+    let Some(mutated) = apply_mutation(original_source, mutation) else {
+        return stale_result(mutation);
+    };
+    let diff = generate_diff(original_source, &mutated);
+
+    if std::fs::write(source_file, &mutated).is_err() {
+        return MutantResult {
+            mutation: mutation.clone(),
+            status: MutantStatus::Unviable,
+            duration_ms: 0,
+            diff,
+            output_snippet: None,
+            killed_by: None,
+        };
+    }
+
+    let start = Instant::now();
+    let timeout = std::time::Duration::from_millis(timeout_ms);
+
+    clear_pycache(source_file);
+
+    let adapter = invocation.runner.adapter();
+    let report = report_path();
+    let mut command = invocation.command();
+    command.args(adapter.report_args(&report));
+    log::debug!("Mutant {} [{}]: {:?}", mutation.id(), mutation.operator, command);
+    command
+        .env("MUTATOR_MUTANT_ID", mutation.id())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+    own_process_group(&mut command);
+    let child = command.spawn();
+
+    let (status, output_snippet, killed_by) = match child {
+        Ok(mut child) => {
+            let mut reported_test = None;
+            signals::track_child(child.id());
+            // Drain both pipes concurrently so a chatty child can't block on a
+            // full pipe, and so partial output survives a timeout kill.
+            let mut stdout = OutputCapture::spawn(child.stdout.take());
+            let mut stderr = OutputCapture::spawn(child.stderr.take());
+            let status = loop {
+                match child.try_wait() {
+                    Ok(Some(exit_status)) => {
+                        stdout.finish();
+                        stderr.finish();
+                        let (stdout_text, stderr_text) = (stdout.snapshot(), stderr.snapshot());
+
+                        if exit_status.success() {
+                            break MutantStatus::Survived;
+                        } else if !invocation.limits.is_empty()
+                            && invocation.limits.exceeded(exit_status, &format!("{}\n{}", stdout_text, stderr_text))
+                        {
+                            break MutantStatus::ResourceExceeded;
+                        } else if let Some(outcome) = adapter.read_report(&report) {
+                            log::debug!("Mutant {}: report says {:?}", mutation.id(), outcome);
+                            match outcome {
+                                ReportOutcome::TestFailed(test) => {
+                                    reported_test = Some(test);
+                                    break MutantStatus::Killed;
+                                }
+                                ReportOutcome::LoadError => break MutantStatus::Unviable,
+                                ReportOutcome::NoTests => break MutantStatus::Uncovered,
                             }
+                        } else if adapter.is_unviable(&stdout_text, &stderr_text) {
+                            break MutantStatus::Unviable;
+                        } else {
+                            break MutantStatus::Killed;
                         }
-                        Ok(None) => {
-                            if start.elapsed() > timeout {
-                                let _ = child.kill();
+                    }
+                    Ok(None) => {
+                        if invocation.early_exit && (stdout.grew() || stderr.grew()) {
+                            let (stdout_text, stderr_text) = (stdout.snapshot(), stderr.snapshot());
+                            if adapter.reports_failure(&stdout_text) || adapter.reports_failure(&stderr_text) {
+                                kill_process_group(&mut child);
                                 let _ = child.wait();
-                                break MutantStatus::Timeout;
+                                stdout.finish();
+                                stderr.finish();
+                                let (stdout_text, stderr_text) = (stdout.snapshot(), stderr.snapshot());
+                                if adapter.is_unviable(&stdout_text, &stderr_text) {
+                                    break MutantStatus::Unviable;
+                                }
+                                break MutantStatus::Killed;
                             }
-                            std::thread::sleep(std::time::Duration::from_millis(10));
                         }
-                        Err(_) => break MutantStatus::Unviable,
+                        if start.elapsed() > timeout {
+                            kill_process_group(&mut child);
+                            let _ = child.wait();
+                            break MutantStatus::Timeout;
+                        }
+                        std::thread::sleep(std::time::Duration::from_millis(10));
                     }
+                    Err(_) => break MutantStatus::Unviable,
                 }
+            };
+            signals::untrack_child();
+            let (out, err) = (stdout.snapshot(), stderr.snapshot());
+            let killed_by = if status == MutantStatus::Killed {
+                reported_test.or_else(|| adapter.failing_test(&out)).or_else(|| adapter.failing_test(&err))
+            } else {
+                None
+            };
+            (status, output_snippet(&out, &err), killed_by)
+        }
+        Err(e) => (
+            MutantStatus::Unviable,
+            Some(format!("Failed to run {}: {}", invocation.test_cmd, e)),
+            None,
+        ),
+    };
+
+    let _ = std::fs::remove_file(&report);
+    let duration_ms = start.elapsed().as_millis() as u64;
+    log::debug!("Mutant {}: {:?} in {}ms", mutation.id(), status, duration_ms);
+
+    MutantResult {
+        mutation: mutation.clone(),
+        status,
+        duration_ms,
+        diff,
+        output_snippet,
+        killed_by,
+    }
+}
+
+/// A fresh path for a mutant run's report (`TestAdapter::report_args`).
+/// Nothing is created; the runner writes it, if it has a report at all.
+fn report_path() -> PathBuf {
+    std::env::temp_dir().join(format!("mutator-report-{}-{:016x}", std::process::id(), fastrand::u64(..)))
+}
+
+/// Maximum number of characters kept from a test run's output.
+const OUTPUT_SNIPPET_CHARS: usize = 2000;
+
+/// Combine stdout and stderr and keep the tail, which is where test runners
+/// report failures (and where an infinite loop's output ends up).
+pub fn output_snippet(stdout: &str, stderr: &str) -> Option<String> {
+    let combined = match (stdout.trim(), stderr.trim()) {
+        ("", "") => return None,
+        (out, "") => out.to_string(),
+        ("", err) => err.to_string(),
+        (out, err) => format!("{}\n{}", out, err),
+    };
+    let char_count = combined.chars().count();
+    if char_count <= OUTPUT_SNIPPET_CHARS {
+        return Some(combined);
+    }
+    let tail: String = combined.chars().skip(char_count - OUTPUT_SNIPPET_CHARS).collect();
+    Some(format!("...{}", tail))
+}
+
+/// Extract the first failing test name from test runner output.
+///
+/// Recognizes pytest (`FAILED file::test - msg`), cargo test
+/// (`test path::name ... FAILED`), jest (`● Suite › name`) and vitest
+/// (`FAIL file > suite > name`).
+pub fn failing_test(output: &str) -> Option<String> {
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(rest) = line.strip_prefix("FAILED ") {
+            let name = rest.split(" - ").next().unwrap_or(rest).trim();
+            if !name.is_empty() {
+                return Some(name.to_string());
             }
-            Err(_) => MutantStatus::Unviable,
-        };
+        }
+        if let Some(rest) = line.strip_prefix("test ") {
+            if let Some(name) = rest.strip_suffix(" ... FAILED") {
+                return Some(name.trim().to_string());
+            }
+        }
+        if let Some(rest) = line.strip_prefix("● ") {
+            if !rest.starts_with("Test suite failed to run") {
+                return Some(rest.trim().to_string());
+            }
+        }
+        if let Some(rest) = line.strip_prefix("FAIL ") {
+            if rest.contains(" > ") {
+                return Some(rest.trim().to_string());
+            }
+        }
+    }
+    None
+}
 
-        let duration_ms = start.elapsed().as_millis() as u64;
+/// Reads a child pipe on a background thread into a shared buffer.
+pub(crate) struct OutputCapture {
+    buf: Arc<Mutex<Vec<u8>>>,
+    handle: Option<JoinHandle<()>>,
+    /// Buffer length at the last `grew` call.
+    seen: usize,
+}
 
-        results.push(MutantResult {
-            mutation: mutation.clone(),
-            status,
-            duration_ms,
-            diff,
+impl OutputCapture {
+    pub(crate) fn spawn<R: Read + Send + 'static>(pipe: Option<R>) -> Self {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let handle = pipe.map(|mut pipe| {
+            let buf = Arc::clone(&buf);
+            std::thread::spawn(move || {
+                let mut chunk = [0u8; 4096];
+                while let Ok(n) = pipe.read(&mut chunk) {
+                    if n == 0 {
+                        break;
+                    }
+                    if let Ok(mut b) = buf.lock() {
+                        b.extend_from_slice(&chunk[..n]);
+                    }
+                }
+            })
         });
+        OutputCapture { buf, handle, seen: 0 }
     }
 
-    // ALWAYS restore original source, even on panic
-    let _ = std::fs::write(source_file, original_source);
-    // Restore pycache validity
-    clear_pycache(source_file);
+    /// Whether output arrived since the last call.
+    fn grew(&mut self) -> bool {
+        let len = self.buf.lock().map(|b| b.len()).unwrap_or(self.seen);
+        let grew = len > self.seen;
+        self.seen = len;
+        grew
+    }
 
-    results
+    /// Wait for the pipe to reach EOF.
+    pub(crate) fn finish(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> String {
+        self.buf
+            .lock()
+            .map(|b| String::from_utf8_lossy(&b).to_string())
+            .unwrap_or_default()
+    }
 }
 
 /// Public wrapper for signal handler access.
@@ -255,151 +851,202 @@ pub fn prepare_isolated(
     abs_test: &Path,
     test_cmd: &str,
     session_id: &str,
+) -> Result<IsolatedContext, String> {
+    prepare_isolated_with(abs_source, abs_test, test_cmd, session_id, CopyMode::Copy)
+}
+
+/// `prepare_isolated` placing files per `copy_mode`.
+pub fn prepare_isolated_with(
+    abs_source: &Path,
+    abs_test: &Path,
+    test_cmd: &str,
+    session_id: &str,
+    copy_mode: CopyMode,
 ) -> Result<IsolatedContext, String> {
     let project_root = copy_tree::find_project_root(abs_source);
     let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
 
     let temp_dir = tempfile::Builder::new()
-        .prefix(&format!("mutator-{}-", session_id))
+        .prefix(&format!("mutator-{}-", state::file_safe(session_id)))
         .tempdir()
         .map_err(|e| format!("Failed to create temp directory: {}", e))?;
 
-    let copy_result = copy_tree::copy_tree(
+    let start = Instant::now();
+    let copy_result = copy_tree::copy_tree_with(
         &project_root,
         abs_source,
         abs_test,
         temp_dir.path(),
+        copy_mode,
     )
     .map_err(|e| format!("Failed to copy project tree: {}", e))?;
+    log::info!(
+        "Copied {} to {} ({:?}) in {}ms",
+        project_root.display(),
+        copy_result.root.display(),
+        copy_mode,
+        start.elapsed().as_millis()
+    );
+    link_node_modules(&project_root, &[abs_source, abs_test], &copy_result.root)
+        .map_err(|e| format!("Failed to link node_modules: {}", e))?;
 
     // Resolve test command: if it's a relative path that exists in the original CWD,
     // use the absolute path so it works from the copied tree.
     let resolved_cmd = resolve_cmd(test_cmd, &copy_result.root, &cwd);
 
     Ok(IsolatedContext {
+        working_dir: copy_result.root.clone(),
         copy_result,
         resolved_cmd,
         _temp_dir: temp_dir,
     })
 }
 
+/// Link the original `node_modules` directories into the copy, which skips
+/// them, so JS runners resolve packages without reinstalling. Covers every
+/// `node_modules` beside the source or test file or in a directory between
+/// them and `project_root`. If `project_root` has none, the nearest one
+/// above it (a hoisted workspace install next to the workspace
+/// `package.json`) is linked at the copy's root. Returns the number of links
+/// created.
+pub fn link_node_modules(project_root: &Path, files: &[&Path], copy_root: &Path) -> std::io::Result<usize> {
+    let mut dirs: Vec<&Path> = Vec::new();
+    for file in files {
+        for dir in file.ancestors().skip(1) {
+            if !dir.starts_with(project_root) {
+                break;
+            }
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+
+    let mut links = Vec::new();
+    for dir in dirs {
+        let original = dir.join("node_modules");
+        if original.is_dir() {
+            let rel = dir.strip_prefix(project_root).unwrap_or(Path::new(""));
+            links.push((original, copy_root.join(rel).join("node_modules")));
+        }
+    }
+    if !project_root.join("node_modules").is_dir() {
+        let hoisted = project_root
+            .ancestors()
+            .skip(1)
+            .find(|d| d.join("node_modules").is_dir() && d.join("package.json").is_file());
+        if let Some(dir) = hoisted {
+            links.push((dir.join("node_modules"), copy_root.join("node_modules")));
+        }
+    }
+
+    let mut created = 0;
+    for (original, link) in &links {
+        if link.symlink_metadata().is_ok() {
+            continue;
+        }
+        if let Some(parent) = link.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        symlink_dir(original, link)?;
+        created += 1;
+    }
+    Ok(created)
+}
+
+#[cfg(unix)]
+fn symlink_dir(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+/// Directory symlinks need Developer Mode or admin rights on Windows.
+#[cfg(windows)]
+fn symlink_dir(original: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(original, link)
+}
+
 /// Run mutations in an isolated copy. Original source is never touched.
+/// Fails if the copied source stops matching `original_source`, the text
+/// the mutations were discovered in.
 pub fn run_mutations_isolated(
     ctx: &IsolatedContext,
     original_source: &str,
     mutations: &[Mutation],
     timeout_ms: u64,
     extra_args: &[&str],
-) -> Vec<MutantResult> {
+) -> Result<Vec<MutantResult>, String> {
     let source_file = &ctx.copy_result.source_file;
-    let test_file = &ctx.copy_result.test_file;
-    let working_dir = &ctx.copy_result.root;
-    let test_cmd = &ctx.resolved_cmd;
+    let invocation = ctx.invocation(extra_args);
+    let expected_hash = source_hash(original_source);
 
     let mut results = Vec::with_capacity(mutations.len());
 
     for mutation in mutations {
-        let mutated = apply_mutation(original_source, mutation);
-        let diff = generate_diff(original_source, &mutated);
-
-        if std::fs::write(source_file, &mutated).is_err() {
-            results.push(MutantResult {
-                mutation: mutation.clone(),
-                status: MutantStatus::Unviable,
-                duration_ms: 0,
-                diff,
-            });
-            continue;
-        }
-
-        let start = Instant::now();
-        let timeout = std::time::Duration::from_millis(timeout_ms);
-
-        clear_pycache(source_file);
-
-        let (program, first_args) = parse_test_cmd(test_cmd);
-        let mut cmd = Command::new(&program);
-        for arg in &first_args {
-            cmd.arg(arg);
-        }
-        if !test_cmd.contains("cargo") {
-            cmd.arg(test_file);
-        }
-        for arg in extra_args {
-            cmd.arg(arg);
-        }
-        let child = cmd
-            .current_dir(working_dir)
-            .env("OBJC_DISABLE_INITIALIZE_FORK_SAFETY", "YES")
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .spawn();
-
-        let status = match child {
-            Ok(mut child) => {
-                loop {
-                    match child.try_wait() {
-                        Ok(Some(exit_status)) => {
-                            let stderr = child
-                                .stderr
-                                .take()
-                                .and_then(|mut s| {
-                                    let mut buf = String::new();
-                                    std::io::Read::read_to_string(&mut s, &mut buf).ok()?;
-                                    Some(buf)
-                                })
-                                .unwrap_or_default();
-
-                            if exit_status.success() {
-                                break MutantStatus::Survived;
-                            } else if stderr.contains("SyntaxError")
-                                || stderr.contains("IndentationError")
-                                || stderr.contains("ImportError")
-                                || stderr.contains("ModuleNotFoundError")
-                            {
-                                break MutantStatus::Unviable;
-                            } else {
-                                break MutantStatus::Killed;
-                            }
-                        }
-                        Ok(None) => {
-                            if start.elapsed() > timeout {
-                                let _ = child.kill();
-                                let _ = child.wait();
-                                break MutantStatus::Timeout;
-                            }
-                            std::thread::sleep(std::time::Duration::from_millis(10));
-                        }
-                        Err(_) => break MutantStatus::Unviable,
-                    }
-                }
-            }
-            Err(_) => MutantStatus::Unviable,
-        };
-
-        let duration_ms = start.elapsed().as_millis() as u64;
-
-        results.push(MutantResult {
-            mutation: mutation.clone(),
-            status,
-            duration_ms,
-            diff,
-        });
+        verify_source_hash(source_file, expected_hash)?;
+        results.push(run_mutant(&invocation, source_file, original_source, mutation, timeout_ms));
 
         // Restore original in the copy for the next mutation
         let _ = std::fs::write(source_file, original_source);
         clear_pycache(source_file);
     }
 
-    results
+    Ok(results)
+}
+
+/// Hash of a source file's content, recorded at discovery so a run can
+/// tell when the file changed underneath it.
+pub fn source_hash(source: &str) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Check that `path` still hashes to `expected`, i.e. nobody edited it
+/// since discovery.
+pub fn verify_source_hash(path: &Path, expected: u64) -> Result<(), String> {
+    let current = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if source_hash(&current) != expected {
+        return Err(format!(
+            "Source changed mid-run: {} was modified after mutations were discovered. \
+             Re-run once the edits are finished.",
+            path.display()
+        ));
+    }
+    Ok(())
 }
 
-pub fn apply_mutation(source: &str, mutation: &Mutation) -> String {
+/// Splice `mutation.replacement` into `source`.
+///
+/// Returns `None` when the mutation's byte range doesn't fit `source`: out
+/// of bounds, not on a UTF-8 character boundary, or covering text other
+/// than `mutation.original` (the file changed after discovery).
+pub fn apply_mutation(source: &str, mutation: &Mutation) -> Option<String> {
+    let span = source.get(mutation.start_byte..mutation.end_byte)?;
+    if span != mutation.original {
+        return None;
+    }
     let mut result = String::with_capacity(source.len());
     result.push_str(&source[..mutation.start_byte]);
     result.push_str(&mutation.replacement);
     result.push_str(&source[mutation.end_byte..]);
-    result
+    Some(result)
+}
+
+/// Result for a mutation whose offsets no longer match the source, so it
+/// can't be applied.
+pub fn stale_result(mutation: &Mutation) -> MutantResult {
+    MutantResult {
+        mutation: mutation.clone(),
+        status: MutantStatus::Unviable,
+        duration_ms: 0,
+        diff: String::new(),
+        output_snippet: Some(format!(
+            "Stale mutation: bytes {}..{} no longer contain {:?}",
+            mutation.start_byte, mutation.end_byte, mutation.original
+        )),
+        killed_by: None,
+    }
 }
 
 pub fn generate_diff(original: &str, mutated: &str) -> String {
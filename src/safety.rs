@@ -10,6 +10,13 @@ pub fn backup_path(source_file: &Path) -> PathBuf {
     backup
 }
 
+/// Copy the source to its backup path before mutating it in place.
+pub fn write_backup(source_file: &Path) -> std::io::Result<PathBuf> {
+    let bak = backup_path(source_file);
+    std::fs::copy(source_file, &bak)?;
+    Ok(bak)
+}
+
 /// Check if a backup file exists from a previous interrupted in-place run.
 pub fn check_interrupted_run(source_file: &Path) -> Option<PathBuf> {
     let bak = backup_path(source_file);
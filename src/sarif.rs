@@ -0,0 +1,91 @@
+//! SARIF 2.1.0 output (`--format sarif`).
+//!
+//! Each survived mutant becomes a `warning` result and each uncovered mutant
+//! a `note`, with the operator as `ruleId` and the mutated span as region.
+
+use serde_json::{Value, json};
+
+use crate::state::{RunResult, SurvivedMutant};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+pub fn to_sarif(result: &RunResult) -> Value {
+    let survived = result
+        .survived_mutants
+        .iter()
+        .map(|m| sarif_result(m, "warning", "No test failed when this code was changed"));
+    let uncovered = result
+        .uncovered_mutants
+        .iter()
+        .map(|m| sarif_result(m, "note", "No test executes this line"));
+    let results: Vec<Value> = survived.chain(uncovered).collect();
+
+    let mut operators: Vec<&str> = result
+        .survived_mutants
+        .iter()
+        .chain(&result.uncovered_mutants)
+        .map(|m| m.operator.as_str())
+        .collect();
+    operators.sort_unstable();
+    operators.dedup();
+    let rules: Vec<Value> = operators
+        .iter()
+        .map(|op| {
+            json!({
+                "id": op,
+                "shortDescription": { "text": format!("Mutation operator `{}`", op) },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "agent-mutator",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+fn sarif_result(m: &SurvivedMutant, level: &str, reason: &str) -> Value {
+    let (end_line, end_column) = end_position(m);
+    json!({
+        "ruleId": m.operator,
+        "level": level,
+        "message": {
+            "text": format!(
+                "Mutant @{}: `{}` -> `{}` ({}). {}.",
+                m.ref_id, m.original, m.replacement, m.operator, reason
+            )
+        },
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": { "uri": m.file.replace('\\', "/") },
+                "region": {
+                    "startLine": m.line,
+                    "startColumn": m.column,
+                    "endLine": end_line,
+                    "endColumn": end_column,
+                }
+            }
+        }],
+        "partialFingerprints": {
+            "mutantRef": m.ref_id,
+        },
+    })
+}
+
+/// End of the mutated span (1-based, exclusive column), from the original text.
+fn end_position(m: &SurvivedMutant) -> (usize, usize) {
+    match m.original.rsplit_once('\n') {
+        Some((head, last)) => (m.line + head.matches('\n').count() + 1, last.chars().count() + 1),
+        None => (m.line, m.column + m.original.chars().count()),
+    }
+}
@@ -0,0 +1,38 @@
+//! Versioned JSON output for `run`, `status` and `show`.
+//!
+//! `--json` output (and the daemon's `run`/`show`/`status` results) is the
+//! serde model in `state` with a top-level `schema_version`:
+//!
+//! - `run`, `status`: a `RunResult` object.
+//! - `show`: a `SurvivedMutant` object.
+//! - `show --line`: a `MutantsNear` object (`file`, `line`, `near`, `mutants`).
+//!
+//! Within a version, fields are only ever added; consumers should ignore
+//! fields they don't know. Removing or renaming a field, or changing its
+//! type or meaning, bumps `SCHEMA_VERSION`. Fields documented as optional
+//! are omitted rather than `null` when empty. The compatibility tests in
+//! `tests/test_schema.rs` pin the version 1 field set.
+
+use serde::Serialize;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// `data`'s fields with `schema_version` alongside them.
+#[derive(Serialize)]
+pub struct Versioned<'a, T: Serialize> {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub data: &'a T,
+}
+
+pub fn versioned<T: Serialize>(data: &T) -> Versioned<'_, T> {
+    Versioned { schema_version: SCHEMA_VERSION, data }
+}
+
+pub fn to_json<T: Serialize>(data: &T) -> String {
+    serde_json::to_string(&versioned(data)).unwrap_or_default()
+}
+
+pub fn to_value<T: Serialize>(data: &T) -> serde_json::Value {
+    serde_json::to_value(versioned(data)).unwrap_or_default()
+}
@@ -0,0 +1,167 @@
+//! Active-session tracking for concurrent agents.
+//!
+//! Every run holds a lockfile under `<project>/.mutator/sessions/` naming
+//! its pid and isolated temp tree. Starting a run first removes the locks
+//! of processes that are gone, together with their temp trees (a killed
+//! agent never cleans up after itself), then checks `--max-sessions`
+//! against the locks that remain.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::state;
+
+/// Contents of a session lockfile.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub session: String,
+    pub pid: u32,
+    /// Isolated copy of the project, once the baseline has created it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_dir: Option<PathBuf>,
+}
+
+/// A live session's lockfile; removed on drop.
+#[derive(Debug)]
+pub struct SessionLock {
+    path: PathBuf,
+    info: SessionInfo,
+}
+
+impl SessionLock {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn info(&self) -> &SessionInfo {
+        &self.info
+    }
+
+    /// Record the session's temp tree so it can be collected if this
+    /// process dies without removing it.
+    pub fn set_temp_dir(&mut self, dir: &Path) -> std::io::Result<()> {
+        self.info.temp_dir = Some(dir.to_path_buf());
+        write_info(&self.path, &self.info)
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Where lockfiles for the project at `project_root` live.
+pub fn sessions_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".mutator").join("sessions")
+}
+
+/// Collect dead sessions, check `max_sessions` against the live ones, and
+/// take a lock for `session`.
+pub fn acquire(dir: &Path, session: &str, max_sessions: Option<usize>) -> Result<SessionLock, String> {
+    collect_garbage(dir);
+    if let Some(max) = max_sessions {
+        let live = live_sessions(dir);
+        if live.len() >= max {
+            let names: Vec<_> = live.iter().map(|s| format!("{} (pid {})", s.session, s.pid)).collect();
+            return Err(format!(
+                "{} sessions already running in this project (--max-sessions {}): {}. \
+                 Wait for one to finish or raise the limit.",
+                live.len(),
+                max,
+                names.join(", ")
+            ));
+        }
+    }
+
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let info = SessionInfo { session: session.to_string(), pid: std::process::id(), temp_dir: None };
+    // The same session name can run twice (e.g. two engines in one daemon),
+    // so the file name gets a random suffix
+    let path = loop {
+        let path = dir.join(format!("{}-{:08x}.lock", state::file_safe(session), fastrand::u32(..)));
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(_) => break path,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(format!("Failed to create {}: {}", path.display(), e)),
+        }
+    };
+    write_info(&path, &info).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(SessionLock { path, info })
+}
+
+/// Sessions whose process is still running.
+pub fn live_sessions(dir: &Path) -> Vec<SessionInfo> {
+    read_locks(dir)
+        .into_iter()
+        .filter(|(_, info)| process_alive(info.pid))
+        .map(|(_, info)| info)
+        .collect()
+}
+
+/// Remove the lockfiles of sessions whose process is gone, and their temp
+/// trees. Returns the sessions removed.
+pub fn collect_garbage(dir: &Path) -> Vec<SessionInfo> {
+    let mut removed = Vec::new();
+    for (path, info) in read_locks(dir) {
+        if process_alive(info.pid) {
+            continue;
+        }
+        if let Some(temp_dir) = &info.temp_dir {
+            // Only ever delete what `runner::prepare_isolated` created
+            let ours = temp_dir
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("mutator-"));
+            if ours {
+                let _ = std::fs::remove_dir_all(temp_dir);
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+        removed.push(info);
+    }
+    removed
+}
+
+fn read_locks(dir: &Path) -> Vec<(PathBuf, SessionInfo)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut locks: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "lock"))
+        .filter_map(|p| {
+            let info = serde_json::from_str(&std::fs::read_to_string(&p).ok()?).ok()?;
+            Some((p, info))
+        })
+        .collect();
+    locks.sort_by(|a, b| a.0.cmp(&b.0));
+    locks
+}
+
+fn write_info(path: &Path, info: &SessionInfo) -> std::io::Result<()> {
+    let json = serde_json::to_string(info).map_err(std::io::Error::other)?;
+    std::fs::write(path, json)
+}
+
+/// Whether `pid` is a running process. Without a way to check, every
+/// session counts as alive so nothing is collected by mistake.
+pub fn process_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        let Ok(pid @ 1..) = i32::try_from(pid) else {
+            return false;
+        };
+        // SAFETY: signal 0 only checks that the process exists
+        if unsafe { libc::kill(pid, 0) } == 0 {
+            return true;
+        }
+        std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = pid;
+        true
+    }
+}
@@ -0,0 +1,143 @@
+//! Ctrl-C / SIGTERM handling.
+//!
+//! The CLI registers what a run has touched (in-place source, isolated temp
+//! tree, results so far). On interrupt the handler restores the backup,
+//! removes the temp tree, saves a partial `RunResult` marked `interrupted`,
+//! and exits with 130. Mutant test processes run in their own process group
+//! (see `runner::run_mutant`), so they don't see the terminal's Ctrl-C; the
+//! handler kills the running one's group itself.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::mutants::MutantResult;
+use crate::state::RunResult;
+use crate::{engine, safety, state};
+
+struct Tracked {
+    display_file: PathBuf,
+    session: Option<String>,
+    in_place_source: Option<PathBuf>,
+    temp_dir: Option<PathBuf>,
+    /// Finished results with their index in discovery order.
+    results: Vec<(usize, MutantResult)>,
+}
+
+static TRACKED: Mutex<Option<Tracked>> = Mutex::new(None);
+
+/// Pid (and process group id) of the running mutant's test process; 0 when
+/// none is running.
+static CHILD: AtomicU32 = AtomicU32::new(0);
+
+fn tracked() -> MutexGuard<'static, Option<Tracked>> {
+    TRACKED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Install the process-wide SIGINT/SIGTERM handler.
+pub fn install() -> Result<(), String> {
+    ctrlc::set_handler(|| interrupt())
+        .map_err(|e| format!("Failed to install signal handler: {}", e))
+}
+
+/// Clean up the tracked run, save its partial results and exit with 130.
+/// Used by the signal handler, and by the TUI where raw mode turns Ctrl-C
+/// into a key press instead of a signal.
+pub fn interrupt() -> ! {
+    #[cfg(unix)]
+    kill_group(CHILD.swap(0, Ordering::SeqCst));
+    // Hold the lock until exit so the run loop blocks in `record` and
+    // can't write another mutant over the restored source.
+    let mut guard = tracked();
+    let session = guard.as_ref().and_then(|t| t.session.clone());
+    if let Some(result) = cleanup_locked(&mut guard) {
+        state::save_last_run(&result, session.as_deref());
+        eprintln!(
+            "Interrupted after {} mutants. Partial results saved.",
+            result.total
+        );
+    }
+    std::process::exit(130);
+}
+
+/// The mutant test process now running, leader of its own process group.
+pub fn track_child(pid: u32) {
+    CHILD.store(pid, Ordering::SeqCst);
+}
+
+pub fn untrack_child() {
+    CHILD.store(0, Ordering::SeqCst);
+}
+
+/// SIGKILL every process in the group led by `pgid`. No-op for 0.
+#[cfg(unix)]
+pub fn kill_group(pgid: u32) {
+    if let Ok(pgid @ 1..) = i32::try_from(pgid) {
+        // SAFETY: kill(2) with a negative pid only signals that group
+        unsafe { libc::kill(-pgid, libc::SIGKILL) };
+    }
+}
+
+/// Start tracking a run. Replaces any previously tracked run.
+pub fn track_run(display_file: &Path, session: Option<&str>) {
+    *tracked() = Some(Tracked {
+        display_file: display_file.to_path_buf(),
+        session: session.map(String::from),
+        in_place_source: None,
+        temp_dir: None,
+        results: Vec::new(),
+    });
+}
+
+/// Source mutated in place; restored from its `.mutator.bak` on interrupt.
+pub fn track_in_place(source_file: &Path) {
+    if let Some(t) = tracked().as_mut() {
+        t.in_place_source = Some(source_file.to_path_buf());
+    }
+}
+
+/// Isolated temp tree; removed on interrupt.
+pub fn track_temp_dir(dir: &Path) {
+    if let Some(t) = tracked().as_mut() {
+        t.temp_dir = Some(dir.to_path_buf());
+    }
+}
+
+/// A finished mutant; `index` is its position among the run's mutations.
+pub fn record(index: usize, result: &MutantResult) {
+    if let Some(t) = tracked().as_mut() {
+        t.results.push((index, result.clone()));
+    }
+}
+
+/// Stop tracking after the run finished normally.
+pub fn untrack() {
+    *tracked() = None;
+}
+
+/// Undo whatever the tracked run left behind and return the partial result.
+/// Returns `None` if no run is tracked.
+pub fn cleanup() -> Option<RunResult> {
+    cleanup_locked(&mut tracked())
+}
+
+fn cleanup_locked(guard: &mut Option<Tracked>) -> Option<RunResult> {
+    let t = guard.take()?;
+
+    if let Some(ref source) = t.in_place_source {
+        if let Some(bak) = safety::check_interrupted_run(source) {
+            let _ = safety::restore_from_backup(source, &bak);
+        }
+    }
+    if let Some(ref dir) = t.temp_dir {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    // Refs follow discovery order, as in a finished run, not run order
+    let mut results = t.results;
+    results.sort_by_key(|(index, _)| *index);
+    let results: Vec<MutantResult> = results.into_iter().map(|(_, r)| r).collect();
+    let mut result = engine::summarize(&results, &t.display_file);
+    result.interrupted = true;
+    Some(result)
+}
@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::mutants::MutantStatus;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct RunResult {
     pub score: f64,
     pub total: usize,
@@ -9,52 +12,227 @@ pub struct RunResult {
     pub survived: usize,
     pub timeout: usize,
     pub unviable: usize,
+    /// Mutants on lines no test executes (only with `--coverage`).
+    #[serde(default)]
+    pub uncovered: usize,
+    /// Survivors the operator swap can't affect; excluded from the score.
+    #[serde(default)]
+    pub equivalent: usize,
+    /// Runs stopped by `--max-memory-mb`/`--cpu-limit`; scored like timeouts.
+    #[serde(default)]
+    pub resource_exceeded: usize,
     pub duration_ms: u64,
     pub survived_mutants: Vec<SurvivedMutant>,
+    /// Timed-out mutants, addressable as `@t1`, `@t2`, ... in `show`.
+    #[serde(default)]
+    pub timeout_mutants: Vec<SurvivedMutant>,
+    /// Uncovered mutants, addressable as `@u1`, `@u2`, ... in `show`.
+    #[serde(default)]
+    pub uncovered_mutants: Vec<SurvivedMutant>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equivalent_mutants: Vec<SurvivedMutant>,
+    /// Resource-limited mutants, addressable as `@r1`, `@r2`, ... in `show`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_exceeded_mutants: Vec<SurvivedMutant>,
+    /// Set when the run was cut short by Ctrl-C/SIGTERM; counts are partial.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub interrupted: bool,
+    /// Number of mutants each test killed, keyed by the runner's test name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub kills_by_test: BTreeMap<String, usize>,
+    /// Per-function breakdown, keyed by enclosing function name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub functions: BTreeMap<String, FunctionScore>,
+    /// Per-operator breakdown, keyed by operator name; `total` is the number
+    /// of mutants the operator generated.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub operators: BTreeMap<String, OperatorScore>,
+    /// Every mutant's outcome in discovery order (only with `--full-results`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub all_mutants: Vec<MutantOutcome>,
+}
+
+/// One mutant's outcome, whatever its status.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutantOutcome {
+    /// Same ref as in the per-status lists; killed and unviable mutants
+    /// have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ref_id: Option<String>,
+    /// Source file as given to `run`; empty in state from older versions.
+    #[serde(default)]
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub operator: String,
+    pub original: String,
+    pub replacement: String,
+    pub status: MutantStatus,
+    pub duration_ms: u64,
+    /// First failing test, for killed mutants.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub killed_by: Option<String>,
+}
+
+/// `show --line`: every recorded mutant within `near` lines of `line`.
+#[derive(Debug, Serialize)]
+pub struct MutantsNear<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<&'a str>,
+    pub line: usize,
+    pub near: usize,
+    pub mutants: Vec<&'a MutantOutcome>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FunctionScore {
+    pub score: f64,
+    pub total: usize,
+    pub killed: usize,
+    pub survived: usize,
+    pub timeout: usize,
+    pub unviable: usize,
+    pub uncovered: usize,
+    #[serde(default)]
+    pub equivalent: usize,
+    #[serde(default)]
+    pub resource_exceeded: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Same counts as `FunctionScore`, grouped by operator instead.
+pub type OperatorScore = FunctionScore;
+
+impl FunctionScore {
+    pub fn testable(&self) -> usize {
+        self.total - self.unviable - self.equivalent
+    }
+}
+
+impl RunResult {
+    /// Mutants the score is computed over: all but unviable and suspected
+    /// equivalent ones.
+    pub fn testable(&self) -> usize {
+        self.total - self.unviable - self.equivalent
+    }
+
+    /// Every mutant with a ref (survived, timed out, uncovered, suspected
+    /// equivalent, resource exceeded), in ref order within each kind.
+    pub fn referenced(&self) -> impl Iterator<Item = &SurvivedMutant> {
+        self.survived_mutants
+            .iter()
+            .chain(&self.timeout_mutants)
+            .chain(&self.uncovered_mutants)
+            .chain(&self.equivalent_mutants)
+            .chain(&self.resource_exceeded_mutants)
+    }
+
+    /// Look up `@m1`, `m1`, `@t1`, `@r1`, ... among `referenced`.
+    pub fn find_ref(&self, mutant_ref: &str) -> Option<&SurvivedMutant> {
+        let ref_id = mutant_ref.trim_start_matches('@');
+        self.referenced().find(|m| m.ref_id == ref_id)
+    }
+
+    /// Mutants in `all_mutants` (any status) within `near` lines of `line`,
+    /// in source order. `file` matches a recorded file when either path ends
+    /// with the other, so `app.py` finds `src/app.py`.
+    pub fn mutants_near<'a>(&'a self, file: Option<&'a str>, line: usize, near: usize) -> MutantsNear<'a> {
+        let mut mutants: Vec<_> = self
+            .all_mutants
+            .iter()
+            .filter(|m| m.line.abs_diff(line) <= near)
+            .filter(|m| file.is_none_or(|f| same_file(&m.file, f)))
+            .collect();
+        mutants.sort_by_key(|m| (m.line, m.column));
+        MutantsNear { file, line, near, mutants }
+    }
+}
+
+fn same_file(recorded: &str, query: &str) -> bool {
+    let (recorded, query) = (Path::new(recorded), Path::new(query));
+    recorded.as_os_str().is_empty() || recorded.ends_with(query) || query.ends_with(recorded)
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SurvivedMutant {
     pub ref_id: String,
     pub file: String,
     pub line: usize,
     pub column: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
     pub operator: String,
     pub original: String,
     pub replacement: String,
     pub diff: String,
     pub context_before: Vec<String>,
     pub context_after: Vec<String>,
+    /// Tail of the test run's stdout/stderr (partial if the run was killed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_snippet: Option<String>,
+}
+
+/// Where the last run is stored. Runs without `--session` use
+/// `.mutator-state.json` in the CWD; named sessions get their own file under
+/// `.mutator/` so concurrent agents don't overwrite each other.
+pub fn state_path(session: Option<&str>) -> PathBuf {
+    state_path_in(&dirs_or_cwd(), session)
 }
 
-fn state_path() -> PathBuf {
-    let dir = dirs_or_cwd();
-    dir.join(".mutator-state.json")
+pub fn state_path_in(dir: &Path, session: Option<&str>) -> PathBuf {
+    match session {
+        Some(id) => dir.join(".mutator").join(format!("state-{}.json", file_safe(id))),
+        None => dir.join(".mutator-state.json"),
+    }
+}
+
+/// Session IDs come from agents verbatim; keep them to one path component.
+/// An ID that needs rewriting gets a hash of the original appended, so `a/b`
+/// and `a_b` (or `.` and the empty ID) don't end up sharing a file.
+pub(crate) fn file_safe(id: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let safe = id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string();
+    if !safe.is_empty() && safe == id {
+        return safe;
+    }
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    id.hash(&mut hasher);
+    format!("{}-{:08x}", safe, hasher.finish() as u32)
 }
 
 fn dirs_or_cwd() -> PathBuf {
     std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
 }
 
-pub fn save_last_run(result: &RunResult) {
-    if let Ok(json) = serde_json::to_string(result) {
-        let _ = std::fs::write(state_path(), json);
+pub fn save_last_run(result: &RunResult, session: Option<&str>) {
+    let path = state_path(session);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
     }
+    save_to_path(result, &path);
 }
 
-pub fn load_last_run() -> Option<RunResult> {
-    let path = state_path();
-    let data = std::fs::read_to_string(path).ok()?;
-    serde_json::from_str(&data).ok()
+pub fn load_last_run(session: Option<&str>) -> Option<RunResult> {
+    load_from_path(&state_path(session))
 }
 
-pub fn save_to_path(result: &RunResult, path: &std::path::Path) {
+pub fn save_to_path(result: &RunResult, path: &Path) {
     if let Ok(json) = serde_json::to_string(result) {
         let _ = std::fs::write(path, json);
     }
 }
 
-pub fn load_from_path(path: &std::path::Path) -> Option<RunResult> {
+pub fn load_from_path(path: &Path) -> Option<RunResult> {
     let data = std::fs::read_to_string(path).ok()?;
     serde_json::from_str(&data).ok()
 }
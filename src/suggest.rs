@@ -0,0 +1,203 @@
+//! `mutator diff-tests`: a test suggestion per survivor.
+//!
+//! Heuristic, from what the last run recorded: the mutated line (taken from
+//! the diff), the operator and its operands. Comparison mutants get concrete
+//! inputs at the boundary, e.g. `x > 0` -> `x >= 0` suggests `x = 0`, where
+//! the two disagree. Everything else gets the operator's assertion hint.
+//! Each suggestion is rendered as a test skeleton in the file's language.
+
+use serde::Serialize;
+
+use crate::markdown::assertion_hint;
+use crate::state::SurvivedMutant;
+use crate::{detect_language, Language};
+
+#[derive(Debug, Serialize)]
+pub struct Suggestion {
+    pub ref_id: String,
+    pub file: String,
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    pub operator: String,
+    pub original: String,
+    pub replacement: String,
+    pub hint: String,
+    /// Inputs where original and mutant behave differently, if known.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inputs: Vec<String>,
+    pub skeleton: String,
+}
+
+pub fn suggest(m: &SurvivedMutant) -> Suggestion {
+    let line = mutated_line(&m.diff);
+    let comparison = line.as_deref().and_then(|line| operands(line, m.column, &m.original));
+    let inputs = match (&comparison, m.operator.as_str()) {
+        (_, "len_boundary") => length_inputs(line.as_deref().and_then(|line| length_bound(line, m.column, &m.original))),
+        (Some((left, right)), "boundary" | "negate_cmp" | "negate_eq") => {
+            boundary_inputs(left, &m.original, &m.replacement, right)
+        }
+        (_, "arith" | "aug_assign") => vec![format!(
+            "operands where `{}` and `{}` give different results (avoid 0 and 1)",
+            m.original.trim(),
+            m.replacement.trim()
+        )],
+        (_, "logic_flip") => vec![format!("exactly one side of `{}` true", m.original.trim())],
+        _ => Vec::new(),
+    };
+    let hint = assertion_hint(&m.operator).to_string();
+    let skeleton = skeleton(m, &hint, &inputs);
+    Suggestion {
+        ref_id: m.ref_id.clone(),
+        file: m.file.clone(),
+        line: m.line,
+        function: m.function.clone(),
+        operator: m.operator.clone(),
+        original: m.original.clone(),
+        replacement: m.replacement.clone(),
+        hint,
+        inputs,
+        skeleton,
+    }
+}
+
+/// The first original line in a `runner::generate_diff` diff.
+fn mutated_line(diff: &str) -> Option<String> {
+    diff.lines()
+        .find_map(|l| l.strip_prefix("- "))
+        .map(|l| l.trim_end().to_string())
+}
+
+/// Where `op` starts in `line`: at 1-based `column`, or its first match.
+fn op_start(line: &str, column: usize, op: &str) -> Option<usize> {
+    match line.get(column.checked_sub(1)?..) {
+        Some(rest) if rest.starts_with(op) => Some(column - 1),
+        _ => line.find(op),
+    }
+}
+
+/// The integer a length is compared with: the literal right after `op`,
+/// or right before it (`0 == len(xs)`).
+fn length_bound(line: &str, column: usize, op: &str) -> Option<usize> {
+    let start = op_start(line, column, op)?;
+    let after = line[start + op.len()..].trim_start();
+    let digits = &after[..after.find(|c: char| !c.is_ascii_digit()).unwrap_or(after.len())];
+    if let Ok(bound) = digits.parse() {
+        return Some(bound);
+    }
+    let before = line[..start].trim_end();
+    before[before.rfind(|c: char| !c.is_ascii_digit()).map_or(0, |i| i + 1)..].parse().ok()
+}
+
+/// A length comparison and its `len_boundary` mutant disagree at exactly
+/// the bound or one past it.
+fn length_inputs(bound: Option<usize>) -> Vec<String> {
+    match bound {
+        Some(0) => vec!["an empty collection".to_string(), "a collection of 1 item".to_string()],
+        Some(n) => vec![format!("a collection of exactly {} items", n), format!("a collection of {} items", n + 1)],
+        None => vec!["a collection whose length equals the compared value, and one item longer".to_string()],
+    }
+}
+
+/// Left and right operands around `op` at 1-based `column`: the identifier,
+/// attribute path or literal directly on each side.
+fn operands(line: &str, column: usize, op: &str) -> Option<(String, String)> {
+    let start = op_start(line, column, op)?;
+    let is_operand = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+    let before = line[..start].trim_end();
+    let left_start = before.rfind(|c: char| !is_operand(c)).map_or(0, |i| i + 1);
+    let left = &before[left_start..];
+    let after = line[start + op.len()..].trim_start();
+    let sign = usize::from(after.starts_with('-'));
+    let right_len = after[sign..].find(|c: char| !is_operand(c)).unwrap_or(after.len() - sign);
+    let right = &after[..sign + right_len];
+    if left.is_empty() || right.is_empty() || right == "-" {
+        return None;
+    }
+    Some((left.to_string(), right.to_string()))
+}
+
+fn boundary_inputs(left: &str, op: &str, replacement: &str, right: &str) -> Vec<String> {
+    let original = format!("{} {} {}", left, op, right);
+    let mutant = format!("{} {} {}", left, replacement, right);
+    let at = format!("{} = {} (`{}` vs `{}`)", left, right, original, mutant);
+    let Ok(bound) = right.parse::<i64>() else {
+        return vec![
+            at,
+            format!("{} just past {} on each side", left, right),
+        ];
+    };
+    let beside = match op {
+        ">" | "<=" | "==" | "!=" | "===" | "!==" => bound + 1,
+        _ => bound - 1,
+    };
+    vec![at, format!("{} = {}", left, beside)]
+}
+
+fn skeleton(m: &SurvivedMutant, hint: &str, inputs: &[String]) -> String {
+    let function = m.function.as_deref().unwrap_or("target");
+    let name = format!("{}_{}", snake(function), m.ref_id);
+    let mut notes = vec![format!(
+        "{}:{} `{}` -> `{}` survived. {}.",
+        m.file,
+        m.line,
+        one_line(&m.original),
+        one_line(&m.replacement),
+        hint
+    )];
+    notes.extend(inputs.iter().map(|i| format!("Try: {}", i)));
+
+    match detect_language(std::path::Path::new(&m.file)) {
+        Some(Language::Rust) => format!(
+            "#[test]\nfn {}() {{\n{}    let result = {}(/* inputs */);\n    assert_eq!(result, /* expected */);\n}}\n",
+            name,
+            comments(&notes, "    // "),
+            function
+        ),
+        Some(Language::JavaScript | Language::TypeScript | Language::Tsx) => format!(
+            "test(\"{} {}\", () => {{\n{}  const result = {}(/* inputs */);\n  expect(result).toBe(/* expected */);\n}});\n",
+            function,
+            m.ref_id,
+            comments(&notes, "  // "),
+            function
+        ),
+        Some(Language::Java) => format!(
+            "@Test\nvoid {}() {{\n{}    var result = {}(/* inputs */);\n    assertEquals(/* expected */, result);\n}}\n",
+            name,
+            comments(&notes, "    // "),
+            function
+        ),
+        _ => format!(
+            "def test_{}():\n{}    result = {}(...)  # inputs\n    assert result == ...  # expected\n",
+            name,
+            comments(&notes, "    # "),
+            function
+        ),
+    }
+}
+
+fn comments(notes: &[String], prefix: &str) -> String {
+    notes.iter().map(|n| format!("{}{}\n", prefix, n)).collect()
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// `isPositive` / `Parser::parse` -> `is_positive` / `parser_parse`.
+fn snake(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else if c.is_alphanumeric() {
+            out.push(c);
+        } else if !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_matches('_').to_string()
+}
@@ -0,0 +1,987 @@
+//! Test runner adapters: how each runner is invoked, and how its output
+//! is read.
+//!
+//! A `Runner` is picked with `--runner`, or detected from the test command
+//! (`pytest`, `npx vitest run`, `cargo nextest run`, ...), then from the
+//! project (`package.json` dev dependencies for `npm test` and friends),
+//! then from the source language.
+//!
+//! Without `--test-cmd`, `detect_test_cmd` picks the command itself from
+//! the project around the source file.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::Deserialize;
+
+use crate::{copy_tree, runner, Language};
+
+/// Runner-specific command construction and output parsing.
+pub trait TestAdapter: Sync {
+    /// Arguments appended after the test file for the baseline run.
+    fn baseline_args(&self) -> &'static [&'static str];
+
+    /// Arguments appended after the test file for each mutant run. Runs
+    /// should stop at the first failure.
+    fn mutation_args(&self) -> &'static [&'static str];
+
+    /// Arguments selecting `filters` (test names) within the test file.
+    fn filter_args(&self, filters: &[String]) -> Vec<String>;
+
+    /// `name` as a filter matching that test alone. Test-map names go
+    /// through this; a `--test-filter` expression is passed as written.
+    fn test_name(&self, name: &str) -> String {
+        name.to_string()
+    }
+
+    /// Whether the test file is passed to the command. Cargo selects
+    /// targets itself and `go test` takes packages.
+    fn passes_test_file(&self) -> bool {
+        true
+    }
+
+    /// Arguments selecting the test class in `test_file` (and `filters`
+    /// within it), for runners that take a class name instead of a file.
+    /// `None` uses `filter_args`.
+    fn class_args(&self, _test_file: &Path, _filters: &[String]) -> Option<Vec<String>> {
+        None
+    }
+
+    /// Whether a failed run means the mutant never loaded (syntax, import
+    /// or compile error) rather than a test catching it.
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool;
+
+    /// Name of the first failing test in the output.
+    fn failing_test(&self, output: &str) -> Option<String> {
+        runner::failing_test(output)
+    }
+
+    /// Arguments making a mutant run also write a structured report (JSON,
+    /// JUnit XML) to `path`, for `read_report`. Empty for runners without
+    /// one.
+    fn report_args(&self, _path: &Path) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// What the report at `path` says about a failed run. `None` when there
+    /// is no report (the runner died before writing it, or was stopped
+    /// early) or it shows nothing conclusive; the output is read instead.
+    fn read_report(&self, _path: &Path) -> Option<ReportOutcome> {
+        None
+    }
+
+    /// Whether partial output (stdout or stderr) already shows a failed
+    /// test, so a mutant run can be stopped early as killed. Only test
+    /// failures count, never load errors, which must still reach
+    /// `is_unviable`.
+    fn reports_failure(&self, output: &str) -> bool;
+
+    /// Whether a run (`exit_code` and combined output) selected no tests
+    /// at all, so a passing baseline would prove nothing.
+    fn ran_no_tests(&self, exit_code: Option<i32>, output: &str) -> bool;
+}
+
+/// A failed run as the runner's own report describes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReportOutcome {
+    /// A test failed: the mutant is killed, by this test.
+    TestFailed(String),
+    /// A test file failed to load or run (syntax, import or config error)
+    /// and no test failed: the mutant is unviable.
+    LoadError,
+    /// No test ran at all, so nothing could catch the mutant: it is
+    /// uncovered, not survived.
+    NoTests,
+}
+
+/// A language's fallback runner and test command (`Language::test_defaults`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestDefaults {
+    pub runner: Runner,
+    pub test_cmd: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Runner {
+    Pytest,
+    Unittest,
+    Jest,
+    Vitest,
+    Mocha,
+    CargoTest,
+    CargoNextest,
+    GoTest,
+    Maven,
+    Gradle,
+}
+
+impl Runner {
+    pub const ALL: [Runner; 10] = [
+        Runner::Pytest,
+        Runner::Unittest,
+        Runner::Jest,
+        Runner::Vitest,
+        Runner::Mocha,
+        Runner::CargoTest,
+        Runner::CargoNextest,
+        Runner::GoTest,
+        Runner::Maven,
+        Runner::Gradle,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Runner::Pytest => "pytest",
+            Runner::Unittest => "unittest",
+            Runner::Jest => "jest",
+            Runner::Vitest => "vitest",
+            Runner::Mocha => "mocha",
+            Runner::CargoTest => "cargo-test",
+            Runner::CargoNextest => "cargo-nextest",
+            Runner::GoTest => "go-test",
+            Runner::Maven => "maven",
+            Runner::Gradle => "gradle",
+        }
+    }
+
+    pub fn adapter(self) -> &'static dyn TestAdapter {
+        match self {
+            Runner::Pytest => &Pytest,
+            Runner::Unittest => &Unittest,
+            Runner::Jest => &Jest,
+            Runner::Vitest => &Vitest,
+            Runner::Mocha => &Mocha,
+            Runner::CargoTest => &CargoTest,
+            Runner::CargoNextest => &CargoNextest,
+            Runner::GoTest => &GoTest,
+            Runner::Maven => &Maven,
+            Runner::Gradle => &Gradle,
+        }
+    }
+
+    /// Whether the runner is cargo, so builds share a target dir.
+    pub fn is_cargo(self) -> bool {
+        matches!(self, Runner::CargoTest | Runner::CargoNextest)
+    }
+
+    /// The runner `test_cmd` names, if any: `pytest`, `.venv/bin/pytest`,
+    /// `python -m unittest`, `npx jest`, `cargo nextest run`, `go test`,
+    /// `mvn test`, `./gradlew test`.
+    pub fn from_command(test_cmd: &str) -> Option<Runner> {
+        let (program, args) = runner::parse_test_cmd(test_cmd);
+        let words: Vec<&str> = std::iter::once(program.as_str())
+            .chain(args.iter().map(String::as_str))
+            .map(|w| w.rsplit('/').next().unwrap_or(w))
+            .collect();
+        let has = |name: &str| words.contains(&name);
+        if has("pytest") || has("py.test") {
+            Some(Runner::Pytest)
+        } else if has("unittest") {
+            Some(Runner::Unittest)
+        } else if has("vitest") {
+            Some(Runner::Vitest)
+        } else if has("jest") {
+            Some(Runner::Jest)
+        } else if has("mocha") {
+            Some(Runner::Mocha)
+        } else if has("cargo") && has("nextest") {
+            Some(Runner::CargoNextest)
+        } else if has("cargo") {
+            Some(Runner::CargoTest)
+        } else if words.first() == Some(&"go") && has("test") {
+            Some(Runner::GoTest)
+        } else if has("mvn") || has("mvnw") {
+            Some(Runner::Maven)
+        } else if has("gradle") || has("gradlew") {
+            Some(Runner::Gradle)
+        } else {
+            None
+        }
+    }
+
+    /// `from_command`, else the JS runner in `package.json` or the Java
+    /// build tool under `project_root`, else the language's usual runner.
+    pub fn detect(test_cmd: &str, language: &Language, project_root: &Path) -> Runner {
+        if let Some(runner) = Runner::from_command(test_cmd) {
+            return runner;
+        }
+        let from_project = match language {
+            Language::JavaScript | Language::TypeScript | Language::Tsx => from_package_json(project_root),
+            Language::Java => from_java_build(project_root),
+            Language::Python | Language::Rust => None,
+        };
+        from_project.unwrap_or(language.test_defaults().runner)
+    }
+}
+
+/// The first of vitest, jest or mocha that `package.json` depends on or
+/// calls from its `test` script.
+fn from_package_json(project_root: &Path) -> Option<Runner> {
+    let content = std::fs::read_to_string(project_root.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let script = package["scripts"]["test"].as_str().and_then(Runner::from_command);
+    if script.is_some() {
+        return script;
+    }
+    let depends_on = |name: &str| {
+        ["dependencies", "devDependencies"]
+            .iter()
+            .any(|section| package[section].get(name).is_some())
+    };
+    [("vitest", Runner::Vitest), ("jest", Runner::Jest), ("mocha", Runner::Mocha)]
+        .into_iter()
+        .find(|(name, _)| depends_on(name))
+        .map(|(_, runner)| runner)
+}
+
+/// Gradle when `project_root` has a Gradle build, Maven for a `pom.xml`.
+fn from_java_build(project_root: &Path) -> Option<Runner> {
+    if ["build.gradle", "build.gradle.kts", "settings.gradle", "settings.gradle.kts"]
+        .iter()
+        .any(|build| project_root.join(build).is_file())
+    {
+        Some(Runner::Gradle)
+    } else if project_root.join("pom.xml").is_file() {
+        Some(Runner::Maven)
+    } else {
+        None
+    }
+}
+
+/// Test command for `source_file` when `--test-cmd` is not given, from the
+/// project it lives in: a virtualenv's pytest (`.venv`, `venv`, `env`), the
+/// runner `package.json` uses, `cargo test`, or the Maven or Gradle
+/// wrapper. Falls back to `pytest`.
+/// `language` overrides the one the extension implies.
+pub fn detect_test_cmd(source_file: &Path, language: Option<Language>) -> String {
+    let source = std::path::absolute(source_file).unwrap_or_else(|_| source_file.to_path_buf());
+    let root = copy_tree::find_project_root(&source);
+    match language.or_else(|| crate::detect_language(&source)) {
+        Some(Language::Rust) => Language::Rust.test_defaults().test_cmd.to_string(),
+        Some(Language::JavaScript | Language::TypeScript | Language::Tsx) => js_test_cmd(&root),
+        Some(Language::Java) => java_test_cmd(&root),
+        _ => python_test_cmd(&root),
+    }
+}
+
+fn python_test_cmd(project_root: &Path) -> String {
+    [".venv", "venv", "env"]
+        .iter()
+        .map(|venv| project_root.join(venv).join("bin").join("pytest"))
+        .find(|pytest| pytest.is_file())
+        .and_then(|pytest| shlex::try_quote(&pytest.to_string_lossy()).ok().map(String::from))
+        .unwrap_or_else(|| Language::Python.test_defaults().test_cmd.to_string())
+}
+
+/// `npx <runner>` for the runner `package.json` names, `npm test --` for a
+/// `test` script that calls something else.
+fn js_test_cmd(project_root: &Path) -> String {
+    let runner_cmd = |runner: Runner| match runner {
+        Runner::Vitest => Some("npx vitest run"),
+        Runner::Jest => Some("npx jest"),
+        Runner::Mocha => Some("npx mocha"),
+        _ => None,
+    };
+    if let Some(cmd) = from_package_json(project_root).and_then(runner_cmd) {
+        return cmd.to_string();
+    }
+    let has_test_script = std::fs::read_to_string(project_root.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|package| package["scripts"]["test"].is_string());
+    if has_test_script {
+        "npm test --".to_string()
+    } else {
+        Language::JavaScript.test_defaults().test_cmd.to_string()
+    }
+}
+
+/// The project's `gradlew` or `mvnw` wrapper, else `gradle test` for a
+/// Gradle build and `mvn -q test` otherwise.
+fn java_test_cmd(project_root: &Path) -> String {
+    let wrapper = |name: &str, args: &str| {
+        let path = project_root.join(name);
+        path.is_file()
+            .then(|| shlex::try_quote(&path.to_string_lossy()).ok().map(|p| format!("{} {}", p, args)))
+            .flatten()
+    };
+    wrapper("gradlew", "test")
+        .or_else(|| wrapper("mvnw", "-q test"))
+        .unwrap_or_else(|| match from_java_build(project_root) {
+            Some(Runner::Gradle) => "gradle test".to_string(),
+            _ => Language::Java.test_defaults().test_cmd.to_string(),
+        })
+}
+
+impl fmt::Display for Runner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Runner {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Runner::ALL.into_iter().find(|r| r.name() == s).ok_or_else(|| {
+            let names: Vec<_> = Runner::ALL.iter().map(|r| r.name()).collect();
+            format!("unknown runner '{}' (expected {})", s, names.join(", "))
+        })
+    }
+}
+
+/// How the test file is passed to the test command (`--test-arg-style`).
+/// `Auto` leaves it to the runner; the others override it for commands the
+/// runner detection can't see through, such as wrapper scripts or
+/// `make test`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestArgStyle {
+    /// The absolute path if the runner takes files (`passes_test_file`),
+    /// nothing for cargo and `go test`.
+    #[default]
+    Auto,
+    /// Always the absolute path.
+    Path,
+    /// Always the path relative to the working directory (`../tests/...`
+    /// when the file is outside it).
+    Relative,
+    /// Never: the command selects its own tests.
+    None,
+}
+
+impl TestArgStyle {
+    pub const ALL: [TestArgStyle; 4] =
+        [TestArgStyle::Auto, TestArgStyle::Path, TestArgStyle::Relative, TestArgStyle::None];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            TestArgStyle::Auto => "auto",
+            TestArgStyle::Path => "path",
+            TestArgStyle::Relative => "relative",
+            TestArgStyle::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for TestArgStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for TestArgStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TestArgStyle::ALL.into_iter().find(|t| t.name() == s).ok_or_else(|| {
+            let names: Vec<_> = TestArgStyle::ALL.iter().map(|t| t.name()).collect();
+            format!("unknown test arg style '{}' (expected {})", s, names.join(", "))
+        })
+    }
+}
+
+/// Python failures that happen before any test runs.
+fn python_unviable(stderr: &str) -> bool {
+    ["SyntaxError", "IndentationError", "ImportError", "ModuleNotFoundError"]
+        .iter()
+        .any(|marker| stderr.contains(marker))
+}
+
+struct Pytest;
+
+impl TestAdapter for Pytest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["-x", "-q", "--tb=short", "--no-header"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["-x", "-q", "--tb=no", "--no-header", "-p", "no:cacheprovider"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        if filters.is_empty() {
+            return Vec::new();
+        }
+        vec!["-k".to_string(), filters.join(" or ")]
+    }
+
+    fn report_args(&self, path: &Path) -> Vec<String> {
+        vec![format!("--junitxml={}", path.display())]
+    }
+
+    fn read_report(&self, path: &Path) -> Option<ReportOutcome> {
+        junit_report(&std::fs::read_to_string(path).ok()?)
+    }
+
+    fn is_unviable(&self, _stdout: &str, stderr: &str) -> bool {
+        python_unviable(stderr)
+    }
+
+    /// An `F` in the progress line: `..F` (`-q`) or `test_app.py ..F`.
+    /// Errors (`E`) are left alone, since collection errors are unviable.
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| {
+            let progress = match line.split_once(".py ") {
+                Some((_, rest)) => rest,
+                None => line,
+            };
+            let progress = progress.split_whitespace().next().unwrap_or("");
+            progress.contains('F') && progress.chars().all(|c| ".sxXFE".contains(c))
+        })
+    }
+
+    /// Exit code 5: nothing collected, or everything deselected by `-k`.
+    fn ran_no_tests(&self, exit_code: Option<i32>, output: &str) -> bool {
+        exit_code == Some(5) || output.contains("no tests ran")
+    }
+}
+
+/// Read pytest's `--junitxml` report. A `<failure>` kills the mutant, and
+/// so does an `<error>` in a fixture; a collection error, or an error that
+/// is a Python load error, makes it unviable. The killing test is named
+/// `classname::name`, e.g. `tests.test_cart.TestCart::test_total`.
+fn junit_report(content: &str) -> Option<ReportOutcome> {
+    let doc = roxmltree::Document::parse(content).ok()?;
+    let cases: Vec<_> = doc.descendants().filter(|n| n.has_tag_name("testcase")).collect();
+    let first = |tag: &str| {
+        cases
+            .iter()
+            .find_map(|case| case.children().find(|c| c.has_tag_name(tag)).map(|outcome| (*case, outcome)))
+    };
+    let name = |case: roxmltree::Node| {
+        let test = case.attribute("name").unwrap_or_default();
+        match case.attribute("classname") {
+            Some(class) if !class.is_empty() => format!("{}::{}", class, test),
+            _ => test.to_string(),
+        }
+    };
+    if let Some((case, _)) = first("failure") {
+        return Some(ReportOutcome::TestFailed(name(case)));
+    }
+    if let Some((case, error)) = first("error") {
+        let message = error.attribute("message").unwrap_or_default();
+        let detail = error.text().unwrap_or_default();
+        if message.starts_with("collection failure") || python_unviable(message) || python_unviable(detail) {
+            return Some(ReportOutcome::LoadError);
+        }
+        return Some(ReportOutcome::TestFailed(name(case)));
+    }
+    let tests: u64 = doc
+        .descendants()
+        .filter(|n| n.has_tag_name("testsuite"))
+        .filter_map(|suite| suite.attribute("tests")?.parse::<u64>().ok())
+        .sum();
+    (cases.is_empty() && tests == 0).then_some(ReportOutcome::NoTests)
+}
+
+struct Unittest;
+
+impl TestAdapter for Unittest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["-f"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["-f", "-q"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        filters.iter().flat_map(|f| ["-k".to_string(), f.clone()]).collect()
+    }
+
+    fn is_unviable(&self, _stdout: &str, stderr: &str) -> bool {
+        python_unviable(stderr)
+    }
+
+    /// `FAIL: test_add (test_app.TestAdd.test_add)`
+    fn failing_test(&self, output: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            let rest = line.strip_prefix("FAIL: ").or_else(|| line.strip_prefix("ERROR: "))?;
+            let name = rest.split(" (").next().unwrap_or(rest).trim();
+            (!name.is_empty()).then(|| name.to_string())
+        })
+    }
+
+    /// `FAIL: test_add (...)`, or `test_add (...) ... FAIL` when verbose.
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| line.starts_with("FAIL: ") || line.ends_with(" ... FAIL"))
+    }
+
+    /// `Ran 0 tests`; Python 3.12+ also exits 5 with `NO TESTS RAN`.
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        output.contains("Ran 0 tests") || output.contains("NO TESTS RAN")
+    }
+}
+
+/// Parse and compile failures from node, tsc/ts-jest and esbuild (vitest).
+const JS_UNVIABLE: &[&str] = &["SyntaxError", "error TS", "TSError", "Transform failed"];
+
+fn js_unviable(stdout: &str, stderr: &str) -> bool {
+    JS_UNVIABLE
+        .iter()
+        .any(|marker| stderr.contains(marker) || stdout.contains(marker))
+}
+
+/// A failed test in jest or vitest output: `✕ adds` / `× adds`, a
+/// `● Cart › adds` failure header, or vitest's `FAIL  a.test.ts > Cart > adds`.
+/// `● Test suite failed to run` is a load error, not a test failure.
+fn js_reports_failure(output: &str) -> bool {
+    output.lines().map(str::trim).any(|line| {
+        line.starts_with('✕')
+            || line.starts_with('×')
+            || line.strip_prefix("● ").is_some_and(|rest| !rest.starts_with("Test suite failed to run"))
+            || line.strip_prefix("FAIL ").is_some_and(|rest| rest.contains(" > "))
+    })
+}
+
+/// Read a jest-format JSON report (vitest's `json` reporter writes the
+/// same shape). The killing test is named like the text output: its
+/// describe blocks and title joined by `separator`.
+fn js_report(path: &Path, separator: &str) -> Option<ReportOutcome> {
+    let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    let suites = report["testResults"].as_array()?;
+    let failed = suites
+        .iter()
+        .flat_map(|suite| suite["assertionResults"].as_array().into_iter().flatten())
+        .find(|test| test["status"] == "failed");
+    if let Some(test) = failed {
+        let name: Vec<&str> = test["ancestorTitles"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|title| title.as_str())
+            .chain(test["title"].as_str())
+            .collect();
+        return Some(ReportOutcome::TestFailed(name.join(separator)));
+    }
+    // A failed suite without a failed test never got to run its tests
+    let broken = report["numRuntimeErrorTestSuites"].as_u64().is_some_and(|n| n > 0)
+        || suites.iter().any(|suite| suite["status"] == "failed");
+    if broken {
+        return Some(ReportOutcome::LoadError);
+    }
+    (report["numTotalTests"] == 0).then_some(ReportOutcome::NoTests)
+}
+
+fn js_filter(flag: &str, filters: &[String]) -> Vec<String> {
+    if filters.is_empty() {
+        return Vec::new();
+    }
+    vec![flag.to_string(), filters.join("|")]
+}
+
+struct Jest;
+
+impl TestAdapter for Jest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["--bail"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["--bail"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        js_filter("-t", filters)
+    }
+
+    /// `-t` takes a regex; names like `adds (a + b)` must match literally.
+    fn test_name(&self, name: &str) -> String {
+        regex::escape(name)
+    }
+
+    fn report_args(&self, path: &Path) -> Vec<String> {
+        vec!["--json".to_string(), format!("--outputFile={}", path.display())]
+    }
+
+    fn read_report(&self, path: &Path) -> Option<ReportOutcome> {
+        js_report(path, " › ")
+    }
+
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool {
+        js_unviable(stdout, stderr)
+    }
+
+    fn reports_failure(&self, output: &str) -> bool {
+        js_reports_failure(output)
+    }
+
+    /// `No tests found, exiting with code 1`, also printed (with code 0)
+    /// under `--passWithNoTests`.
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        output.contains("No tests found")
+    }
+}
+
+struct Vitest;
+
+impl TestAdapter for Vitest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["--bail=1"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["--bail=1"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        js_filter("-t", filters)
+    }
+
+    /// `-t` takes a regex; names like `adds (a + b)` must match literally.
+    fn test_name(&self, name: &str) -> String {
+        regex::escape(name)
+    }
+
+    /// The default reporter stays on for the text output.
+    fn report_args(&self, path: &Path) -> Vec<String> {
+        vec![
+            "--reporter=default".to_string(),
+            "--reporter=json".to_string(),
+            format!("--outputFile.json={}", path.display()),
+        ]
+    }
+
+    fn read_report(&self, path: &Path) -> Option<ReportOutcome> {
+        js_report(path, " > ")
+    }
+
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool {
+        js_unviable(stdout, stderr)
+    }
+
+    fn reports_failure(&self, output: &str) -> bool {
+        js_reports_failure(output)
+    }
+
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        output.contains("No test files found") || output.contains("No test found in suite")
+    }
+}
+
+struct Mocha;
+
+impl TestAdapter for Mocha {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["--bail"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["--bail", "--reporter=dot"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        js_filter("--grep", filters)
+    }
+
+    fn test_name(&self, name: &str) -> String {
+        regex::escape(name)
+    }
+
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool {
+        js_unviable(stdout, stderr)
+    }
+
+    /// A `!` from the dot reporter, or a numbered `  1) Cart` failure.
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().map(str::trim).any(|line| {
+            let dots = !line.is_empty() && line.chars().all(|c| ".,!".contains(c));
+            let numbered = line
+                .split_once(") ")
+                .is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+            (dots && line.contains('!')) || numbered
+        })
+    }
+
+    /// `  0 passing (1ms)` with no failures.
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        output.lines().any(|line| line.trim().starts_with("0 passing")) && !output.contains(" failing")
+    }
+
+    /// `  1) Cart` / `       adds items:` — the numbered suite line plus
+    /// the test title under it.
+    fn failing_test(&self, output: &str) -> Option<String> {
+        let mut lines = output.lines().map(str::trim);
+        while let Some(line) = lines.next() {
+            let Some((number, suite)) = line.split_once(") ") else {
+                continue;
+            };
+            if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            let title = lines.next().unwrap_or("").trim_end_matches(':');
+            return Some(match (suite.trim_end_matches(':'), title) {
+                (suite, "") => suite.to_string(),
+                (suite, title) => format!("{} {}", suite, title),
+            });
+        }
+        None
+    }
+}
+
+/// rustc errors; cargo prints them on stderr before any test runs.
+const CARGO_UNVIABLE: &[&str] = &["error[E", "error: could not compile", "error: aborting due to"];
+
+fn cargo_unviable(stderr: &str) -> bool {
+    CARGO_UNVIABLE.iter().any(|marker| stderr.contains(marker))
+}
+
+struct CargoTest;
+
+impl TestAdapter for CargoTest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["--", "--test-threads=1"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["--", "--test-threads=1"]
+    }
+
+    /// Libtest filters are positional, after the `--` from the run args.
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        filters.to_vec()
+    }
+
+    fn passes_test_file(&self) -> bool {
+        false
+    }
+
+    fn is_unviable(&self, _stdout: &str, stderr: &str) -> bool {
+        cargo_unviable(stderr)
+    }
+
+    /// `test tests::adds ... FAILED`
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| line.starts_with("test ") && line.ends_with(" ... FAILED"))
+    }
+
+    /// Every test binary (and the doc-tests) printed `running 0 tests`.
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        let counts: Vec<_> = output.lines().filter_map(|line| line.trim().strip_prefix("running ")).collect();
+        !counts.is_empty() && counts.iter().all(|count| count.starts_with("0 tests"))
+    }
+}
+
+struct CargoNextest;
+
+impl TestAdapter for CargoNextest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["--test-threads=1"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["--test-threads=1", "--failure-output=never", "--success-output=never"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        filters.to_vec()
+    }
+
+    fn passes_test_file(&self) -> bool {
+        false
+    }
+
+    fn is_unviable(&self, _stdout: &str, stderr: &str) -> bool {
+        cargo_unviable(stderr)
+    }
+
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| line.trim().starts_with("FAIL ["))
+    }
+
+    /// Exit code 4 (`NO_TESTS_RUN`), or `Starting 0 tests` from older
+    /// versions.
+    fn ran_no_tests(&self, exit_code: Option<i32>, output: &str) -> bool {
+        exit_code == Some(4) || output.contains("Starting 0 tests") || output.contains("no tests to run")
+    }
+
+    /// `        FAIL [   0.004s] my_crate tests::adds`
+    fn failing_test(&self, output: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("FAIL [")?;
+            let (_, name) = rest.split_once("] ")?;
+            let name = name.split_whitespace().last()?;
+            Some(name.to_string())
+        })
+    }
+}
+
+struct GoTest;
+
+impl TestAdapter for GoTest {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &["-failfast", "-count=1"]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["-failfast", "-count=1"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        if filters.is_empty() {
+            return Vec::new();
+        }
+        vec!["-run".to_string(), format!("^({})$", filters.join("|"))]
+    }
+
+    fn passes_test_file(&self) -> bool {
+        false
+    }
+
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool {
+        [stdout, stderr]
+            .iter()
+            .any(|out| out.contains("[build failed]") || out.contains("[setup failed]"))
+    }
+
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| line.trim().starts_with("--- FAIL: "))
+    }
+
+    /// Every package line is `[no test files]` or `[no tests to run]`.
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        let packages: Vec<_> =
+            output.lines().filter(|line| line.starts_with("ok ") || line.starts_with("? ")).collect();
+        !packages.is_empty()
+            && packages.iter().all(|line| line.contains("[no test files]") || line.contains("[no tests to run]"))
+    }
+
+    /// `--- FAIL: TestAdd (0.00s)`
+    fn failing_test(&self, output: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("--- FAIL: ")?;
+            Some(rest.split(" (").next().unwrap_or(rest).to_string())
+        })
+    }
+}
+
+/// The test class a JUnit test file declares: `FooTest` for
+/// `src/test/java/com/x/FooTest.java`.
+fn test_class(test_file: &Path) -> String {
+    test_file.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+struct Maven;
+
+impl TestAdapter for Maven {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["-Dsurefire.skipAfterFailureCount=1"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        if filters.is_empty() {
+            return Vec::new();
+        }
+        vec![format!("-Dtest=*#{}", filters.join("+"))]
+    }
+
+    fn passes_test_file(&self) -> bool {
+        false
+    }
+
+    /// `-Dtest=FooTest#adds+removes`. Other modules of a multi-module
+    /// build have no such class, which must not fail them.
+    fn class_args(&self, test_file: &Path, filters: &[String]) -> Option<Vec<String>> {
+        let class = test_class(test_file);
+        let selector = match filters {
+            [] => class,
+            _ => format!("{}#{}", class, filters.join("+")),
+        };
+        Some(vec![format!("-Dtest={}", selector), "-Dsurefire.failIfNoSpecifiedTests=false".to_string()])
+    }
+
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool {
+        [stdout, stderr]
+            .iter()
+            .any(|out| out.contains("COMPILATION ERROR") || out.contains("Compilation failure"))
+    }
+
+    /// `[ERROR] com.x.FooTest.adds -- Time elapsed: 0.01 s <<< FAILURE!`;
+    /// `<<< ERROR!` is a test throwing, which catches the mutant too.
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| line.contains("<<< FAILURE!") || line.contains("<<< ERROR!"))
+    }
+
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        output.contains("No tests to run.")
+            || output.contains("No tests were executed")
+            || output.contains("No tests matching pattern")
+    }
+
+    /// The test method line, not the `Tests run: ...` class summary.
+    fn failing_test(&self, output: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            if !line.contains("<<< FAILURE!") && !line.contains("<<< ERROR!") {
+                return None;
+            }
+            let rest = line.trim().strip_prefix("[ERROR]").unwrap_or(line).trim();
+            if rest.starts_with("Tests run:") {
+                return None;
+            }
+            rest.split_whitespace().next().map(String::from)
+        })
+    }
+}
+
+struct Gradle;
+
+impl TestAdapter for Gradle {
+    fn baseline_args(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn mutation_args(&self) -> &'static [&'static str] {
+        &["--fail-fast"]
+    }
+
+    fn filter_args(&self, filters: &[String]) -> Vec<String> {
+        filters.iter().flat_map(|f| ["--tests".to_string(), format!("*.{}", f)]).collect()
+    }
+
+    fn passes_test_file(&self) -> bool {
+        false
+    }
+
+    /// `--tests FooTest`, or `--tests FooTest.adds` per filter.
+    fn class_args(&self, test_file: &Path, filters: &[String]) -> Option<Vec<String>> {
+        let class = test_class(test_file);
+        if filters.is_empty() {
+            return Some(vec!["--tests".to_string(), class]);
+        }
+        Some(filters.iter().flat_map(|f| ["--tests".to_string(), format!("{}.{}", class, f)]).collect())
+    }
+
+    fn is_unviable(&self, stdout: &str, stderr: &str) -> bool {
+        [stdout, stderr].iter().any(|out| {
+            out.contains("Compilation failed")
+                || out.contains("compileJava FAILED")
+                || out.contains("compileTestJava FAILED")
+        })
+    }
+
+    /// `FooTest > adds() FAILED`; task lines (`> Task :test FAILED`) don't
+    /// count.
+    fn reports_failure(&self, output: &str) -> bool {
+        output.lines().any(|line| line.contains(" > ") && line.trim_end().ends_with(" FAILED"))
+    }
+
+    fn ran_no_tests(&self, _exit_code: Option<i32>, output: &str) -> bool {
+        output.contains("No tests found for given includes")
+    }
+
+    /// `FooTest > adds() FAILED` -> `FooTest.adds`
+    fn failing_test(&self, output: &str) -> Option<String> {
+        output.lines().find_map(|line| {
+            let rest = line.trim_end().strip_suffix(" FAILED")?;
+            let (class, test) = rest.trim().split_once(" > ")?;
+            Some(format!("{}.{}", class, test.trim_end_matches("()")))
+        })
+    }
+}
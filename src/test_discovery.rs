@@ -0,0 +1,114 @@
+//! Finds the test file for a source file when `--test` is omitted, from the
+//! naming conventions of each language: `test_<name>.py` and
+//! `<name>_test.py`, `<name>.test.ts` and `<name>.spec.ts`, `tests/<name>.rs`
+//! or a Rust file's own `#[cfg(test)]` module, `<Name>Test.java` under
+//! `src/test/java` for `src/main/java/<Name>.java`. Candidates are looked for next
+//! to the source, in its `tests/` (or `test/`, `__tests__/`) sibling, and in
+//! the project root's test directory, mirroring the source's package path.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::engine::EngineError;
+use crate::{Language, copy_tree};
+
+const JS_EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs", "mts", "cts"];
+const TEST_DIRS: &[&str] = &["tests", "test", "__tests__"];
+
+/// The single test file matching `source_file`. No match is
+/// `NoTestFile`; several are `AmbiguousTestFile`, listing them.
+pub fn find_test_file(source_file: &Path) -> Result<PathBuf, EngineError> {
+    if !source_file.exists() {
+        return Err(EngineError::SourceNotFound(source_file.to_path_buf()));
+    }
+    let mut found = candidates(source_file);
+    match found.len() {
+        0 => Err(EngineError::NoTestFile(source_file.to_path_buf())),
+        1 => Ok(found.remove(0)),
+        _ => Err(EngineError::AmbiguousTestFile { source: source_file.to_path_buf(), candidates: found }),
+    }
+}
+
+/// Every existing test file that matches `source_file` by convention,
+/// sorted and without duplicates.
+pub fn candidates(source_file: &Path) -> Vec<PathBuf> {
+    let source = std::path::absolute(source_file).unwrap_or_else(|_| source_file.to_path_buf());
+    let Some(language) = crate::detect_language(&source) else {
+        return Vec::new();
+    };
+    let Some(name) = module_name(&source) else {
+        return Vec::new();
+    };
+    let mut found = BTreeSet::new();
+    if matches!(language, Language::Rust) {
+        let root = copy_tree::find_project_root(&source);
+        for file in [format!("{}.rs", name), format!("test_{}.rs", name), format!("{}_test.rs", name)] {
+            found.insert(root.join("tests").join(file));
+        }
+        let inline = std::fs::read_to_string(&source).is_ok_and(|s| s.contains("#[cfg(test)]"));
+        if inline {
+            found.insert(source.clone());
+        }
+    } else if matches!(language, Language::Java) {
+        let files = [format!("{}Test.java", name), format!("{}Tests.java", name), format!("Test{}.java", name)];
+        let dir = source.parent().unwrap_or(&source);
+        for dir in std::iter::once(dir.to_path_buf()).chain(maven_test_dir(dir)) {
+            found.extend(files.iter().map(|file| dir.join(file)));
+        }
+    } else {
+        let files = test_file_names(&language, &name);
+        for dir in search_dirs(&source) {
+            found.extend(files.iter().map(|file| dir.join(file)));
+        }
+    }
+    found.into_iter().filter(|path| path.is_file()).collect()
+}
+
+/// The name tests refer to the module by: the file stem, or the parent
+/// directory for package files (`__init__.py`, `index.ts`, `mod.rs`).
+fn module_name(source: &Path) -> Option<String> {
+    let stem = source.file_stem()?.to_str()?;
+    if matches!(stem, "__init__" | "index" | "mod") {
+        return source.parent()?.file_name()?.to_str().map(String::from);
+    }
+    Some(stem.to_string())
+}
+
+fn test_file_names(language: &Language, name: &str) -> Vec<String> {
+    match language {
+        Language::Python => vec![format!("test_{}.py", name), format!("{}_test.py", name)],
+        _ => JS_EXTENSIONS
+            .iter()
+            .flat_map(|ext| [format!("{}.test.{}", name, ext), format!("{}.spec.{}", name, ext)])
+            .collect(),
+    }
+}
+
+/// `src/test/<path>` for a directory under `src/main/<path>`, the Maven
+/// and Gradle layout.
+fn maven_test_dir(dir: &Path) -> Option<PathBuf> {
+    let parts: Vec<_> = dir.components().collect();
+    let main = (1..parts.len()).rev().find(|&i| parts[i - 1].as_os_str() == "src" && parts[i].as_os_str() == "main")?;
+    let mut mirror: PathBuf = parts[..main].iter().collect();
+    mirror.push("test");
+    mirror.extend(&parts[main + 1..]);
+    Some(mirror)
+}
+
+/// The source's directory and its test siblings, then the project root's
+/// test directories, both flat and mirroring the source's path under the
+/// root (`src/pkg/a.py` -> `tests/pkg/`).
+fn search_dirs(source: &Path) -> Vec<PathBuf> {
+    let dir = source.parent().unwrap_or(source);
+    let root = copy_tree::find_project_root(source);
+    let mut dirs = vec![dir.to_path_buf()];
+    dirs.extend(TEST_DIRS.iter().map(|t| dir.join(t)));
+    let package = dir.strip_prefix(&root).ok().map(|rel| rel.strip_prefix("src").unwrap_or(rel));
+    for test_dir in TEST_DIRS {
+        dirs.push(root.join(test_dir));
+        if let Some(package) = package {
+            dirs.push(root.join(test_dir).join(package));
+        }
+    }
+    dirs
+}
@@ -0,0 +1,48 @@
+//! Function -> test mapping for `--test-map`.
+//!
+//! A JSON object keyed by function name. Values are a test name or a list
+//! of test names, forwarded to the runner's filter (`pytest -k`,
+//! `cargo test NAME`, `jest -t`):
+//!
+//! ```json
+//! { "add": ["test_add", "test_add_negative"], "parse": "test_parse" }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub type TestMap = BTreeMap<String, Vec<String>>;
+
+pub fn load(path: &Path) -> Result<TestMap, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read test map {}: {}", path.display(), e))?;
+    parse(&content).map_err(|e| format!("Invalid test map {}: {}", path.display(), e))
+}
+
+pub fn parse(content: &str) -> Result<TestMap, String> {
+    let value: serde_json::Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    let obj = value
+        .as_object()
+        .ok_or("expected a JSON object mapping function names to tests")?;
+
+    let mut map = TestMap::new();
+    for (function, tests) in obj {
+        let tests = match tests {
+            serde_json::Value::String(s) => vec![s.clone()],
+            serde_json::Value::Array(items) => items
+                .iter()
+                .map(|t| {
+                    t.as_str()
+                        .map(String::from)
+                        .ok_or_else(|| format!("tests for '{}' must be strings", function))
+                })
+                .collect::<Result<_, _>>()?,
+            _ => return Err(format!("tests for '{}' must be a string or a list of strings", function)),
+        };
+        if tests.is_empty() {
+            return Err(format!("no tests listed for '{}'", function));
+        }
+        map.insert(function.clone(), tests);
+    }
+    Ok(map)
+}
@@ -0,0 +1,365 @@
+//! Interactive terminal UI for `mutator tui` (feature `tui`).
+//!
+//! The run happens on a worker thread that sends each `MutantResult` over a
+//! channel; the UI thread redraws as results arrive and handles keys. State
+//! lives in `App` so rendering can be tested against a `TestBackend`.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::Duration;
+
+use ratatui::Frame;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Gauge, Paragraph, Row, Table, TableState, Wrap};
+
+use crate::engine::{EngineError, MutatorEngine};
+use crate::mutants::{MutantResult, MutantStatus, Mutation};
+use crate::signals;
+
+/// Messages from the worker thread.
+pub enum Progress {
+    BaselineDone,
+    /// A finished mutant and its index in `App::mutations`.
+    Result(usize, Box<MutantResult>),
+    Finished,
+    /// The run stopped with this error; the worker returns the typed error.
+    Failed(String),
+}
+
+/// How the UI was left.
+pub enum TuiExit {
+    /// All mutants ran and the user closed the UI.
+    Completed(Vec<MutantResult>),
+    /// The run failed before finishing (e.g. the baseline).
+    Failed(EngineError),
+    /// The user quit while mutants were still running.
+    Interrupted,
+}
+
+pub struct App {
+    pub file: String,
+    pub mutations: Vec<Mutation>,
+    /// One slot per mutation, filled as results arrive in run order.
+    pub results: Vec<Option<MutantResult>>,
+    /// Indices into `mutations` in the order they run.
+    pub order: Vec<usize>,
+    pub selected: usize,
+    pub show_detail: bool,
+    pub baseline_done: bool,
+    pub finished: bool,
+    pub error: Option<String>,
+    /// Keep the selection on the running mutant until the user navigates.
+    follow: bool,
+}
+
+impl App {
+    pub fn new(file: impl Into<String>, mutations: Vec<Mutation>) -> Self {
+        App {
+            file: file.into(),
+            results: vec![None; mutations.len()],
+            order: (0..mutations.len()).collect(),
+            mutations,
+            selected: 0,
+            show_detail: false,
+            baseline_done: false,
+            finished: false,
+            error: None,
+            follow: true,
+        }
+    }
+
+    pub fn apply(&mut self, progress: Progress) {
+        match progress {
+            Progress::BaselineDone => self.baseline_done = true,
+            Progress::Result(index, result) => {
+                if let Some(slot) = self.results.get_mut(index) {
+                    *slot = Some(*result);
+                }
+                if self.follow {
+                    self.selected = self.running().unwrap_or(index);
+                }
+            }
+            Progress::Finished => self.finished = true,
+            Progress::Failed(e) => {
+                self.error = Some(e);
+                self.finished = true;
+            }
+        }
+    }
+
+    /// Run mutants in `order` (`--shuffle`, `--prioritize`) rather than as
+    /// listed.
+    pub fn with_order(mut self, order: Vec<usize>) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Number of finished mutants.
+    pub fn done(&self) -> usize {
+        self.results.iter().flatten().count()
+    }
+
+    /// The mutant presumed running: the next unfinished one in `order` after
+    /// the last to finish. Timed-out mutants report only after their retry
+    /// at the end of the run, so then it is the first unfinished one.
+    fn running(&self) -> Option<usize> {
+        if !self.baseline_done || self.finished {
+            return None;
+        }
+        let unfinished = |i: &&usize| self.results.get(**i).is_some_and(Option::is_none);
+        let last = self.order.iter().rposition(|&i| self.results.get(i).is_some_and(Option::is_some));
+        let after = last.map_or(0, |p| p + 1);
+        self.order[after..].iter().find(unfinished).or_else(|| self.order.iter().find(unfinished)).copied()
+    }
+
+    pub fn next(&mut self) {
+        self.follow = false;
+        if self.selected + 1 < self.mutations.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.follow = false;
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn toggle_detail(&mut self) {
+        self.show_detail = !self.show_detail;
+    }
+
+    /// Number of results with `status`.
+    pub fn count(&self, status: MutantStatus) -> usize {
+        self.results.iter().flatten().filter(|r| r.status == status).count()
+    }
+
+    fn status_cell(&self, index: usize) -> Span<'static> {
+        match self.results.get(index).and_then(Option::as_ref) {
+            Some(r) => match r.status {
+                MutantStatus::Killed => Span::styled("killed", Style::new().fg(Color::Green)),
+                MutantStatus::Survived => {
+                    Span::styled("SURVIVED", Style::new().fg(Color::Red).add_modifier(Modifier::BOLD))
+                }
+                MutantStatus::Timeout => Span::styled("timeout", Style::new().fg(Color::Yellow)),
+                MutantStatus::Unviable => Span::styled("unviable", Style::new().fg(Color::DarkGray)),
+                MutantStatus::Uncovered => Span::styled("uncovered", Style::new().fg(Color::Magenta)),
+                MutantStatus::SuspectedEquivalent => {
+                    Span::styled("equivalent?", Style::new().fg(Color::DarkGray))
+                }
+                MutantStatus::ResourceExceeded => Span::styled("limit", Style::new().fg(Color::Yellow)),
+            },
+            None if self.running() == Some(index) => {
+                Span::styled("running", Style::new().fg(Color::Cyan))
+            }
+            None => Span::styled("pending", Style::new().fg(Color::DarkGray)),
+        }
+    }
+}
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(5),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let total = app.mutations.len();
+    let done = app.done();
+    let label = if let Some(ref e) = app.error {
+        e.lines().next().unwrap_or_default().to_string()
+    } else if !app.baseline_done {
+        "running baseline...".to_string()
+    } else {
+        format!(
+            "{}/{} · {} killed · {} survived · {} timeout · {} unviable",
+            done,
+            total,
+            app.count(MutantStatus::Killed),
+            app.count(MutantStatus::Survived),
+            app.count(MutantStatus::Timeout),
+            app.count(MutantStatus::Unviable),
+        )
+    };
+    let ratio = if total > 0 { done as f64 / total as f64 } else { 1.0 };
+    let gauge_color = if app.error.is_some() { Color::Red } else { Color::Cyan };
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::default().borders(Borders::ALL).title(format!(" mutator: {} ", app.file)))
+            .gauge_style(Style::new().fg(gauge_color))
+            .ratio(ratio.clamp(0.0, 1.0))
+            .label(label),
+        header,
+    );
+
+    let [list_area, detail_area] = if app.show_detail {
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(body)
+    } else {
+        Layout::horizontal([Constraint::Percentage(100), Constraint::Length(0)]).areas(body)
+    };
+
+    let rows = app.mutations.iter().enumerate().map(|(i, m)| {
+        Row::new(vec![
+            Line::from(format!("{}", i + 1)),
+            Line::from(format!("{}:{}", m.line, m.column)),
+            Line::from(m.operator.clone()),
+            Line::from(format!("{} → {}", one_line(&m.original), one_line(&m.replacement))),
+            Line::from(app.status_cell(i)),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Length(8),
+            Constraint::Length(16),
+            Constraint::Min(10),
+            Constraint::Length(11),
+        ],
+    )
+    .header(Row::new(vec!["#", "line", "operator", "change", "status"]).style(Style::new().add_modifier(Modifier::BOLD)))
+    .block(Block::default().borders(Borders::ALL).title(" mutants "))
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, list_area, &mut state);
+
+    if app.show_detail {
+        frame.render_widget(
+            Paragraph::new(detail_lines(app))
+                .block(Block::default().borders(Borders::ALL).title(" detail "))
+                .wrap(Wrap { trim: false }),
+            detail_area,
+        );
+    }
+
+    let help = if app.finished {
+        "↑/↓ select · enter details · q quit"
+    } else {
+        "↑/↓ select · enter details · q stop run"
+    };
+    frame.render_widget(Paragraph::new(help).style(Style::new().fg(Color::DarkGray)), footer);
+}
+
+fn detail_lines(app: &App) -> Vec<Line<'static>> {
+    let Some(m) = app.mutations.get(app.selected) else {
+        return Vec::new();
+    };
+    let dim = Style::new().fg(Color::DarkGray);
+    let mut lines = vec![Line::from(format!("{}:{} [{}]", app.file, m.line, m.operator)), Line::from("")];
+    lines.extend(m.context_before.iter().map(|l| Line::styled(l.clone(), dim)));
+    let result = app.results.get(app.selected).and_then(Option::as_ref);
+    match result {
+        Some(r) => {
+            for l in r.diff.lines() {
+                if l.starts_with('-') && !l.starts_with("---") {
+                    lines.push(Line::styled(l.to_string(), Style::new().fg(Color::Red)));
+                } else if l.starts_with('+') && !l.starts_with("+++") {
+                    lines.push(Line::styled(l.to_string(), Style::new().fg(Color::Green)));
+                }
+            }
+        }
+        None => {
+            lines.push(Line::styled(format!("- {}", one_line(&m.original)), Style::new().fg(Color::Red)));
+            lines.push(Line::styled(format!("+ {}", one_line(&m.replacement)), Style::new().fg(Color::Green)));
+        }
+    }
+    lines.extend(m.context_after.iter().map(|l| Line::styled(l.clone(), dim)));
+
+    if let Some(r) = result {
+        if let Some(ref test) = r.killed_by {
+            lines.push(Line::from(""));
+            lines.push(Line::from(format!("killed by {}", test)));
+        }
+        if let Some(ref snippet) = r.output_snippet {
+            lines.push(Line::from(""));
+            lines.push(Line::styled("Test output:", dim));
+            lines.extend(snippet.lines().map(|l| Line::styled(l.to_string(), dim)));
+        }
+    }
+    lines
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Run `mutations` on a worker thread and show progress until the user
+/// quits. The terminal is restored before returning.
+pub fn run(mut engine: MutatorEngine, mutations: Vec<Mutation>, file: &str) -> std::io::Result<TuiExit> {
+    let (tx, rx) = mpsc::channel();
+    let order = engine.run_order(&mutations);
+    let worker_mutations = mutations.clone();
+    let worker = std::thread::spawn(move || {
+        let results = engine.baseline().and_then(|_| {
+            if let Some(dir) = engine.temp_dir() {
+                signals::track_temp_dir(dir);
+            }
+            let _ = tx.send(Progress::BaselineDone);
+            engine.run_all_with(&worker_mutations, |i, r| {
+                signals::record(i, r);
+                let _ = tx.send(Progress::Result(i, Box::new(r.clone())));
+            })
+        });
+        let _ = tx.send(match results {
+            Ok(_) => Progress::Finished,
+            Err(ref e) => Progress::Failed(e.to_string()),
+        });
+        results
+    });
+
+    let mut app = App::new(file, mutations).with_order(order);
+    let mut terminal = ratatui::init();
+    let outcome = event_loop(&mut terminal, &mut app, &rx);
+    ratatui::restore();
+
+    if !outcome? {
+        return Ok(TuiExit::Interrupted);
+    }
+    Ok(match worker.join() {
+        Ok(Ok(results)) => TuiExit::Completed(results),
+        Ok(Err(e)) => TuiExit::Failed(e),
+        Err(_) => TuiExit::Failed(EngineError::Setup("mutation worker panicked".into())),
+    })
+}
+
+/// Returns true once the run finished and the user closed the UI, false
+/// if the user quit mid-run.
+fn event_loop(
+    terminal: &mut ratatui::DefaultTerminal,
+    app: &mut App,
+    rx: &Receiver<Progress>,
+) -> std::io::Result<bool> {
+    loop {
+        loop {
+            match rx.try_recv() {
+                Ok(progress) => app.apply(progress),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    app.finished = true;
+                    break;
+                }
+            }
+        }
+        terminal.draw(|frame| draw(frame, app))?;
+
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(app.finished),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(app.finished),
+            KeyCode::Down | KeyCode::Char('j') => app.next(),
+            KeyCode::Up | KeyCode::Char('k') => app.previous(),
+            KeyCode::Enter | KeyCode::Char(' ') => app.toggle_detail(),
+            _ => {}
+        }
+    }
+}
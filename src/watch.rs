@@ -0,0 +1,149 @@
+//! `mutator watch`: re-run on source or test changes.
+//!
+//! `Watcher` wraps a filesystem notifier on the files' parent directories
+//! (editors often save by renaming a temp file over the original, which a
+//! watch on the file itself would miss). `ResultCache` makes re-runs
+//! incremental: while the test file is unchanged, a mutant inside a function
+//! whose text is byte-for-byte the same as last run keeps its result.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher as _};
+
+use crate::engine;
+use crate::mutants::{FunctionSpan, MutantResult, Mutation};
+use crate::Language;
+
+pub struct Watcher {
+    _inner: RecommendedWatcher,
+    rx: Receiver<notify::Result<notify::Event>>,
+    targets: Vec<PathBuf>,
+}
+
+impl Watcher {
+    pub fn new(files: &[&Path]) -> Result<Self, String> {
+        let (tx, rx) = mpsc::channel();
+        let mut inner = notify::recommended_watcher(tx).map_err(|e| format!("Failed to start file watcher: {}", e))?;
+        let mut targets = Vec::new();
+        for file in files {
+            let file = file
+                .canonicalize()
+                .map_err(|e| format!("Cannot watch {}: {}", file.display(), e))?;
+            let dir = file.parent().unwrap_or(Path::new("/")).to_path_buf();
+            if !targets.iter().any(|t: &PathBuf| t.parent() == Some(dir.as_path())) {
+                inner
+                    .watch(&dir, RecursiveMode::NonRecursive)
+                    .map_err(|e| format!("Cannot watch {}: {}", dir.display(), e))?;
+            }
+            targets.push(file);
+        }
+        Ok(Watcher { _inner: inner, rx, targets })
+    }
+
+    /// Block until a watched file changes, then keep collecting events until
+    /// `settle` passes without one, so a burst of writes from a single save
+    /// triggers one run. Returns the changed files.
+    pub fn wait(&self, settle: Duration) -> Vec<PathBuf> {
+        let mut changed = Vec::new();
+        while changed.is_empty() {
+            match self.rx.recv() {
+                Ok(event) => self.collect(event, &mut changed),
+                Err(_) => return changed,
+            }
+        }
+        loop {
+            match self.rx.recv_timeout(settle) {
+                Ok(event) => self.collect(event, &mut changed),
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => return changed,
+            }
+        }
+    }
+
+    fn collect(&self, event: notify::Result<notify::Event>, changed: &mut Vec<PathBuf>) {
+        let Ok(event) = event else {
+            return;
+        };
+        if event.kind.is_access() {
+            return;
+        }
+        for path in event.paths {
+            if let Some(target) = self.targets.iter().find(|t| **t == path) {
+                if !changed.contains(target) {
+                    changed.push(target.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Identifies a mutant by its function's text rather than its position,
+/// so edits elsewhere in the file don't invalidate it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct MutantKey {
+    function_text: String,
+    offset: usize,
+    len: usize,
+    replacement: String,
+}
+
+#[derive(Default)]
+pub struct ResultCache {
+    test_source: String,
+    results: HashMap<MutantKey, MutantResult>,
+}
+
+impl ResultCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The cached result for each of `mutations`, moved to the mutation's
+    /// current position, or `None` if it has to run. Everything runs when
+    /// the test file changed.
+    pub fn lookup(
+        &self,
+        lang: &Language,
+        source: &str,
+        test_source: &str,
+        mutations: &[Mutation],
+    ) -> Vec<Option<MutantResult>> {
+        if self.results.is_empty() || test_source != self.test_source {
+            return vec![None; mutations.len()];
+        }
+        let spans = engine::function_spans(lang, source);
+        mutations
+            .iter()
+            .map(|m| {
+                let cached = self.results.get(&key(source, &spans, m)?)?;
+                Some(MutantResult { mutation: m.clone(), ..cached.clone() })
+            })
+            .collect()
+    }
+
+    /// Replace the cache with the results of the run on `source`.
+    pub fn store(&mut self, lang: &Language, source: &str, test_source: &str, results: &[MutantResult]) {
+        let spans = engine::function_spans(lang, source);
+        self.test_source = test_source.to_string();
+        self.results = results
+            .iter()
+            .filter_map(|r| Some((key(source, &spans, &r.mutation)?, r.clone())))
+            .collect();
+    }
+}
+
+/// Mutants outside any function have no stable key and always run.
+fn key(source: &str, spans: &[FunctionSpan], m: &Mutation) -> Option<MutantKey> {
+    let span = spans
+        .iter()
+        .filter(|s| s.start_byte <= m.start_byte && m.end_byte <= s.end_byte)
+        .min_by_key(|s| s.end_byte - s.start_byte)?;
+    Some(MutantKey {
+        function_text: source.get(span.start_byte..span.end_byte)?.to_string(),
+        offset: m.start_byte - span.start_byte,
+        len: m.end_byte - m.start_byte,
+        replacement: m.replacement.clone(),
+    })
+}
@@ -0,0 +1,125 @@
+//! `mutator report`: the last run's survived and uncovered mutants as XML
+//! for CI dashboards that already read test or lint results.
+//!
+//! - JUnit (`--junit`): each mutant is a failing test case, with the file as
+//!   `classname`, the ref as `name`, and the diff in its context as the
+//!   failure text.
+//! - Checkstyle (`--checkstyle`): each mutant is a `warning` on its line,
+//!   with `mutator.<operator>` as `source`.
+
+use crate::markdown;
+use crate::state::{RunResult, SurvivedMutant};
+
+/// The mutants a report lists, survivors first, with what happened to each.
+fn reported(result: &RunResult) -> impl Iterator<Item = (&SurvivedMutant, &'static str)> {
+    let survived = result.survived_mutants.iter().map(|m| (m, "Survived mutant"));
+    let uncovered = result.uncovered_mutants.iter().map(|m| (m, "Uncovered mutant"));
+    survived.chain(uncovered)
+}
+
+fn message(m: &SurvivedMutant, kind: &str) -> String {
+    format!("{}: '{}' → '{}' ({})", kind, m.original, m.replacement, m.operator)
+}
+
+pub fn to_junit(result: &RunResult) -> String {
+    let count = result.survived_mutants.len() + result.uncovered_mutants.len();
+    let seconds = result.duration_ms as f64 / 1000.0;
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str(&format!(
+        "<testsuites name=\"mutator\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+        count, count, seconds
+    ));
+    out.push_str(&format!(
+        "  <testsuite name=\"mutator\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+        count, count, seconds
+    ));
+    for (m, kind) in reported(result) {
+        out.push_str(&format!(
+            "    <testcase classname=\"{}\" name=\"@{}\">\n",
+            escape(&m.file),
+            escape(&m.ref_id)
+        ));
+        out.push_str(&format!(
+            "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+            escape(&message(m, kind)),
+            escape(&m.operator),
+            escape(&failure_text(m))
+        ));
+        if let Some(snippet) = &m.output_snippet {
+            out.push_str(&format!("      <system-out>{}</system-out>\n", escape(snippet)));
+        }
+        out.push_str("    </testcase>\n");
+    }
+    out.push_str("  </testsuite>\n</testsuites>\n");
+    out
+}
+
+/// Location, the changed lines between their context (as `show` prints
+/// them), and the assertion that would kill the mutant.
+fn failure_text(m: &SurvivedMutant) -> String {
+    let mut text = format!("{}:{}:{}", m.file, m.line, m.column);
+    if let Some(function) = &m.function {
+        text.push_str(&format!(" in {}", function));
+    }
+    text.push_str("\n\n");
+    for line in &m.context_before {
+        text.push_str(format!("  {}", line).trim_end());
+        text.push('\n');
+    }
+    for line in m.diff.lines() {
+        text.push_str(&format!("{}\n", line));
+    }
+    for line in &m.context_after {
+        text.push_str(format!("  {}", line).trim_end());
+        text.push('\n');
+    }
+    text.push('\n');
+    text.push_str(markdown::assertion_hint(&m.operator));
+    text.push('\n');
+    text
+}
+
+pub fn to_checkstyle(result: &RunResult) -> String {
+    let mut files: Vec<(&str, Vec<String>)> = Vec::new();
+    for (m, kind) in reported(result) {
+        let error = format!(
+            "    <error line=\"{}\" column=\"{}\" severity=\"warning\" message=\"{}\" source=\"mutator.{}\"/>\n",
+            m.line,
+            m.column,
+            escape(&format!("{} (@{}). {}", message(m, kind), m.ref_id, markdown::assertion_hint(&m.operator))),
+            escape(&m.operator)
+        );
+        match files.iter_mut().find(|(file, _)| *file == m.file) {
+            Some((_, errors)) => errors.push(error),
+            None => files.push((&m.file, vec![error])),
+        }
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"4.3\">\n");
+    for (file, errors) in files {
+        out.push_str(&format!("  <file name=\"{}\">\n", escape(file)));
+        out.extend(errors);
+        out.push_str("  </file>\n");
+    }
+    out.push_str("</checkstyle>\n");
+    out
+}
+
+/// Text and attribute values. Control characters XML 1.0 can't hold
+/// (from test output) are dropped.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\n' | '\t' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,49 @@
+use mutator::baseline_cache::{self, CachedBaseline};
+use std::path::Path;
+
+fn entry(key: &str, duration_ms: u64) -> CachedBaseline {
+    CachedBaseline { key: key.to_string(), passed: true, duration_ms, output: None }
+}
+
+#[test]
+fn cache_path_is_under_dot_mutator() {
+    assert_eq!(
+        baseline_cache::cache_path(Path::new("/project")),
+        Path::new("/project/.mutator/baseline-cache.json")
+    );
+}
+
+#[test]
+fn store_and_lookup_by_key() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = baseline_cache::cache_path(dir.path());
+    assert!(baseline_cache::lookup(&path, "a").is_none());
+
+    baseline_cache::store(&path, entry("a", 100));
+    baseline_cache::store(&path, entry("b", 200));
+    baseline_cache::store(&path, entry("a", 150));
+
+    assert_eq!(baseline_cache::lookup(&path, "a").unwrap().duration_ms, 150);
+    assert_eq!(baseline_cache::lookup(&path, "b").unwrap().duration_ms, 200);
+}
+
+#[test]
+fn oldest_entries_are_dropped() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("cache.json");
+    for i in 0..40 {
+        baseline_cache::store(&path, entry(&i.to_string(), i));
+    }
+    assert!(baseline_cache::lookup(&path, "0").is_none());
+    assert!(baseline_cache::lookup(&path, "39").is_some());
+}
+
+#[test]
+fn corrupt_cache_is_a_miss() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("cache.json");
+    std::fs::write(&path, "{not json").unwrap();
+    assert!(baseline_cache::lookup(&path, "a").is_none());
+    baseline_cache::store(&path, entry("a", 1));
+    assert!(baseline_cache::lookup(&path, "a").is_some());
+}
@@ -0,0 +1,110 @@
+use mutator::compare;
+use mutator::mutants::MutantStatus;
+use mutator::state::{FunctionScore, MutantOutcome, RunResult, SurvivedMutant};
+
+fn survivor(ref_id: &str, line: usize, replacement: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: "app.py".into(),
+        line,
+        column: 13,
+        function: Some("is_positive".into()),
+        operator: "boundary".into(),
+        original: ">".into(),
+        replacement: replacement.into(),
+        ..Default::default()
+    }
+}
+
+fn outcome(line: usize, replacement: &str, status: MutantStatus) -> MutantOutcome {
+    MutantOutcome {
+        ref_id: None,
+        file: "app.py".into(),
+        line,
+        column: 13,
+        function: None,
+        operator: "boundary".into(),
+        original: ">".into(),
+        replacement: replacement.into(),
+        status,
+        duration_ms: 0,
+        killed_by: None,
+    }
+}
+
+fn score(score: f64, killed: usize, survived: usize) -> FunctionScore {
+    FunctionScore { score, total: killed + survived, killed, survived, ..Default::default() }
+}
+
+#[test]
+fn mutant_id_matches_runner_format() {
+    assert_eq!(compare::mutant_id(&survivor("m1", 5, ">=")), "5:13:boundary:>=");
+}
+
+#[test]
+fn reports_newly_killed_and_newly_surviving() {
+    let before = RunResult {
+        score: 0.5,
+        survived_mutants: vec![survivor("m1", 5, ">="), survivor("m2", 9, "<")],
+        ..Default::default()
+    };
+    let after = RunResult {
+        score: 0.75,
+        survived_mutants: vec![survivor("m1", 9, "<"), survivor("m2", 12, "==")],
+        ..Default::default()
+    };
+
+    let c = compare::compare(&before, &after);
+
+    assert_eq!(c.score_delta, 0.25);
+    assert_eq!(c.newly_killed.len(), 1);
+    assert_eq!(c.newly_killed[0].id, "5:13:boundary:>=");
+    assert_eq!(c.newly_killed[0].ref_id, "m1");
+    assert_eq!(c.newly_surviving.len(), 1);
+    assert_eq!(c.newly_surviving[0].id, "12:13:boundary:==");
+    assert_eq!(c.newly_surviving[0].ref_id, "m2");
+}
+
+#[test]
+fn still_timing_out_is_not_killed() {
+    let before = RunResult { survived_mutants: vec![survivor("m1", 5, ">=")], ..Default::default() };
+    let after = RunResult { timeout_mutants: vec![survivor("t1", 5, ">=")], ..Default::default() };
+
+    assert!(compare::compare(&before, &after).newly_killed.is_empty());
+}
+
+#[test]
+fn full_results_only_count_killed_mutants() {
+    let before = RunResult {
+        survived_mutants: vec![survivor("m1", 5, ">="), survivor("m2", 9, "<")],
+        ..Default::default()
+    };
+    // m2's code is gone: not generated in the second run, so not killed
+    let after = RunResult {
+        all_mutants: vec![outcome(5, ">=", MutantStatus::Killed), outcome(7, "<=", MutantStatus::Killed)],
+        ..Default::default()
+    };
+
+    let c = compare::compare(&before, &after);
+    let killed: Vec<_> = c.newly_killed.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(killed, vec!["5:13:boundary:>="]);
+}
+
+#[test]
+fn function_changes_skip_unchanged_functions() {
+    let mut before = RunResult::default();
+    before.functions.insert("add".into(), score(1.0, 2, 0));
+    before.functions.insert("is_positive".into(), score(0.5, 1, 1));
+    before.functions.insert("removed".into(), score(1.0, 1, 0));
+    let mut after = RunResult::default();
+    after.functions.insert("add".into(), score(1.0, 2, 0));
+    after.functions.insert("is_positive".into(), score(1.0, 2, 0));
+
+    let c = compare::compare(&before, &after);
+
+    let names: Vec<_> = c.functions.iter().map(|f| f.function.as_str()).collect();
+    assert_eq!(names, vec!["is_positive", "removed"]);
+    assert_eq!(c.functions[0].score_delta, Some(0.5));
+    assert!(c.functions[1].after.is_none());
+    assert_eq!(c.functions[1].score_delta, None);
+}
@@ -0,0 +1,99 @@
+use mutator::coverage::CoverageReport;
+use std::collections::BTreeSet;
+use std::path::Path;
+
+const COBERTURA: &str = r#"<?xml version="1.0" ?>
+<coverage version="7.4.0" line-rate="0.5">
+    <sources><source>/home/dev/project</source></sources>
+    <packages>
+        <package name="." line-rate="0.5">
+            <classes>
+                <class name="app.py" filename="app.py" line-rate="0.5">
+                    <methods/>
+                    <lines>
+                        <line number="1" hits="1"/>
+                        <line number="2" hits="1"/>
+                        <line number="4" hits="1"/>
+                        <line number="5" hits="0"/>
+                    </lines>
+                </class>
+            </classes>
+        </package>
+    </packages>
+</coverage>
+"#;
+
+const COVERAGE_JSON: &str = r#"{
+    "meta": {"version": "7.4.0"},
+    "files": {
+        "src/app.py": {
+            "executed_lines": [1, 2, 4],
+            "missing_lines": [5],
+            "excluded_lines": []
+        }
+    }
+}"#;
+
+const LCOV: &str = "TN:\nSF:src/lib.rs\nDA:1,3\nDA:2,0\nDA:7,0\nend_of_record\nSF:src/lib.rs\nDA:2,1\nend_of_record\n";
+
+fn lines(v: &[usize]) -> BTreeSet<usize> {
+    v.iter().copied().collect()
+}
+
+#[test]
+fn parses_cobertura_xml() {
+    let report = CoverageReport::parse(COBERTURA).unwrap();
+    let uncovered = report.uncovered_lines(Path::new("/home/dev/project/app.py")).unwrap();
+    assert_eq!(uncovered, lines(&[5]));
+}
+
+#[test]
+fn parses_coverage_py_json() {
+    let report = CoverageReport::parse(COVERAGE_JSON).unwrap();
+    let uncovered = report.uncovered_lines(Path::new("/repo/src/app.py")).unwrap();
+    assert_eq!(uncovered, lines(&[5]));
+}
+
+#[test]
+fn parses_lcov_and_merges_records() {
+    let report = CoverageReport::parse(LCOV).unwrap();
+    // Line 2 is hit by the second record, so only line 7 stays uncovered
+    let uncovered = report.uncovered_lines(Path::new("/repo/src/lib.rs")).unwrap();
+    assert_eq!(uncovered, lines(&[7]));
+}
+
+#[test]
+fn lcov_leading_dot_slash_matches() {
+    let report = CoverageReport::parse("SF:./src/app.js\nDA:3,0\nend_of_record\n").unwrap();
+    assert!(report.lines_for(Path::new("/repo/src/app.js")).is_some());
+}
+
+#[test]
+fn unknown_file_is_none() {
+    let report = CoverageReport::parse(COVERAGE_JSON).unwrap();
+    assert!(report.uncovered_lines(Path::new("/repo/src/other.py")).is_none());
+    // Suffix match is component-wise, not string-wise
+    assert!(report.uncovered_lines(Path::new("/repo/mysrc/app.py")).is_none());
+}
+
+#[test]
+fn longest_suffix_wins() {
+    let lcov = "SF:app.py\nDA:1,0\nend_of_record\nSF:pkg/app.py\nDA:1,1\nend_of_record\n";
+    let report = CoverageReport::parse(lcov).unwrap();
+    assert!(report.uncovered_lines(Path::new("/repo/pkg/app.py")).unwrap().is_empty());
+    assert_eq!(report.uncovered_lines(Path::new("/repo/app.py")).unwrap(), lines(&[1]));
+}
+
+#[test]
+fn empty_or_malformed_report_is_error() {
+    assert!(CoverageReport::parse("").is_err());
+    assert!(CoverageReport::parse("{\"totals\": {}}").is_err());
+    assert!(CoverageReport::parse("DA:1,0\n").is_err());
+    assert!(CoverageReport::parse("<coverage><unclosed></coverage>").is_err());
+}
+
+#[test]
+fn load_missing_file_is_error() {
+    let err = CoverageReport::load(Path::new("/nonexistent/coverage.xml")).unwrap_err();
+    assert!(err.contains("Failed to read coverage file"));
+}
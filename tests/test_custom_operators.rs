@@ -0,0 +1,67 @@
+use mutator::custom_operators;
+use mutator::discovery::DiscoverOptions;
+use mutator::engine;
+use mutator::Language;
+use std::path::Path;
+
+#[test]
+fn parses_operators_with_defaults() {
+    let ops = custom_operators::parse(
+        r#"[
+            {"name": "cents", "languages": ["py"], "kinds": ["integer"], "match": "^100$", "replacement": "10"},
+            {"name": "zero", "kinds": ["integer"], "replacement": "0"}
+        ]"#,
+    )
+    .unwrap();
+    assert_eq!(ops[0].name, "cents");
+    assert!(ops[0].applies_to(Language::Python));
+    assert!(!ops[0].applies_to(Language::Rust));
+    assert!(ops[1].applies_to(Language::Rust), "no languages means all of them");
+}
+
+#[test]
+fn rejects_bad_operators() {
+    let no_target = custom_operators::parse(r#"[{"name": "x", "replacement": "y"}]"#).unwrap_err();
+    assert!(no_target.contains("needs kinds, match or both"), "{}", no_target);
+    let bad_regex = custom_operators::parse(r#"[{"name": "x", "match": "(", "replacement": "y"}]"#).unwrap_err();
+    assert!(bad_regex.contains("invalid match"), "{}", bad_regex);
+    assert!(custom_operators::parse(r#"[{"name": "x", "match": "a", "replace": "y"}]"#).is_err());
+    assert!(custom_operators::parse(r#"[{"name": "x", "match": "a", "replacement": "y", "languages": ["cobol"]}]"#).is_err());
+    let err = custom_operators::load(Path::new("/nonexistent/ops.json")).unwrap_err();
+    assert!(err.contains("Failed to read operators file"));
+}
+
+#[test]
+fn custom_operators_join_discovery_in_functions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source = dir.path().join("app.py");
+    std::fs::write(
+        &source,
+        "RATE = 100\n\ndef price(x):\n    print(round(x))\n    return round(x * 100)\n\ndef test_price():\n    assert price(1) == 100\n",
+    )
+    .unwrap();
+    let ops = dir.path().join("ops.json");
+    std::fs::write(
+        &ops,
+        r#"[
+            {"name": "cents", "kinds": ["integer"], "match": "^(1)00$", "replacement": "${1}0"},
+            {"name": "round_down", "match": "^round$", "replacement": "floor"},
+            {"name": "rust_only", "languages": ["rust"], "match": "x", "replacement": "y"}
+        ]"#,
+    )
+    .unwrap();
+
+    let operators = engine::load_custom_operators(&ops).unwrap();
+    let options = DiscoverOptions { operators: &operators, ..Default::default() };
+    let listed = engine::list_mutations(&source, None, Some("price"), &options).unwrap();
+    let custom: Vec<_> = listed
+        .iter()
+        .filter(|m| m.operator != "arith" && m.operator != "return_val")
+        .map(|m| (m.line, m.operator.as_str(), m.replacement.as_str()))
+        .collect();
+    // Not in the skipped print call, the test or module-level code
+    assert_eq!(custom, [(5, "cents", "10"), (5, "round_down", "floor")]);
+
+    let toplevel = engine::list_mutations(&source, None, None, &DiscoverOptions { include_toplevel: true, ..options }).unwrap();
+    assert!(toplevel.iter().any(|m| m.line == 1 && m.operator == "cents"));
+}
@@ -0,0 +1,61 @@
+#![cfg(unix)]
+
+use mutator::daemon::Daemon;
+
+fn call(daemon: &mut Daemon, request: &str) -> (serde_json::Value, bool) {
+    let (line, shutdown) = daemon.handle_line(request);
+    (serde_json::from_str(&line).unwrap(), shutdown)
+}
+
+#[test]
+fn malformed_request_is_a_parse_error() {
+    let (response, shutdown) = call(&mut Daemon::new(), "{not json");
+    assert_eq!(response["jsonrpc"], "2.0");
+    assert!(response["id"].is_null());
+    assert_eq!(response["error"]["code"], -32700);
+    assert!(!shutdown);
+}
+
+#[test]
+fn unknown_method_is_reported_with_request_id() {
+    let (response, _) = call(&mut Daemon::new(), r#"{"jsonrpc":"2.0","id":7,"method":"mutate"}"#);
+    assert_eq!(response["id"], 7);
+    assert_eq!(response["error"]["code"], -32601);
+}
+
+#[test]
+fn run_with_bad_params_is_invalid_params() {
+    let (response, _) = call(
+        &mut Daemon::new(),
+        r#"{"jsonrpc":"2.0","id":1,"method":"run","params":{"file":"app.py","tset":"test_app.py"}}"#,
+    );
+    assert_eq!(response["error"]["code"], -32602);
+}
+
+#[test]
+fn run_with_missing_source_carries_exit_code() {
+    let (response, _) = call(
+        &mut Daemon::new(),
+        r#"{"jsonrpc":"2.0","id":1,"method":"run","params":{"file":"/nonexistent/app.py","test":"/nonexistent/test_app.py"}}"#,
+    );
+    assert_eq!(response["error"]["code"], -32000);
+    assert_eq!(response["error"]["data"]["exit_code"], 2);
+}
+
+#[test]
+fn status_without_a_run_fails() {
+    let (response, _) = call(
+        &mut Daemon::new(),
+        r#"{"jsonrpc":"2.0","id":"a","method":"status","params":{"session":"no-such-daemon-session"}}"#,
+    );
+    assert_eq!(response["id"], "a");
+    assert_eq!(response["error"]["message"], "No previous run found");
+}
+
+#[test]
+fn shutdown_is_acknowledged() {
+    let (response, shutdown) = call(&mut Daemon::new(), r#"{"jsonrpc":"2.0","id":2,"method":"shutdown"}"#);
+    assert!(response["result"].is_null());
+    assert!(response.get("error").is_none());
+    assert!(shutdown);
+}
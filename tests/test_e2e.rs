@@ -12,6 +12,17 @@ fn mutator_bin() -> std::path::PathBuf {
     path
 }
 
+/// A long-running `mutator` process (the daemon) that is killed and
+/// reaped when the test ends, even when an assertion fails first.
+struct KillOnDrop(std::process::Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
 fn create_python_project(dir: &Path) {
     std::fs::write(
         dir.join("app.py"),
@@ -71,12 +82,94 @@ fn e2e_full_run_json_output() {
     let result: serde_json::Value = serde_json::from_str(stdout.trim())
         .unwrap_or_else(|e| panic!("Invalid JSON: {e}\nstdout: {stdout}\nstderr: {}", String::from_utf8_lossy(&output.stderr)));
 
+    assert_eq!(result["schema_version"], mutator::schema::SCHEMA_VERSION);
     assert!(result["total"].as_u64().unwrap() > 0, "Should find mutations");
     assert!(result["killed"].as_u64().unwrap() > 0, "Should kill some mutants");
     assert!(result["score"].as_f64().unwrap() > 0.0, "Score should be > 0");
     assert!(result["survived_mutants"].is_array());
 }
 
+#[test]
+fn e2e_full_results_lists_every_mutant() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    let output = Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_app.py", "--json", "-f", "add", "--full-results", "--test-cmd", "pytest"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+
+    let result: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    let all = result["all_mutants"].as_array().unwrap();
+    assert_eq!(all.len() as u64, result["total"].as_u64().unwrap());
+    let killed: Vec<_> = all.iter().filter(|m| m["status"] == "Killed").collect();
+    assert_eq!(killed.len() as u64, result["killed"].as_u64().unwrap());
+    assert!(killed.iter().all(|m| m["killed_by"].is_string() && m.get("ref_id").is_none()));
+}
+
+#[test]
+fn e2e_show_line_lists_mutants_near_location() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    let run = |args: &[&str]| {
+        Command::new(mutator_bin())
+            .args(args)
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run mutator")
+    };
+
+    run(&["run", "app.py", "-t", "test_app.py", "--quiet", "--test-cmd", "pytest"]);
+    let output = run(&["show", "--line", "6"]);
+    assert_eq!(output.status.code(), Some(2), "needs a --full-results run");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--full-results"));
+
+    run(&["run", "app.py", "-t", "test_app.py", "--quiet", "--full-results", "--test-cmd", "pytest"]);
+    let output = run(&["show", "--line", "app.py:6", "--near", "0", "--json"]);
+    assert_eq!(output.status.code(), Some(0));
+    let found: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    assert_eq!(found["file"], "app.py");
+    assert_eq!(found["line"], 6);
+    let mutants = found["mutants"].as_array().unwrap();
+    assert!(!mutants.is_empty());
+    assert!(mutants.iter().all(|m| m["line"] == 6 && m["file"] == "app.py"));
+    assert!(mutants.iter().any(|m| m["status"] == "Killed"));
+}
+
+#[test]
+fn e2e_diff_tests_suggests_boundary_inputs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    std::fs::write(
+        dir.path().join("test_app.py"),
+        "from app import is_positive\n\ndef test_is_positive():\n    assert is_positive(5)\n",
+    )
+    .unwrap();
+
+    Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_app.py", "-f", "is_positive", "--quiet", "--test-cmd", "pytest"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    let output = Command::new(mutator_bin())
+        .args(["diff-tests", "--json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+
+    let suggestions: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    let boundary = suggestions
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["operator"] == "boundary")
+        .expect("boundary survivor");
+    assert_eq!(boundary["inputs"][0], "n = 0 (`n > 0` vs `n >= 0`)");
+    assert!(boundary["skeleton"].as_str().unwrap().starts_with("def test_is_positive_m"));
+}
+
 #[test]
 fn e2e_function_scoping() {
     let dir = tempfile::TempDir::new().unwrap();
@@ -175,6 +268,33 @@ fn e2e_missing_test_file() {
     assert_eq!(output.status.code(), Some(2));
 }
 
+#[test]
+fn e2e_test_file_discovered_when_omitted() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    let output = Command::new(mutator_bin())
+        .args(["run", "app.py", "-f", "add", "--json", "--test-cmd", "pytest"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+
+    let result: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    assert!(result["killed"].as_u64().unwrap() > 0, "Should run test_app.py: {result}");
+
+    std::fs::create_dir(dir.path().join("tests")).unwrap();
+    std::fs::copy(dir.path().join("test_app.py"), dir.path().join("tests/test_app.py")).unwrap();
+    let output = Command::new(mutator_bin())
+        .args(["run", "app.py", "--test-cmd", "pytest"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Several test files match"), "Should list candidates: {stderr}");
+}
+
 #[test]
 fn e2e_invalid_function_name() {
     let dir = tempfile::TempDir::new().unwrap();
@@ -208,6 +328,50 @@ fn e2e_isolation_does_not_modify_original() {
     assert_eq!(original, after, "Source file should not be modified after isolated run");
 }
 
+#[test]
+fn e2e_source_from_stdin_is_mutated_at_the_declared_path() {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    std::fs::write(dir.path().join("test_draft.py"), "from draft import double\n\ndef test_double():\n    assert double(3) == 6\n").unwrap();
+
+    let mut child = Command::new(mutator_bin())
+        .args(["run", "-", "--stdin-path", "draft.py", "-t", "test_draft.py", "--json", "--test-cmd", "pytest"])
+        .current_dir(dir.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run mutator");
+    child.stdin.take().unwrap().write_all(b"def double(n):\n    return n * 2\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    let result: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    assert!(result["total"].as_u64().unwrap() > 0);
+    assert_eq!(result["killed"], result["total"]);
+    assert!(!dir.path().join("draft.py").exists());
+}
+
+#[test]
+fn e2e_link_copy_mode_does_not_modify_original() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    let original = std::fs::read_to_string(dir.path().join("app.py")).unwrap();
+    let tests = std::fs::read_to_string(dir.path().join("test_app.py")).unwrap();
+
+    let output = Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_app.py", "--json", "--copy-mode", "link"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    let json: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    assert_eq!(json["killed"], json["total"]);
+
+    assert_eq!(std::fs::read_to_string(dir.path().join("app.py")).unwrap(), original);
+    assert_eq!(std::fs::read_to_string(dir.path().join("test_app.py")).unwrap(), tests);
+}
+
 #[test]
 fn e2e_quiet_mode_no_output() {
     let dir = tempfile::TempDir::new().unwrap();
@@ -255,3 +419,353 @@ fn e2e_temp_dirs_cleaned_up() {
         leftover.iter().map(|e| e.path()).collect::<Vec<_>>()
     );
 }
+
+#[cfg(unix)]
+#[test]
+fn e2e_interrupted_baseline_removes_temp_dir() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    let started = dir.path().join("started");
+    std::fs::write(dir.path().join("test_slow.sh"), format!("touch {}\nsleep 5\n", started.display())).unwrap();
+
+    let session_id = format!("interrupted-baseline-{}", std::process::id());
+    let mut child = Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_slow.sh", "-q", "--test-cmd", "sh", "--session", &session_id])
+        .current_dir(dir.path())
+        .spawn()
+        .expect("failed to run mutator");
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while !started.exists() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert!(started.exists(), "baseline never started");
+    Command::new("kill").args(["-INT", &child.id().to_string()]).status().unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(130));
+
+    let leftover: Vec<_> = std::fs::read_dir(std::env::temp_dir())
+        .unwrap()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_name().to_string_lossy().contains(&format!("mutator-{}", session_id)))
+        .map(|e| e.path())
+        .collect();
+    assert!(leftover.is_empty(), "Temp dir left after interrupted baseline: {:?}", leftover);
+}
+
+fn run_weak_tests(dir: &Path, extra: &[&str]) -> Option<i32> {
+    std::fs::write(dir.join("test_weak.py"), "from app import add\n\ndef test_add():\n    add(1, 2)\n").unwrap();
+    Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_weak.py", "-f", "add", "-q", "--test-cmd", "pytest"])
+        .args(extra)
+        .current_dir(dir)
+        .output()
+        .expect("failed to run mutator")
+        .status
+        .code()
+}
+
+#[test]
+fn e2e_fail_on_policies() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    assert_eq!(run_weak_tests(dir.path(), &[]), Some(1), "survivors fail by default");
+    assert_eq!(run_weak_tests(dir.path(), &["--fail-under", "0.0"]), Some(0));
+    assert_eq!(run_weak_tests(dir.path(), &["--fail-under", "0.5"]), Some(1));
+    assert_eq!(run_weak_tests(dir.path(), &["--fail-on", "never"]), Some(0));
+    assert_eq!(run_weak_tests(dir.path(), &["--fail-on", "threshold"]), Some(2), "threshold needs --fail-under");
+    assert_eq!(run_weak_tests(dir.path(), &["--fail-under", "1.5"]), Some(2));
+}
+
+#[test]
+fn e2e_sessions_keep_separate_state() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    for (session, function) in [("agent-a", "add"), ("agent-b", "greet")] {
+        Command::new(mutator_bin())
+            .args(["run", "app.py", "-t", "test_app.py", "-q", "-f", function, "--test-cmd", "pytest"])
+            .args(["--session", session])
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run mutator");
+    }
+    assert!(dir.path().join(".mutator/state-agent-a.json").exists());
+    assert!(dir.path().join(".mutator/state-agent-b.json").exists());
+    assert!(!dir.path().join(".mutator-state.json").exists(), "named sessions don't touch the default state");
+
+    let status = |session: &str| -> serde_json::Value {
+        let output = Command::new(mutator_bin())
+            .args(["status", "--json", "--session", session])
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run mutator");
+        serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap()
+    };
+    assert_ne!(status("agent-a")["total"], status("agent-b")["total"]);
+
+    let missing = Command::new(mutator_bin())
+        .args(["status", "--session", "agent-c"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert_eq!(missing.status.code(), Some(2));
+}
+
+#[test]
+fn e2e_history_records_each_run() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    for function in ["add", "add"] {
+        Command::new(mutator_bin())
+            .args(["run", "app.py", "-t", "test_app.py", "-q", "-f", function, "--test-cmd", "pytest"])
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run mutator");
+    }
+
+    let output = Command::new(mutator_bin())
+        .args(["history", "--json", "--file", "app.py"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    let points: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    let points = points.as_array().unwrap();
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0]["function"], "add");
+    assert!(points[0]["delta"].is_null());
+    assert_eq!(points[1]["delta"], 0.0);
+}
+
+#[test]
+fn e2e_compare_reports_mutants_killed_by_new_tests() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    let run = || {
+        Command::new(mutator_bin())
+            .args(["run", "app.py", "-t", "test_app.py", "-q", "-f", "is_positive", "--test-cmd", "pytest"])
+            .current_dir(dir.path())
+            .output()
+            .expect("failed to run mutator")
+    };
+
+    let too_few = Command::new(mutator_bin())
+        .args(["compare"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert_eq!(too_few.status.code(), Some(2));
+
+    // Weak test first: `n >= 0` survives
+    std::fs::write(
+        dir.path().join("test_app.py"),
+        "from app import is_positive\n\ndef test_is_positive():\n    assert is_positive(1) is True\n",
+    )
+    .unwrap();
+    run();
+    create_python_project(dir.path());
+    run();
+
+    let output = Command::new(mutator_bin())
+        .args(["compare", "--json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    let comparison: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    assert!(comparison["score_delta"].as_f64().unwrap() > 0.0);
+    let killed = comparison["newly_killed"].as_array().unwrap();
+    assert!(killed.iter().any(|m| m["operator"] == "boundary" && m["replacement"] == ">="), "{}", comparison);
+    assert!(comparison["newly_surviving"].as_array().unwrap().is_empty());
+    assert_eq!(comparison["functions"][0]["function"], "is_positive");
+
+    let text = Command::new(mutator_bin())
+        .args(["compare", "~1", "~0"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert!(String::from_utf8_lossy(&text.stdout).contains("Newly killed"));
+}
+
+#[test]
+fn e2e_list_runs_no_tests() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    let output = Command::new(mutator_bin())
+        .args(["list", "app.py", "-f", "is_positive", "--json"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert_eq!(output.status.code(), Some(0));
+
+    let items: serde_json::Value = serde_json::from_str(String::from_utf8_lossy(&output.stdout).trim()).unwrap();
+    let items = items.as_array().unwrap();
+    assert!(items.iter().any(|m| m["operator"] == "boundary" && m["original"] == ">"));
+    assert!(!dir.path().join(".mutator-state.json").exists(), "list must not record a run");
+}
+
+#[cfg(feature = "tui")]
+#[test]
+fn e2e_tui_requires_terminal() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    let output = Command::new(mutator_bin())
+        .args(["tui", "app.py", "-t", "test_app.py"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("needs a terminal"));
+    assert!(!dir.path().join(".mutator-state.json").exists());
+}
+
+#[test]
+fn e2e_watch_reruns_on_change_and_reuses_unchanged_functions() {
+    use std::io::BufRead;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+
+    let mut child = Command::new(mutator_bin())
+        .args(["watch", "app.py", "-t", "test_app.py", "-f", "is_positive", "--json"])
+        .current_dir(dir.path())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .expect("failed to run mutator");
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout).lines().map_while(Result::ok) {
+            let _ = tx.send(line);
+        }
+    });
+    let next_run = || -> Vec<serde_json::Value> {
+        let mut lines = Vec::new();
+        loop {
+            let line = rx.recv_timeout(std::time::Duration::from_secs(60)).expect("no run summary");
+            let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+            let done = value.get("score").is_some();
+            lines.push(value);
+            if done {
+                return lines;
+            }
+        }
+    };
+
+    let first = next_run();
+    assert!(first.iter().any(|v| v["event"] == "mutant" && v["reused"] == false));
+
+    // Shift every line down; is_positive's text is unchanged
+    let source = std::fs::read_to_string(dir.path().join("app.py")).unwrap();
+    std::fs::write(dir.path().join("app.py"), format!("import math\n{}", source)).unwrap();
+    let second = next_run();
+    let _ = child.kill();
+    let _ = child.wait();
+
+    let mutants: Vec<_> = second.iter().filter(|v| v["event"] == "mutant").collect();
+    assert!(!mutants.is_empty());
+    assert!(mutants.iter().all(|v| v["reused"] == true));
+    let summary = second.last().unwrap();
+    assert_eq!(summary["total"], first.last().unwrap()["total"]);
+    assert_eq!(mutants[0]["line"].as_u64().unwrap(), first[0]["line"].as_u64().unwrap() + 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn e2e_daemon_serves_runs_and_reuses_copy() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    let socket = dir.path().join("d.sock");
+
+    let mut child = KillOnDrop(
+        Command::new(mutator_bin())
+            .args(["daemon", "--socket", "d.sock"])
+            .current_dir(dir.path())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .expect("failed to run mutator"),
+    );
+    let stream = (0..100)
+        .find_map(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            UnixStream::connect(&socket).ok()
+        })
+        .expect("daemon did not start");
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut writer = stream;
+    let mut call = |request: serde_json::Value| -> serde_json::Value {
+        writeln!(writer, "{}", request).unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    };
+
+    let run = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "run",
+        "params": {"file": "app.py", "test": "test_app.py", "function": "is_positive"}});
+    let first = call(run.clone());
+    assert_eq!(first["result"]["killed"], first["result"]["total"]);
+
+    // Weaken the tests: the warm copy must pick up the edit
+    std::fs::write(
+        dir.path().join("test_app.py"),
+        "from app import is_positive\n\ndef test_is_positive():\n    assert is_positive(1)\n",
+    )
+    .unwrap();
+    let second = call(run);
+    assert!(second["result"]["survived"].as_u64().unwrap() > 0);
+
+    let status = call(serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "status"}));
+    assert_eq!(status["result"]["survived"], second["result"]["survived"]);
+    let show = call(serde_json::json!({"jsonrpc": "2.0", "id": 3, "method": "show", "params": {"ref": "@m1"}}));
+    assert_eq!(show["result"]["ref_id"], "m1");
+
+    let bye = call(serde_json::json!({"jsonrpc": "2.0", "id": 4, "method": "shutdown"}));
+    assert_eq!(bye["id"], 4);
+    assert_eq!(child.0.wait().unwrap().code(), Some(0));
+    assert!(!socket.exists());
+}
+
+#[test]
+fn e2e_log_file_records_commands_and_mutant_runs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_python_project(dir.path());
+    let log = dir.path().join("mutator.log");
+
+    let output = Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_app.py", "-q", "-f", "is_positive", "--test-cmd", "pytest", "--log-file"])
+        .arg(&log)
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.stderr.is_empty(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let text = std::fs::read_to_string(&log).unwrap();
+    assert!(text.contains("Test command `pytest`"), "{}", text);
+    assert!(text.contains("Discovered"), "{}", text);
+    assert!(text.contains("Copied"), "{}", text);
+    assert!(text.contains("Baseline"), "{}", text);
+    assert!(text.contains("Mutant 6:"), "{}", text);
+
+    // -v keeps to the summary lines, on stderr
+    let verbose = Command::new(mutator_bin())
+        .args(["-v", "list", "app.py", "-f", "is_positive"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    assert!(String::from_utf8_lossy(&verbose.stderr).is_empty());
+    let run = Command::new(mutator_bin())
+        .args(["run", "app.py", "-t", "test_app.py", "-q", "-f", "is_positive", "--test-cmd", "pytest", "-v"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run mutator");
+    let stderr = String::from_utf8_lossy(&run.stderr);
+    assert!(stderr.contains("Discovered"), "{}", stderr);
+    assert!(!stderr.contains("Mutant 6:"), "{}", stderr);
+}
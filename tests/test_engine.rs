@@ -0,0 +1,1236 @@
+use mutator::discovery::DiscoverOptions;
+use mutator::engine::{self, EngineConfig, EngineError, MutatorEngine};
+use mutator::mutants::{self, MutantStatus};
+use mutator::test_adapters::{Runner, TestArgStyle};
+use std::path::Path;
+
+fn create_project(dir: &Path) {
+    std::fs::write(dir.join("pyproject.toml"), "[project]").unwrap();
+    std::fs::write(
+        dir.join("app.py"),
+        "def add(a, b):\n    return a + b\n\ndef is_positive(n):\n    return n > 0\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("test_app.py"), "").unwrap();
+}
+
+fn config(dir: &Path, test_cmd: &str) -> EngineConfig {
+    let mut config = EngineConfig::new(dir.join("app.py"), dir.join("test_app.py"));
+    config.test_cmd = test_cmd.to_string();
+    config
+}
+
+#[test]
+fn config_new_uses_cli_defaults() {
+    let config = EngineConfig::new("app.py", "test_app.py");
+    assert_eq!(config.test_cmd, "pytest");
+    assert_eq!(config.timeout_mult, 3.0);
+    assert!(config.function.is_none());
+    assert!(!config.in_place);
+}
+
+#[test]
+fn new_missing_source_is_usage_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("test_app.py"), "").unwrap();
+
+    let err = MutatorEngine::new(config(dir.path(), "true")).err().unwrap();
+    assert!(matches!(err, EngineError::SourceNotFound(_)));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn source_text_is_mutated_in_the_copy_instead_of_the_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let buffer = "def double(n):\n    return n * 2  # unsaved\n";
+
+    // The declared path needn't exist; the copy gets the buffer there
+    let mut config = EngineConfig::new(dir.path().join("draft.py"), dir.path().join("test_app.py"));
+    config.test_cmd = "sh -c 'grep -q unsaved draft.py'".to_string();
+    config.source_text = Some(buffer.to_string());
+    let mut engine = MutatorEngine::new(config).unwrap();
+    assert_eq!(engine.source(), buffer);
+    let mutations = engine.discover();
+    assert!(mutations.iter().all(|m| m.function.as_deref() == Some("double")));
+
+    engine.baseline().unwrap();
+    let results = engine.run_all(&mutations).unwrap();
+    assert_eq!(results.len(), mutations.len());
+    assert!(!dir.path().join("draft.py").exists());
+}
+
+#[test]
+fn source_text_cannot_be_mutated_in_place() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut config = config(dir.path(), "true");
+    config.source_text = Some("x = 1\n".to_string());
+    config.in_place = true;
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::SourceTextInPlace));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn new_unsupported_language() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("main.go"), "package main").unwrap();
+    std::fs::write(dir.path().join("test_app.py"), "").unwrap();
+
+    let config = EngineConfig::new(dir.path().join("main.go"), dir.path().join("test_app.py"));
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::UnsupportedLanguage(_)));
+    assert!(err.to_string().contains("Unsupported file type"));
+}
+
+#[test]
+fn language_override_handles_files_without_an_extension() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("deploy"), "def retries(n):\n    return n + 1\n").unwrap();
+    std::fs::write(dir.path().join("test_deploy.py"), "").unwrap();
+
+    let mut config = EngineConfig::new(dir.path().join("deploy"), dir.path().join("test_deploy.py"));
+    assert!(matches!(MutatorEngine::new(config.clone()), Err(EngineError::UnsupportedLanguage(_))));
+    config.language = Some(mutator::Language::Python);
+    let engine = MutatorEngine::new(config).unwrap();
+    assert!(!engine.discover().is_empty());
+
+    let listed = engine::list_mutations(&dir.path().join("deploy"), Some(mutator::Language::Python), None, &DiscoverOptions::default()).unwrap();
+    assert_eq!(listed.len(), engine.discover().len());
+}
+
+#[test]
+fn new_unknown_function_lists_available() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut config = config(dir.path(), "true");
+    config.function = Some("nope".to_string());
+    let err = MutatorEngine::new(config).err().unwrap();
+    match err {
+        EngineError::FunctionNotFound { name, available } => {
+            assert_eq!(name, "nope");
+            assert_eq!(available, vec!["add", "is_positive"]);
+        }
+        other => panic!("Expected FunctionNotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn discover_respects_function_scope() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let all = MutatorEngine::new(config(dir.path(), "true")).unwrap().discover();
+    let mut scoped_config = config(dir.path(), "true");
+    scoped_config.function = Some("add".to_string());
+    let scoped = MutatorEngine::new(scoped_config).unwrap().discover();
+
+    assert!(!scoped.is_empty());
+    assert!(scoped.len() < all.len());
+}
+
+#[test]
+fn function_scope_accepts_plain_and_qualified_method_names() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    std::fs::write(
+        dir.path().join("app.py"),
+        "class A:\n    def check(self, x):\n        return x > 0\n\nclass B:\n    def check(self, x):\n        return x < 0\n",
+    )
+    .unwrap();
+
+    let mut qualified = config(dir.path(), "true");
+    qualified.function = Some("B.check".to_string());
+    let mutations = MutatorEngine::new(qualified).unwrap().discover();
+    assert!(!mutations.is_empty());
+    assert!(mutations.iter().all(|m| m.line == 7));
+
+    let mut plain = config(dir.path(), "true");
+    plain.function = Some("check".to_string());
+    assert!(MutatorEngine::new(plain).is_ok());
+
+    let mut missing = config(dir.path(), "true");
+    missing.function = Some("C.check".to_string());
+    match MutatorEngine::new(missing).err().unwrap() {
+        EngineError::FunctionNotFound { available, .. } => assert_eq!(available, vec!["A.check", "B.check"]),
+        other => panic!("Expected FunctionNotFound, got {:?}", other),
+    }
+}
+
+#[test]
+fn include_toplevel_adds_module_level_mutants() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    std::fs::write(dir.path().join("app.py"), "LIMIT = 10 * 2\n\ndef add(a, b):\n    return a + b\n").unwrap();
+
+    let default = MutatorEngine::new(config(dir.path(), "true")).unwrap().discover();
+    assert!(default.iter().all(|m| m.line == 4));
+
+    let mut toplevel = config(dir.path(), "true");
+    toplevel.include_toplevel = true;
+    let mutations = MutatorEngine::new(toplevel).unwrap().discover();
+    assert!(mutations.iter().any(|m| m.line == 1 && m.function.is_none()));
+    assert!(mutations.windows(2).all(|w| w[0].line <= w[1].line));
+
+    // Scoping to a function leaves module-level code out
+    let mut scoped = config(dir.path(), "true");
+    scoped.include_toplevel = true;
+    scoped.function = Some("add".to_string());
+    assert!(MutatorEngine::new(scoped).unwrap().discover().iter().all(|m| m.line == 4));
+}
+
+#[test]
+fn skip_decorated_leaves_out_decorated_functions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    std::fs::write(
+        dir.path().join("app.py"),
+        "import functools\n\n@functools.cache\ndef double(x):\n    return x * 2\n\ndef add(a, b):\n    return a + b\n",
+    )
+    .unwrap();
+
+    let mut skipping = config(dir.path(), "true");
+    skipping.skip_decorated = vec!["cache".to_string()];
+    let mutations = MutatorEngine::new(skipping).unwrap().discover();
+    assert!(!mutations.is_empty());
+    assert!(mutations.iter().all(|m| m.line == 8));
+
+    let listed = engine::list_mutations(&dir.path().join("app.py"), None, None, &DiscoverOptions::default()).unwrap();
+    assert!(listed.iter().any(|m| m.line == 5));
+}
+
+#[test]
+fn precheck_marks_mutants_that_do_not_compile_unviable() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let source = std::fs::read_to_string(dir.path().join("app.py")).unwrap();
+    let mutations = MutatorEngine::new(config(dir.path(), "true")).unwrap().discover();
+    let mut broken = mutations.into_iter().find(|m| m.operator == "return_val").unwrap();
+    broken.replacement = "+ +".to_string();
+
+    let mut unchecked = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    assert_eq!(unchecked.run_one(&broken).unwrap().status, MutantStatus::Survived);
+
+    let mut checked = config(dir.path(), "true");
+    checked.precheck = true;
+    let mut engine = MutatorEngine::new(checked).unwrap();
+    let result = engine.run_one(&broken).unwrap();
+    assert_eq!(result.status, MutantStatus::Unviable);
+    assert!(result.output_snippet.is_some());
+    assert_eq!(std::fs::read_to_string(dir.path().join("app.py")).unwrap(), source);
+}
+
+#[test]
+fn precheck_failing_on_the_original_stops_the_run() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    std::fs::write(dir.path().join("app.py"), "def add(a, b):\n    return a +\n").unwrap();
+
+    let mut checked = config(dir.path(), "true");
+    checked.precheck = true;
+    let err = MutatorEngine::new(checked).unwrap().baseline().unwrap_err();
+    assert!(matches!(err, EngineError::Setup(ref msg) if msg.contains("py_compile")), "{:?}", err);
+    assert_eq!(err.exit_code(), 3);
+}
+
+#[test]
+fn command_plan_shows_commands_without_running_them() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let log = dir.path().join("ran");
+    let mut config = config(dir.path(), &format!("touch {}", log.display()));
+    config.runner = Some(Runner::Pytest);
+    config.env = vec![("APP_MODE".to_string(), "test".to_string())];
+    let engine = MutatorEngine::new(config).unwrap();
+
+    let plan = engine.command_plan();
+    assert_eq!(plan.baseline.program, "touch");
+    assert!(plan.baseline.args.iter().any(|a| a.ends_with("test_app.py")));
+    assert_eq!(plan.baseline.cwd.as_deref(), Some(dir.path().canonicalize().unwrap().as_path()));
+    assert!(plan.baseline.env.contains(&("APP_MODE".to_string(), "test".to_string())));
+    assert!(plan.mutant.env.iter().any(|(k, _)| k == "MUTATOR_MUTANT_ID"));
+    assert!(plan.mutant.shell().contains("MUTATOR_MUTANT_ID='<mutant id>'"));
+    assert!(plan.build.is_none());
+    assert!(plan.isolated_from.is_some());
+    assert!(!log.exists());
+}
+
+#[test]
+fn discover_orders_by_line_then_operator() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let mutations = MutatorEngine::new(config(dir.path(), "true")).unwrap().discover();
+    let order: Vec<_> = mutations.iter().map(|m| (m.line, m.operator.as_str())).collect();
+    assert_eq!(
+        order,
+        [(2, "arith"), (2, "return_val"), (5, "boundary"), (5, "negate_cmp"), (5, "return_val")]
+    );
+}
+
+#[test]
+fn shuffle_changes_run_order_but_not_result_order() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let log = dir.path().join("ids.log");
+    let mut config = config(dir.path(), &format!("sh -c 'echo \"$MUTATOR_MUTANT_ID\" >> {}'", log.display()));
+    config.shuffle = Some(7);
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+
+    let mut reported = Vec::new();
+    let results = engine.run_all_with(&mutations, |i, r| reported.push((i, r.mutation.id()))).unwrap();
+    let result_ids: Vec<_> = results.iter().map(|r| r.mutation.id()).collect();
+    let discovered: Vec<_> = mutations.iter().map(|m| m.id()).collect();
+    assert_eq!(result_ids, discovered);
+    // Each result is reported in run order with its discovery index
+    assert_eq!(reported.iter().map(|(i, _)| *i).collect::<Vec<_>>(), engine.run_order(&mutations));
+    assert!(reported.iter().all(|(i, id)| *id == discovered[*i]));
+
+    let logged = std::fs::read_to_string(&log).unwrap();
+    let ran: Vec<_> = logged.lines().filter(|l| !l.is_empty()).collect();
+    let expected: Vec<_> = mutants::run_order(mutations.len(), Some(7)).into_iter().map(|i| discovered[i].as_str()).collect();
+    assert_eq!(ran, expected);
+    assert_ne!(ran, discovered);
+    assert_eq!(mutants::run_order(5, Some(7)), mutants::run_order(5, Some(7)));
+    assert_eq!(mutants::run_order(5, None), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn discover_honours_context_lines() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut none_config = config(dir.path(), "true");
+    none_config.context_lines = 0;
+    let none = MutatorEngine::new(none_config).unwrap().discover();
+    assert!(none.iter().all(|m| m.context_before.is_empty() && m.context_after.is_empty()));
+
+    let mut wide_config = config(dir.path(), "true");
+    wide_config.context_lines = 5;
+    let wide = MutatorEngine::new(wide_config).unwrap().discover();
+    let add = wide.iter().find(|m| m.line == 2).unwrap();
+    assert_eq!(add.context_before, vec!["def add(a, b):"]);
+    assert_eq!(add.context_after, vec!["", "def is_positive(n):", "    return n > 0"]);
+}
+
+#[test]
+fn list_mutations_needs_no_test_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    std::fs::remove_file(dir.path().join("test_app.py")).unwrap();
+
+    let listed = engine::list_mutations(&dir.path().join("app.py"), None, Some("add"), &DiscoverOptions::default()).unwrap();
+    assert!(listed.iter().any(|m| m.operator == "arith" && m.original == "+"));
+    assert!(listed.iter().all(|m| m.line <= 2));
+}
+
+#[test]
+fn list_mutations_matches_discover() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let discovered = MutatorEngine::new(config(dir.path(), "true")).unwrap().discover();
+    let listed = engine::list_mutations(&dir.path().join("app.py"), None, None, &DiscoverOptions::default()).unwrap();
+    assert_eq!(listed.len(), discovered.len());
+}
+
+#[test]
+fn enable_adds_opt_in_string_mutations() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    std::fs::write(dir.path().join("app.py"), "def mode():\n    name = \"fast\"\n    return name\n").unwrap();
+    let app = dir.path().join("app.py");
+
+    let default = engine::list_mutations(&app, None, None, &DiscoverOptions::default()).unwrap();
+    assert!(default.iter().all(|m| m.operator != "string_mut"));
+    let enabled = engine::list_mutations(&app, None, None, &DiscoverOptions { enable: &["string_mut".to_string()], ..Default::default() }).unwrap();
+    assert!(enabled.iter().any(|m| m.operator == "string_mut" && m.original == "\"fast\""));
+
+    let mut config = config(dir.path(), "true");
+    config.enable = vec!["string_mutt".to_string()];
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::UnknownOperator(_)));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn list_mutations_validates_function() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let err = engine::list_mutations(&dir.path().join("app.py"), None, Some("nope"), &DiscoverOptions::default()).unwrap_err();
+    assert!(matches!(err, EngineError::FunctionNotFound { .. }));
+    let err = engine::list_mutations(&dir.path().join("missing.py"), None, None, &DiscoverOptions::default()).unwrap_err();
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn baseline_failure_is_typed() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut engine = MutatorEngine::new(config(dir.path(), "false")).unwrap();
+    let err = engine.baseline().err().unwrap();
+    assert!(matches!(err, EngineError::BaselineFailed(_)));
+    assert_eq!(err.exit_code(), 3);
+}
+
+#[test]
+fn baseline_that_runs_no_tests_aborts() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut config = config(dir.path(), "sh -c 'echo \"no tests ran in 0.01s\"; exit 5'");
+    config.runner = Some(Runner::Pytest);
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let err = engine.baseline().err().unwrap();
+    assert!(matches!(err, EngineError::NoTests(_)));
+    assert!(err.to_string().contains("ran no tests"), "{}", err);
+    assert_eq!(err.exit_code(), 3);
+}
+
+#[test]
+fn baseline_sets_timeout() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut engine = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    assert!(engine.timeout_ms().is_none());
+    engine.baseline().unwrap();
+    assert!(engine.timeout_ms().unwrap() >= 2000);
+}
+
+/// `sh` test script that exits with the next status in `statuses` on each
+/// run (the last one repeats), counting runs in `runs.log`.
+fn scripted_project(dir: &Path, statuses: &str) -> EngineConfig {
+    create_project(dir);
+    let log = dir.join("runs.log");
+    std::fs::write(
+        dir.join("test_app.sh"),
+        format!(
+            "echo run >> {log}\nn=$(wc -l < {log})\nset -- {statuses}\nshift $(( n > $# ? $# - 1 : n - 1 ))\nexit $1\n",
+            log = log.display(),
+            statuses = statuses
+        ),
+    )
+    .unwrap();
+    let mut config = EngineConfig::new(dir.join("app.py"), dir.join("test_app.sh"));
+    config.test_cmd = "sh".to_string();
+    config.in_place = true;
+    config
+}
+
+#[test]
+fn baseline_retries_detect_flaky_tests() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = scripted_project(dir.path(), "0 1 0");
+    config.baseline_retries = 2;
+    let err = MutatorEngine::new(config).unwrap().baseline().err().unwrap();
+    assert!(matches!(&err, EngineError::FlakyTests(output) if output.contains("run 2 of 3 failed")), "{}", err);
+    assert_eq!(err.exit_code(), 3);
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = scripted_project(dir.path(), "1");
+    config.baseline_retries = 2;
+    let err = MutatorEngine::new(config).unwrap().baseline().err().unwrap();
+    assert!(matches!(err, EngineError::BaselineFailed(_)));
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = scripted_project(dir.path(), "0");
+    config.baseline_retries = 2;
+    MutatorEngine::new(config).unwrap().baseline().unwrap();
+    assert_eq!(std::fs::read_to_string(dir.path().join("runs.log")).unwrap().lines().count(), 3);
+}
+
+/// Project whose `sh` test script exits with `status`, logging each run to
+/// a file outside the project so the log doesn't change the tree.
+fn logged_project(dir: &Path, log: &Path, status: i32) -> EngineConfig {
+    create_project(dir);
+    std::fs::write(dir.join("test_app.sh"), format!("echo run >> {}\nexit {}\n", log.display(), status)).unwrap();
+    let mut config = EngineConfig::new(dir.join("app.py"), dir.join("test_app.sh"));
+    config.test_cmd = "sh".to_string();
+    config.in_place = true;
+    config
+}
+
+fn run_count(log: &Path) -> usize {
+    std::fs::read_to_string(log).map(|s| s.lines().count()).unwrap_or(0)
+}
+
+#[test]
+fn baseline_is_cached_until_the_tree_changes() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let logs = tempfile::TempDir::new().unwrap();
+    let log = logs.path().join("runs.log");
+    let config = logged_project(dir.path(), &log, 0);
+
+    let first = MutatorEngine::new(config.clone()).unwrap().baseline().unwrap();
+    let mut engine = MutatorEngine::new(config.clone()).unwrap();
+    assert_eq!(engine.baseline().unwrap(), first);
+    assert_eq!(run_count(&log), 1);
+    assert!(engine.timeout_ms().is_some());
+    assert!(dir.path().join(".mutator").join("baseline-cache.json").exists());
+
+    let mut uncached = config.clone();
+    uncached.baseline_cache = false;
+    MutatorEngine::new(uncached).unwrap().baseline().unwrap();
+    assert_eq!(run_count(&log), 2);
+
+    std::fs::write(dir.path().join("helper.py"), "X = 1\n").unwrap();
+    MutatorEngine::new(config.clone()).unwrap().baseline().unwrap();
+    assert_eq!(run_count(&log), 3);
+
+    let mut filtered = config;
+    filtered.test_filter = Some("add".to_string());
+    MutatorEngine::new(filtered).unwrap().baseline().unwrap();
+    assert_eq!(run_count(&log), 4);
+}
+
+#[test]
+fn failed_baseline_is_cached_too() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let logs = tempfile::TempDir::new().unwrap();
+    let log = logs.path().join("runs.log");
+    let config = logged_project(dir.path(), &log, 1);
+
+    assert!(MutatorEngine::new(config.clone()).unwrap().baseline().is_err());
+    let err = MutatorEngine::new(config).unwrap().baseline().err().unwrap();
+    assert!(matches!(&err, EngineError::BaselineFailed(output) if output.contains("--no-baseline-cache")), "{}", err);
+    assert_eq!(run_count(&log), 1);
+}
+
+#[test]
+fn verify_survivors_reruns_and_keeps_kills() {
+    // Baseline passes, the mutant passes once, then fails
+    let dir = tempfile::TempDir::new().unwrap();
+    let config = scripted_project(dir.path(), "0 0 1");
+    let mut engine = MutatorEngine::new(config.clone()).unwrap();
+    let mutations = engine.discover();
+    assert_eq!(engine.run_one(&mutations[0]).unwrap().status, MutantStatus::Survived);
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = scripted_project(dir.path(), "0 0 1");
+    config.verify_survivors = true;
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+    assert_eq!(engine.run_one(&mutations[0]).unwrap().status, MutantStatus::Killed);
+    assert_eq!(std::fs::read_to_string(dir.path().join("runs.log")).unwrap().lines().count(), 3);
+}
+
+#[test]
+fn timed_out_mutants_get_a_second_chance() {
+    // Baseline passes, the mutant's first run outlasts the timeout, the
+    // retry fails fast
+    let slow_then_fail = |dir: &Path| {
+        let mut config = scripted_project(dir, "0 0 1");
+        let script = std::fs::read_to_string(dir.join("test_app.sh")).unwrap();
+        std::fs::write(dir.join("test_app.sh"), script.replace("set --", "[ $n -eq 2 ] && sleep 5\nset --")).unwrap();
+        config.timeout_mult = 1.0;
+        config
+    };
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut engine = MutatorEngine::new(slow_then_fail(dir.path())).unwrap();
+    let mutations = engine.discover();
+    assert_eq!(engine.run_one(&mutations[0]).unwrap().status, MutantStatus::Killed);
+    assert_eq!(std::fs::read_to_string(dir.path().join("runs.log")).unwrap().lines().count(), 3);
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = slow_then_fail(dir.path());
+    config.timeout_retry = 0.0;
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+    assert_eq!(engine.run_one(&mutations[0]).unwrap().status, MutantStatus::Timeout);
+    assert_eq!(std::fs::read_to_string(dir.path().join("runs.log")).unwrap().lines().count(), 2);
+}
+
+#[test]
+fn timeout_retries_stop_when_the_source_changes() {
+    // The mutant's run times out and, meanwhile, the original is edited
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut config = scripted_project(dir.path(), "0 0 1");
+    let edited = "def add(a, b):\n    return a * b\n";
+    let script = std::fs::read_to_string(dir.path().join("test_app.sh")).unwrap();
+    let edit = format!("[ $n -eq 2 ] && printf '{}' > {} && sleep 5\nset --", edited, dir.path().join("app.py").display());
+    std::fs::write(dir.path().join("test_app.sh"), script.replace("set --", &edit)).unwrap();
+    config.timeout_mult = 1.0;
+    config.in_place = false;
+
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+    let err = engine.run_all(&mutations[..1]).err().unwrap();
+    assert!(matches!(err, EngineError::SourceChanged(_)), "{}", err);
+    // No retry ran on the stale mutant, and the edit survives
+    assert_eq!(std::fs::read_to_string(dir.path().join("runs.log")).unwrap().lines().count(), 2);
+    assert_eq!(std::fs::read_to_string(dir.path().join("app.py")).unwrap(), edited);
+}
+
+#[test]
+fn tests_run_in_the_package_dir_or_cwd() {
+    // A monorepo package with its code under src/; the test script logs
+    // where it runs
+    let dir = tempfile::TempDir::new().unwrap();
+    let package = dir.path().join("packages").join("app");
+    std::fs::create_dir_all(package.join("src")).unwrap();
+    std::fs::create_dir_all(package.join("tests")).unwrap();
+    std::fs::write(package.join("pyproject.toml"), "[project]").unwrap();
+    std::fs::write(package.join("src").join("app.py"), "def add(a, b):\n    return a + b\n").unwrap();
+    let log = dir.path().join("cwd.log");
+    std::fs::write(package.join("tests").join("test_app.sh"), format!("pwd > {}\n", log.display())).unwrap();
+    let run_in = |in_place: bool, cwd: Option<&Path>| {
+        let mut config = EngineConfig::new(package.join("src").join("app.py"), package.join("tests").join("test_app.sh"));
+        config.test_cmd = "sh".to_string();
+        config.in_place = in_place;
+        config.cwd = cwd.map(Path::to_path_buf);
+        MutatorEngine::new(config).unwrap().baseline().unwrap();
+        std::path::PathBuf::from(std::fs::read_to_string(&log).unwrap().trim())
+    };
+
+    assert_eq!(run_in(true, None).canonicalize().unwrap(), package.canonicalize().unwrap());
+    assert_eq!(run_in(true, Some(dir.path())).canonicalize().unwrap(), dir.path().canonicalize().unwrap());
+    // Isolated runs use the same directory inside the copy
+    let copied = run_in(false, Some(&package.join("src")));
+    assert!(copied.ends_with("src") && !copied.starts_with(dir.path()), "{}", copied.display());
+
+    let mut config = EngineConfig::new(package.join("src").join("app.py"), package.join("tests").join("test_app.sh"));
+    config.cwd = Some(dir.path().join("missing"));
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::CwdNotFound(_)));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn run_all_isolated_leaves_original_untouched() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let original = std::fs::read_to_string(dir.path().join("app.py")).unwrap();
+
+    let mut engine = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    let mutations = engine.discover();
+    let results = engine.run_all(&mutations).unwrap();
+
+    assert_eq!(results.len(), mutations.len());
+    assert!(results.iter().all(|r| r.status == MutantStatus::Survived));
+    assert_eq!(std::fs::read_to_string(dir.path().join("app.py")).unwrap(), original);
+}
+
+#[test]
+fn run_all_errors_when_source_edited_after_discovery() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut engine = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    let mutations = engine.discover();
+    let edited = "def add(a, b):\n    return a - b\n";
+    std::fs::write(dir.path().join("app.py"), edited).unwrap();
+
+    let err = engine.run_all(&mutations).err().unwrap();
+    assert!(matches!(err, EngineError::SourceChanged(_)));
+    assert!(err.to_string().contains("Source changed mid-run"), "{}", err);
+    assert_eq!(err.exit_code(), 3);
+    // The agent's edit survives
+    assert_eq!(std::fs::read_to_string(dir.path().join("app.py")).unwrap(), edited);
+}
+
+#[test]
+fn baseline_takes_a_session_lock_and_respects_max_sessions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let sessions_dir = dir.path().join(".mutator").join("sessions");
+
+    let mut first = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    first.baseline().unwrap();
+    let live = mutator::sessions::live_sessions(&sessions_dir);
+    assert_eq!(live.len(), 1);
+    assert_eq!(live[0].temp_dir.as_deref(), first.temp_dir());
+
+    let mut limited = config(dir.path(), "true");
+    limited.max_sessions = Some(1);
+    let err = MutatorEngine::new(limited.clone()).unwrap().baseline().err().unwrap();
+    assert!(matches!(err, EngineError::TooManySessions(_)), "{}", err);
+    assert_eq!(err.exit_code(), 3);
+
+    drop(first);
+    assert!(mutator::sessions::live_sessions(&sessions_dir).is_empty());
+    MutatorEngine::new(limited).unwrap().baseline().unwrap();
+}
+
+#[test]
+fn run_one_in_place_restores_source() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let original = std::fs::read_to_string(dir.path().join("app.py")).unwrap();
+
+    let mut config = config(dir.path(), "false");
+    config.in_place = true;
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+
+    // Baseline with `false` fails, so run_one must surface the error
+    let err = engine.run_one(&mutations[0]).err().unwrap();
+    assert!(matches!(err, EngineError::BaselineFailed(_)));
+    assert_eq!(std::fs::read_to_string(dir.path().join("app.py")).unwrap(), original);
+}
+
+#[test]
+fn run_one_returns_single_result() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut config = config(dir.path(), "true");
+    config.in_place = true;
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+    let result = engine.run_one(&mutations[0]).unwrap();
+
+    assert_eq!(result.status, MutantStatus::Survived);
+    assert!(!result.diff.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("app.py")).unwrap(),
+        engine.source()
+    );
+}
+
+#[test]
+fn summarize_counts_and_refs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut engine = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    let mutations = engine.discover();
+    let results = engine.run_all(&mutations).unwrap();
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    assert_eq!(summary.total, mutations.len());
+    assert_eq!(summary.survived, mutations.len());
+    assert_eq!(summary.score, 0.0);
+    assert_eq!(summary.survived_mutants[0].ref_id, "m1");
+    assert_eq!(summary.survived_mutants[0].file, "app.py");
+}
+
+#[test]
+fn summarize_empty_scores_perfect() {
+    let summary = engine::summarize(&[], Path::new("app.py"));
+    assert_eq!(summary.total, 0);
+    assert_eq!(summary.score, 1.0);
+}
+
+#[test]
+fn summarize_assigns_timeout_refs() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def add(a, b):\n    return a + b\n",
+        None,
+    );
+    let results: Vec<_> = mutations
+        .iter()
+        .map(|m| mutator::mutants::MutantResult {
+            mutation: m.clone(),
+            status: MutantStatus::Timeout,
+            duration_ms: 1,
+            diff: String::new(),
+            output_snippet: Some("partial".to_string()),
+            killed_by: None,
+        })
+        .collect();
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    assert_eq!(summary.timeout, mutations.len());
+    assert!(summary.survived_mutants.is_empty());
+    assert_eq!(summary.timeout_mutants[0].ref_id, "t1");
+    assert_eq!(summary.timeout_mutants[0].output_snippet.as_deref(), Some("partial"));
+}
+
+#[test]
+fn summarize_tallies_kills_by_test() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def add(a, b):\n    return a + b\n",
+        None,
+    );
+    let result = |killed_by: Option<&str>| mutator::mutants::MutantResult {
+        mutation: mutations[0].clone(),
+        status: if killed_by.is_some() { MutantStatus::Killed } else { MutantStatus::Survived },
+        duration_ms: 1,
+        diff: String::new(),
+        output_snippet: None,
+        killed_by: killed_by.map(String::from),
+    };
+    let results = vec![
+        result(Some("test_app.py::test_add")),
+        result(Some("test_app.py::test_add")),
+        result(Some("test_app.py::test_sub")),
+        result(None),
+    ];
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    assert_eq!(summary.kills_by_test["test_app.py::test_add"], 2);
+    assert_eq!(summary.kills_by_test["test_app.py::test_sub"], 1);
+    assert_eq!(summary.kills_by_test.len(), 2);
+}
+
+#[test]
+fn all_mutants_lists_every_status_with_matching_refs() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def add(a, b):\n    return a + b\n",
+        None,
+    );
+    let result = |status: MutantStatus, killed_by: Option<&str>| mutator::mutants::MutantResult {
+        mutation: mutations[0].clone(),
+        status,
+        duration_ms: 7,
+        diff: String::new(),
+        output_snippet: None,
+        killed_by: killed_by.map(String::from),
+    };
+    let results = vec![
+        result(MutantStatus::Killed, Some("test_app.py::test_add")),
+        result(MutantStatus::Survived, None),
+        result(MutantStatus::Timeout, None),
+        result(MutantStatus::Survived, None),
+        result(MutantStatus::Unviable, None),
+    ];
+    let summary = engine::summarize(&results, Path::new("app.py"));
+    assert!(summary.all_mutants.is_empty(), "only filled on request");
+
+    let all = engine::all_mutants(&results, Path::new("app.py"));
+    let refs: Vec<_> = all.iter().map(|m| m.ref_id.as_deref()).collect();
+    assert_eq!(refs, vec![None, Some("m1"), Some("t1"), Some("m2"), None]);
+    assert_eq!(summary.survived_mutants[1].ref_id, "m2");
+    assert_eq!(all[0].status, MutantStatus::Killed);
+    assert_eq!(all[0].killed_by.as_deref(), Some("test_app.py::test_add"));
+    assert_eq!(all[0].duration_ms, 7);
+    assert_eq!(all[0].operator, mutations[0].operator);
+    assert_eq!(all[0].line, 2);
+    assert_eq!(all[0].file, "app.py");
+}
+
+#[test]
+fn summarize_assigns_resource_exceeded_refs() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def add(a, b):\n    return a + b\n",
+        None,
+    );
+    let results: Vec<_> = mutations
+        .iter()
+        .map(|m| mutator::mutants::MutantResult {
+            mutation: m.clone(),
+            status: MutantStatus::ResourceExceeded,
+            duration_ms: 1,
+            diff: String::new(),
+            output_snippet: Some("MemoryError".to_string()),
+            killed_by: None,
+        })
+        .collect();
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    assert_eq!(summary.resource_exceeded, mutations.len());
+    assert_eq!(summary.score, 0.0, "scored like a timeout");
+    assert_eq!(summary.find_ref("@r1").unwrap().output_snippet.as_deref(), Some("MemoryError"));
+    assert_eq!(summary.functions["add"].resource_exceeded, mutations.len());
+}
+
+#[test]
+fn coverage_marks_unexecuted_lines_uncovered() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    // `add` (line 2) is executed, `is_positive` (line 5) is not
+    let coverage = dir.path().join("lcov.info");
+    std::fs::write(&coverage, "SF:app.py\nDA:1,1\nDA:2,1\nDA:4,1\nDA:5,0\nend_of_record\n").unwrap();
+
+    let mut config = config(dir.path(), "true");
+    config.coverage = Some(coverage);
+    let mut engine = MutatorEngine::new(config).unwrap();
+    let mutations = engine.discover();
+    let results = engine.run_all(&mutations).unwrap();
+
+    for r in &results {
+        let expected = if r.mutation.line == 5 { MutantStatus::Uncovered } else { MutantStatus::Survived };
+        assert_eq!(r.status, expected, "line {}", r.mutation.line);
+    }
+    let summary = engine::summarize(&results, Path::new("app.py"));
+    assert!(summary.uncovered > 0);
+    assert_eq!(summary.uncovered_mutants[0].ref_id, "u1");
+    assert_eq!(summary.uncovered_mutants[0].line, 5);
+    assert!(!summary.uncovered_mutants[0].diff.is_empty());
+    assert_eq!(summary.survived + summary.uncovered, summary.total);
+}
+
+#[test]
+fn coverage_without_entry_for_source_is_usage_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let coverage = dir.path().join("lcov.info");
+    std::fs::write(&coverage, "SF:other.py\nDA:1,1\nend_of_record\n").unwrap();
+
+    let mut config = config(dir.path(), "true");
+    config.coverage = Some(coverage);
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::Coverage(_)));
+    assert_eq!(err.exit_code(), 2);
+    assert!(err.to_string().contains("has no data for"));
+}
+
+#[test]
+fn include_tests_config_reaches_rust_parser() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "fn add(a: i32, b: i32) -> i32 { a + b }\n#[cfg(test)]\nmod tests {\n    fn two() -> i32 { 1 + 1 }\n}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("test_lib.rs"), "").unwrap();
+
+    let config = EngineConfig::new(dir.path().join("lib.rs"), dir.path().join("test_lib.rs"));
+    let prod = MutatorEngine::new(config.clone()).unwrap().discover();
+    let mut with_tests = config;
+    with_tests.include_tests = true;
+    let all = MutatorEngine::new(with_tests).unwrap().discover();
+
+    assert!(prod.iter().all(|m| m.line == 1));
+    assert!(all.iter().any(|m| m.line == 4));
+}
+
+#[test]
+fn js_test_files_skipped_unless_included() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("package.json"), "{}").unwrap();
+    std::fs::write(
+        dir.path().join("math.test.js"),
+        "function add(a, b) { return a + b; }\ntest(\"add\", () => expect(add(1, 2)).toBe(3));\n",
+    )
+    .unwrap();
+
+    let mut config = EngineConfig::new(dir.path().join("math.test.js"), dir.path().join("math.test.js"));
+    assert!(MutatorEngine::new(config.clone()).unwrap().discover().is_empty());
+    config.function = Some("add".to_string());
+    assert!(!MutatorEngine::new(config.clone()).unwrap().discover().is_empty());
+    config.function = None;
+    config.include_tests = true;
+    assert!(!MutatorEngine::new(config).unwrap().discover().is_empty());
+}
+
+#[test]
+fn runner_detected_from_command_project_and_language() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let engine = MutatorEngine::new(config(dir.path(), "true")).unwrap();
+    assert_eq!(engine.runner(), Runner::Pytest);
+
+    let mut config = config(dir.path(), "python -m unittest");
+    assert_eq!(MutatorEngine::new(config.clone()).unwrap().runner(), Runner::Unittest);
+    config.runner = Some(Runner::Pytest);
+    assert_eq!(MutatorEngine::new(config).unwrap().runner(), Runner::Pytest);
+}
+
+#[test]
+fn test_map_filters_runner_for_mapped_function() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let args_log = dir.path().join("args.log");
+    std::fs::write(
+        dir.path().join("test_app.sh"),
+        format!("echo \"$@\" >> {}\n", args_log.display()),
+    )
+    .unwrap();
+    let map = dir.path().join("map.json");
+    std::fs::write(&map, r#"{"add": ["test_add", "test_add_zero"]}"#).unwrap();
+
+    let mut config = EngineConfig::new(dir.path().join("app.py"), dir.path().join("test_app.sh"));
+    config.test_cmd = "sh".to_string();
+    config.function = Some("add".to_string());
+    config.test_map = Some(map);
+    let mut engine = MutatorEngine::new(config).unwrap();
+    assert_eq!(engine.test_filters(), ["test_add", "test_add_zero"]);
+
+    let mutations = engine.discover();
+    engine.run_one(&mutations[0]).unwrap();
+    let logged = std::fs::read_to_string(&args_log).unwrap();
+    let runs: Vec<_> = logged.lines().collect();
+    assert_eq!(runs.len(), 2, "baseline + one mutant");
+    assert!(runs.iter().all(|r| r.contains("-k test_add or test_add_zero")), "{:?}", runs);
+}
+
+#[test]
+fn test_map_unmapped_function_runs_whole_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let map = dir.path().join("map.json");
+    std::fs::write(&map, r#"{"add": "test_add"}"#).unwrap();
+
+    let mut config = config(dir.path(), "true");
+    config.function = Some("is_positive".to_string());
+    config.test_map = Some(map);
+    let engine = MutatorEngine::new(config).unwrap();
+    assert!(engine.test_filters().is_empty());
+}
+
+#[test]
+fn invalid_test_map_is_usage_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let map = dir.path().join("map.json");
+    std::fs::write(&map, "not json").unwrap();
+
+    let mut config = config(dir.path(), "true");
+    config.function = Some("add".to_string());
+    config.test_map = Some(map);
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::TestMap(_)));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn env_reaches_baseline_and_mutant_runs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let env_log = dir.path().join("env.log");
+    std::fs::write(
+        dir.path().join("test_app.sh"),
+        format!(
+            "echo \"$DATABASE_URL $REGION $MUTATOR_ACTIVE ${{MUTATOR_MUTANT_ID:-baseline}}\" >> {}\n",
+            env_log.display()
+        ),
+    )
+    .unwrap();
+    let env_file = dir.path().join(".env.test");
+    std::fs::write(&env_file, "DATABASE_URL=sqlite://test.db\nREGION=eu\n").unwrap();
+
+    let mut config = EngineConfig::new(dir.path().join("app.py"), dir.path().join("test_app.sh"));
+    config.test_cmd = "sh".to_string();
+    config.env_file = Some(env_file);
+    config.env = vec![("REGION".to_string(), "us".to_string())];
+    let mut engine = MutatorEngine::new(config).unwrap();
+
+    let mutations = engine.discover();
+    engine.run_one(&mutations[0]).unwrap();
+    let logged = std::fs::read_to_string(&env_log).unwrap();
+    let runs: Vec<_> = logged.lines().collect();
+    assert_eq!(
+        runs,
+        vec![
+            "sqlite://test.db us 1 baseline".to_string(),
+            format!("sqlite://test.db us 1 {}", mutations[0].id()),
+        ]
+    );
+}
+
+#[test]
+fn missing_env_file_is_usage_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut config = config(dir.path(), "true");
+    config.env_file = Some(dir.path().join(".env.missing"));
+    let err = MutatorEngine::new(config).err().unwrap();
+    assert!(matches!(err, EngineError::Env(_)));
+    assert_eq!(err.exit_code(), 2);
+}
+
+#[test]
+fn test_filter_forwarded_and_overrides_map() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let map = dir.path().join("map.json");
+    std::fs::write(&map, r#"{"add": "test_add"}"#).unwrap();
+
+    let mut config = config(dir.path(), "true");
+    config.function = Some("add".to_string());
+    config.test_map = Some(map);
+    config.test_filter = Some("add and not slow".to_string());
+    let engine = MutatorEngine::new(config).unwrap();
+    assert_eq!(engine.test_filters(), ["add and not slow"]);
+    assert_eq!(
+        engine.runner().adapter().filter_args(engine.test_filters()),
+        vec!["-k", "add and not slow"]
+    );
+}
+
+#[test]
+fn jest_escapes_test_map_names_but_not_test_filter() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    let map = dir.path().join("map.json");
+    std::fs::write(&map, r#"{"add": ["adds (a + b)", "adds zero"]}"#).unwrap();
+
+    let mut config = config(dir.path(), "npx jest");
+    config.runner = Some(Runner::Jest);
+    config.function = Some("add".to_string());
+    config.test_map = Some(map);
+    let args = |config: &EngineConfig| MutatorEngine::new(config.clone()).unwrap().command_plan().baseline.args;
+
+    let mapped = args(&config);
+    assert!(mapped.ends_with(&["-t".to_string(), r"adds \(a \+ b\)|adds zero".to_string()]), "{:?}", mapped);
+
+    config.test_filter = Some("add|sub".to_string());
+    let filtered = args(&config);
+    assert!(filtered.ends_with(&["-t".to_string(), "add|sub".to_string()]), "{:?}", filtered);
+}
+
+#[test]
+fn test_filter_without_function() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+
+    let mut config = config(dir.path(), "true");
+    config.test_filter = Some("test_add".to_string());
+    let engine = MutatorEngine::new(config).unwrap();
+    assert_eq!(engine.test_filters(), ["test_add"]);
+}
+
+#[test]
+fn summarize_scores_each_function() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def add(a, b):\n    return a + b\n\ndef is_positive(n):\n    return n > 0\n",
+        None,
+    );
+    let results: Vec<_> = mutations
+        .iter()
+        .map(|m| mutator::mutants::MutantResult {
+            mutation: m.clone(),
+            status: if m.function.as_deref() == Some("add") {
+                MutantStatus::Killed
+            } else {
+                MutantStatus::Survived
+            },
+            duration_ms: 1,
+            diff: String::new(),
+            output_snippet: None,
+            killed_by: None,
+        })
+        .collect();
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    assert_eq!(summary.functions.len(), 2);
+    assert_eq!(summary.functions["add"].score, 1.0);
+    assert_eq!(summary.functions["is_positive"].score, 0.0);
+    assert_eq!(summary.functions["is_positive"].survived, summary.survived);
+    assert_eq!(summary.survived_mutants[0].function.as_deref(), Some("is_positive"));
+}
+
+#[test]
+fn summarize_counts_each_operator() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def is_positive(n):\n    return n > 0\n",
+        None,
+    );
+    let results: Vec<_> = mutations
+        .iter()
+        .map(|m| mutator::mutants::MutantResult {
+            mutation: m.clone(),
+            status: if m.operator == "boundary" { MutantStatus::Survived } else { MutantStatus::Killed },
+            duration_ms: 1,
+            diff: String::new(),
+            output_snippet: None,
+            killed_by: None,
+        })
+        .collect();
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    let generated: usize = summary.operators.values().map(|o| o.total).sum();
+    assert_eq!(generated, summary.total);
+    let boundary = &summary.operators["boundary"];
+    assert_eq!((boundary.total, boundary.killed, boundary.survived), (1, 0, 1));
+    assert_eq!(boundary.score, 0.0);
+    let negate = &summary.operators["negate_cmp"];
+    assert_eq!((negate.total, negate.killed, negate.timeout), (1, 1, 0));
+    assert_eq!(negate.score, 1.0);
+}
+
+#[test]
+fn cargo_target_dir_only_for_cargo_rust_runs() {
+    let dir = tempfile::TempDir::new().unwrap();
+    create_project(dir.path());
+    assert!(MutatorEngine::new(config(dir.path(), "pytest")).unwrap().cargo_target_dir().is_none());
+
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "pub fn one() -> i32 {\n    1\n}\n").unwrap();
+    let mut rust = EngineConfig::new(dir.path().join("lib.rs"), dir.path().join("lib.rs"));
+    rust.test_cmd = "cargo test".to_string();
+    rust.session = Some("agent-7".to_string());
+    let engine = MutatorEngine::new(rust).unwrap();
+    assert!(engine.cargo_target_dir().unwrap().ends_with("mutator/agent-7"));
+}
+
+#[test]
+fn test_arg_style_overrides_cargo_target_selection() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::create_dir_all(dir.path().join("tests")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), "pub fn one() -> i32 {\n    1\n}\n").unwrap();
+    std::fs::write(dir.path().join("tests/math.rs"), "").unwrap();
+    let mut config = EngineConfig::new(dir.path().join("src/lib.rs"), dir.path().join("tests/math.rs"));
+    config.test_cmd = "./scripts/cargo-wrapper".to_string();
+
+    // The wrapper isn't recognised, so the Rust default applies: cargo
+    // selects the integration test target itself
+    let auto = MutatorEngine::new(config.clone()).unwrap().command_plan();
+    assert_eq!(auto.baseline.args[..2], ["--test", "math"]);
+
+    // Tests run from the crate's directory, in place too
+    config.in_place = true;
+    config.test_arg_style = TestArgStyle::Relative;
+    let relative = MutatorEngine::new(config.clone()).unwrap().command_plan();
+    assert_eq!(relative.baseline.args[0], "tests/math.rs");
+    assert!(!relative.baseline.args.contains(&"--test".to_string()));
+
+    config.test_arg_style = TestArgStyle::None;
+    let none = MutatorEngine::new(config).unwrap().command_plan();
+    assert!(!none.baseline.args.iter().any(|a| a.contains("math")));
+}
+
+#[test]
+fn workspace_members_are_tested_with_cargo_p() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let member = dir.path().join("crates").join("core");
+    std::fs::create_dir_all(member.join("src")).unwrap();
+    std::fs::create_dir_all(member.join("tests")).unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+    std::fs::write(member.join("Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+    std::fs::write(member.join("src/lib.rs"), "pub fn one() -> i32 {\n    1\n}\n").unwrap();
+    std::fs::write(member.join("tests/math.rs"), "").unwrap();
+    let mut config = EngineConfig::new(member.join("src/lib.rs"), member.join("tests/math.rs"));
+    config.test_cmd = "cargo test".to_string();
+
+    let plan = MutatorEngine::new(config).unwrap().command_plan();
+    assert_eq!(plan.baseline.args[1..5], ["-p", "core", "--test", "math"]);
+    // The whole workspace is copied, since the member can't build alone
+    assert_eq!(plan.isolated_from.as_deref(), Some(dir.path()));
+}
+
+#[test]
+fn summarize_excludes_suspected_equivalent_from_score() {
+    let mutations = engine::discover_mutations(
+        &mutator::Language::Python,
+        "def add(a, b):\n    return a + b\n",
+        None,
+    );
+    let statuses = [MutantStatus::Killed, MutantStatus::SuspectedEquivalent];
+    let results: Vec<_> = mutations
+        .iter()
+        .zip(statuses)
+        .map(|(m, status)| mutator::mutants::MutantResult {
+            mutation: m.clone(),
+            status,
+            duration_ms: 1,
+            diff: String::new(),
+            output_snippet: None,
+            killed_by: None,
+        })
+        .collect();
+    let summary = engine::summarize(&results, Path::new("app.py"));
+
+    assert_eq!(summary.equivalent, 1);
+    assert_eq!(summary.testable(), 1);
+    assert_eq!(summary.score, 1.0);
+    assert_eq!(summary.equivalent_mutants[0].ref_id, "e1");
+    assert_eq!(summary.functions["add"].equivalent, 1);
+}
@@ -0,0 +1,40 @@
+use mutator::env_file;
+
+#[test]
+fn parses_pairs_comments_export_and_quotes() {
+    let env = env_file::parse(
+        "# test database\nDATABASE_URL=postgres://localhost/app_test?sslmode=disable\n\nexport SECRET_KEY=\"not a secret\"\nNAME='x'\nEMPTY=\n",
+    )
+    .unwrap();
+    assert_eq!(
+        env,
+        vec![
+            ("DATABASE_URL".to_string(), "postgres://localhost/app_test?sslmode=disable".to_string()),
+            ("SECRET_KEY".to_string(), "not a secret".to_string()),
+            ("NAME".to_string(), "x".to_string()),
+            ("EMPTY".to_string(), String::new()),
+        ]
+    );
+}
+
+#[test]
+fn line_without_equals_is_rejected_with_line_number() {
+    let err = env_file::parse("A=1\nnot a pair\n").unwrap_err();
+    assert!(err.contains("line 2"), "{}", err);
+}
+
+#[test]
+fn pair_keeps_value_verbatim_and_checks_name() {
+    assert_eq!(
+        env_file::parse_pair("OPTS=--a=1 --b").unwrap(),
+        ("OPTS".to_string(), "--a=1 --b".to_string())
+    );
+    assert!(env_file::parse_pair("=value").is_err());
+    assert!(env_file::parse_pair("BAD KEY=1").is_err());
+}
+
+#[test]
+fn load_reports_missing_file() {
+    let err = env_file::load(std::path::Path::new("/nonexistent/.env.test")).unwrap_err();
+    assert!(err.contains("Failed to read env file"), "{}", err);
+}
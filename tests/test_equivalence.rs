@@ -0,0 +1,88 @@
+use mutator::equivalence;
+use mutator::mutants::Mutation;
+use mutator::{parser, parser_js, parser_rust, Language};
+
+fn find<'a>(mutations: &'a [Mutation], original: &str, replacement: &str) -> &'a Mutation {
+    mutations
+        .iter()
+        .find(|m| m.original == original && m.replacement == replacement)
+        .unwrap_or_else(|| panic!("no {} -> {} mutant", original, replacement))
+}
+
+fn mutation(start_byte: usize, original: &str, replacement: &str) -> Mutation {
+    Mutation {
+        line: 1,
+        column: start_byte + 1,
+        start_byte,
+        end_byte: start_byte + original.len(),
+        operator: "arith".into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        context_before: vec![],
+        context_after: vec![],
+        function: None,
+    }
+}
+
+#[test]
+fn dedupe_drops_repeats_and_no_ops() {
+    let mutations = vec![
+        mutation(4, "+", "-"),
+        mutation(4, "+", "-"),
+        mutation(4, "+", "*"),
+        mutation(8, "x", "x"),
+    ];
+    let kept = equivalence::dedupe(mutations);
+    assert_eq!(kept.len(), 2);
+    assert_eq!(kept[0].replacement, "-");
+    assert_eq!(kept[1].replacement, "*");
+}
+
+fn equivalent(lang: &Language, source: &str, original: &str, replacement: &str) -> bool {
+    let mutations = match lang {
+        Language::Python => parser::discover_mutations(source, None),
+        Language::Rust => parser_rust::discover_mutations(source, None),
+        _ => parser_js::discover_mutations(source, None, parser_js::JsDialect::JavaScript),
+    };
+    equivalence::is_suspected_equivalent(lang, source, find(&mutations, original, replacement))
+}
+
+#[test]
+fn identity_operands_are_equivalent() {
+    assert!(equivalent(&Language::Python, "def f(x):\n    return x * 1\n", "*", "/"));
+    assert!(equivalent(&Language::Python, "def f(x):\n    return x ** 1\n", "**", "*"));
+    assert!(equivalent(&Language::JavaScript, "function f(x) { return x + 0; }\n", "+", "-"));
+    assert!(equivalent(&Language::Rust, "fn f(x: i32) -> i32 {\n    x - 0\n}\n", "-", "+"));
+}
+
+#[test]
+fn identical_operands_in_same_result_group() {
+    // a // a and a / a are both 1
+    assert!(equivalent(&Language::Python, "def f(a):\n    return a // a\n", "//", "/"));
+    // a <= a is true, a < a is false
+    assert!(!equivalent(&Language::Python, "def f(a):\n    return a <= a\n", "<=", "<"));
+    // a - a is 0, a + a is 2a
+    assert!(!equivalent(&Language::Rust, "fn f(a: i32) -> i32 {\n    a - a\n}\n", "-", "+"));
+}
+
+#[test]
+fn ordinary_operands_are_not_equivalent() {
+    assert!(!equivalent(&Language::Python, "def f(a, b):\n    return a * b\n", "*", "/"));
+    assert!(!equivalent(&Language::JavaScript, "function f(x) { return x + 1; }\n", "+", "-"));
+}
+
+#[test]
+fn non_operator_mutants_are_not_equivalent() {
+    let source = "def f(x):\n    return x * 1\n";
+    let mutations = parser::discover_mutations(source, None);
+    let ret = mutations.iter().find(|m| m.operator == "return_val").unwrap();
+    assert!(!equivalence::is_suspected_equivalent(&Language::Python, source, ret));
+}
+
+#[test]
+fn argument_swap_in_symmetric_call_is_equivalent() {
+    assert!(equivalent(&Language::Python, "def f(a, b):\n    return max(a, b)\n", "a, b", "b, a"));
+    assert!(equivalent(&Language::JavaScript, "function f(a, b) { return Math.min(a, b); }\n", "a, b", "b, a"));
+    assert!(equivalent(&Language::Rust, "fn f(a: i32, b: i32) -> i32 {\n    std::cmp::max(a, b)\n}\n", "a, b", "b, a"));
+    assert!(!equivalent(&Language::Python, "def f(a, b):\n    return divmod(a, b)\n", "a, b", "b, a"));
+}
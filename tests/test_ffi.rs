@@ -0,0 +1,62 @@
+use mutator::ffi;
+use serde_json::{json, Value};
+use std::ffi::{CStr, CString};
+
+fn call(request: &Value, f: fn(&str) -> String) -> Value {
+    serde_json::from_str(&f(&request.to_string())).unwrap()
+}
+
+#[test]
+fn discover_then_apply() {
+    let source = "def add(a, b):\n    return a + b\n";
+    let found = call(&json!({ "source": source, "path": "app.py", "function": "add" }), ffi::discover_json);
+    let mutation = found["mutations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|m| m["operator"] == "arith")
+        .unwrap()
+        .clone();
+
+    let applied = call(&json!({ "source": source, "mutation": mutation }), ffi::apply_json);
+    assert_eq!(applied["mutated"], "def add(a, b):\n    return a - b\n");
+    assert!(applied["diff"].as_str().unwrap().contains("+     return a - b"));
+
+    let stale = call(&json!({ "source": "x = 1\n", "mutation": mutation }), ffi::apply_json);
+    assert!(stale["error"].as_str().unwrap().starts_with("Stale mutation"));
+}
+
+#[test]
+fn discover_takes_language_and_custom_operators() {
+    let request = json!({
+        "source": "fn cents() -> u32 { 100 }\n",
+        "language": "rust",
+        "operators": [{ "name": "cents", "kinds": ["integer_literal"], "match": "^100$", "replacement": "10" }],
+    });
+    let found = call(&request, ffi::discover_json);
+    assert!(found["mutations"].as_array().unwrap().iter().any(|m| m["operator"] == "cents"));
+}
+
+#[test]
+fn bad_requests_report_errors() {
+    let no_language = call(&json!({ "source": "x" }), ffi::discover_json);
+    assert!(no_language["error"].as_str().unwrap().contains("Unknown language"));
+    let unknown_op = call(&json!({ "source": "x", "path": "a.py", "enable": ["nope"] }), ffi::discover_json);
+    assert!(unknown_op["error"].as_str().unwrap().contains("'nope'"));
+    assert!(ffi::discover_json("not json").contains("Invalid request"));
+}
+
+#[test]
+fn c_functions_round_trip_strings() {
+    let request = CString::new(json!({ "source": "def f():\n    return True\n", "path": "a.py" }).to_string()).unwrap();
+    unsafe {
+        let response = ffi::mutator_discover(request.as_ptr());
+        let text = CStr::from_ptr(response).to_str().unwrap().to_string();
+        ffi::mutator_free(response);
+        assert!(text.contains("\"mutations\""));
+
+        let response = ffi::mutator_apply(std::ptr::null());
+        assert!(CStr::from_ptr(response).to_str().unwrap().contains("null pointer"));
+        ffi::mutator_free(response);
+    }
+}
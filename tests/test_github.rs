@@ -0,0 +1,70 @@
+use mutator::github;
+use mutator::state::{RunResult, SurvivedMutant};
+
+fn mutant(ref_id: &str, operator: &str, original: &str, replacement: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: "src/app.py".into(),
+        line: 10,
+        column: 12,
+        operator: operator.into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn survivors_become_warning_commands() {
+    let result = RunResult {
+        survived_mutants: vec![mutant("m1", "boundary", ">", ">=")],
+        uncovered_mutants: vec![mutant("u1", "arith", "+", "-")],
+        ..Default::default()
+    };
+    let lines: Vec<_> = github::to_annotations(&result).lines().map(String::from).collect();
+
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[0],
+        "::warning file=src/app.py,line=10,col=12,title=mutator%3A boundary::Survived mutant: '>' → '>=' (boundary, @m1). Test the exact boundary value"
+    );
+    assert!(lines[1].starts_with("::warning file=src/app.py,line=10,col=12,title=mutator%3A arith::Uncovered mutant: '+' → '-'"));
+}
+
+#[test]
+fn all_killed_prints_nothing() {
+    let result = RunResult { score: 1.0, total: 2, killed: 2, ..Default::default() };
+    assert_eq!(github::to_annotations(&result), "");
+}
+
+#[test]
+fn multiline_changes_and_odd_paths_are_escaped() {
+    let mut m = mutant("m1", "block_remove", "x = 1\n    y = 100%", "pass");
+    m.file = "src/a,b:c.py".into();
+    let result = RunResult { survived_mutants: vec![m], ..Default::default() };
+    let out = github::to_annotations(&result);
+
+    assert_eq!(out.lines().count(), 1);
+    assert!(out.starts_with("::warning file=src/a%2Cb%3Ac.py,"));
+    assert!(out.contains("'x = 1%0A    y = 100%25' → 'pass'"));
+}
+
+#[test]
+fn step_summary_is_appended() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("summary.md");
+    std::fs::write(&path, "# Earlier step\n").unwrap();
+    let result = RunResult {
+        score: 0.5,
+        total: 2,
+        killed: 1,
+        survived: 1,
+        survived_mutants: vec![mutant("m1", "boundary", ">", ">=")],
+        ..Default::default()
+    };
+
+    github::append_step_summary(&path, &result).unwrap();
+    let summary = std::fs::read_to_string(&path).unwrap();
+    assert!(summary.starts_with("# Earlier step\n## Mutation testing"));
+    assert!(summary.contains("| @m1 |"));
+}
@@ -0,0 +1,67 @@
+use mutator::gitlab;
+use mutator::state::{RunResult, SurvivedMutant};
+
+fn mutant(ref_id: &str, line: usize, operator: &str, original: &str, replacement: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: "src/app.py".into(),
+        line,
+        column: 12,
+        function: Some("check".into()),
+        operator: operator.into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn survivors_and_uncovered_become_issues() {
+    let result = RunResult {
+        survived_mutants: vec![mutant("m1", 10, "boundary", ">", ">=")],
+        uncovered_mutants: vec![mutant("u1", 14, "arith", "+", "-")],
+        ..Default::default()
+    };
+    let report = gitlab::to_codequality(&result);
+    let issues = report.as_array().unwrap();
+
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0]["check_name"], "mutator/boundary");
+    assert_eq!(issues[0]["severity"], "major");
+    assert_eq!(issues[0]["location"]["path"], "src/app.py");
+    assert_eq!(issues[0]["location"]["lines"]["begin"], 10);
+    assert!(issues[0]["description"].as_str().unwrap().starts_with("Survived mutant: `>` -> `>=` (boundary, @m1)"));
+    assert_eq!(issues[1]["severity"], "minor");
+    assert_eq!(issues[0]["fingerprint"].as_str().unwrap().len(), 16);
+}
+
+#[test]
+fn fingerprints_survive_moved_lines_but_not_other_changes() {
+    let fingerprint = |m: SurvivedMutant| {
+        let result = RunResult { survived_mutants: vec![m], ..Default::default() };
+        gitlab::to_codequality(&result)[0]["fingerprint"].clone()
+    };
+    let base = fingerprint(mutant("m1", 10, "boundary", ">", ">="));
+
+    assert_eq!(fingerprint(mutant("m7", 25, "boundary", ">", ">=")), base);
+    assert_ne!(fingerprint(mutant("m1", 10, "negate_cmp", ">", "<=")), base);
+    let mut elsewhere = mutant("m1", 10, "boundary", ">", ">=");
+    elsewhere.file = "src/other.py".into();
+    assert_ne!(fingerprint(elsewhere), base);
+}
+
+#[test]
+fn identical_mutants_get_distinct_fingerprints() {
+    let result = RunResult {
+        survived_mutants: vec![mutant("m1", 10, "arith", "+", "-"), mutant("m2", 11, "arith", "+", "-")],
+        ..Default::default()
+    };
+    let report = gitlab::to_codequality(&result);
+    assert_ne!(report[0]["fingerprint"], report[1]["fingerprint"]);
+}
+
+#[test]
+fn all_killed_is_an_empty_report() {
+    let result = RunResult { score: 1.0, total: 2, killed: 2, ..Default::default() };
+    assert_eq!(gitlab::to_codequality(&result), serde_json::json!([]));
+}
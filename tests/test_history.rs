@@ -0,0 +1,98 @@
+use mutator::history::{self, HistoryEntry};
+use mutator::state::RunResult;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn entry(file: &str, function: Option<&str>, score: f64) -> HistoryEntry {
+    let result = RunResult { score, total: 10, killed: (score * 10.0) as usize, ..Default::default() };
+    HistoryEntry::new(&result, Path::new(file), function, None)
+}
+
+#[test]
+fn format_utc_epoch_and_leap_day() {
+    assert_eq!(history::format_utc(0), "1970-01-01T00:00:00Z");
+    assert_eq!(history::format_utc(951_782_400), "2000-02-29T00:00:00Z");
+    assert_eq!(history::format_utc(1_735_689_599), "2024-12-31T23:59:59Z");
+}
+
+#[test]
+fn append_and_load_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let path = history::history_path_in(dir.path());
+
+    history::append_to(&path, &entry("app.py", Some("add"), 0.5));
+    history::append_to(&path, &entry("app.py", None, 0.8));
+
+    let loaded = history::load_from(&path);
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].function.as_deref(), Some("add"));
+    assert_eq!(loaded[1].score, 0.8);
+    assert!(path.ends_with(".mutator/history.jsonl"));
+}
+
+#[test]
+fn load_skips_malformed_lines() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("history.jsonl");
+    history::append_to(&path, &entry("app.py", None, 0.5));
+    std::fs::write(&path, format!("{}{{\"timestamp\": \n", std::fs::read_to_string(&path).unwrap())).unwrap();
+
+    assert_eq!(history::load_from(&path).len(), 1);
+    assert!(history::load_from(&dir.path().join("missing.jsonl")).is_empty());
+}
+
+#[test]
+fn trend_deltas_are_per_file_and_function() {
+    let entries = vec![
+        entry("src/app.py", Some("add"), 0.5),
+        entry("src/app.py", Some("greet"), 0.9),
+        entry("src/app.py", Some("add"), 0.75),
+        entry("src/other.py", None, 1.0),
+    ];
+    let points = history::trend(entries, None);
+    assert_eq!(points[0].delta, None);
+    assert_eq!(points[1].delta, None);
+    assert_eq!(points[2].delta, Some(0.25));
+    assert_eq!(points[3].delta, None);
+}
+
+#[test]
+fn trend_filters_by_file_suffix() {
+    let entries = vec![entry("src/app.py", None, 0.5), entry("src/other.py", None, 1.0)];
+    let points = history::trend(entries, Some(Path::new("app.py")));
+    assert_eq!(points.len(), 1);
+    assert_eq!(points[0].entry.file, "src/app.py");
+}
+
+#[test]
+fn record_keeps_a_snapshot_per_run() {
+    let dir = TempDir::new().unwrap();
+    let result = RunResult { score: 0.5, total: 4, killed: 2, ..Default::default() };
+
+    history::record_in(dir.path(), &result, Path::new("app.py"), None, None);
+    history::record_in(dir.path(), &result, Path::new("app.py"), None, None);
+
+    let entries = history::load_from(&history::history_path_in(dir.path()));
+    assert_eq!(entries.len(), 2);
+    assert_ne!(entries[0].run, entries[1].run);
+    let snapshot = history::load_run(dir.path(), &entries[1]).unwrap();
+    assert_eq!(snapshot.killed, 2);
+    assert!(history::load_run(dir.path(), &entry("app.py", None, 0.5)).is_none());
+}
+
+#[test]
+fn series_follows_the_latest_file_and_function() {
+    let mut entries = vec![
+        entry("src/app.py", Some("add"), 0.5),
+        entry("src/app.py", None, 0.6),
+        entry("src/other.py", None, 0.7),
+        entry("src/app.py", Some("add"), 0.8),
+    ];
+    entries[1].session = Some("agent-a".into());
+
+    let scores = |s: Vec<HistoryEntry>| s.iter().map(|e| e.score).collect::<Vec<_>>();
+    assert_eq!(scores(history::series(entries.clone(), None, None)), vec![0.5, 0.8]);
+    assert_eq!(scores(history::series(entries.clone(), Some(Path::new("other.py")), None)), vec![0.7]);
+    assert_eq!(scores(history::series(entries.clone(), None, Some("agent-a"))), vec![0.6]);
+    assert!(history::series(entries, Some(Path::new("missing.py")), None).is_empty());
+}
@@ -30,9 +30,66 @@ fn detect_tsx_jsx() {
     assert!(matches!(mutator::detect_language(Path::new("foo.jsx")), Some(mutator::Language::Tsx)));
 }
 
+#[test]
+fn detect_java() {
+    assert!(matches!(mutator::detect_language(Path::new("Foo.java")), Some(mutator::Language::Java)));
+}
+
 #[test]
 fn detect_unknown_returns_none() {
     assert!(mutator::detect_language(Path::new("foo.go")).is_none());
-    assert!(mutator::detect_language(Path::new("foo.java")).is_none());
+    assert!(mutator::detect_language(Path::new("Foo.kt")).is_none());
     assert!(mutator::detect_language(Path::new("foo")).is_none());
 }
+
+#[test]
+fn language_names_round_trip() {
+    for language in mutator::Language::ALL {
+        assert_eq!(language.to_string().parse::<mutator::Language>(), Ok(language));
+    }
+    assert_eq!("py".parse(), Ok(mutator::Language::Python));
+    assert_eq!("TypeScript".parse(), Ok(mutator::Language::TypeScript));
+    let err = "go".parse::<mutator::Language>().unwrap_err();
+    assert!(err.contains("python, rust, js, ts, tsx"), "{}", err);
+}
+
+#[test]
+fn language_from_extension_and_defaults() {
+    use mutator::test_adapters::Runner;
+    use mutator::Language;
+
+    assert_eq!(Language::from_extension("mts"), Some(Language::TypeScript));
+    assert_eq!(Language::from_extension("go"), None);
+    assert_eq!(Language::Rust.test_defaults().runner, Runner::CargoTest);
+    assert_eq!(Language::Python.test_defaults().test_cmd, "pytest");
+    assert_eq!(Language::Java.test_defaults().runner, Runner::Maven);
+    assert_eq!(serde_json::to_string(&Language::JavaScript).unwrap(), "\"js\"");
+}
+
+#[test]
+fn language_parser_discovers_and_lists_functions() {
+    use mutator::mutation_parser::ParseOptions;
+    use mutator::Language;
+
+    let parser = Language::Python.parser();
+    let source = "def add(a, b):\n    return a + b\n\nclass Cart:\n    def total(self):\n        return 0\n";
+    assert_eq!(parser.list_functions(source), ["add", "Cart.total"]);
+    let mutations = parser.discover(source, Some("add"), &ParseOptions::default());
+    assert!(!mutations.is_empty());
+    assert!(mutations.iter().all(|m| m.function.as_deref() == Some("add")));
+
+    let ts = Language::TypeScript.parser();
+    assert_eq!(ts.list_functions("function f(x: number): number { return x + 1; }"), ["f"]);
+}
+
+#[test]
+fn discovery_works_on_text_alone() {
+    use mutator::discovery::{self, DiscoverOptions};
+    let source = "export function add(a, b) {\n  return a + b;\n}\n";
+    let options = DiscoverOptions::default();
+    let lang = mutator::Language::TypeScript;
+    let mutations = discovery::discover(&lang, source, None, &options, Path::new("/nowhere/math.ts"));
+    assert!(mutations.iter().any(|m| m.operator == "arith" && m.function.as_deref() == Some("add")));
+    // Test files are recognised by name alone
+    assert!(discovery::discover(&lang, source, None, &options, Path::new("/nowhere/math.test.ts")).is_empty());
+}
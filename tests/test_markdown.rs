@@ -0,0 +1,76 @@
+use mutator::markdown;
+use mutator::state::{RunResult, SurvivedMutant};
+
+fn mutant(ref_id: &str, operator: &str, original: &str, replacement: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: "src/app.py".into(),
+        line: 10,
+        column: 12,
+        operator: operator.into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn survivors_render_as_table_rows() {
+    let result = RunResult {
+        score: 0.75,
+        total: 4,
+        killed: 3,
+        survived: 1,
+        survived_mutants: vec![mutant("m1", "boundary", ">", ">=")],
+        ..Default::default()
+    };
+    let md = markdown::to_markdown(&result);
+
+    assert!(md.contains("**Score:** 75.0% (3/4 killed) · 1 survived"));
+    assert!(md.contains("| Ref | Location | Operator | Change | Suggested assertion |"));
+    assert!(md.contains("| @m1 | `src/app.py:10` | boundary | `>` → `>=` | Test the exact boundary value |"));
+}
+
+#[test]
+fn all_killed_has_no_table() {
+    let result = RunResult { score: 1.0, total: 2, killed: 2, ..Default::default() };
+    let md = markdown::to_markdown(&result);
+    assert!(md.contains("(2/2 killed)"));
+    assert!(!md.contains("| Ref |"));
+}
+
+#[test]
+fn uncovered_rows_follow_survivors() {
+    let result = RunResult {
+        survived_mutants: vec![mutant("m1", "arith", "+", "-")],
+        uncovered_mutants: vec![mutant("u1", "arith", "*", "/")],
+        ..Default::default()
+    };
+    let md = markdown::to_markdown(&result);
+    let m1 = md.find("@m1").unwrap();
+    let u1 = md.find("@u1").unwrap();
+    assert!(m1 < u1);
+    assert!(md[u1..].contains("executes this line"));
+}
+
+#[test]
+fn code_cells_are_table_safe() {
+    let result = RunResult {
+        survived_mutants: vec![
+            mutant("m1", "logic_flip", "a || b", "a && b"),
+            mutant("m2", "block_remove", "x = 1\n    y = 2", "pass"),
+            mutant("m3", "string_mut", "`tpl`", ""),
+        ],
+        ..Default::default()
+    };
+    let md = markdown::to_markdown(&result);
+    assert!(md.contains("`a \\|\\| b` → `a && b`"));
+    assert!(md.contains("`x = 1 y = 2` → `pass`"));
+    assert!(md.contains("`` `tpl` `` → *(empty)*"));
+}
+
+#[test]
+fn unknown_operator_gets_generic_hint() {
+    assert_eq!(markdown::assertion_hint("boundary"), "Test the exact boundary value");
+    assert!(markdown::assertion_hint("no_such_op").contains("distinguishes"));
+}
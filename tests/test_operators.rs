@@ -16,6 +16,13 @@ fn string_single_quote_empty_becomes_mutator_xx() {
     assert_eq!(ops[0].replacement, "\"mutator_xx\"");
 }
 
+#[test]
+fn string_prefix_ignored_for_empty_check() {
+    assert_eq!(operators::string_mutations("b''")[0].replacement, "\"mutator_xx\"");
+    assert_eq!(operators::string_prefix("rb'x'"), "rb");
+    assert_eq!(operators::string_prefix("\"x\""), "");
+}
+
 #[test]
 fn string_nonempty_becomes_empty() {
     let ops = operators::string_mutations("\"hello\"");
@@ -265,3 +272,109 @@ fn arithmetic_pow_to_mul() {
 fn arithmetic_unknown_returns_empty() {
     assert!(operators::arithmetic_mutations("^").is_empty());
 }
+
+#[test]
+fn aug_assign_add_sub_swap() {
+    assert_eq!(operators::augmented_assignment_mutations("+=")[0].replacement, "-=");
+    assert_eq!(operators::augmented_assignment_mutations("-=")[0].replacement, "+=");
+}
+
+#[test]
+fn aug_assign_mul_div_swap() {
+    assert_eq!(operators::augmented_assignment_mutations("*=")[0].replacement, "/=");
+    assert_eq!(operators::augmented_assignment_mutations("/=")[0].replacement, "*=");
+}
+
+#[test]
+fn aug_assign_unknown_returns_empty() {
+    assert!(operators::augmented_assignment_mutations("|=").is_empty());
+}
+
+#[test]
+fn default_value_bool_flip() {
+    assert_eq!(operators::default_value_mutations("True")[0].replacement, "False");
+    assert_eq!(operators::default_value_mutations("False")[0].replacement, "True");
+}
+
+#[test]
+fn default_value_numbers() {
+    assert_eq!(operators::default_value_mutations("0")[0].replacement, "1");
+    assert_eq!(operators::default_value_mutations("10")[0].replacement, "0");
+    assert_eq!(operators::default_value_mutations("-1")[0].replacement, "0");
+    assert_eq!(operators::default_value_mutations("0.5")[0].replacement, "0.0");
+}
+
+#[test]
+fn default_value_strings() {
+    let ops = operators::default_value_mutations("\"\"");
+    assert_eq!(ops[0].operator_name, "default_arg");
+    assert_eq!(ops[0].replacement, "\"mutator_xx\"");
+    assert_eq!(operators::default_value_mutations("'utf-8'")[0].replacement, "\"\"");
+}
+
+#[test]
+fn default_value_none_and_computed_skipped() {
+    assert!(operators::default_value_mutations("None").is_empty());
+    assert!(operators::default_value_mutations("[]").is_empty());
+    assert!(operators::default_value_mutations("DEFAULT_TIMEOUT").is_empty());
+}
+
+#[test]
+fn loop_control_swaps_break_and_continue() {
+    assert_eq!(operators::loop_control_mutations("break")[0].replacement, "continue");
+    assert_eq!(operators::loop_control_mutations("continue")[0].replacement, "break");
+    assert!(operators::loop_control_mutations("pass").is_empty());
+}
+
+#[test]
+fn loop_zero_iterations_uses_false() {
+    let ops = operators::loop_zero_iterations();
+    assert_eq!(ops[0].operator_name, "loop_zero");
+    assert_eq!(ops[0].replacement, "False");
+}
+
+#[test]
+fn except_body_raise_and_pass() {
+    let ops = operators::except_body_mutations("log(e)\n        return None");
+    let reps: Vec<_> = ops.iter().map(|o| o.replacement).collect();
+    assert_eq!(reps, vec!["raise", "pass"]);
+    assert_eq!(operators::except_body_mutations("pass")[0].replacement, "raise");
+}
+
+#[test]
+fn except_body_lone_raise_left_to_raise_remove() {
+    assert!(operators::except_body_mutations("raise").is_empty());
+    let ops = operators::except_body_mutations("raise AppError(e)");
+    assert_eq!(ops.len(), 1);
+    assert_eq!(ops[0].replacement, "raise");
+}
+
+#[test]
+fn exception_sibling_is_not_a_subclass() {
+    assert_eq!(operators::exception_sibling("ValueError"), Some("TypeError"));
+    assert_eq!(operators::exception_sibling("KeyError"), Some("IndexError"));
+    assert_eq!(operators::exception_sibling("Exception"), None);
+    assert_eq!(operators::exception_sibling("MyError"), None);
+}
+
+#[test]
+fn length_boundary_keeps_equality_one_sided() {
+    let op = |text, left| operators::length_boundary(text, left).map(|op| (op.operator_name, op.replacement));
+    assert_eq!(op(">", true), Some(("len_boundary", ">=")));
+    assert_eq!(op("<=", false), Some(("len_boundary", "<")));
+    assert_eq!(op("===", true), Some(("len_boundary", ">=")));
+    assert_eq!(op("==", false), Some(("len_boundary", "<=")));
+    assert_eq!(op("!=", true), Some(("len_boundary", "<")));
+    assert_eq!(op("in", true), None);
+}
+
+#[test]
+fn string_method_swaps_are_mirrored() {
+    for (a, b) in [("startswith", "endswith"), ("upper", "lower"), ("startsWith", "endsWith"), ("starts_with", "ends_with")] {
+        assert_eq!(operators::string_method_swap(a), Some(b));
+        assert_eq!(operators::string_method_swap(b), Some(a));
+    }
+    assert_eq!(operators::string_method_swap("split"), None);
+    assert!(operators::is_trim_method("strip") && operators::is_trim_method("trimStart"));
+    assert!(!operators::is_trim_method("split"));
+}
@@ -0,0 +1,17 @@
+use mutator::output;
+use std::time::Duration;
+
+#[test]
+fn progress_line_shows_kill_rate_and_eta() {
+    let line = output::progress_line(10, 40, 6, 8, Duration::from_secs(20));
+    assert_eq!(line, "  [10/40] 75% killed · ETA 1m 00s");
+}
+
+#[test]
+fn progress_line_before_and_after_the_run() {
+    assert_eq!(output::progress_line(0, 40, 0, 0, Duration::ZERO), "  [0/40]");
+    // Nothing left, so no ETA
+    assert_eq!(output::progress_line(40, 40, 40, 40, Duration::from_secs(90)), "  [40/40] 100% killed");
+    // Unviable mutants finished but aren't scored yet
+    assert_eq!(output::progress_line(2, 3, 0, 0, Duration::from_secs(2)), "  [2/3] · ETA 1s");
+}
@@ -1,3 +1,4 @@
+use mutator::mutants;
 use mutator::parser;
 
 #[test]
@@ -16,6 +17,91 @@ def check(x):
     assert_eq!(comparisons[0].column, 10);
 }
 
+#[test]
+fn length_comparisons_get_len_boundary() {
+    let source = "def check(xs, n):\n    if len(xs) > 0:\n        return 0 == len(xs)\n    return len(xs) != n and x > 0\n";
+    let mutations = parser::discover_mutations(source, Some("check"));
+    let ops = |op: &str| -> Vec<String> {
+        mutations.iter().filter(|m| m.operator == op).map(|m| format!("{} -> {}", m.original, m.replacement)).collect()
+    };
+    assert_eq!(ops("len_boundary"), ["> -> >=", "== -> <=", "!= -> <"]);
+    // The plain comparison keeps `boundary`; negations are unchanged
+    assert_eq!(ops("boundary"), ["> -> >="]);
+    assert_eq!(ops("negate_cmp"), ["> -> <=", "> -> <="]);
+}
+
+#[test]
+fn string_methods_swapped_and_strip_removed() {
+    let source = "def check(s):\n    if s.strip().startswith(\"x\"):\n        return s.upper()\n    return re.compile(s)\n";
+    let mutations = parser::discover_mutations(source, Some("check"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "string_method").map(|m| m.replacement.as_str()).collect();
+    assert_eq!(swaps, ["endswith", "lower"]);
+    let strip: Vec<_> = mutations.iter().filter(|m| m.operator == "trim_remove").collect();
+    assert_eq!(strip.len(), 1);
+    assert_eq!((strip[0].original.as_str(), strip[0].replacement.as_str()), ("s.strip()", "s"));
+}
+
+#[test]
+fn collection_methods_only_when_enabled() {
+    use mutator::mutation_parser::ParseOptions;
+    let source = "def check(xs, q):\n    top = q.pop()\n    q.pop()\n    return sorted(xs, key=len), min(xs), sorted(xs, reverse=True)\n";
+    let parser = mutator::Language::Python.parser();
+    let options = ParseOptions { collection_method: true, ..Default::default() };
+    let changes: Vec<_> = parser
+        .discover(source, Some("check"), &options)
+        .into_iter()
+        .filter(|m| m.operator == "collection_method")
+        .map(|m| format!("{} -> {}", m.original, m.replacement))
+        .collect();
+    // The `q.pop()` statement is left to `call_remove`
+    assert_eq!(changes, ["q.pop() -> q[-1]", "key=len -> key=len, reverse=True", "min -> max"]);
+
+    let default = parser.discover(source, Some("check"), &ParseOptions::default());
+    assert!(default.iter().all(|m| m.operator != "collection_method"));
+}
+
+#[test]
+fn sorted_reverse_keeps_comments_inside_the_call() {
+    use mutator::mutation_parser::ParseOptions;
+    let source = "def order(xs):\n    return sorted(\n        xs,  # newest first\n    )\n";
+    let options = ParseOptions { collection_method: true, ..Default::default() };
+    let mutations = mutator::Language::Python.parser().discover(source, Some("order"), &options);
+    let m = mutations.iter().find(|m| m.operator == "collection_method").unwrap();
+    let mutated = format!("{}{}{}", &source[..m.start_byte], m.replacement, &source[m.end_byte..]);
+    assert_eq!(mutated, "def order(xs):\n    return sorted(\n        xs, reverse=True,  # newest first\n    )\n");
+}
+
+#[test]
+fn asyncio_gather_sleep_and_async_bodies() {
+    let source = r#"
+async def run(client, urls, done):
+    a, b = await asyncio.gather(client.get(urls[0]), fetch(urls[1]), return_exceptions=True)
+    async for chunk in client.stream():
+        done.append(chunk)
+    async with client.lock():
+        done.clear()
+    for url in urls:
+        done.append(url)
+    await asyncio.sleep(0.5)
+    await asyncio.sleep(0)
+    return a
+"#;
+    let mutations = parser::discover_mutations(source, Some("run"));
+    let gather: Vec<_> = mutations.iter().filter(|m| m.operator == "gather_drop").collect();
+    assert_eq!(gather.len(), 2);
+    let apply = |m: &mutants::Mutation| mutator::runner::apply_mutation(source, m).unwrap();
+    assert!(apply(gather[0]).contains("asyncio.gather(fetch(urls[1]), return_exceptions=True)"));
+    assert!(apply(gather[1]).contains("asyncio.gather(client.get(urls[0]), return_exceptions=True)"));
+
+    let sleeps: Vec<_> = mutations.iter().filter(|m| m.operator == "sleep_zero").collect();
+    assert_eq!(sleeps.len(), 1);
+    assert_eq!((sleeps[0].original.as_str(), sleeps[0].line), ("0.5", 10));
+
+    // Only the async loop and context manager lose their bodies
+    let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").map(|m| m.line).collect();
+    assert_eq!(blocks, [5, 7]);
+}
+
 #[test]
 fn discovers_boolean_operator_mutations() {
     let source = r#"
@@ -175,7 +261,7 @@ def check(x):
 "#;
     let mutations = parser::discover_mutations(source, Some("check"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1, "Expected at least 1 block_remove mutation");
+    assert!(!blocks.is_empty(), "Expected at least 1 block_remove mutation");
     assert!(blocks[0].replacement.contains("pass"));
     assert_eq!(blocks[0].column, 9, "block body column should be 9 (indented)");
 }
@@ -233,6 +319,38 @@ def test_something():
     assert!(!names.contains(&"test_something".to_string()));
 }
 
+#[test]
+fn methods_are_scoped_by_qualified_name() {
+    let source = r#"
+class Range:
+    def check(self, x):
+        return x > 0
+
+class Limit:
+    def check(self, x):
+        return x < 10
+
+    class Inner:
+        def check(self, x):
+            def helper():
+                return x == 1
+            return helper()
+"#;
+    let names = parser::list_functions(source);
+    assert_eq!(names, vec!["Range.check", "Limit.check", "Limit.Inner.check", "Limit.Inner.check.helper"]);
+
+    let limit = parser::discover_mutations(source, Some("Limit.check"));
+    assert!(!limit.is_empty());
+    assert!(limit.iter().all(|m| m.line == 8), "{:?}", limit.iter().map(|m| m.line).collect::<Vec<_>>());
+    assert!(limit.iter().all(|m| m.function.as_deref() == Some("Limit.check")));
+
+    let inner = parser::discover_mutations(source, Some("Limit.Inner.check"));
+    assert!(inner.iter().any(|m| m.function.as_deref() == Some("Limit.Inner.check.helper")));
+    // Unqualified still finds the first definition
+    let first = parser::discover_mutations(source, Some("check"));
+    assert!(first.iter().all(|m| m.line == 4));
+}
+
 #[test]
 fn skips_print_calls() {
     let source = r#"
@@ -454,6 +572,19 @@ def check(x, y):
         "Last context_before should be line before mutation, got: {:?}", cmp.context_before);
 }
 
+#[test]
+fn assign_context_is_clamped_to_the_file() {
+    let source = "def check(x, y):\n    return x > y\n";
+    let mut mutations = parser::discover_mutations(source, Some("check"));
+    mutants::assign_context(&mut mutations, source, 10);
+    let cmp = mutations.iter().find(|m| m.operator == "boundary").unwrap();
+    assert_eq!(cmp.context_before, vec!["def check(x, y):"]);
+    assert!(cmp.context_after.is_empty());
+
+    mutants::assign_context(&mut mutations, source, 0);
+    assert!(mutations.iter().all(|m| m.context_before.is_empty() && m.context_after.is_empty()));
+}
+
 #[test]
 fn context_at_end_of_function() {
     let source = "def check(x):\n    return x > 0\n";
@@ -498,7 +629,7 @@ def check(x):
 "#;
     let mutations = parser::discover_mutations(source, Some("check"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1);
+    assert!(!blocks.is_empty());
     assert_eq!(blocks[0].line, 4, "block_remove should point to the first line of the block body");
 }
 
@@ -514,3 +645,441 @@ def check(x):
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
     assert!(blocks.is_empty(), "pass body should not generate block_remove");
 }
+
+#[test]
+fn match_statement_case_removal() {
+    let source = r#"
+def label(x):
+    match x:
+        case 1:
+            return "one"
+        case _:
+            return "other"
+"#;
+    let mutations = parser::discover_mutations(source, Some("label"));
+    let arms: Vec<_> = mutations.iter().filter(|m| m.operator == "arm_remove").collect();
+    assert_eq!(arms.len(), 2);
+    assert!(arms[0].original.starts_with("case 1:"));
+    assert_eq!(arms[0].replacement, "");
+    assert_eq!(arms[0].line, 4);
+}
+
+#[test]
+fn match_statement_single_case_not_removed() {
+    let source = r#"
+def label(x):
+    match x:
+        case _:
+            return "other"
+"#;
+    let mutations = parser::discover_mutations(source, Some("label"));
+    assert!(mutations.iter().all(|m| m.operator != "arm_remove"));
+}
+
+#[test]
+fn conditional_expression_branches_swapped() {
+    let source = r#"
+def label(x):
+    y = "pos" if x > 0 else "neg"
+    return y
+"#;
+    let mutations = parser::discover_mutations(source, Some("label"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "ternary_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].original, "\"pos\" if x > 0 else \"neg\"");
+    assert_eq!(swaps[0].replacement, "\"neg\" if x > 0 else \"pos\"");
+}
+
+#[test]
+fn first_two_call_arguments_swapped() {
+    let source = r#"
+def window(items, start, end):
+    logger.info("window %s", start, end)
+    return slice_of(items, start, end=end)
+"#;
+    let mutations = parser::discover_mutations(source, Some("window"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "arg_swap").collect();
+    assert_eq!(swaps.len(), 1, "{:?}", swaps);
+    assert_eq!(swaps[0].original, "items, start");
+    assert_eq!(swaps[0].replacement, "start, items");
+}
+
+#[test]
+fn arg_swap_skips_keywords_splats_and_mixed_literals() {
+    let source = r#"
+def build(a, rest, opts):
+    one(a, key=1)
+    two(*rest, a)
+    three("name", 3)
+    four(1, 2)
+"#;
+    let mutations = parser::discover_mutations(source, Some("build"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "arg_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].replacement, "2, 1");
+}
+
+#[test]
+fn standalone_call_statements_removed() {
+    let source = r#"
+def update(self, key):
+    self.cache.invalidate(key)
+    value = self.load(key)
+    print(value)
+    self.save()
+"#;
+    let mutations = parser::discover_mutations(source, Some("update"));
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "call_remove").collect();
+    let originals: Vec<_> = removed.iter().map(|m| m.original.as_str()).collect();
+    assert_eq!(originals, ["self.cache.invalidate(key)", "self.save()"]);
+    assert!(removed.iter().all(|m| m.replacement == "pass"));
+}
+
+#[test]
+fn lone_call_in_if_body_left_to_block_remove() {
+    let source = "def reset(xs, c):\n    if c:\n        xs.clear()\n    else:\n        xs.sort()\n";
+    let mutations = parser::discover_mutations(source, Some("reset"));
+    let ops: Vec<_> = mutations
+        .iter()
+        .filter(|m| matches!(m.operator.as_str(), "block_remove" | "call_remove"))
+        .map(|m| (m.operator.as_str(), m.line))
+        .collect();
+    // `else` bodies get no block_remove, so their call is still removed
+    assert_eq!(ops, [("block_remove", 3), ("call_remove", 5)]);
+}
+
+#[test]
+fn comprehension_filters_dropped_and_negated() {
+    let source = r#"
+def positives(xs, index):
+    kept = [x for x in xs if x > 0]
+    names = {k: v for k, v in index.items() if v.active}
+    return kept, names, sum(x for x in xs if not x)
+"#;
+    let mutations = parser::discover_mutations(source, Some("positives"));
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "filter_remove").collect();
+    assert_eq!(removed.len(), 3);
+    assert_eq!(removed[0].original, " if x > 0");
+    assert_eq!(removed[0].replacement, "");
+
+    let negated: Vec<_> = mutations.iter().filter(|m| m.operator == "filter_negate").collect();
+    let replacements: Vec<_> = negated.iter().map(|m| m.replacement.as_str()).collect();
+    assert_eq!(replacements, ["not (x > 0)", "not v.active"]);
+}
+
+#[test]
+fn string_mutations_are_opt_in() {
+    let source = r#"
+def configure(user):
+    """Docstring stays."""
+    mode = "strict"
+    options = {"level": 'high', "label": f"user {user}"}
+    logger.info("configured", extra={"user": "x"})
+    empty = ""
+    return mode, options, empty
+"#;
+    assert!(parser::discover_mutations(source, None).iter().all(|m| m.operator != "string_mut"));
+
+    let mutations = parser::discover_mutations_with(source, None, true, &[]);
+    let strings: Vec<_> = mutations
+        .iter()
+        .filter(|m| m.operator == "string_mut")
+        .map(|m| (m.original.as_str(), m.replacement.as_str()))
+        .collect();
+    assert_eq!(
+        strings,
+        [
+            ("\"strict\"", "\"\""),
+            ("'high'", "\"\""),
+            ("f\"user {user}\"", "f\"{user}\""),
+            ("\"\"", "\"mutator_xx\""),
+        ]
+    );
+}
+
+#[test]
+fn string_mutations_keep_prefixes() {
+    let source = "def f(x):\n    raw = b\"bytes\"\n    none = rb''\n    url = f\"{x.base}/api/{x.id!r:>4}\"\n    bare = f\"{x}{x}\"\n";
+    let mutations = parser::discover_mutations_with(source, None, true, &[]);
+    let strings: Vec<_> = mutations
+        .iter()
+        .filter(|m| m.operator == "string_mut")
+        .map(|m| (m.original.as_str(), m.replacement.as_str()))
+        .collect();
+    assert_eq!(
+        strings,
+        [
+            ("b\"bytes\"", "b\"\""),
+            ("rb''", "rb\"mutator_xx\""),
+            ("f\"{x.base}/api/{x.id!r:>4}\"", "f\"{x.base}{x.id!r:>4}\""),
+        ]
+    );
+}
+
+#[test]
+fn discovers_augmented_assignment_mutations() {
+    let source = r#"
+def total(xs):
+    t = 0
+    for x in xs:
+        t += x
+    return t
+"#;
+    let mutations = parser::discover_mutations(source, Some("total"));
+    let aug: Vec<_> = mutations.iter().filter(|m| m.operator == "aug_assign").collect();
+    assert_eq!(aug.len(), 1);
+    assert_eq!(aug[0].original, "+=");
+    assert_eq!(aug[0].replacement, "-=");
+    assert_eq!(aug[0].line, 5);
+    assert_eq!(aug[0].column, 11);
+}
+
+#[test]
+fn augmented_string_accumulation_skipped() {
+    let source = r#"
+def build(n):
+    s = ""
+    s += "x"
+    return s
+"#;
+    let mutations = parser::discover_mutations(source, Some("build"));
+    assert!(!mutations.iter().any(|m| m.operator == "aug_assign"));
+}
+
+#[test]
+fn discovers_default_argument_mutations() {
+    let source = r#"
+def fetch(url, retries: int = 3, verbose=False, sep="", timeout=None):
+    return url
+"#;
+    let mutations = parser::discover_mutations(source, Some("fetch"));
+    let defaults: Vec<_> = mutations.iter().filter(|m| m.operator == "default_arg").collect();
+    let pairs: Vec<_> = defaults.iter().map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(pairs, vec![("3", "0"), ("False", "True"), ("\"\"", "\"mutator_xx\"")]);
+    // The boolean default is not double-counted as bool_flip
+    assert!(!mutations.iter().any(|m| m.operator == "bool_flip"));
+}
+
+#[test]
+fn discovers_loop_mutations() {
+    let source = r#"
+def drain(q):
+    while q.pending():
+        item = q.pop()
+        if item is None:
+            continue
+        if item == "stop":
+            break
+"#;
+    let mutations = parser::discover_mutations(source, Some("drain"));
+    let zero: Vec<_> = mutations.iter().filter(|m| m.operator == "loop_zero").collect();
+    assert_eq!(zero.len(), 1);
+    assert_eq!(zero[0].original, "q.pending()");
+    assert_eq!(zero[0].replacement, "False");
+
+    let control: Vec<_> = mutations.iter().filter(|m| m.operator == "loop_control").collect();
+    let pairs: Vec<_> = control.iter().map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(pairs, vec![("continue", "break"), ("break", "continue")]);
+}
+
+#[test]
+fn while_true_left_to_bool_flip() {
+    let source = "def spin():\n    while True:\n        break\n";
+    let mutations = parser::discover_mutations(source, Some("spin"));
+    assert!(!mutations.iter().any(|m| m.operator == "loop_zero"));
+    assert!(mutations.iter().any(|m| m.operator == "bool_flip"));
+}
+
+#[test]
+fn discovers_exception_path_mutations() {
+    let source = r#"
+def load(path):
+    try:
+        return read(path)
+    except FileNotFoundError as e:
+        log(e)
+        return None
+    except (KeyError, IndexError):
+        pass
+"#;
+    let mutations = parser::discover_mutations(source, Some("load"));
+    let bodies: Vec<_> = mutations.iter().filter(|m| m.operator == "except_body").collect();
+    let reps: Vec<_> = bodies.iter().map(|m| m.replacement.trim()).collect();
+    assert_eq!(reps, vec!["raise", "pass", "raise"]);
+
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "except_swap").collect();
+    assert_eq!(swaps.len(), 1, "KeyError/IndexError swap to each other, so only one swap");
+    assert_eq!(swaps[0].original, "FileNotFoundError");
+    assert_eq!(swaps[0].replacement, "PermissionError");
+}
+
+#[test]
+fn raise_becomes_pass() {
+    let source = r#"
+def check(x):
+    if x < 0:
+        raise ValueError("negative")
+    return x
+"#;
+    let mutations = parser::discover_mutations(source, Some("check"));
+    let raises: Vec<_> = mutations.iter().filter(|m| m.operator == "raise_remove").collect();
+    assert_eq!(raises.len(), 1);
+    assert_eq!(raises[0].original, "raise ValueError(\"negative\")");
+    assert_eq!(raises[0].replacement, "pass");
+}
+
+#[test]
+fn except_body_mutation_is_valid_python_layout() {
+    let source = "def f():\n    try:\n        g()\n    except ValueError:\n        h()\n";
+    let mutations = parser::discover_mutations(source, Some("f"));
+    let m = mutations.iter().find(|m| m.operator == "except_body" && m.replacement.trim() == "pass").unwrap();
+    let mutated = format!("{}{}{}", &source[..m.start_byte], m.replacement, &source[m.end_byte..]);
+    assert!(mutated.ends_with("    except ValueError:\n        \n        pass\n"));
+    assert!(!mutated.contains("h()"));
+}
+
+#[test]
+fn single_line_except_body_is_replaced_inline() {
+    let source = "def f():\n    try:\n        g()\n    except ValueError: h()\n    return 1\n";
+    let mutations = parser::discover_mutations(source, Some("f"));
+    let m = mutations.iter().find(|m| m.operator == "except_body" && m.replacement == "pass").unwrap();
+    let mutated = format!("{}{}{}", &source[..m.start_byte], m.replacement, &source[m.end_byte..]);
+    assert_eq!(mutated, "def f():\n    try:\n        g()\n    except ValueError: pass\n    return 1\n");
+}
+
+#[test]
+fn mutations_carry_innermost_function() {
+    let source = "def outer(x):\n    def inner(y):\n        return y + 1\n    return inner(x) * 2\n";
+    let mutations = parser::discover_mutations(source, None);
+    let plus = mutations.iter().find(|m| m.original == "+").unwrap();
+    let times = mutations.iter().find(|m| m.original == "*").unwrap();
+    assert_eq!(plus.function.as_deref(), Some("outer.inner"));
+    assert_eq!(times.function.as_deref(), Some("outer"));
+}
+
+#[test]
+fn nested_functions_are_listed_and_scoped_by_parent() {
+    let source = r#"
+def outer(xs):
+    def keep(x):
+        return x > 0
+
+    def wrap(f):
+        def inner(y):
+            return f(y) + 1
+        return inner
+    return [x * 2 for x in xs if keep(x)]
+"#;
+    let names = parser::list_functions(source);
+    assert_eq!(names, vec!["outer", "outer.keep", "outer.wrap", "outer.wrap.inner"]);
+
+    let inner = parser::discover_mutations(source, Some("outer.wrap.inner"));
+    assert!(!inner.is_empty());
+    assert!(inner.iter().all(|m| m.line == 8 && m.function.as_deref() == Some("outer.wrap.inner")));
+    let keep = parser::discover_mutations(source, Some("keep"));
+    assert!(keep.iter().all(|m| m.line == 4));
+    // The parent still covers its closures
+    let outer = parser::discover_mutations(source, Some("outer"));
+    assert!(outer.iter().any(|m| m.function.as_deref() == Some("outer.keep")));
+    assert!(outer.iter().any(|m| m.line == 10 && m.function.as_deref() == Some("outer")));
+}
+
+#[test]
+fn offsets_match_original_with_multibyte_docstrings_and_strings() {
+    let source = r#"
+def check(x):
+    """Grüße 🐍 — 检查 the value."""
+    label = "日本語 😀"
+    if x > 0 and label:
+        return "正"
+    return "負"
+"#;
+    let mutations = parser::discover_mutations(source, Some("check"));
+    assert!(mutations.iter().any(|m| m.original == ">"));
+    for m in &mutations {
+        assert_eq!(source.get(m.start_byte..m.end_byte), Some(m.original.as_str()), "{}", m.operator);
+        assert!(mutator::runner::apply_mutation(source, m).is_some(), "{}", m.operator);
+    }
+}
+
+#[test]
+fn toplevel_mutations_skip_functions_and_main_guard() {
+    let source = r#"
+LIMIT = 10 * 2
+DEBUG = False
+
+def add(a, b):
+    return a + b
+
+if LIMIT > 5:
+    DEBUG = True
+
+if __name__ == '__main__':
+    print(add(1, 2) + 1)
+"#;
+    let mutations = parser::toplevel_mutations(source, false);
+    let lines: Vec<_> = mutations.iter().map(|m| m.line).collect();
+    assert!(lines.contains(&2), "{:?}", lines);
+    assert!(lines.contains(&3), "{:?}", lines);
+    assert!(lines.contains(&8), "{:?}", lines);
+    assert!(!lines.contains(&6), "function body mutated: {:?}", lines);
+    assert!(lines.iter().all(|&l| l < 11), "__main__ guard mutated: {:?}", lines);
+    assert!(mutations.iter().all(|m| m.function.is_none()));
+}
+
+#[test]
+fn overload_and_abstract_stubs_are_skipped() {
+    let source = r#"
+from abc import ABC, abstractmethod
+from typing import overload
+
+class Shape(ABC):
+    @abstractmethod
+    def area(self) -> float:
+        return 0 + 0
+
+@overload
+def parse(x: int) -> int: ...
+@overload
+def parse(x: str) -> str: ...
+def parse(x):
+    return x * 2
+"#;
+    assert_eq!(parser::list_functions(source), vec!["parse"]);
+    let mutations = parser::discover_mutations(source, None);
+    assert!(!mutations.is_empty());
+    assert!(mutations.iter().all(|m| m.line == 15), "{:?}", mutations.iter().map(|m| m.line).collect::<Vec<_>>());
+    // A named function resolves to the implementation, not the first stub
+    assert!(parser::discover_mutations(source, Some("parse")).iter().all(|m| m.line == 15));
+}
+
+#[test]
+fn skip_decorated_matches_full_or_last_dotted_name() {
+    let source = r#"
+import functools
+
+class Account:
+    @property
+    def overdrawn(self):
+        return self.balance < 0
+
+    @functools.lru_cache(maxsize=None)
+    def rate(self):
+        return self.base * 2
+
+    def deposit(self, amount):
+        self.balance += amount
+"#;
+    let skip = ["property".to_string(), "lru_cache".to_string()];
+    let mutations = parser::discover_mutations_with(source, None, false, &skip);
+    assert!(!mutations.is_empty());
+    assert!(mutations.iter().all(|m| m.function.as_deref() == Some("Account.deposit")));
+
+    let full = parser::discover_mutations_with(source, None, false, &["functools.lru_cache".to_string()]);
+    assert!(full.iter().any(|m| m.function.as_deref() == Some("Account.overdrawn")));
+    assert!(!full.iter().any(|m| m.function.as_deref() == Some("Account.rate")));
+
+    // Naming a skipped function mutates it anyway
+    let named = parser::discover_mutations_with(source, Some("overdrawn"), false, &skip);
+    assert!(!named.is_empty());
+}
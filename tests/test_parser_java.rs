@@ -0,0 +1,290 @@
+use std::path::Path;
+
+use mutator::mutation_parser::{MutationParser, ParseOptions};
+use mutator::parser_java::{self, JavaParser};
+
+fn java_mutations(source: &str, func: Option<&str>) -> Vec<mutator::mutants::Mutation> {
+    parser_java::discover_mutations(source, func)
+}
+
+fn operators(mutations: &[mutator::mutants::Mutation]) -> Vec<&str> {
+    mutations.iter().map(|m| m.operator.as_str()).collect()
+}
+
+// --- Function discovery ---
+
+#[test]
+fn discovers_methods_and_constructors() {
+    let source = r#"
+package com.shop;
+
+class Cart {
+    private int total;
+
+    Cart(int start) {
+        if (start > 0) {
+            total = start;
+        }
+    }
+
+    boolean isEmpty() {
+        return total == 0;
+    }
+
+    abstract static class Rule {
+        abstract boolean applies(int total);
+
+        int limit(int n) {
+            return n * 2;
+        }
+    }
+}
+"#;
+    assert_eq!(parser_java::list_functions(source), ["Cart.isEmpty", "Cart.Rule.limit"]);
+    let spans: Vec<_> = parser_java::function_spans(source).into_iter().map(|s| s.name).collect();
+    assert_eq!(spans, ["Cart.Cart", "Cart.isEmpty", "Cart.Rule.limit"]);
+
+    let mutations = java_mutations(source, Some("Cart.Rule.limit"));
+    assert!(mutations.iter().all(|m| m.function.as_deref() == Some("Cart.Rule.limit")));
+    assert!(mutations.iter().any(|m| m.operator == "arith" && m.replacement == "/"));
+
+    let ctor = java_mutations(source, Some("Cart"));
+    assert!(ctor.iter().any(|m| m.operator == "boundary"), "{:?}", operators(&ctor));
+}
+
+#[test]
+fn junit_tests_and_fixtures_are_skipped() {
+    let source = r#"
+class CartTest {
+    @BeforeEach
+    void setUp() { count = 1 + 1; }
+
+    @Test
+    void adds() { assertEquals(2, 1 + 1); }
+
+    @org.junit.jupiter.api.Test
+    void removes() { assertTrue(a > b); }
+
+    int helper(int a) { return a - 1; }
+}
+"#;
+    let mutations = java_mutations(source, None);
+    assert!(mutations.iter().all(|m| m.function.as_deref() == Some("CartTest.helper")), "{:?}", mutations);
+    assert!(!mutations.is_empty());
+
+    let all = parser_java::discover_mutations_with(source, None, true);
+    assert!(all.iter().any(|m| m.function.as_deref() == Some("CartTest.adds")));
+    assert!(!java_mutations(source, Some("adds")).is_empty());
+}
+
+#[test]
+fn test_files_recognised_by_name_and_layout() {
+    assert!(parser_java::is_test_file(Path::new("src/test/java/com/x/Cart.java")));
+    assert!(parser_java::is_test_file(Path::new("CartTest.java")));
+    assert!(parser_java::is_test_file(Path::new("CartTests.java")));
+    assert!(parser_java::is_test_file(Path::new("TestCart.java")));
+    assert!(!parser_java::is_test_file(Path::new("src/main/java/com/x/Testable.java")));
+    assert!(!parser_java::is_test_file(Path::new("src/main/java/com/x/Cart.java")));
+}
+
+// --- Operators ---
+
+#[test]
+fn comparisons_logic_and_arithmetic() {
+    let source = r#"
+class Calc {
+    boolean check(int a, int b, String s) {
+        String label = "n=" + a;
+        return a >= b && s != null || a % 2 == 0;
+    }
+}
+"#;
+    let mutations = java_mutations(source, Some("check"));
+    let ops = operators(&mutations);
+    assert!(ops.contains(&"boundary"));
+    assert!(ops.contains(&"negate_cmp"));
+    assert!(ops.contains(&"negate_eq"));
+    assert!(ops.contains(&"logic_flip"));
+    assert!(mutations.iter().any(|m| m.operator == "arith" && m.original == "%"));
+    // String concatenation is left alone
+    assert!(!mutations.iter().any(|m| m.operator == "arith" && m.original == "+"));
+}
+
+#[test]
+fn length_comparisons_use_len_boundary() {
+    let source = r#"
+class Items {
+    boolean any(java.util.List<String> xs, int[] arr, String s) {
+        return xs.size() > 0 && arr.length >= 1 && s.length() == 0;
+    }
+}
+"#;
+    let mutations = java_mutations(source, Some("any"));
+    let len: Vec<_> = mutations.iter().filter(|m| m.operator == "len_boundary").collect();
+    assert_eq!(len.len(), 3, "{:?}", operators(&mutations));
+    assert!(!mutations.iter().any(|m| m.operator == "boundary"));
+}
+
+#[test]
+fn return_values_follow_the_declared_type() {
+    let source = r#"
+class Values {
+    boolean flag(int x) { return x > 1; }
+    int count() { return items.size(); }
+    double ratio() { return 0.0; }
+    String name() { return first + last; }
+    Optional<User> find(int id) { return repo.get(id); }
+    List<String> names() { return names; }
+    User owner() { return owner; }
+    void reset() { return; }
+}
+"#;
+    let replacement = |func: &str| {
+        java_mutations(source, Some(func))
+            .into_iter()
+            .find(|m| m.operator == "return_val")
+            .map(|m| m.replacement)
+    };
+    assert_eq!(replacement("flag").as_deref(), Some("return !(x > 1);"));
+    assert_eq!(replacement("count").as_deref(), Some("return 0;"));
+    assert_eq!(replacement("ratio").as_deref(), Some("return 1.0;"));
+    assert_eq!(replacement("name").as_deref(), Some("return \"\";"));
+    assert_eq!(replacement("find").as_deref(), Some("return Optional.empty();"));
+    assert_eq!(replacement("names").as_deref(), Some("return List.of();"));
+    assert_eq!(replacement("owner").as_deref(), Some("return null;"));
+    assert_eq!(replacement("reset"), None);
+}
+
+#[test]
+fn numeric_return_values_keep_their_type() {
+    let source = r#"
+class Numbers {
+    long zero() { return 0L; }
+    long big() { return 1_000L; }
+    Long boxed() { return count; }
+    float half() { return 0.5f; }
+    Float none() { return 0.0F; }
+    double hex() { return 0x0; }
+    java.util.List<String> all() { return names; }
+    java.util.Optional<String> first() { return name; }
+    Iterable<String> each() { return names; }
+}
+"#;
+    let replacement = |func: &str| {
+        java_mutations(source, Some(func))
+            .into_iter()
+            .find(|m| m.operator == "return_val")
+            .map(|m| m.replacement)
+    };
+    assert_eq!(replacement("zero").as_deref(), Some("return 1L;"));
+    assert_eq!(replacement("big").as_deref(), Some("return 0L;"));
+    assert_eq!(replacement("boxed").as_deref(), Some("return 0L;"));
+    assert_eq!(replacement("half").as_deref(), Some("return 0.0f;"));
+    assert_eq!(replacement("none").as_deref(), Some("return 1.0f;"));
+    assert_eq!(replacement("hex").as_deref(), Some("return 1.0;"));
+    assert_eq!(replacement("all").as_deref(), Some("return java.util.List.of();"));
+    assert_eq!(replacement("first").as_deref(), Some("return java.util.Optional.empty();"));
+    assert_eq!(replacement("each").as_deref(), Some("return java.util.List.of();"));
+}
+
+#[test]
+fn blocks_loops_and_control_flow() {
+    let source = r#"
+class Loop {
+    int sum(int[] xs) {
+        int total = 0;
+        int i = 0;
+        while (i < xs.length) {
+            if (xs[i] < 0) {
+                break;
+            } else {
+                total += xs[i];
+            }
+            i++;
+        }
+        switch (total) {
+            case 0:
+                break;
+        }
+        return total > 10 ? 10 : total;
+    }
+}
+"#;
+    let mutations = java_mutations(source, Some("sum"));
+    let ops = operators(&mutations);
+    assert_eq!(ops.iter().filter(|op| **op == "block_remove").count(), 3, "{:?}", ops);
+    assert!(mutations.iter().any(|m| m.operator == "loop_zero" && m.original == "i < xs.length"));
+    assert!(mutations.iter().any(|m| m.operator == "aug_assign" && m.replacement == "-="));
+    assert!(mutations.iter().any(|m| m.operator == "ternary_swap" && m.replacement == "total : 10"));
+    // Only the loop's `break` flips; the switch's has no loop to continue
+    assert_eq!(ops.iter().filter(|op| **op == "loop_control").count(), 1);
+}
+
+#[test]
+fn calls_strings_and_arguments() {
+    let source = r#"
+class Users {
+    void save(User u, String name) {
+        validate(u);
+        if (!name.trim().startsWith("x")) {
+            store.put(u.id(), name);
+        }
+        log.info("saved {}", name);
+        System.out.println(name.isEmpty());
+    }
+}
+"#;
+    let mutations = java_mutations(source, Some("save"));
+    assert!(mutations.iter().any(|m| m.operator == "call_remove" && m.original == "validate(u);"));
+    assert!(mutations.iter().any(|m| m.operator == "negate_remove"));
+    assert!(mutations.iter().any(|m| m.operator == "string_method" && m.replacement == "endsWith"));
+    assert!(mutations.iter().any(|m| m.operator == "trim_remove" && m.replacement == "name"));
+    assert!(mutations.iter().any(|m| m.operator == "arg_swap" && m.replacement == "name, u.id()"));
+    // Logging and printing are never mutated
+    assert!(mutations.iter().all(|m| m.line < 9), "{:?}", mutations);
+}
+
+#[test]
+fn lone_call_in_block_left_to_block_remove() {
+    let source = r#"
+class Items {
+    void reset(java.util.List<Integer> xs, boolean c) {
+        if (c) {
+            xs.clear();
+        } else {
+            xs.sort(null);
+            xs.add(1);
+        }
+    }
+}
+"#;
+    let mutations = java_mutations(source, Some("reset"));
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "call_remove").map(|m| m.original.as_str()).collect();
+    assert_eq!(removed, ["xs.sort(null);", "xs.add(1);"]);
+    assert_eq!(operators(&mutations).iter().filter(|op| **op == "block_remove").count(), 2);
+}
+
+#[test]
+fn min_max_swap_is_opt_in() {
+    let source = "class M { int clamp(int x) { return Math.min(x, 10); } }";
+    assert!(!java_mutations(source, None).iter().any(|m| m.operator == "collection_method"));
+    let options = ParseOptions { collection_method: true, ..Default::default() };
+    let mutations = JavaParser.discover(source, None, &options);
+    assert!(mutations.iter().any(|m| m.operator == "collection_method" && m.replacement == "max"));
+}
+
+#[test]
+fn toplevel_covers_field_initializers_only() {
+    let source = r#"
+class Limits {
+    static final int MAX = 10 * 2;
+    static final boolean STRICT = true;
+
+    int cap(int x) { return x + MAX; }
+}
+"#;
+    let mutations = parser_java::toplevel_mutations(source);
+    assert!(mutations.iter().any(|m| m.operator == "arith" && m.original == "*"));
+    assert!(mutations.iter().any(|m| m.operator == "bool_flip"));
+    assert!(mutations.iter().all(|m| m.line <= 4), "{:?}", mutations);
+}
@@ -247,7 +247,7 @@ function f(x) {
 "#;
     let mutations = js_mutations(source, Some("f"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1, "Should create block_remove mutation for if body");
+    assert!(!blocks.is_empty(), "Should create block_remove mutation for if body");
     assert_eq!(blocks[0].replacement, "{}");
 }
 
@@ -301,7 +301,7 @@ function f(arr) {
 "#;
     let mutations = js_mutations(source, Some("f"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1, "Should create block_remove for for-loop body");
+    assert!(!blocks.is_empty(), "Should create block_remove for for-loop body");
 }
 
 #[test]
@@ -316,7 +316,7 @@ function f(x) {
 "#;
     let mutations = js_mutations(source, Some("f"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1, "Should create block_remove for while-loop body");
+    assert!(!blocks.is_empty(), "Should create block_remove for while-loop body");
 }
 
 #[test]
@@ -331,7 +331,7 @@ function f(obj) {
 "#;
     let mutations = js_mutations(source, Some("f"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1, "Should create block_remove for for-in loop body");
+    assert!(!blocks.is_empty(), "Should create block_remove for for-in loop body");
 }
 
 // --- Function scoping ---
@@ -380,11 +380,64 @@ class MyClass {
     let names = parser_js::list_functions(source, JsDialect::JavaScript);
     assert!(names.contains(&"foo".to_string()));
     assert!(names.contains(&"bar".to_string()));
-    assert!(names.contains(&"baz".to_string()));
+    assert!(names.contains(&"MyClass.baz".to_string()));
     assert!(!names.contains(&"testSomething".to_string()), "Should skip test functions");
     assert!(!names.contains(&"constructor".to_string()), "Should skip constructor");
 }
 
+#[test]
+fn methods_are_scoped_by_qualified_name() {
+    let source = r#"
+class Range {
+    check(x) { return x > 0; }
+}
+class Limit {
+    check(x) {
+        const helper = () => x === 1;
+        return x < 10;
+    }
+}
+"#;
+    let names = parser_js::list_functions(source, JsDialect::TypeScript);
+    assert_eq!(names, vec!["Range.check", "Limit.check", "Limit.check.helper"]);
+
+    let limit = ts_mutations(source, Some("Limit.check"));
+    assert!(!limit.is_empty());
+    assert!(limit.iter().all(|m| (6..=8).contains(&m.line)));
+    assert!(limit.iter().any(|m| m.function.as_deref() == Some("Limit.check")));
+    assert!(limit.iter().any(|m| m.function.as_deref() == Some("Limit.check.helper")));
+    assert!(ts_mutations(source, Some("Range.check")).iter().all(|m| m.line == 3));
+}
+
+#[test]
+fn nested_functions_are_listed_and_scoped_by_parent() {
+    let source = r#"
+function outer(xs) {
+    function keep(x) {
+        return x > 0;
+    }
+    const scale = (x) => x * 2;
+    return xs.filter(keep).map((x) => {
+        const clamp = (y) => y < 10;
+        return clamp(scale(x));
+    });
+}
+"#;
+    let names = parser_js::list_functions(source, JsDialect::JavaScript);
+    assert_eq!(names, vec!["outer", "outer.keep", "outer.scale", "outer.clamp"]);
+
+    let scale = js_mutations(source, Some("outer.scale"));
+    assert!(!scale.is_empty());
+    assert!(scale.iter().all(|m| m.line == 6 && m.function.as_deref() == Some("outer.scale")));
+    // The anonymous callback adds nothing to the name
+    let clamp = js_mutations(source, Some("clamp"));
+    assert!(clamp.iter().all(|m| m.line == 8 && m.function.as_deref() == Some("outer.clamp")));
+    // The parent still covers its closures
+    let outer = js_mutations(source, Some("outer"));
+    assert!(outer.iter().any(|m| m.function.as_deref() == Some("outer.keep")));
+    assert!(outer.iter().any(|m| m.function.as_deref() == Some("outer")));
+}
+
 // --- Skip console.log ---
 
 #[test]
@@ -808,3 +861,472 @@ function check(x) {
     assert!(!comparison.context_before.is_empty(), "context_before should not be empty");
     assert!(!comparison.context_after.is_empty(), "context_after should not be empty");
 }
+
+// --- Switch case removal ---
+
+#[test]
+fn switch_case_body_replaced_with_break() {
+    let source = r#"
+function label(x) {
+    switch (x) {
+        case 1:
+            y = "one";
+            break;
+        case 2:
+        case 3:
+            return "few";
+        default:
+            return "many";
+    }
+}
+"#;
+    let mutations = js_mutations(source, Some("label"));
+    let arms: Vec<_> = mutations.iter().filter(|m| m.operator == "arm_remove").collect();
+    assert_eq!(arms.len(), 3, "fallthrough label without body is skipped");
+    assert_eq!(arms[0].original, "y = \"one\";\n            break;");
+    assert_eq!(arms[0].replacement, "break;");
+    assert_eq!(arms[1].original, "return \"few\";");
+    assert_eq!(arms[2].original, "return \"many\";");
+}
+
+#[test]
+fn switch_case_with_only_break_not_mutated() {
+    let source = r#"
+function label(x) {
+    switch (x) {
+        case 1:
+            break;
+    }
+}
+"#;
+    let mutations = js_mutations(source, Some("label"));
+    assert!(mutations.iter().all(|m| m.operator != "arm_remove"));
+}
+
+// --- Ternary swap ---
+
+#[test]
+fn ternary_branches_swapped() {
+    let source = r#"
+function label(x) {
+    return x > 0 ? "pos" : "neg";
+}
+"#;
+    let mutations = js_mutations(source, Some("label"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "ternary_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].original, "\"pos\" : \"neg\"");
+    assert_eq!(swaps[0].replacement, "\"neg\" : \"pos\"");
+}
+
+#[test]
+fn first_two_call_arguments_swapped() {
+    let source = r#"
+function clampAll(values, lo, hi) {
+    console.log("clamping", lo, hi);
+    logger.debug(lo, hi);
+    return clamp(values, lo, hi);
+}
+"#;
+    let mutations = js_mutations(source, Some("clampAll"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "arg_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].original, "values, lo");
+    assert_eq!(swaps[0].replacement, "lo, values");
+}
+
+#[test]
+fn arg_swap_skips_spread_and_single_argument_calls() {
+    let source = "function f(xs, y) {\n  g(...xs, y);\n  return h(y);\n}\n";
+    let mutations = js_mutations(source, Some("f"));
+    assert!(mutations.iter().all(|m| m.operator != "arg_swap"));
+}
+
+#[test]
+fn standalone_call_statements_removed() {
+    let source = r#"
+class Store extends Base {
+    constructor(items) {
+        super(items);
+        this.items = [];
+    }
+    add(x) {
+        this.items.push(x);
+        console.log("added", x);
+        return this.size();
+    }
+}
+"#;
+    let mutations = js_mutations(source, None);
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "call_remove").collect();
+    assert_eq!(removed.len(), 1, "{:?}", removed);
+    assert_eq!(removed[0].original, "this.items.push(x);");
+    assert_eq!(removed[0].replacement, ";");
+}
+
+#[test]
+fn lone_call_in_block_left_to_block_remove() {
+    let source = "function reset(xs, c) {\n    if (c) {\n        xs.clear();\n    }\n    for (const x of xs) {\n        use(x);\n        save(x);\n    }\n}\n";
+    let mutations = js_mutations(source, Some("reset"));
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "call_remove").map(|m| m.original.as_str()).collect();
+    assert_eq!(removed, ["use(x);", "save(x);"]);
+    assert_eq!(mutations.iter().filter(|m| m.operator == "block_remove").count(), 2);
+}
+
+const WITH_TESTS: &str = r#"
+export function isAdult(age) {
+    return age >= 18;
+}
+
+describe("isAdult", () => {
+    function makeAge(n) {
+        return n + 1;
+    }
+    beforeEach(() => { counter = counter + 1; });
+    it.each([[17], [18]])("age %i", (age) => {
+        expect(isAdult(age)).toBe(age > 17);
+    });
+});
+
+test("boundary", () => {
+    expect(isAdult(18) && true).toBe(true);
+});
+"#;
+
+#[test]
+fn test_definitions_skipped_by_default() {
+    let prod = js_mutations(WITH_TESTS, None);
+    assert!(!prod.is_empty());
+    assert!(prod.iter().all(|m| m.line <= 4), "{:?}", prod.iter().map(|m| m.line).collect::<Vec<_>>());
+
+    let all = parser_js::discover_mutations_with(WITH_TESTS, None, JsDialect::JavaScript, true);
+    assert!(all.iter().any(|m| m.line == 8)); // helper inside describe
+}
+
+#[test]
+fn helpers_inside_test_definitions_not_listed() {
+    assert_eq!(parser_js::list_functions(WITH_TESTS, JsDialect::JavaScript), ["isAdult"]);
+    // Still reachable when named explicitly
+    assert!(!js_mutations(WITH_TESTS, Some("makeAge")).is_empty());
+}
+
+#[test]
+fn test_files_recognized_by_name() {
+    use std::path::Path;
+    assert!(parser_js::is_test_file(Path::new("src/cart.test.ts")));
+    assert!(parser_js::is_test_file(Path::new("src/cart.spec.jsx")));
+    assert!(parser_js::is_test_file(Path::new("src/__tests__/cart.ts")));
+    assert!(!parser_js::is_test_file(Path::new("src/contest.ts")));
+    assert!(!parser_js::is_test_file(Path::new("src/testing/cart.ts")));
+}
+
+#[test]
+fn parameter_and_destructuring_defaults_mutated() {
+    let source = r#"
+function fetchAll(url, limit = 10, verbose = false, { retries = 3, mode = "fast" } = {}) {
+    const [first = 0] = url;
+    return get(url, limit, verbose, retries, mode, first);
+}
+"#;
+    let mutations = js_mutations(source, Some("fetchAll"));
+    let defaults: Vec<_> = mutations
+        .iter()
+        .filter(|m| m.operator == "default_arg")
+        .map(|m| (m.original.as_str(), m.replacement.as_str()))
+        .collect();
+    assert_eq!(
+        defaults,
+        [("10", "11"), ("false", "true"), ("3", "4"), ("\"fast\"", "\"\""), ("0", "1")]
+    );
+}
+
+#[test]
+fn typescript_parameter_defaults_mutated() {
+    let source = "function page(size: number = 20, strict?: boolean): number {\n  return size;\n}\n";
+    let mutations = ts_mutations(source, Some("page"));
+    assert!(mutations.iter().any(|m| m.operator == "default_arg" && m.original == "20" && m.replacement == "21"));
+}
+
+#[test]
+fn conditional_render_negated_and_removed() {
+    let source = r#"
+function Cart({ items, user }) {
+    return (
+        <div>
+            {items.length > 0 && <List items={items} />}
+            {user.isAdmin && (
+                <AdminPanel />
+            )}
+            {!user && <Login />}
+            {ready && "Loading"}
+        </div>
+    );
+}
+"#;
+    let mutations = tsx_mutations(source, Some("Cart"));
+    let negated: Vec<_> = mutations
+        .iter()
+        .filter(|m| m.operator == "render_negate")
+        .map(|m| m.replacement.as_str())
+        .collect();
+    assert_eq!(negated, ["!(items.length > 0)", "!user.isAdmin"]);
+
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "render_remove").collect();
+    assert_eq!(removed.len(), 3);
+    assert!(removed.iter().all(|m| m.replacement == "null"));
+    assert_eq!(removed[2].original, "!user && <Login />");
+}
+
+// --- Optional chaining ---
+
+fn apply(source: &str, m: &mutator::mutants::Mutation) -> String {
+    format!("{}{}{}", &source[..m.start_byte], m.replacement, &source[m.end_byte..])
+}
+
+#[test]
+fn optional_member_becomes_plain_member() {
+    let source = "function name(user) {\n  return user?.name;\n}\n";
+    let mutations = js_mutations(source, Some("name"));
+    let chains: Vec<_> = mutations.iter().filter(|m| m.operator == "optional_chain").collect();
+    assert_eq!(chains.len(), 1);
+    assert_eq!(chains[0].original, "?.");
+    assert!(apply(source, chains[0]).contains("return user.name;"));
+}
+
+#[test]
+fn optional_subscript_and_call_drop_guard() {
+    let source = "function first(xs, cb) {\n  cb?.(xs);\n  return xs?.[0];\n}\n";
+    let mutations = js_mutations(source, Some("first"));
+    let chains: Vec<_> = mutations.iter().filter(|m| m.operator == "optional_chain").collect();
+    assert_eq!(chains.len(), 2);
+    assert!(apply(source, chains[0]).contains("cb(xs);"));
+    assert!(apply(source, chains[1]).contains("return xs[0];"));
+}
+
+#[test]
+fn optional_chain_typescript() {
+    let source = "function city(u: User): string | undefined {\n  return u.address?.city;\n}\n";
+    let mutations = ts_mutations(source, Some("city"));
+    let chains: Vec<_> = mutations.iter().filter(|m| m.operator == "optional_chain").collect();
+    assert_eq!(chains.len(), 1);
+    assert!(apply(source, chains[0]).contains("u.address.city"));
+}
+
+#[test]
+fn plain_member_not_mutated() {
+    let source = "function name(user) {\n  return user.name;\n}\n";
+    let mutations = js_mutations(source, Some("name"));
+    assert!(!mutations.iter().any(|m| m.operator == "optional_chain"));
+}
+
+// --- async/await and Promise returns ---
+
+#[test]
+fn return_await_becomes_null() {
+    let source = "async function load(id) {\n  return await fetchUser(id);\n}\n";
+    let mutations = js_mutations(source, Some("load"));
+    let rets: Vec<_> = mutations.iter().filter(|m| m.operator == "return_val").collect();
+    assert_eq!(rets.len(), 1);
+    assert_eq!(rets[0].replacement, "return null;");
+    // `return await x` -> `return x` is equivalent, so no await_remove here
+    assert!(!mutations.iter().any(|m| m.operator == "await_remove"));
+}
+
+#[test]
+fn return_promise_resolve_keeps_promise() {
+    let source = "function cached(key) {\n  return Promise.resolve(cache[key]);\n}\n";
+    let mutations = js_mutations(source, Some("cached"));
+    let rets: Vec<_> = mutations.iter().filter(|m| m.operator == "return_val").collect();
+    assert_eq!(rets.len(), 1);
+    assert_eq!(rets[0].replacement, "return Promise.resolve(null);");
+}
+
+#[test]
+fn return_promise_resolve_null_not_mutated() {
+    let source = "function nothing() {\n  return Promise.resolve(null);\n}\n";
+    let mutations = js_mutations(source, Some("nothing"));
+    assert!(!mutations.iter().any(|m| m.operator == "return_val"));
+}
+
+#[test]
+fn await_removed_from_expression() {
+    let source = "async function total(ids) {\n  const rows = await db.query(ids);\n  return rows.length;\n}\n";
+    let mutations = ts_mutations(source, Some("total"));
+    let awaits: Vec<_> = mutations.iter().filter(|m| m.operator == "await_remove").collect();
+    assert_eq!(awaits.len(), 1);
+    assert_eq!(awaits[0].original, "await ");
+    assert!(apply(source, awaits[0]).contains("const rows = db.query(ids);"));
+}
+
+#[test]
+fn length_comparisons_get_len_boundary() {
+    let source = "function check(items, n) {\n  return items.length === 0 || n >= items?.length || n > 0;\n}\n";
+    let mutations = js_mutations(source, Some("check"));
+    let lens: Vec<_> = mutations.iter().filter(|m| m.operator == "len_boundary").map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(lens, [("===", ">="), (">=", ">")]);
+    assert_eq!(mutations.iter().filter(|m| m.operator == "boundary").count(), 1);
+}
+
+#[test]
+fn string_methods_swapped_and_trim_removed() {
+    let source = "function check(s) {\n  return s.trim().startsWith('a') ? s.toUpperCase() : s?.trimEnd();\n}\n";
+    let mutations = js_mutations(source, Some("check"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "string_method").map(|m| m.replacement.as_str()).collect();
+    assert_eq!(swaps, ["endsWith", "toLowerCase"]);
+    let trims: Vec<_> = mutations.iter().filter(|m| m.operator == "trim_remove").map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(trims, [("s.trim()", "s"), ("s?.trimEnd()", "s")]);
+}
+
+#[test]
+fn collection_methods_only_when_enabled() {
+    use mutator::mutation_parser::ParseOptions;
+    let source = "function check(a) {\n  a.push(1);\n  return Math.max(...a) + a.slice(0, 3).length + a.slice(x, 0).length + a.max;\n}\n";
+    let parser = mutator::Language::JavaScript.parser();
+    let options = ParseOptions { collection_method: true, ..Default::default() };
+    let changes: Vec<_> = parser
+        .discover(source, Some("check"), &options)
+        .into_iter()
+        .filter(|m| m.operator == "collection_method")
+        .map(|m| format!("{} -> {}", m.original, m.replacement))
+        .collect();
+    assert_eq!(changes, ["push -> unshift", "max -> min", "0 -> 1", "3 -> 2"]);
+    assert!(parser.discover(source, Some("check"), &ParseOptions::default()).iter().all(|m| m.operator != "collection_method"));
+}
+
+// --- Loop control ---
+
+#[test]
+fn while_condition_becomes_false() {
+    let source = "function drain(q) {\n  while (q.length > 0) {\n    q.pop();\n  }\n}\n";
+    let mutations = js_mutations(source, Some("drain"));
+    let zero: Vec<_> = mutations.iter().filter(|m| m.operator == "loop_zero").collect();
+    assert_eq!(zero.len(), 1);
+    assert!(apply(source, zero[0]).contains("while (false)"));
+}
+
+#[test]
+fn break_continue_swap_keeps_label() {
+    let source = "function find(rows) {\n  outer: for (const r of rows) {\n    for (const c of r) {\n      if (c) break outer;\n      continue;\n    }\n  }\n}\n";
+    let mutations = js_mutations(source, Some("find"));
+    let control: Vec<_> = mutations.iter().filter(|m| m.operator == "loop_control").collect();
+    assert_eq!(control.len(), 2);
+    assert!(apply(source, control[0]).contains("if (c) continue outer;"));
+    assert_eq!(control[1].replacement, "break");
+}
+
+#[test]
+fn switch_break_not_swapped() {
+    let source = "function kind(x) {\n  switch (x) {\n    case 1:\n      return 'a';\n    default:\n      break;\n  }\n}\n";
+    let mutations = js_mutations(source, Some("kind"));
+    assert!(!mutations.iter().any(|m| m.operator == "loop_control"));
+}
+
+#[test]
+fn switch_break_inside_loop_not_swapped() {
+    let source = "function run(xs) {\n  for (const x of xs) {\n    switch (x) {\n      case 1:\n        go();\n        break;\n    }\n  }\n}\n";
+    let mutations = js_mutations(source, Some("run"));
+    assert!(!mutations.iter().any(|m| m.operator == "loop_control"));
+}
+
+#[test]
+fn mutations_carry_function_name() {
+    let source = "function add(a, b) { return a + b; }\nconst sub = (a, b) => a - b;\nclass C { mul(a, b) { return a * b; } }\n";
+    let mutations = js_mutations(source, None);
+    let function_of = |op: &str| mutations.iter().find(|m| m.original == op).and_then(|m| m.function.clone());
+    assert_eq!(function_of("+").as_deref(), Some("add"));
+    assert_eq!(function_of("-").as_deref(), Some("sub"));
+    assert_eq!(function_of("*").as_deref(), Some("C.mul"));
+}
+
+#[test]
+fn offsets_match_original_with_multibyte_comments_and_strings() {
+    let source = r#"
+/** Grüße 🚀 — 检查 the value. */
+function check(x) {
+    const label = `日本語 😀 ${x}`;
+    if (x > 0 && label) {
+        return "正";
+    }
+    return "負";
+}
+"#;
+    let mutations = js_mutations(source, Some("check"));
+    assert!(mutations.iter().any(|m| m.original == ">"));
+    for m in &mutations {
+        assert_eq!(source.get(m.start_byte..m.end_byte), Some(m.original.as_str()), "{}", m.operator);
+        assert!(mutator::runner::apply_mutation(source, m).is_some(), "{}", m.operator);
+    }
+}
+
+#[test]
+fn toplevel_mutations_skip_functions_and_methods() {
+    let source = r#"
+const RATES = { base: 10 * 2, enabled: true };
+
+function add(a, b) {
+    return a + b;
+}
+
+class Calc {
+    scale = 2 * 3;
+    mul(a, b) {
+        return a * b;
+    }
+}
+
+describe("add", () => {
+    it("works", () => expect(add(1, 2) === 3).toBe(true));
+});
+"#;
+    let mutations = parser_js::toplevel_mutations(source, JsDialect::JavaScript);
+    let lines: Vec<_> = mutations.iter().map(|m| m.line).collect();
+    assert!(lines.contains(&2), "{:?}", lines);
+    assert!(lines.contains(&9), "{:?}", lines);
+    assert!(!lines.contains(&5), "function body mutated: {:?}", lines);
+    assert!(!lines.contains(&11), "method body mutated: {:?}", lines);
+    assert!(!lines.contains(&16), "test callback mutated: {:?}", lines);
+}
+
+// --- Typed returns (TypeScript) ---
+
+fn typed_returns(source: &str) -> Vec<String> {
+    ts_mutations(source, None)
+        .into_iter()
+        .filter(|m| m.operator == "return_val")
+        .map(|m| m.replacement)
+        .collect()
+}
+
+#[test]
+fn typed_returns_follow_declared_type() {
+    assert_eq!(typed_returns("function f(x: number): number { return x + 1; }"), vec!["return 0;"]);
+    assert_eq!(typed_returns("function f(): number { return 0; }"), vec!["return 1;"]);
+    assert_eq!(typed_returns("function f(x: string): string { return x.trim(); }"), vec!["return \"\";"]);
+    assert_eq!(typed_returns("function f(x: number): boolean { return x > 0; }"), vec!["return !(x > 0);"]);
+    assert_eq!(typed_returns("function f(): boolean { return true; }"), vec!["return false;"]);
+    assert_eq!(typed_returns("function f(xs: Foo[]): Foo[] { return xs.slice(1); }"), vec!["return [];"]);
+    assert_eq!(typed_returns("function f(xs: Foo[]): Array<Foo> { return xs; }"), vec!["return [];"]);
+    assert_eq!(typed_returns("function f(x: unknown): x is Foo { return isFoo(x); }"), vec!["return !(isFoo(x));"]);
+    assert_eq!(typed_returns("function f(m: Map<string, Foo>): Foo | undefined { return m.get(\"a\"); }"), vec!["return undefined;"]);
+}
+
+#[test]
+fn typed_returns_cover_methods_arrows_and_async() {
+    assert_eq!(typed_returns("class A { size(): number { return this.n; } }"), vec!["return 0;"]);
+    assert_eq!(typed_returns("const f = (s: string): string => { return s; };"), vec!["return \"\";"]);
+    assert_eq!(typed_returns("async function f(): Promise<number> { return await count(); }"), vec!["return 0;"]);
+}
+
+#[test]
+fn typed_returns_skip_values_already_minimal() {
+    assert!(typed_returns("function f(): string { return \"\"; }").is_empty());
+    assert!(typed_returns("function f(): number[] { return []; }").is_empty());
+}
+
+#[test]
+fn unknown_return_types_fall_back_to_expression_shape() {
+    // A named type has no literal to swap in
+    assert_eq!(typed_returns("function f(): Foo { return { a: 1 }; }"), vec!["return {};"]);
+    // No annotation in plain JS
+    assert_eq!(typed_returns("function f(x) { return x > 0; }"), vec!["return null;"]);
+}
@@ -12,6 +12,42 @@ fn check(x: i32) -> bool {
     assert!(cmp.len() >= 2, "Expected at least 2 comparison mutations, got {}", cmp.len());
 }
 
+#[test]
+fn length_comparisons_get_len_boundary() {
+    let source = "fn check(v: &[u8], n: usize) -> bool {\n    v.len() <= n && 0 != v.len() && v.len(n) > 0\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("check"));
+    let lens: Vec<_> = mutations.iter().filter(|m| m.operator == "len_boundary").map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(lens, [("<=", "<"), ("!=", ">")]);
+    // `len(n)` with arguments isn't a collection length
+    assert_eq!(mutations.iter().filter(|m| m.operator == "boundary").count(), 1);
+}
+
+#[test]
+fn string_methods_swapped() {
+    let source = "fn check(s: &str) -> bool {\n    s.trim().starts_with('a') || s.to_lowercase() == \"b\"\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("check"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "string_method").map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(swaps, [("starts_with", "ends_with"), ("to_lowercase", "to_uppercase")]);
+    // `trim_remove` is Python and JS/TS only
+    assert!(mutations.iter().all(|m| m.operator != "trim_remove"));
+}
+
+#[test]
+fn collection_methods_only_when_enabled() {
+    use mutator::mutation_parser::ParseOptions;
+    let source = "fn check(v: &[i32], a: i32) -> i32 {\n    v.first().copied().unwrap_or(0) + a.min(3) + std::cmp::max(a, 1) + v.iter().count() as i32\n}\n";
+    let parser = mutator::Language::Rust.parser();
+    let options = ParseOptions { collection_method: true, ..Default::default() };
+    let changes: Vec<_> = parser
+        .discover(source, Some("check"), &options)
+        .into_iter()
+        .filter(|m| m.operator == "collection_method")
+        .map(|m| (m.original, m.replacement))
+        .collect();
+    assert_eq!(changes, [("first".into(), "last".into()), ("min".into(), "max".into()), ("max".into(), "min".into())]);
+    assert!(parser.discover(source, Some("check"), &ParseOptions::default()).iter().all(|m| m.operator != "collection_method"));
+}
+
 #[test]
 fn discovers_strict_equality() {
     let source = r#"
@@ -170,7 +206,7 @@ fn check(x: i32) -> i32 {
 "#;
     let mutations = parser_rust::discover_mutations(source, Some("check"));
     let blocks: Vec<_> = mutations.iter().filter(|m| m.operator == "block_remove").collect();
-    assert!(blocks.len() >= 1, "Should create block_remove for if body");
+    assert!(!blocks.is_empty(), "Should create block_remove for if body");
     assert_eq!(blocks[0].replacement, "{}");
 }
 
@@ -322,7 +358,8 @@ fn check(x: i32) -> i32 {
 "#;
     let mutations = parser_rust::discover_mutations(source, Some("check"));
     // Only + operators should produce mutations, not << >> & |
-    for m in &mutations {
+    // (the trailing expression also gets a return_val mutation)
+    for m in mutations.iter().filter(|m| m.operator != "return_val") {
         assert!(m.operator == "arith" && m.original == "+",
             "Only + mutations expected, got {} on '{}'", m.operator, m.original);
     }
@@ -458,6 +495,35 @@ impl Foo {
     assert!(!a_only.is_empty(), "Should find method_a inside impl block");
 }
 
+#[test]
+fn methods_are_scoped_by_qualified_name() {
+    let source = r#"
+struct Range;
+struct Limit<T>(T);
+impl Range {
+    fn check(x: i32) -> bool { x > 0 }
+}
+impl<T> std::fmt::Debug for Limit<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+}
+impl<T> Limit<T> {
+    fn check(x: i32) -> bool { x < 10 }
+}
+trait Bounded {
+    fn within(x: i32) -> bool { x >= 0 }
+}
+fn check(x: i32) -> bool { x == 1 }
+"#;
+    let names = parser_rust::list_functions(source);
+    assert_eq!(names, vec!["Range::check", "Limit::fmt", "Limit::check", "Bounded::within", "check"]);
+
+    let limit = parser_rust::discover_mutations(source, Some("Limit::check"));
+    assert!(!limit.is_empty());
+    assert!(limit.iter().all(|m| m.line == 11));
+    assert!(limit.iter().all(|m| m.function.as_deref() == Some("Limit::check")));
+    assert!(parser_rust::discover_mutations(source, Some("Bounded::within")).iter().all(|m| m.line == 14));
+}
+
 // --- Return mutations: various types ---
 
 #[test]
@@ -523,7 +589,7 @@ fn check() -> Result<(), String> {
 }
 
 #[test]
-fn return_number_becomes_default() {
+fn return_number_becomes_zero() {
     let source = r#"
 fn check() -> i32 {
     return 42;
@@ -532,7 +598,7 @@ fn check() -> i32 {
     let mutations = parser_rust::discover_mutations(source, Some("check"));
     let rets: Vec<_> = mutations.iter().filter(|m| m.operator == "return_val").collect();
     assert_eq!(rets.len(), 1);
-    assert!(rets[0].replacement.contains("Default::default()"), "Expected Default::default(), got: {}", rets[0].replacement);
+    assert_eq!(rets[0].replacement, "return 0");
 }
 
 // --- Boolean false literal ---
@@ -606,3 +672,583 @@ fn check(x: i32) -> bool {
     let comparison = mutations.iter().find(|m| m.operator == "boundary").unwrap();
     assert!(!comparison.context_before.is_empty(), "context_before should not be empty");
 }
+
+// --- Match arm removal ---
+
+#[test]
+fn match_arm_removed_when_catch_all_present() {
+    let source = r#"
+fn label(x: u8) -> &'static str {
+    match x {
+        1 => "one",
+        2 => { "two" }
+        _ => "other",
+    }
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("label"));
+    let arms: Vec<_> = mutations.iter().filter(|m| m.operator == "arm_remove").collect();
+    assert_eq!(arms.len(), 2, "catch-all arm must be kept");
+    assert_eq!(arms[0].original, "1 => \"one\",");
+    assert_eq!(arms[1].original, "2 => { \"two\" }");
+    assert_eq!(arms[0].replacement, "");
+}
+
+#[test]
+fn match_arm_binding_counts_as_catch_all() {
+    let source = r#"
+fn label(x: Option<u8>) -> u8 {
+    match x {
+        Some(1) => 1,
+        other => 0,
+    }
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("label"));
+    let arms: Vec<_> = mutations.iter().filter(|m| m.operator == "arm_remove").collect();
+    assert_eq!(arms.len(), 1);
+    assert_eq!(arms[0].original, "Some(1) => 1,");
+}
+
+#[test]
+fn match_arm_not_removed_without_catch_all() {
+    let source = r#"
+fn label(x: Option<u8>) -> u8 {
+    match x {
+        Some(v) => v,
+        None => 0,
+        n if true => 1,
+    }
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("label"));
+    assert!(mutations.iter().all(|m| m.operator != "arm_remove"));
+}
+
+// --- If/else branch swap ---
+
+#[test]
+fn if_else_expression_branches_swapped() {
+    let source = r#"
+fn label(x: i32) -> u8 {
+    let y = if x > 0 { 1 } else { 2 };
+    y
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("label"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "ternary_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].original, "{ 1 } else { 2 }");
+    assert_eq!(swaps[0].replacement, "{ 2 } else { 1 }");
+}
+
+#[test]
+fn if_else_swap_only_on_final_else_block() {
+    let source = r#"
+fn label(x: i32) -> u8 {
+    if x > 5 { 3 } else if x > 0 { 4 } else { 5 }
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("label"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "ternary_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].original, "{ 4 } else { 5 }");
+}
+
+#[test]
+fn first_two_call_arguments_swapped() {
+    let source = r#"
+fn span(text: &str, start: usize, end: usize) -> String {
+    println!("{} {}", start, end);
+    slice(text, start, end)
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("span"));
+    let swaps: Vec<_> = mutations.iter().filter(|m| m.operator == "arg_swap").collect();
+    assert_eq!(swaps.len(), 1);
+    assert_eq!(swaps[0].original, "text, start");
+    assert_eq!(swaps[0].replacement, "start, text");
+}
+
+#[test]
+fn standalone_call_statements_removed() {
+    let source = r#"
+fn record(log: &mut Vec<u32>, x: u32) -> usize {
+    log.push(x);
+    notify(x);
+    let n = log.len();
+    compute(n)
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("record"));
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "call_remove").collect();
+    let originals: Vec<_> = removed.iter().map(|m| m.original.as_str()).collect();
+    assert_eq!(originals, ["log.push(x);", "notify(x);"]);
+    assert!(removed.iter().all(|m| m.replacement == ";"));
+}
+
+#[test]
+fn lone_call_in_if_body_left_to_block_remove() {
+    let source = "fn reset(xs: &mut Vec<u32>, c: bool) {\n    if c {\n        xs.clear();\n    }\n    xs.sort();\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("reset"));
+    let removed: Vec<_> = mutations.iter().filter(|m| m.operator == "call_remove").map(|m| m.original.as_str()).collect();
+    assert_eq!(removed, ["xs.sort();"]);
+    assert!(mutations.iter().any(|m| m.operator == "block_remove" && m.line == 2));
+}
+
+#[test]
+fn if_let_some_value_replaced_with_none() {
+    let source = r#"
+fn first_even(items: &[u32], cache: Option<u32>) -> u32 {
+    if let Some(hit) = cache {
+        return hit;
+    }
+    let mut it = items.iter();
+    while let Some(x) = it.next() {
+        if let Ok(n) = parse(x) {
+            return n;
+        }
+    }
+    0
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("first_even"));
+    let nones: Vec<_> = mutations.iter().filter(|m| m.operator == "if_let_none").collect();
+    let originals: Vec<_> = nones.iter().map(|m| m.original.as_str()).collect();
+    assert_eq!(originals, ["cache", "it.next()"]);
+    assert!(nones.iter().all(|m| m.replacement == "None"));
+}
+
+#[test]
+fn matches_macro_negated() {
+    let source = r#"
+fn is_digit(c: char, t: Token) -> bool {
+    matches!(c, '0'..='9') && !matches!(t, Token::Eof)
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("is_digit"));
+    let negated: Vec<_> = mutations.iter().filter(|m| m.operator == "negate_matches").collect();
+    assert_eq!(negated.len(), 1);
+    assert_eq!(negated[0].replacement, "!matches!(c, '0'..='9')");
+}
+
+#[test]
+fn option_and_result_predicates_swapped() {
+    let source = r#"
+fn ready(a: Option<u8>, b: Result<u8, ()>) -> bool {
+    a.is_some() && b.is_err() && a.is_some_and(|x| x > 1)
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("ready"));
+    let swaps: Vec<_> = mutations
+        .iter()
+        .filter(|m| m.operator == "predicate_swap")
+        .map(|m| (m.original.as_str(), m.replacement.as_str()))
+        .collect();
+    assert_eq!(swaps, [("is_some", "is_none"), ("is_err", "is_ok")]);
+}
+
+#[test]
+fn range_bounds_tweaked() {
+    let source = r#"
+fn sum_window(xs: &[u32], lo: usize, n: usize) -> u32 {
+    let mut total = 0;
+    for i in 0..n {
+        total += xs[i];
+    }
+    total + xs[lo..=n].len() as u32 + xs[..n].len() as u32
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("sum_window"));
+    let tweaks: Vec<_> = mutations
+        .iter()
+        .filter(|m| m.operator == "range_tweak")
+        .map(|m| (m.original.as_str(), m.replacement.as_str()))
+        .collect();
+    assert_eq!(
+        tweaks,
+        [("..", "..="), ("0", "1"), ("..=", ".."), ("lo", "lo + 1"), ("..", "..=")]
+    );
+}
+
+// --- Implicit (trailing expression) returns ---
+
+fn tail_returns(source: &str, func: &str) -> Vec<mutator::mutants::Mutation> {
+    parser_rust::discover_mutations(source, Some(func))
+        .into_iter()
+        .filter(|m| m.operator == "return_val")
+        .collect()
+}
+
+#[test]
+fn trailing_expression_gets_return_val() {
+    let source = r#"
+fn is_positive(x: i32) -> bool {
+    let zero = 0;
+    x > zero
+}
+"#;
+    let rets = tail_returns(source, "is_positive");
+    assert_eq!(rets.len(), 1);
+    assert_eq!(rets[0].original, "x > zero");
+    assert_eq!(rets[0].replacement, "!(x > zero)");
+    assert_eq!(rets[0].line, 4);
+}
+
+#[test]
+fn trailing_zero_becomes_one() {
+    let source = "fn zero() -> i32 {\n    0\n}\n";
+    let rets = tail_returns(source, "zero");
+    assert_eq!(rets.len(), 1);
+    assert_eq!(rets[0].replacement, "1");
+}
+
+#[test]
+fn trailing_bool_literal_left_to_bool_flip() {
+    let source = "fn yes() -> bool {\n    true\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("yes"));
+    assert_eq!(mutations.len(), 1);
+    assert_eq!(mutations[0].operator, "bool_flip");
+}
+
+#[test]
+fn unit_function_tail_not_mutated() {
+    let source = "fn run(x: i32) {\n    helper(x)\n}\n";
+    assert!(tail_returns(source, "run").is_empty());
+}
+
+#[test]
+fn statement_with_semicolon_is_not_tail() {
+    let source = "fn count(v: &[i32]) -> usize {\n    let n = v.len();\n    return n;\n}\n";
+    let rets = tail_returns(source, "count");
+    // Only the explicit return
+    assert_eq!(rets.len(), 1);
+    assert!(rets[0].replacement.starts_with("return "));
+}
+
+#[test]
+fn trailing_if_else_mutates_each_branch() {
+    let source = r#"
+fn clamp(x: i32) -> i32 {
+    if x > 10 {
+        10
+    } else if x < 0 {
+        0
+    } else {
+        x
+    }
+}
+"#;
+    let rets = tail_returns(source, "clamp");
+    let originals: Vec<_> = rets.iter().map(|m| m.original.as_str()).collect();
+    assert_eq!(originals, vec!["10", "0", "x"]);
+}
+
+#[test]
+fn trailing_match_mutates_arm_values() {
+    let source = r#"
+fn score(c: char) -> u32 {
+    match c {
+        'a' => 1,
+        'b' => { 2 }
+        _ => 0,
+    }
+}
+"#;
+    let rets = tail_returns(source, "score");
+    let originals: Vec<_> = rets.iter().map(|m| m.original.as_str()).collect();
+    assert_eq!(originals, vec!["1", "2", "0"]);
+}
+
+#[test]
+fn trailing_ok_unit_not_mutated() {
+    let source = "fn save() -> Result<(), String> {\n    write()?;\n    Ok(())\n}\n";
+    assert!(tail_returns(source, "save").is_empty());
+}
+
+#[test]
+fn nested_fn_has_its_own_tail() {
+    let source = r#"
+fn outer() -> i32 {
+    fn inner() -> i32 { 5 }
+    inner()
+}
+"#;
+    let rets = tail_returns(source, "outer");
+    let originals: Vec<_> = rets.iter().map(|m| m.original.as_str()).collect();
+    assert_eq!(originals, vec!["inner()", "5"]);
+}
+
+// --- ? operator and Option/Result mutations ---
+
+#[test]
+fn try_operator_becomes_unwrap() {
+    let source = "fn load(p: &str) -> Result<String, E> {\n    let s = read(p)?;\n    Ok(s)\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("load"));
+    let tries: Vec<_> = mutations.iter().filter(|m| m.operator == "try_unwrap").collect();
+    assert_eq!(tries.len(), 1);
+    assert_eq!(tries[0].original, "?");
+    assert_eq!(tries[0].replacement, ".unwrap()");
+    let mutated = format!("{}{}{}", &source[..tries[0].start_byte], tries[0].replacement, &source[tries[0].end_byte..]);
+    assert!(mutated.contains("read(p).unwrap();"));
+}
+
+#[test]
+fn some_becomes_none() {
+    let source = "fn first(v: &[i32]) -> Option<i32> {\n    let x = v[0];\n    Some(x)\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("first"));
+    let somes: Vec<_> = mutations.iter().filter(|m| m.operator == "some_none").collect();
+    assert_eq!(somes.len(), 1);
+    assert_eq!(somes[0].original, "Some(x)");
+    assert_eq!(somes[0].replacement, "None");
+    // The tail return is covered by some_none, not duplicated as return_val
+    assert!(!mutations.iter().any(|m| m.operator == "return_val"));
+}
+
+#[test]
+fn some_pattern_not_mutated() {
+    let source = "fn get(o: Option<i32>) -> i32 {\n    if let Some(v) = o { return v; }\n    return 0;\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("get"));
+    assert!(!mutations.iter().any(|m| m.operator == "some_none"));
+}
+
+#[test]
+fn unwrap_or_becomes_unwrap_or_default() {
+    let source = "fn port(p: Option<u16>) -> u16 {\n    let port = p.unwrap_or(8080);\n    return port;\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("port"));
+    let ors: Vec<_> = mutations.iter().filter(|m| m.operator == "unwrap_or_default").collect();
+    assert_eq!(ors.len(), 1);
+    assert_eq!(ors[0].original, "unwrap_or(8080)");
+    assert_eq!(ors[0].replacement, "unwrap_or_default()");
+}
+
+#[test]
+fn unwrap_or_default_like_value_skipped() {
+    let source = "fn count(p: Option<u16>) -> u16 {\n    let n = p.unwrap_or(0);\n    return n;\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("count"));
+    assert!(!mutations.iter().any(|m| m.operator == "unwrap_or_default"));
+}
+
+// --- Loop control ---
+
+#[test]
+fn while_condition_becomes_false() {
+    let source = "fn drain(v: &mut Vec<i32>) {\n    while !v.is_empty() {\n        v.pop();\n    }\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("drain"));
+    let zero: Vec<_> = mutations.iter().filter(|m| m.operator == "loop_zero").collect();
+    assert_eq!(zero.len(), 1);
+    assert_eq!(zero[0].original, "!v.is_empty()");
+    assert_eq!(zero[0].replacement, "false");
+}
+
+#[test]
+fn while_let_not_mutated() {
+    let source = "fn drain(v: &mut Vec<i32>) {\n    while let Some(x) = v.pop() {\n        use_it(x);\n    }\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("drain"));
+    assert!(!mutations.iter().any(|m| m.operator == "loop_zero"));
+}
+
+#[test]
+fn break_continue_swap() {
+    let source = "fn scan(v: &[i32]) {\n    'outer: for x in v {\n        if *x < 0 { continue; }\n        if *x == 0 { break 'outer; }\n    }\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("scan"));
+    let control: Vec<_> = mutations.iter().filter(|m| m.operator == "loop_control").collect();
+    let pairs: Vec<_> = control.iter().map(|m| (m.original.as_str(), m.replacement.as_str())).collect();
+    assert_eq!(pairs, vec![("continue", "break"), ("break", "continue")]);
+}
+
+#[test]
+fn break_with_value_not_swapped() {
+    let source = "fn first(v: &[i32]) -> i32 {\n    let found = loop {\n        break v[0];\n    };\n    return found;\n}\n";
+    let mutations = parser_rust::discover_mutations(source, Some("first"));
+    assert!(!mutations.iter().any(|m| m.operator == "loop_control"));
+}
+
+// --- Test code filtering ---
+
+const WITH_TESTS: &str = r#"
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[test]
+fn top_level_test() {
+    let ok = add(1, 2) > 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn helper() -> bool {
+        1 + 1 == 2
+    }
+
+    #[test]
+    fn adds() {
+        assert_eq!(add(1, 2), 3);
+    }
+}
+
+#[tokio::test]
+// async runtime test
+async fn async_test() {
+    let ok = 1 < 2;
+}
+"#;
+
+#[test]
+fn test_code_skipped_by_default() {
+    let mutations = parser_rust::discover_mutations(WITH_TESTS, None);
+    assert!(!mutations.is_empty());
+    assert!(mutations.iter().all(|m| m.line <= 4), "only `add` should be mutated: {:?}",
+        mutations.iter().map(|m| m.line).collect::<Vec<_>>());
+}
+
+#[test]
+fn include_tests_walks_test_code() {
+    let all = parser_rust::discover_mutations_with(WITH_TESTS, None, true);
+    let prod = parser_rust::discover_mutations(WITH_TESTS, None);
+    assert!(all.len() > prod.len());
+    assert!(all.iter().any(|m| m.line == 8));  // top_level_test
+    assert!(all.iter().any(|m| m.line == 16)); // tests::helper
+    assert!(all.iter().any(|m| m.line == 28)); // async_test
+}
+
+#[test]
+fn explicit_function_scope_still_finds_tests() {
+    let mutations = parser_rust::discover_mutations(WITH_TESTS, Some("helper"));
+    assert!(!mutations.is_empty());
+}
+
+#[test]
+fn cfg_feature_module_not_treated_as_test() {
+    let source = "#[cfg(feature = \"testing\")]\nmod extra {\n    fn f(x: i32) -> bool { x > 0 }\n}\n";
+    let mutations = parser_rust::discover_mutations(source, None);
+    assert!(!mutations.is_empty());
+}
+
+#[test]
+fn cfg_predicates_are_evaluated() {
+    let source = r#"
+#[cfg(not(test))]
+fn prod_only(x: i32) -> bool { x > 0 }
+
+#[cfg(any(test, feature = "x"))]
+fn maybe(x: i32) -> bool { x > 1 }
+
+#[cfg(all(test, feature = "x"))]
+fn test_only(x: i32) -> bool { x > 2 }
+
+#[cfg(any(test, all(test, unix)))]
+fn also_test_only(x: i32) -> bool { x > 3 }
+"#;
+    let mutated: Vec<_> = parser_rust::discover_mutations(source, None)
+        .into_iter()
+        .filter_map(|m| m.function)
+        .collect();
+    assert!(mutated.iter().any(|f| f == "prod_only"));
+    assert!(mutated.iter().any(|f| f == "maybe"));
+    assert!(!mutated.iter().any(|f| f == "test_only" || f == "also_test_only"), "{:?}", mutated);
+}
+
+#[test]
+fn mutations_carry_function_name() {
+    let source = r#"
+impl Calc {
+    fn add(&self, a: i32) -> i32 {
+        a + self.base
+    }
+}
+
+fn double(x: i32) -> i32 {
+    x * 2
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, None);
+    let plus = mutations.iter().find(|m| m.original == "+").unwrap();
+    let times = mutations.iter().find(|m| m.original == "*").unwrap();
+    assert_eq!(plus.function.as_deref(), Some("Calc::add"));
+    assert_eq!(times.function.as_deref(), Some("double"));
+}
+
+#[test]
+fn offsets_match_original_with_multibyte_doc_comments_and_strings() {
+    let source = r#"
+/// Grüße 🦀 — 检查 the value.
+#[doc = "属性 ✨"]
+fn check(x: i32) -> &'static str {
+    let _label = "日本語 😀";
+    if x > 0 { "正" } else { "負" }
+}
+"#;
+    let mutations = parser_rust::discover_mutations(source, Some("check"));
+    assert!(mutations.iter().any(|m| m.original == ">"));
+    for m in &mutations {
+        assert_eq!(source.get(m.start_byte..m.end_byte), Some(m.original.as_str()), "{}", m.operator);
+        assert!(mutator::runner::apply_mutation(source, m).is_some(), "{}", m.operator);
+    }
+}
+
+#[test]
+fn toplevel_mutations_cover_consts_and_statics() {
+    let source = r#"
+const LIMIT: u32 = 10 * 2;
+static ENABLED: bool = true;
+
+fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    const SEED: u32 = 1 + 1;
+}
+"#;
+    let lines: Vec<_> = parser_rust::toplevel_mutations(source, false).iter().map(|m| m.line).collect();
+    assert!(lines.contains(&2), "{:?}", lines);
+    assert!(lines.contains(&3), "{:?}", lines);
+    assert!(!lines.contains(&6), "function body mutated: {:?}", lines);
+    assert!(!lines.contains(&11), "test module mutated: {:?}", lines);
+
+    let with_tests: Vec<_> = parser_rust::toplevel_mutations(source, true).iter().map(|m| m.line).collect();
+    assert!(with_tests.contains(&11), "{:?}", with_tests);
+}
+
+// --- Typed returns ---
+
+#[test]
+fn typed_returns_follow_the_signature() {
+    let cases = [
+        ("fn f(v: &[u8]) -> Option<u8> {\n    v.first().copied()\n}\n", vec!["None"]),
+        ("fn f(x: f64) -> f64 {\n    x * 2.0\n}\n", vec!["0.0"]),
+        ("fn f(n: usize) -> usize {\n    n + 1\n}\n", vec!["0"]),
+        ("fn f(s: &str) -> String {\n    s.to_uppercase()\n}\n", vec!["String::new()"]),
+        ("fn f(s: &str) -> Vec<String> {\n    s.split(',').map(String::from).collect()\n}\n", vec!["vec![]"]),
+        ("fn f(v: Vec<u8>) -> std::vec::Vec<u8> {\n    v\n}\n", vec!["vec![]"]),
+    ];
+    for (source, expected) in cases {
+        let rets: Vec<_> = tail_returns(source, "f").into_iter().map(|m| m.replacement).collect();
+        assert_eq!(rets, expected, "{}", source);
+    }
+}
+
+#[test]
+fn typed_returns_skip_results_and_unknown_types() {
+    let result = "fn f(s: &str) -> Result<u32, std::num::ParseIntError> {\n    s.parse()\n}\n";
+    assert!(tail_returns(result, "f").is_empty());
+    let io = "fn f() -> std::io::Result<Vec<u8>> {\n    return std::fs::read(\"x\");\n}\n";
+    assert!(tail_returns(io, "f").is_empty());
+    // No value of an arbitrary type can be built
+    let named = "fn f(c: &Config) -> Settings {\n    c.settings.clone()\n}\n";
+    assert!(tail_returns(named, "f").is_empty());
+    // Already the empty value
+    assert!(tail_returns("fn f() -> Vec<u8> {\n    Vec::new()\n}\n", "f").is_empty());
+}
+
+#[test]
+fn typed_returns_use_the_enclosing_closure_signature() {
+    let source = "fn f(v: &[i32]) -> Vec<i32> {\n    let pick = |x: i32| -> bool { return x > 1; };\n    v.iter().copied().filter(|x| pick(*x)).collect()\n}\n";
+    let rets: Vec<_> = tail_returns(source, "f").into_iter().map(|m| m.replacement).collect();
+    assert_eq!(rets, vec!["vec![]", "return !(x > 1)"]);
+}
@@ -0,0 +1,63 @@
+use mutator::mutants::{MutantStatus, Mutation};
+use mutator::precheck::{self, Precheck};
+use mutator::Language;
+
+/// Replace the first `original` in `source` with `replacement`.
+fn mutation(source: &str, original: &str, replacement: &str) -> Mutation {
+    let start_byte = source.find(original).unwrap();
+    Mutation {
+        line: source[..start_byte].lines().count().max(1),
+        column: 1,
+        start_byte,
+        end_byte: start_byte + original.len(),
+        operator: "test".to_string(),
+        original: original.to_string(),
+        replacement: replacement.to_string(),
+        context_before: Vec::new(),
+        context_after: Vec::new(),
+        function: None,
+    }
+}
+
+#[test]
+fn check_command_follows_the_language() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let py = dir.path().join("app.py");
+    let python = Precheck::for_file(&Language::Python, &py, &[]).unwrap();
+    assert_eq!(python.describe(), format!("python3 -m py_compile {}", py.display()));
+    let js = Precheck::for_file(&Language::JavaScript, &dir.path().join("app.js"), &[]).unwrap();
+    assert!(js.describe().starts_with("node --check "));
+
+    // Nothing to run without a crate or a tsconfig
+    assert!(Precheck::for_file(&Language::Rust, &dir.path().join("src/lib.rs"), &[]).is_none());
+    assert!(Precheck::for_file(&Language::TypeScript, &dir.path().join("app.ts"), &[]).is_none());
+    assert!(Precheck::for_file(&Language::Tsx, &dir.path().join("App.jsx"), &[]).is_none());
+
+    std::fs::write(dir.path().join("Cargo.toml"), "").unwrap();
+    std::fs::write(dir.path().join("tsconfig.json"), "{}").unwrap();
+    let rust = Precheck::for_file(&Language::Rust, &dir.path().join("src/lib.rs"), &[]).unwrap();
+    assert_eq!(rust.describe(), "cargo check --tests --quiet");
+    let ts = Precheck::for_file(&Language::TypeScript, &dir.path().join("src/app.ts"), &[]).unwrap();
+    assert_eq!(ts.describe(), "npx --no-install tsc --noEmit -p .");
+}
+
+#[test]
+fn mutant_that_does_not_compile_is_unviable() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let file = dir.path().join("app.py");
+    let source = "def add(a, b):\n    return a + b\n";
+    std::fs::write(&file, source).unwrap();
+    let check = Precheck::for_file(&Language::Python, &file, &[]).unwrap();
+    assert!(check.run(None).is_ok());
+
+    let broken = mutation(source, "a + b", "a +");
+    let result = precheck::check_mutant(&check, &file, source, &broken, 10_000).unwrap();
+    assert_eq!(result.status, MutantStatus::Unviable);
+    assert!(result.output_snippet.unwrap().contains("SyntaxError"));
+    assert!(result.diff.contains("return a +"));
+
+    let fine = mutation(source, "a + b", "a - b");
+    assert!(precheck::check_mutant(&check, &file, source, &fine, 10_000).is_none());
+    // The mutant is left on disk for the caller to restore
+    assert!(std::fs::read_to_string(&file).unwrap().contains("a - b"));
+}
@@ -0,0 +1,109 @@
+use mutator::history;
+use mutator::mutants::{MutantStatus, Mutation};
+use mutator::priority;
+use mutator::state::{MutantOutcome, RunResult, SurvivedMutant};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+
+fn mutation(line: usize, operator: &str) -> Mutation {
+    Mutation {
+        line,
+        column: 5,
+        start_byte: 0,
+        end_byte: 1,
+        operator: operator.to_string(),
+        original: "+".to_string(),
+        replacement: "-".to_string(),
+        context_before: vec![],
+        context_after: vec![],
+        function: None,
+    }
+}
+
+fn killed_on(line: usize) -> MutantOutcome {
+    MutantOutcome {
+        ref_id: None,
+        file: "app.py".into(),
+        line,
+        column: 5,
+        function: None,
+        operator: "arith".into(),
+        original: "+".into(),
+        replacement: "-".into(),
+        status: MutantStatus::Killed,
+        duration_ms: 10,
+        killed_by: Some("test_add".into()),
+    }
+}
+
+#[test]
+fn recently_changed_lines_run_first() {
+    let mutations = [mutation(2, "arith"), mutation(5, "arith"), mutation(9, "arith")];
+    let blame = HashMap::from([(2, 1_000), (5, 3_000), (9, 2_000)]);
+    assert_eq!(priority::run_order(&mutations, &blame, None), [1, 2, 0]);
+}
+
+#[test]
+fn lines_with_fewer_kills_then_condition_flips_run_first() {
+    let mutations = [mutation(2, "arith"), mutation(2, "boundary"), mutation(5, "arith"), mutation(5, "negate_cmp")];
+    let last_run = RunResult { all_mutants: vec![killed_on(2), killed_on(2)], ..Default::default() };
+
+    let order = priority::run_order(&mutations, &HashMap::new(), Some(&last_run));
+    assert_eq!(order, [3, 2, 1, 0]);
+    // Without history only the operator counts
+    assert_eq!(priority::run_order(&mutations, &HashMap::new(), None), [1, 3, 0, 2]);
+}
+
+#[test]
+fn survivor_lines_count_as_unkilled_without_full_results() {
+    let mutations = [mutation(2, "arith"), mutation(5, "arith")];
+    let survivor = SurvivedMutant { ref_id: "m1".into(), line: 5, ..Default::default() };
+    let last_run = RunResult { survived_mutants: vec![survivor], ..Default::default() };
+    assert_eq!(priority::run_order(&mutations, &HashMap::new(), Some(&last_run)), [1, 0]);
+}
+
+#[test]
+fn blame_times_put_uncommitted_lines_last_changed() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let git = |args: &[&str]| {
+        let status = Command::new("git")
+            .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+            .args(args)
+            .current_dir(dir.path())
+            .env("GIT_AUTHOR_DATE", "2020-01-01T00:00:00Z")
+            .env("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z")
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    };
+    let file = dir.path().join("app.py");
+    std::fs::write(&file, "def add(a, b):\n    return a + b\n").unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "app.py"]);
+    git(&["commit", "-q", "-m", "add"]);
+    std::fs::write(&file, "def add(a, b):\n    return a - b\n").unwrap();
+
+    let times = priority::blame_times(&file);
+    assert_eq!(times[&1], 1_577_836_800);
+    assert!(times[&2] > times[&1]);
+
+    let outside = tempfile::TempDir::new().unwrap();
+    std::fs::write(outside.path().join("app.py"), "x = 1\n").unwrap();
+    assert!(priority::blame_times(&outside.path().join("app.py")).is_empty());
+}
+
+#[test]
+fn last_run_is_the_latest_recorded_for_the_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let first = RunResult { total: 1, ..Default::default() };
+    let second = RunResult { total: 2, ..Default::default() };
+    let other = RunResult { total: 3, ..Default::default() };
+    history::record_in(dir.path(), &first, Path::new("src/app.py"), None, None);
+    history::record_in(dir.path(), &second, Path::new("src/app.py"), None, None);
+    history::record_in(dir.path(), &other, Path::new("src/other.py"), None, None);
+
+    assert_eq!(priority::last_run(dir.path(), Path::new("app.py")).unwrap().total, 2);
+    assert!(priority::last_run(dir.path(), Path::new("missing.py")).is_none());
+}
@@ -1,7 +1,7 @@
-use mutator::mutants::Mutation;
+use mutator::mutants::{MutantStatus, Mutation};
 use mutator::runner;
+use mutator::test_adapters::{Runner, TestArgStyle};
 use std::path::Path;
-use mutator;
 
 fn make_mutation(start: usize, end: usize, replacement: &str, original: &str) -> Mutation {
     Mutation {
@@ -14,6 +14,7 @@ fn make_mutation(start: usize, end: usize, replacement: &str, original: &str) ->
         replacement: replacement.to_string(),
         context_before: vec![],
         context_after: vec![],
+        function: None,
     }
 }
 
@@ -24,7 +25,7 @@ fn apply_mutation_replaces_at_correct_offset() {
     let source = "if x > 0:";
     let mutation = make_mutation(5, 6, ">=", ">");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, "if x >= 0:");
+    assert_eq!(result.as_deref(), Some("if x >= 0:"));
 }
 
 #[test]
@@ -32,7 +33,7 @@ fn apply_mutation_at_start() {
     let source = "> 0";
     let mutation = make_mutation(0, 1, ">=", ">");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, ">= 0");
+    assert_eq!(result.as_deref(), Some(">= 0"));
 }
 
 #[test]
@@ -40,7 +41,7 @@ fn apply_mutation_at_end() {
     let source = "x > 0";
     let mutation = make_mutation(4, 5, "1", "0");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, "x > 1");
+    assert_eq!(result.as_deref(), Some("x > 1"));
 }
 
 #[test]
@@ -48,7 +49,7 @@ fn apply_mutation_replacement_longer_than_original() {
     let source = "return True";
     let mutation = make_mutation(0, 11, "return False", "return True");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, "return False");
+    assert_eq!(result.as_deref(), Some("return False"));
 }
 
 #[test]
@@ -56,7 +57,7 @@ fn apply_mutation_replacement_shorter_than_original() {
     let source = "return True";
     let mutation = make_mutation(0, 11, "pass", "return True");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, "pass");
+    assert_eq!(result.as_deref(), Some("pass"));
 }
 
 #[test]
@@ -64,7 +65,7 @@ fn apply_mutation_empty_replacement() {
     let source = "not x";
     let mutation = make_mutation(0, 4, "", "not ");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, "x");
+    assert_eq!(result.as_deref(), Some("x"));
 }
 
 #[test]
@@ -72,7 +73,39 @@ fn apply_mutation_preserves_surrounding_code() {
     let source = "if a > b and c < d:";
     let mutation = make_mutation(5, 6, ">=", ">");
     let result = runner::apply_mutation(source, &mutation);
-    assert_eq!(result, "if a >= b and c < d:");
+    assert_eq!(result.as_deref(), Some("if a >= b and c < d:"));
+}
+
+#[test]
+fn apply_mutation_after_multibyte_text() {
+    let source = "s = \"héllo 🦀 世界\"; x > 0";
+    let start = source.find('>').unwrap();
+    let mutation = make_mutation(start, start + 1, ">=", ">");
+    let result = runner::apply_mutation(source, &mutation);
+    assert_eq!(result.as_deref(), Some("s = \"héllo 🦀 世界\"; x >= 0"));
+}
+
+#[test]
+fn apply_mutation_inside_a_character_returns_none() {
+    let source = "x = \"🦀\"";
+    // Byte 6 is inside the 4-byte crab.
+    let mutation = make_mutation(6, 7, "a", "?");
+    assert_eq!(runner::apply_mutation(source, &mutation), None);
+}
+
+#[test]
+fn apply_mutation_out_of_range_returns_none() {
+    let source = "x > 0";
+    let mutation = make_mutation(10, 11, ">=", ">");
+    assert_eq!(runner::apply_mutation(source, &mutation), None);
+}
+
+#[test]
+fn apply_mutation_stale_original_returns_none() {
+    // Source edited after discovery: byte 2 is now '<'.
+    let source = "x < 0";
+    let mutation = make_mutation(2, 3, ">=", ">");
+    assert_eq!(runner::apply_mutation(source, &mutation), None);
 }
 
 // --- generate_diff ---
@@ -143,6 +176,44 @@ fn parse_test_cmd_npx() {
     assert_eq!(args, vec!["vitest", "run"]);
 }
 
+#[test]
+fn parse_test_cmd_keeps_quoted_args_together() {
+    let (program, args) = runner::parse_test_cmd(r#"pytest -k "adds and subtracts" -x"#);
+    assert_eq!(program, "pytest");
+    assert_eq!(args, vec!["-k", "adds and subtracts", "-x"]);
+
+    let (_, args) = runner::parse_test_cmd("npx vitest run -t 'parses \"quoted\" names'");
+    assert_eq!(args, vec!["vitest", "run", "-t", r#"parses "quoted" names"#]);
+}
+
+#[test]
+fn parse_test_cmd_handles_escapes() {
+    let (program, args) = runner::parse_test_cmd(r"./my\ tools/run-tests --filter a\ b");
+    assert_eq!(program, "./my tools/run-tests");
+    assert_eq!(args, vec!["--filter", "a b"]);
+}
+
+#[test]
+fn parse_test_cmd_unbalanced_quote_falls_back_to_whitespace() {
+    let (program, args) = runner::parse_test_cmd(r#"pytest -k "unterminated"#);
+    assert_eq!(program, "pytest");
+    assert_eq!(args, vec!["-k", "\"unterminated"]);
+}
+
+#[test]
+fn resolve_paths_resolves_program_and_keeps_quoted_args() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let bin = dir.path().join("venv bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    std::fs::write(bin.join("pytest"), "").unwrap();
+    let source = dir.path().join("app.py");
+
+    let (_, _, _, cmd) = runner::resolve_paths(&source, &source, r#"'venv bin/pytest' -k "a and b""#);
+    let (program, args) = runner::parse_test_cmd(&cmd);
+    assert_eq!(Path::new(&program), bin.join("pytest"));
+    assert_eq!(args, vec!["-k", "a and b"]);
+}
+
 // --- resolve_paths ---
 
 #[test]
@@ -217,6 +288,7 @@ fn run_baseline_passing_test() {
             assert!(duration_ms < 10000, "Should complete quickly");
         }
         runner::BaselineResult::Failed(msg) => panic!("Expected Ok, got Failed: {}", msg),
+        runner::BaselineResult::NoTests(msg) => panic!("Expected Ok, got NoTests: {}", msg),
     }
 }
 
@@ -231,6 +303,7 @@ fn run_baseline_failing_test() {
     match result {
         runner::BaselineResult::Ok { .. } => panic!("Expected Failed, got Ok"),
         runner::BaselineResult::Failed(_) => {}
+        runner::BaselineResult::NoTests(msg) => panic!("Expected Failed, got NoTests: {}", msg),
     }
 }
 
@@ -246,6 +319,7 @@ fn run_baseline_nonexistent_command() {
         runner::BaselineResult::Failed(msg) => {
             assert!(msg.contains("Failed to run"), "Expected 'Failed to run' message, got: {}", msg);
         }
+        runner::BaselineResult::NoTests(msg) => panic!("Expected Failed, got NoTests: {}", msg),
     }
 }
 
@@ -260,6 +334,7 @@ fn run_baseline_with_extra_args() {
     match result {
         runner::BaselineResult::Ok { .. } => {}
         runner::BaselineResult::Failed(msg) => panic!("Expected Ok, got Failed: {}", msg),
+        runner::BaselineResult::NoTests(msg) => panic!("Expected Ok, got NoTests: {}", msg),
     }
 }
 
@@ -275,9 +350,26 @@ fn run_baseline_cargo_cmd_skips_test_file_arg() {
     match result {
         runner::BaselineResult::Ok { .. } => {}
         runner::BaselineResult::Failed(msg) => panic!("Expected Ok, got Failed: {}", msg),
+        runner::BaselineResult::NoTests(msg) => panic!("Expected Ok, got NoTests: {}", msg),
     }
 }
 
+#[test]
+fn run_baseline_that_selects_no_tests_is_reported() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let test_file = dir.path().join("test_app.py");
+    std::fs::write(&test_file, "").unwrap();
+
+    // pytest's exit code for "no tests ran"
+    let invocation = runner::TestInvocation::new("sh -c 'exit 5'", &test_file, dir.path(), &[]).with_runner(Runner::Pytest);
+    assert!(matches!(runner::run_baseline_with(&invocation), runner::BaselineResult::NoTests(_)));
+
+    // A passing cargo run with no tests in it
+    let invocation = runner::TestInvocation::new("echo 'running 0 tests'", &test_file, dir.path(), &[])
+        .with_runner(Runner::CargoTest);
+    assert!(matches!(runner::run_baseline_with(&invocation), runner::BaselineResult::NoTests(_)));
+}
+
 // --- run_mutations ---
 
 #[test]
@@ -290,7 +382,7 @@ fn run_mutations_killed_mutant() {
     std::fs::write(&source_file, source).unwrap();
     std::fs::write(&test_file, "").unwrap();
 
-    let mutation = make_mutation(4, 5, "-", "+");
+    let mutation = make_mutation(6, 7, "-", "+");
 
     // 'false' always fails -> mutation is "killed"
     let results = runner::run_mutations(
@@ -315,7 +407,7 @@ fn run_mutations_survived_mutant() {
     std::fs::write(&source_file, source).unwrap();
     std::fs::write(&test_file, "").unwrap();
 
-    let mutation = make_mutation(4, 5, "-", "+");
+    let mutation = make_mutation(6, 7, "-", "+");
 
     // 'true' always succeeds -> mutation "survived"
     let results = runner::run_mutations(
@@ -327,6 +419,29 @@ fn run_mutations_survived_mutant() {
     assert_eq!(results[0].status, mutator::mutants::MutantStatus::Survived);
 }
 
+#[test]
+fn run_mutations_stale_mutation_is_unviable() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.py");
+
+    let source = "x = \"🦀\"\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "").unwrap();
+
+    let mutation = make_mutation(6, 7, "-", "+");
+
+    let results = runner::run_mutations(
+        &source_file, &test_file, source, &[mutation],
+        "true", dir.path(), 5000, &[],
+    );
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].status, MutantStatus::Unviable);
+    assert!(results[0].output_snippet.as_deref().unwrap().contains("Stale mutation"));
+    assert_eq!(std::fs::read_to_string(&source_file).unwrap(), source);
+}
+
 #[test]
 fn run_mutations_restores_original_on_completion() {
     let dir = tempfile::TempDir::new().unwrap();
@@ -416,6 +531,23 @@ fn prepare_isolated_session_id_in_path() {
     assert!(path_str.contains("mutator-my-agent-42"), "Temp dir should contain session ID: {}", path_str);
 }
 
+#[test]
+fn prepare_isolated_session_id_with_slashes() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("pyproject.toml"), "[project]").unwrap();
+    std::fs::write(root.join("app.py"), "x = 1").unwrap();
+    std::fs::write(root.join("test_app.py"), "").unwrap();
+
+    let ctx = runner::prepare_isolated(&root.join("app.py"), &root.join("test_app.py"), "pytest", "team/agent 1")
+        .unwrap();
+
+    assert!(ctx.copy_result.source_file.exists());
+    let temp = ctx.copy_result.root.ancestors().find(|p| p.parent() == Some(std::env::temp_dir().as_path()));
+    let name = temp.and_then(|p| p.file_name()).unwrap().to_string_lossy().into_owned();
+    assert!(name.starts_with("mutator-team_agent_1-"), "{}", name);
+}
+
 // --- run_mutations_isolated ---
 
 #[test]
@@ -434,17 +566,49 @@ fn run_mutations_isolated_does_not_touch_original() {
     ).unwrap();
 
     let source = "x = 1 + 2\n";
-    let mutation = make_mutation(4, 5, "-", "+");
+    let mutation = make_mutation(6, 7, "-", "+");
 
     let results = runner::run_mutations_isolated(
         &ctx, source, &[mutation], 5000, &[],
-    );
+    ).unwrap();
 
     assert_eq!(results.len(), 1);
     // Original file untouched
     assert_eq!(std::fs::read_to_string(root.join("app.py")).unwrap(), source);
 }
 
+#[test]
+fn run_mutations_isolated_errors_when_source_changed_mid_run() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("pyproject.toml"), "[project]").unwrap();
+    std::fs::write(root.join("app.py"), "x = 1 + 2\n").unwrap();
+    std::fs::write(root.join("test_app.py"), "").unwrap();
+
+    let ctx = runner::prepare_isolated(
+        &root.join("app.py"),
+        &root.join("test_app.py"),
+        "true",
+        "iso-changed",
+    ).unwrap();
+
+    // Discovery saw an older version of the file
+    let discovered = "x = 1 + 20\n";
+    let mutation = make_mutation(6, 7, "-", "+");
+
+    let err = runner::run_mutations_isolated(
+        &ctx, discovered, &[mutation], 5000, &[],
+    ).unwrap_err();
+
+    assert!(err.contains("Source changed mid-run"), "{}", err);
+}
+
+#[test]
+fn source_hash_differs_on_any_edit() {
+    assert_eq!(runner::source_hash("x = 1\n"), runner::source_hash("x = 1\n"));
+    assert_ne!(runner::source_hash("x = 1\n"), runner::source_hash("x = 2\n"));
+}
+
 // --- clear_pycache ---
 
 #[test]
@@ -463,3 +627,479 @@ fn clear_pycache_removes_matching_files() {
     assert!(!cache_dir.join("app.cpython-311.pyc").exists(), "Should remove matching .pyc");
     assert!(cache_dir.join("other.cpython-311.pyc").exists(), "Should not remove unrelated .pyc");
 }
+
+// --- output capture ---
+
+#[test]
+fn run_mutations_timeout_captures_partial_output() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.sh");
+
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "echo started loop\nsleep 5\n").unwrap();
+
+    let results = runner::run_mutations(
+        &source_file, &test_file, source, &[make_mutation(6, 7, "-", "+")],
+        "sh", dir.path(), 300, &[],
+    );
+
+    assert_eq!(results[0].status, mutator::mutants::MutantStatus::Timeout);
+    let snippet = results[0].output_snippet.as_deref().unwrap_or_default();
+    assert!(snippet.contains("started loop"), "Expected partial output, got: {:?}", snippet);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn run_mutations_timeout_kills_whole_process_group() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.sh");
+    let pid_file = dir.path().join("worker.pid");
+
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    // A wrapper that leaves a worker behind, like npm scripts or xdist
+    std::fs::write(&test_file, format!("sleep 30 &\necho $! > {}\nwait\n", pid_file.display())).unwrap();
+
+    let results = runner::run_mutations(
+        &source_file, &test_file, source, &[make_mutation(6, 7, "-", "+")],
+        "sh", dir.path(), 300, &[],
+    );
+    assert_eq!(results[0].status, mutator::mutants::MutantStatus::Timeout);
+
+    let pid = std::fs::read_to_string(&pid_file).unwrap().trim().to_string();
+    let alive = || {
+        std::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .is_ok_and(|stat| !stat.rsplit(')').next().unwrap_or_default().trim_start().starts_with('Z'))
+    };
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(2);
+    while alive() && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert!(!alive(), "worker {} outlived the timeout", pid);
+}
+
+#[test]
+fn run_mutations_captures_output_on_exit() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.sh");
+
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "echo out\necho err >&2\nexit 1\n").unwrap();
+
+    let results = runner::run_mutations(
+        &source_file, &test_file, source, &[make_mutation(6, 7, "-", "+")],
+        "sh", dir.path(), 5000, &[],
+    );
+
+    assert_eq!(results[0].status, mutator::mutants::MutantStatus::Killed);
+    assert_eq!(results[0].output_snippet.as_deref(), Some("out\nerr"));
+}
+
+#[cfg(unix)]
+#[test]
+fn cpu_limit_marks_runaway_mutant_resource_exceeded() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.sh");
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "while :; do :; done\n").unwrap();
+
+    let invocation = runner::TestInvocation::new("sh", &test_file, dir.path(), &[])
+        .with_limits(runner::ResourceLimits { cpu_seconds: Some(1), ..Default::default() });
+    let result = runner::run_mutant(&invocation, &source_file, source, &make_mutation(6, 7, "-", "+"), 20_000);
+
+    assert_eq!(result.status, mutator::mutants::MutantStatus::ResourceExceeded);
+    assert!(result.duration_ms < 10_000);
+}
+
+#[test]
+fn compile_errors_mark_js_and_rust_mutants_unviable() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("app.test.js");
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "").unwrap();
+    let mutation = make_mutation(6, 7, "-", "+");
+    let run = |cmd: &str, runner: Runner| {
+        let invocation = runner::TestInvocation::new(cmd, &test_file, dir.path(), &[]).with_runner(runner);
+        runner::run_mutant(&invocation, &source_file, source, &mutation, 20_000).status
+    };
+
+    let rustc = "sh -c 'echo \"error[E0308]: mismatched types\" >&2; exit 101'";
+    assert_eq!(run(rustc, Runner::CargoTest), MutantStatus::Unviable);
+    let node = "sh -c 'echo \"SyntaxError: Unexpected token )\" >&2; exit 1'";
+    assert_eq!(run(node, Runner::Jest), MutantStatus::Unviable);
+    let assertion = "sh -c 'echo \"test add ... FAILED\"; exit 101'";
+    assert_eq!(run(assertion, Runner::CargoTest), MutantStatus::Killed);
+}
+
+#[cfg(unix)]
+#[test]
+fn memory_limit_recognizes_allocation_failures() {
+    use std::os::unix::process::ExitStatusExt;
+    let failed = std::process::ExitStatus::from_raw(1 << 8);
+    let limited = runner::ResourceLimits { max_memory_mb: Some(256), ..Default::default() };
+
+    assert!(limited.exceeded(failed, "Traceback (most recent call last):\nMemoryError\n"));
+    assert!(limited.exceeded(failed, "memory allocation of 1073741824 bytes failed"));
+    assert!(limited.exceeded(failed, "FATAL ERROR: Reached heap limit Allocation failed - JavaScript heap out of memory"));
+    assert!(!limited.exceeded(failed, "AssertionError: assert 3 == 4"));
+    assert!(!runner::ResourceLimits::default().exceeded(failed, "MemoryError"));
+}
+
+#[test]
+fn output_snippet_empty_is_none() {
+    assert!(runner::output_snippet("", "  \n").is_none());
+}
+
+#[test]
+fn output_snippet_keeps_tail_of_long_output() {
+    let long = format!("{}END", "x".repeat(5000));
+    let snippet = runner::output_snippet(&long, "").unwrap();
+    assert!(snippet.starts_with("..."));
+    assert!(snippet.ends_with("END"));
+    assert!(snippet.chars().count() <= 2003);
+}
+
+#[test]
+fn run_mutations_records_killing_test() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.sh");
+
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "echo 'FAILED test_app.py::test_add - assert 3 == -1'\nexit 1\n").unwrap();
+
+    let results = runner::run_mutations(
+        &source_file, &test_file, source, &[make_mutation(6, 7, "-", "+")],
+        "sh", dir.path(), 5000, &[],
+    );
+
+    assert_eq!(results[0].killed_by.as_deref(), Some("test_app.py::test_add"));
+}
+
+#[test]
+fn survivors_have_no_killing_test() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.sh");
+
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "echo 'FAILED test_app.py::test_add'\nexit 0\n").unwrap();
+
+    let results = runner::run_mutations(
+        &source_file, &test_file, source, &[make_mutation(6, 7, "-", "+")],
+        "sh", dir.path(), 5000, &[],
+    );
+
+    assert_eq!(results[0].status, mutator::mutants::MutantStatus::Survived);
+    assert!(results[0].killed_by.is_none());
+}
+
+#[test]
+fn failing_test_pytest() {
+    let out = "F\n=== short test summary info ===\nFAILED tests/test_app.py::TestAdd::test_add - assert 4 == 3\n1 failed";
+    assert_eq!(runner::failing_test(out).as_deref(), Some("tests/test_app.py::TestAdd::test_add"));
+}
+
+#[test]
+fn failing_test_cargo() {
+    let out = "running 2 tests\ntest math::tests::adds ... ok\ntest math::tests::subtracts ... FAILED\n";
+    assert_eq!(runner::failing_test(out).as_deref(), Some("math::tests::subtracts"));
+}
+
+#[test]
+fn failing_test_jest() {
+    let out = "FAIL src/math.test.js\n  ● math › adds numbers\n\n    expect(received).toBe(expected)";
+    assert_eq!(runner::failing_test(out).as_deref(), Some("math › adds numbers"));
+}
+
+#[test]
+fn failing_test_vitest() {
+    let out = " FAIL  src/math.test.ts > math > adds numbers\nAssertionError: expected 4 to be 3";
+    assert_eq!(runner::failing_test(out).as_deref(), Some("src/math.test.ts > math > adds numbers"));
+}
+
+#[test]
+fn failing_test_unrecognized_output() {
+    assert!(runner::failing_test("Traceback (most recent call last):\nboom").is_none());
+    assert!(runner::failing_test("  ● Test suite failed to run\n").is_none());
+}
+
+#[test]
+fn cargo_target_args_for_integration_tests() {
+    assert_eq!(runner::cargo_target_args(Path::new("/p/tests/math.rs")), vec!["--test", "math"]);
+    assert_eq!(runner::cargo_target_args(Path::new("/p/tests/suite/main.rs")), vec!["--test", "suite"]);
+}
+
+#[test]
+fn cargo_target_args_empty_for_unit_tests() {
+    assert!(runner::cargo_target_args(Path::new("/p/src/lib.rs")).is_empty());
+    assert!(runner::cargo_target_args(Path::new("/p/tests/helpers/mod.txt")).is_empty());
+}
+
+#[test]
+fn cargo_package_args_select_workspace_members() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let member = dir.path().join("crates").join("core");
+    std::fs::create_dir_all(member.join("src")).unwrap();
+    std::fs::write(
+        member.join("Cargo.toml"),
+        "[package]\nname = \"acme-core\"\nversion.workspace = true\n\n[dependencies]\nname = \"x\"\n",
+    )
+    .unwrap();
+    // Outside a workspace cargo tests the one crate anyway
+    assert!(runner::cargo_package_args(&member.join("src").join("lib.rs")).is_empty());
+
+    std::fs::write(dir.path().join("Cargo.toml"), "[workspace]\nmembers = [\"crates/*\"]\n").unwrap();
+    assert_eq!(runner::cargo_package_args(&member.join("src").join("lib.rs")), vec!["-p", "acme-core"]);
+    assert!(runner::cargo_package_args(&dir.path().join("build.rs")).is_empty());
+}
+
+#[test]
+fn cargo_target_dir_is_keyed_by_session() {
+    let root = Path::new("/p");
+    let default = runner::cargo_target_dir(root, None);
+    let agent = runner::cargo_target_dir(root, Some("agent/1"));
+    assert!(default.ends_with("mutator/default"));
+    let name = agent.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(name.starts_with("agent_1-"), "{}", name);
+    assert_ne!(agent, runner::cargo_target_dir(root, Some("agent_1")));
+    assert!(runner::cargo_target_dir(root, Some("..")).starts_with(root.join("target/mutator")));
+    if std::env::var_os("CARGO_TARGET_DIR").is_none() {
+        assert_eq!(default, Path::new("/p/target/mutator/default"));
+    }
+}
+
+#[test]
+fn seed_cargo_target_dir_copies_dependency_artifacts_once() {
+    if std::env::var_os("CARGO_TARGET_DIR").is_some() {
+        return;
+    }
+    let dir = tempfile::TempDir::new().unwrap();
+    let debug = dir.path().join("target").join("debug");
+    for sub in ["deps", "build", ".fingerprint", "incremental"] {
+        std::fs::create_dir_all(debug.join(sub)).unwrap();
+        std::fs::write(debug.join(sub).join("artifact"), sub).unwrap();
+    }
+    let session_dir = runner::cargo_target_dir(dir.path(), Some("s1"));
+
+    assert!(runner::seed_cargo_target_dir(dir.path(), &session_dir).unwrap());
+    let seeded = session_dir.join("debug");
+    assert_eq!(std::fs::read_to_string(seeded.join("deps").join("artifact")).unwrap(), "deps");
+    assert!(seeded.join(".fingerprint").join("artifact").exists());
+    assert!(!seeded.join("incremental").exists());
+
+    assert!(!runner::seed_cargo_target_dir(dir.path(), &session_dir).unwrap());
+}
+
+#[test]
+fn seed_cargo_target_dir_needs_a_prior_build() {
+    if std::env::var_os("CARGO_TARGET_DIR").is_some() {
+        return;
+    }
+    let dir = tempfile::TempDir::new().unwrap();
+    let session_dir = runner::cargo_target_dir(dir.path(), None);
+    assert!(!runner::seed_cargo_target_dir(dir.path(), &session_dir).unwrap());
+    assert!(!session_dir.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn node_modules_are_linked_into_isolated_copy() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path().join("app");
+    std::fs::create_dir_all(root.join("node_modules").join("left-pad")).unwrap();
+    std::fs::create_dir_all(root.join("packages").join("ui").join("node_modules")).unwrap();
+    std::fs::create_dir_all(root.join("packages").join("ui").join("src")).unwrap();
+    std::fs::write(root.join("package.json"), "{}").unwrap();
+    let source = root.join("packages").join("ui").join("src").join("index.js");
+    let test = root.join("packages").join("ui").join("index.test.js");
+    std::fs::write(&source, "export const x = 1;").unwrap();
+    std::fs::write(&test, "").unwrap();
+
+    let ctx = runner::prepare_isolated(&source, &test, "npx vitest run", "nm").unwrap();
+    let copy = &ctx.copy_result.root;
+
+    let top = copy.join("node_modules");
+    assert!(top.symlink_metadata().unwrap().file_type().is_symlink());
+    assert_eq!(std::fs::read_link(&top).unwrap(), root.join("node_modules"));
+    assert!(top.join("left-pad").is_dir());
+    assert!(copy.join("packages").join("ui").join("node_modules").symlink_metadata().unwrap().file_type().is_symlink());
+}
+
+#[cfg(unix)]
+#[test]
+fn hoisted_node_modules_above_project_root_are_linked() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let workspace = dir.path();
+    std::fs::write(workspace.join("package.json"), "{\"workspaces\": [\"pkg\"]}").unwrap();
+    std::fs::create_dir_all(workspace.join("node_modules").join("vitest")).unwrap();
+    let root = workspace.join("pkg");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("package.json"), "{}").unwrap();
+    let copy = tempfile::TempDir::new().unwrap();
+
+    let linked = runner::link_node_modules(&root, &[&root.join("index.js")], copy.path()).unwrap();
+    assert_eq!(linked, 1);
+    assert!(copy.path().join("node_modules").join("vitest").is_dir());
+    assert_eq!(runner::link_node_modules(&root, &[&root.join("index.js")], copy.path()).unwrap(), 0);
+}
+
+#[test]
+fn invocation_env_is_applied() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let invocation = runner::TestInvocation::new("sh -c", Path::new("test"), dir.path(), &[])
+        .with_env("MUTATOR_PROBE", "yes");
+    let cmd = invocation.command();
+    let probe: Vec<_> = cmd.get_envs().filter(|(k, _)| *k == "MUTATOR_PROBE").collect();
+    assert_eq!(probe, vec![(std::ffi::OsStr::new("MUTATOR_PROBE"), Some(std::ffi::OsStr::new("yes")))]);
+}
+
+#[test]
+fn test_arg_style_controls_the_test_file_arg() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let test_file = dir.path().join("tests").join("test_app.py");
+    let args = |runner: Runner, style: TestArgStyle| -> Vec<String> {
+        let invocation = runner::TestInvocation::new("./scripts/check -q", &test_file, dir.path(), &["-x"])
+            .with_runner(runner)
+            .with_test_arg_style(style);
+        invocation.command().get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+    };
+    let path = test_file.to_string_lossy().into_owned();
+
+    assert_eq!(args(Runner::Pytest, TestArgStyle::Auto), vec!["-q", path.as_str(), "-x"]);
+    assert_eq!(args(Runner::CargoTest, TestArgStyle::Auto), vec!["-q", "-x"]);
+    assert_eq!(args(Runner::CargoTest, TestArgStyle::Path), vec!["-q", path.as_str(), "-x"]);
+    assert_eq!(args(Runner::Pytest, TestArgStyle::Relative), vec!["-q", "tests/test_app.py", "-x"]);
+    assert_eq!(args(Runner::Pytest, TestArgStyle::None), vec!["-q", "-x"]);
+}
+
+#[test]
+fn adaptive_timeout_follows_survivor_durations() {
+    use runner::AdaptiveTimeout;
+
+    // Slow warmup baseline: starts at baseline * mult + 2s
+    let mut timeout = AdaptiveTimeout::new(10_000, 3.0, None);
+    assert_eq!(timeout.timeout_ms(), 32_000);
+    for _ in 0..AdaptiveTimeout::MIN_SAMPLES - 1 {
+        timeout.record(1_000);
+    }
+    assert_eq!(timeout.timeout_ms(), 32_000, "too few samples to adapt");
+    timeout.record(1_000);
+    assert_eq!(timeout.timeout_ms(), 5_000);
+
+    // p95 of the window: one outlier in 20 is ignored, two are not
+    for _ in 0..AdaptiveTimeout::WINDOW {
+        timeout.record(1_000);
+    }
+    timeout.record(9_000);
+    assert_eq!(timeout.timeout_ms(), 5_000);
+    timeout.record(9_000);
+    assert_eq!(timeout.timeout_ms(), 29_000);
+
+    let capped = AdaptiveTimeout::new(10_000, 3.0, Some(15_000));
+    assert_eq!(capped.timeout_ms(), 15_000);
+}
+
+#[test]
+fn early_exit_stops_run_at_first_reported_failure() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("lib.rs");
+    let test_file = dir.path().join("tests.rs");
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "").unwrap();
+    let mutation = make_mutation(6, 7, "-", "+");
+    let slow_failure = "sh -c 'echo \"test tests::adds ... FAILED\"; sleep 30; exit 101'";
+
+    let invocation = runner::TestInvocation::new(slow_failure, &test_file, dir.path(), &[])
+        .with_runner(Runner::CargoTest)
+        .with_early_exit(true);
+    let result = runner::run_mutant(&invocation, &source_file, source, &mutation, 20_000);
+    assert_eq!(result.status, MutantStatus::Killed);
+    assert_eq!(result.killed_by.as_deref(), Some("tests::adds"));
+    assert!(result.duration_ms < 10_000, "took {}ms", result.duration_ms);
+
+    let invocation = invocation.with_early_exit(false);
+    let result = runner::run_mutant(&invocation, &source_file, source, &mutation, 1_000);
+    assert_eq!(result.status, MutantStatus::Timeout);
+}
+
+#[test]
+fn jest_report_decides_killed_or_unviable() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("cart.js");
+    let test_file = dir.path().join("cart.test.js");
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "").unwrap();
+    let mutation = make_mutation(6, 7, "-", "+");
+    // Copies $REPORT to the --outputFile path and fails, printing nothing
+    let fake_jest = dir.path().join("fake-jest");
+    std::fs::write(
+        &fake_jest,
+        "#!/bin/sh\nfor arg in \"$@\"; do\n  case \"$arg\" in --outputFile=*) cp \"$REPORT\" \"${arg#--outputFile=}\";; esac\ndone\nexit 1\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&fake_jest, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let run = |report: &str| {
+        let report_file = dir.path().join("report.json");
+        std::fs::write(&report_file, report).unwrap();
+        let invocation = runner::TestInvocation::new(&fake_jest.to_string_lossy(), &test_file, dir.path(), &[])
+            .with_runner(Runner::Jest)
+            .with_env("REPORT", report_file.to_string_lossy());
+        runner::run_mutant(&invocation, &source_file, source, &mutation, 10_000)
+    };
+
+    let failed = run(r#"{"testResults": [{"status": "failed", "assertionResults": [
+        {"ancestorTitles": ["Cart"], "title": "adds items", "status": "failed"}]}]}"#);
+    assert_eq!(failed.status, MutantStatus::Killed);
+    assert_eq!(failed.killed_by.as_deref(), Some("Cart › adds items"));
+
+    let broken = run(r#"{"numRuntimeErrorTestSuites": 1, "testResults": [
+        {"status": "failed", "message": "Cannot find module", "assertionResults": []}]}"#);
+    assert_eq!(broken.status, MutantStatus::Unviable);
+
+    // No report: the exit code decides, as for other runners
+    let missing = run("");
+    assert_eq!(missing.status, MutantStatus::Killed);
+    assert!(missing.killed_by.is_none());
+}
+
+#[test]
+fn pytest_run_that_collects_no_tests_is_uncovered() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let source_file = dir.path().join("app.py");
+    let test_file = dir.path().join("test_app.py");
+    let source = "x = 1 + 2\n";
+    std::fs::write(&source_file, source).unwrap();
+    std::fs::write(&test_file, "").unwrap();
+    let mutation = make_mutation(6, 7, "-", "+");
+    // pytest's "no tests ran" exit, with the empty report it writes
+    let fake_pytest = dir.path().join("fake-pytest");
+    std::fs::write(
+        &fake_pytest,
+        "#!/bin/sh\nfor arg in \"$@\"; do\n  case \"$arg\" in --junitxml=*) echo '<testsuites><testsuite name=\"pytest\" tests=\"0\"/></testsuites>' > \"${arg#--junitxml=}\";; esac\ndone\nexit 5\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&fake_pytest, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let invocation = runner::TestInvocation::new(&fake_pytest.to_string_lossy(), &test_file, dir.path(), &[])
+        .with_runner(Runner::Pytest);
+    let result = runner::run_mutant(&invocation, &source_file, source, &mutation, 10_000);
+    assert_eq!(result.status, MutantStatus::Uncovered);
+}
@@ -47,3 +47,15 @@ fn restore_from_backup_restores_and_cleans() {
     assert_eq!(std::fs::read_to_string(&source).unwrap(), "original");
     assert!(!backup.exists());
 }
+
+#[test]
+fn write_backup_copies_source() {
+    let dir = TempDir::new().unwrap();
+    let source = dir.path().join("app.py");
+    std::fs::write(&source, "original").unwrap();
+
+    let bak = safety::write_backup(&source).unwrap();
+    assert_eq!(bak, safety::backup_path(&source));
+    assert_eq!(std::fs::read_to_string(&bak).unwrap(), "original");
+    assert_eq!(safety::check_interrupted_run(&source), Some(bak));
+}
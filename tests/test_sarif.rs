@@ -0,0 +1,75 @@
+use mutator::sarif;
+use mutator::state::{RunResult, SurvivedMutant};
+
+fn mutant(ref_id: &str, operator: &str, original: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: "src/app.py".into(),
+        line: 10,
+        column: 12,
+        operator: operator.into(),
+        original: original.into(),
+        replacement: ">=".into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn survivors_become_warning_results() {
+    let result = RunResult {
+        survived_mutants: vec![mutant("m1", "boundary", ">"), mutant("m2", "boundary", "<")],
+        ..Default::default()
+    };
+    let log = sarif::to_sarif(&result);
+
+    assert_eq!(log["version"], "2.1.0");
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "agent-mutator");
+    assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 1);
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "boundary");
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "boundary");
+    assert_eq!(results[0]["level"], "warning");
+    assert!(results[0]["message"]["text"].as_str().unwrap().contains("@m1"));
+
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/app.py");
+    assert_eq!(location["region"]["startLine"], 10);
+    assert_eq!(location["region"]["startColumn"], 12);
+    assert_eq!(location["region"]["endLine"], 10);
+    assert_eq!(location["region"]["endColumn"], 13);
+}
+
+#[test]
+fn multiline_original_spans_lines() {
+    let result = RunResult {
+        survived_mutants: vec![mutant("m1", "block_remove", "x = 1\n        y = 2")],
+        ..Default::default()
+    };
+    let log = sarif::to_sarif(&result);
+    let region = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["endLine"], 11);
+    assert_eq!(region["endColumn"], 14);
+}
+
+#[test]
+fn uncovered_mutants_are_notes() {
+    let result = RunResult {
+        uncovered_mutants: vec![mutant("u1", "arith", "+")],
+        ..Default::default()
+    };
+    let log = sarif::to_sarif(&result);
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["level"], "note");
+    assert!(results[0]["message"]["text"].as_str().unwrap().contains("No test executes"));
+}
+
+#[test]
+fn clean_run_has_empty_results() {
+    let log = sarif::to_sarif(&RunResult::default());
+    assert!(log["runs"][0]["results"].as_array().unwrap().is_empty());
+    assert!(log["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap().is_empty());
+}
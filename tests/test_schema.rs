@@ -0,0 +1,143 @@
+use mutator::schema::{self, SCHEMA_VERSION};
+use mutator::mutants::MutantStatus;
+use mutator::state::{FunctionScore, MutantOutcome, RunResult, SurvivedMutant};
+use std::collections::BTreeSet;
+
+fn mutant(ref_id: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: "src/app.py".into(),
+        line: 2,
+        column: 13,
+        function: Some("add".into()),
+        operator: "arithmetic".into(),
+        original: "+".into(),
+        replacement: "-".into(),
+        diff: "-    return a + b\n+    return a - b\n".into(),
+        context_before: vec!["def add(a, b):".into()],
+        context_after: vec![],
+        output_snippet: Some("1 passed".into()),
+    }
+}
+
+fn full_result() -> RunResult {
+    RunResult {
+        score: 0.5,
+        total: 4,
+        killed: 1,
+        survived: 1,
+        timeout: 1,
+        unviable: 0,
+        uncovered: 1,
+        equivalent: 0,
+        resource_exceeded: 1,
+        duration_ms: 1200,
+        survived_mutants: vec![mutant("m1")],
+        timeout_mutants: vec![mutant("t1")],
+        uncovered_mutants: vec![mutant("u1")],
+        equivalent_mutants: vec![mutant("e1")],
+        resource_exceeded_mutants: vec![mutant("r1")],
+        interrupted: true,
+        kills_by_test: [("test_add".to_string(), 1)].into(),
+        functions: [("add".to_string(), FunctionScore::default())].into(),
+        operators: [("arithmetic".to_string(), FunctionScore::default())].into(),
+        all_mutants: vec![MutantOutcome {
+            ref_id: Some("m1".into()),
+            file: "app.py".into(),
+            line: 2,
+            column: 13,
+            function: Some("add".into()),
+            operator: "arithmetic".into(),
+            original: "+".into(),
+            replacement: "-".into(),
+            status: MutantStatus::Survived,
+            duration_ms: 300,
+            killed_by: None,
+        }],
+    }
+}
+
+fn keys(value: &serde_json::Value) -> BTreeSet<&str> {
+    value.as_object().unwrap().keys().map(String::as_str).collect()
+}
+
+#[test]
+fn run_result_v1_fields() {
+    let value = schema::to_value(&full_result());
+    assert_eq!(value["schema_version"], 1);
+    assert_eq!(
+        keys(&value),
+        BTreeSet::from([
+            "schema_version",
+            "score",
+            "total",
+            "killed",
+            "survived",
+            "timeout",
+            "unviable",
+            "uncovered",
+            "equivalent",
+            "resource_exceeded",
+            "duration_ms",
+            "survived_mutants",
+            "timeout_mutants",
+            "uncovered_mutants",
+            "equivalent_mutants",
+            "resource_exceeded_mutants",
+            "interrupted",
+            "kills_by_test",
+            "functions",
+            "operators",
+            "all_mutants",
+        ])
+    );
+}
+
+#[test]
+fn optional_run_fields_are_omitted_when_empty() {
+    let value = schema::to_value(&RunResult::default());
+    for optional in ["equivalent_mutants", "resource_exceeded_mutants", "interrupted", "kills_by_test", "functions", "operators", "all_mutants"] {
+        assert!(value.get(optional).is_none(), "{} should be omitted", optional);
+    }
+}
+
+#[test]
+fn mutant_v1_fields() {
+    let value = schema::to_value(&mutant("m1"));
+    assert_eq!(value["schema_version"], SCHEMA_VERSION);
+    assert_eq!(
+        keys(&value),
+        BTreeSet::from([
+            "schema_version",
+            "ref_id",
+            "file",
+            "line",
+            "column",
+            "function",
+            "operator",
+            "original",
+            "replacement",
+            "diff",
+            "context_before",
+            "context_after",
+            "output_snippet",
+        ])
+    );
+}
+
+#[test]
+fn v1_document_round_trips() {
+    let json = schema::to_json(&full_result());
+    let parsed: RunResult = serde_json::from_str(&json).unwrap();
+    assert_eq!(schema::to_json(&parsed), json);
+}
+
+#[test]
+fn unknown_fields_are_ignored_when_reading() {
+    let parsed: RunResult = serde_json::from_str(
+        r#"{"schema_version": 2, "score": 1.0, "total": 1, "killed": 1, "survived": 0, "timeout": 0,
+            "unviable": 0, "duration_ms": 10, "survived_mutants": [], "added_later": {"x": 1}}"#,
+    )
+    .unwrap();
+    assert_eq!(parsed.killed, 1);
+}
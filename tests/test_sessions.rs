@@ -0,0 +1,112 @@
+use mutator::sessions::{self, SessionInfo};
+use std::path::Path;
+
+/// Pid of a process that has already exited.
+fn dead_pid() -> u32 {
+    let mut child = std::process::Command::new("true").spawn().unwrap();
+    let pid = child.id();
+    child.wait().unwrap();
+    pid
+}
+
+fn write_lock(dir: &Path, name: &str, info: &SessionInfo) {
+    std::fs::create_dir_all(dir).unwrap();
+    std::fs::write(dir.join(name), serde_json::to_string(info).unwrap()).unwrap();
+}
+
+#[test]
+fn sessions_dir_is_under_dot_mutator() {
+    assert_eq!(
+        sessions::sessions_dir(Path::new("/project")),
+        Path::new("/project/.mutator/sessions")
+    );
+}
+
+#[test]
+fn acquire_writes_lock_and_drop_removes_it() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let sessions_dir = dir.path().join("sessions");
+
+    let lock = sessions::acquire(&sessions_dir, "agent/1", None).unwrap();
+    assert!(lock.path().exists());
+    assert!(lock.path().file_name().unwrap().to_str().unwrap().starts_with("agent_1-"));
+    let live = sessions::live_sessions(&sessions_dir);
+    assert_eq!(live.len(), 1);
+    assert_eq!(live[0].session, "agent/1");
+    assert_eq!(live[0].pid, std::process::id());
+
+    let path = lock.path().to_path_buf();
+    drop(lock);
+    assert!(!path.exists());
+}
+
+#[test]
+fn set_temp_dir_is_recorded_in_lock() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let mut lock = sessions::acquire(dir.path(), "s", None).unwrap();
+    lock.set_temp_dir(Path::new("/tmp/mutator-s-abc")).unwrap();
+
+    let live = sessions::live_sessions(dir.path());
+    assert_eq!(live[0].temp_dir.as_deref(), Some(Path::new("/tmp/mutator-s-abc")));
+}
+
+#[test]
+fn acquire_enforces_max_sessions() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let _first = sessions::acquire(dir.path(), "a", Some(2)).unwrap();
+    let second = sessions::acquire(dir.path(), "a", Some(2)).unwrap();
+
+    let err = sessions::acquire(dir.path(), "c", Some(2)).unwrap_err();
+    assert!(err.contains("2 sessions already running"), "{}", err);
+    assert!(err.contains("--max-sessions 2"), "{}", err);
+
+    drop(second);
+    sessions::acquire(dir.path(), "c", Some(2)).unwrap();
+}
+
+#[test]
+fn dead_sessions_are_collected_with_their_temp_tree() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let sessions_dir = dir.path().join("sessions");
+    let temp_tree = dir.path().join("mutator-dead-xyz");
+    std::fs::create_dir_all(temp_tree.join("src")).unwrap();
+    write_lock(
+        &sessions_dir,
+        "dead-1.lock",
+        &SessionInfo { session: "dead".into(), pid: dead_pid(), temp_dir: Some(temp_tree.clone()) },
+    );
+
+    // The dead session doesn't count against the limit
+    let lock = sessions::acquire(&sessions_dir, "new", Some(1)).unwrap();
+
+    assert!(!temp_tree.exists());
+    assert!(!sessions_dir.join("dead-1.lock").exists());
+    assert!(lock.path().exists());
+}
+
+#[test]
+fn collect_garbage_keeps_dirs_it_did_not_create() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let sessions_dir = dir.path().join("sessions");
+    let project = dir.path().join("project");
+    std::fs::create_dir_all(&project).unwrap();
+    write_lock(
+        &sessions_dir,
+        "x-1.lock",
+        &SessionInfo { session: "x".into(), pid: dead_pid(), temp_dir: Some(project.clone()) },
+    );
+
+    let removed = sessions::collect_garbage(&sessions_dir);
+
+    assert_eq!(removed.len(), 1);
+    assert!(project.exists());
+}
+
+#[test]
+fn live_sessions_are_not_collected() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let lock = sessions::acquire(dir.path(), "live", None).unwrap();
+
+    assert!(sessions::collect_garbage(dir.path()).is_empty());
+    assert!(lock.path().exists());
+}
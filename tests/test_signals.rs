@@ -0,0 +1,106 @@
+use mutator::mutants::{MutantResult, MutantStatus, Mutation};
+use mutator::{safety, signals};
+use std::path::Path;
+use std::sync::Mutex;
+use tempfile::TempDir;
+
+// Tracked state is process-global; serialize tests that touch it.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn result(status: MutantStatus) -> MutantResult {
+    MutantResult {
+        mutation: Mutation {
+            line: 2,
+            column: 13,
+            start_byte: 0,
+            end_byte: 1,
+            operator: "arithmetic".into(),
+            original: "+".into(),
+            replacement: "-".into(),
+            context_before: vec![],
+            context_after: vec![],
+            function: None,
+        },
+        status,
+        duration_ms: 5,
+        diff: String::new(),
+        output_snippet: None,
+        killed_by: None,
+    }
+}
+
+#[test]
+fn cleanup_without_tracked_run_is_none() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    signals::untrack();
+    assert!(signals::cleanup().is_none());
+}
+
+#[test]
+fn cleanup_restores_in_place_backup() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = TempDir::new().unwrap();
+    let source = dir.path().join("app.py");
+    std::fs::write(&source, "original").unwrap();
+    safety::write_backup(&source).unwrap();
+    std::fs::write(&source, "mutated").unwrap();
+
+    signals::track_run(Path::new("app.py"), None);
+    signals::track_in_place(&source);
+    let result = signals::cleanup().unwrap();
+
+    assert!(result.interrupted);
+    assert_eq!(std::fs::read_to_string(&source).unwrap(), "original");
+    assert!(safety::check_interrupted_run(&source).is_none());
+}
+
+#[test]
+fn cleanup_removes_temp_dir() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let dir = TempDir::new().unwrap();
+    let temp = dir.path().join("mutator-copy");
+    std::fs::create_dir_all(temp.join("src")).unwrap();
+
+    signals::track_run(Path::new("app.py"), None);
+    signals::track_temp_dir(&temp);
+    signals::cleanup().unwrap();
+
+    assert!(!temp.exists());
+}
+
+#[test]
+fn cleanup_summarizes_recorded_results() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    signals::track_run(Path::new("app.py"), None);
+    signals::record(0, &result(MutantStatus::Killed));
+    signals::record(1, &result(MutantStatus::Survived));
+    let summary = signals::cleanup().unwrap();
+
+    assert_eq!(summary.total, 2);
+    assert_eq!(summary.killed, 1);
+    assert_eq!(summary.survived_mutants[0].ref_id, "m1");
+    assert!(signals::cleanup().is_none(), "cleanup should untrack");
+}
+
+#[test]
+fn record_without_tracked_run_is_ignored() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    signals::untrack();
+    signals::record(0, &result(MutantStatus::Killed));
+    assert!(signals::cleanup().is_none());
+}
+
+#[test]
+fn cleanup_orders_results_by_discovery_index() {
+    let _guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    signals::track_run(Path::new("app.py"), None);
+    // A shuffled run finishing the second mutant first
+    let mut second = result(MutantStatus::Survived);
+    second.mutation.line = 9;
+    signals::record(1, &second);
+    signals::record(0, &result(MutantStatus::Survived));
+    let summary = signals::cleanup().unwrap();
+
+    let survivors: Vec<_> = summary.survived_mutants.iter().map(|m| (m.ref_id.as_str(), m.line)).collect();
+    assert_eq!(survivors, [("m1", 2), ("m2", 9)]);
+}
@@ -1,4 +1,6 @@
-use mutator::state::{self, RunResult, SurvivedMutant};
+use mutator::mutants::MutantStatus;
+use mutator::state::{self, MutantOutcome, RunResult, SurvivedMutant};
+use std::path::Path;
 use tempfile::TempDir;
 
 #[test]
@@ -23,8 +25,10 @@ fn run_result_serializes_to_json() {
                 diff: "- x > 0\n+ x >= 0\n".into(),
                 context_before: vec!["line before".into()],
                 context_after: vec!["line after".into()],
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -44,6 +48,7 @@ fn run_result_roundtrips_through_json() {
         unviable: 0,
         duration_ms: 1234,
         survived_mutants: vec![],
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -70,6 +75,7 @@ fn survived_mutant_serializes_all_fields() {
         diff: "- x == 0\n+ x != 0\n".into(),
         context_before: vec!["before1".into(), "before2".into()],
         context_after: vec!["after1".into()],
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&mutant).unwrap();
@@ -108,6 +114,7 @@ fn run_result_with_survivors_roundtrips() {
                 diff: "- x > 0\n+ x >= 0\n".into(),
                 context_before: vec![],
                 context_after: vec![],
+                ..Default::default()
             },
             SurvivedMutant {
                 ref_id: "m2".into(),
@@ -120,8 +127,10 @@ fn run_result_with_survivors_roundtrips() {
                 diff: "- true\n+ false\n".into(),
                 context_before: vec!["fn check()".into()],
                 context_after: vec!["return x".into()],
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let json = serde_json::to_string(&result).unwrap();
@@ -160,8 +169,10 @@ fn save_and_load_roundtrip_via_path() {
                 diff: "- x > 0\n+ x >= 0\n".into(),
                 context_before: vec![],
                 context_after: vec![],
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     state::save_to_path(&result, &path);
@@ -206,6 +217,7 @@ fn save_empty_result_and_load() {
         unviable: 0,
         duration_ms: 0,
         survived_mutants: vec![],
+        ..Default::default()
     };
 
     state::save_to_path(&result, &path);
@@ -229,21 +241,137 @@ fn save_last_run_writes_file_to_cwd() {
         unviable: 0,
         duration_ms: 2000,
         survived_mutants: vec![],
+        ..Default::default()
     };
 
     // Change CWD to temp dir so save_last_run writes there
     let original_dir = std::env::current_dir().unwrap();
     std::env::set_current_dir(dir.path()).unwrap();
 
-    state::save_last_run(&result);
+    state::save_last_run(&result, None);
 
     let state_file = dir.path().join(".mutator-state.json");
     assert!(state_file.exists(), "save_last_run should create .mutator-state.json in CWD");
 
-    let loaded = state::load_last_run().unwrap();
+    let loaded = state::load_last_run(None).unwrap();
     assert_eq!(loaded.score, 0.9);
     assert_eq!(loaded.total, 10);
     assert_eq!(loaded.killed, 9);
 
     std::env::set_current_dir(original_dir).unwrap();
 }
+
+#[test]
+fn state_without_timeout_mutants_still_loads() {
+    let json = r#"{"score":1.0,"total":0,"killed":0,"survived":0,"timeout":0,"unviable":0,"duration_ms":0,"survived_mutants":[]}"#;
+    let loaded: RunResult = serde_json::from_str(json).unwrap();
+    assert!(loaded.timeout_mutants.is_empty());
+}
+
+#[test]
+fn output_snippet_omitted_when_absent() {
+    let mutant = SurvivedMutant {
+        ref_id: "m1".into(),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&mutant).unwrap();
+    assert!(!json.contains("output_snippet"));
+}
+
+#[test]
+fn interrupted_flag_omitted_when_false() {
+    let json = serde_json::to_string(&RunResult::default()).unwrap();
+    assert!(!json.contains("interrupted"));
+
+    let result = RunResult {
+        interrupted: true,
+        ..Default::default()
+    };
+    let loaded: RunResult = serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+    assert!(loaded.interrupted);
+}
+
+// --- session-keyed state paths ---
+
+#[test]
+fn state_path_without_session_is_legacy_file() {
+    let dir = Path::new("/work");
+    assert_eq!(state::state_path_in(dir, None), dir.join(".mutator-state.json"));
+}
+
+#[test]
+fn state_path_per_session() {
+    let dir = Path::new("/work");
+    assert_eq!(
+        state::state_path_in(dir, Some("agent-1")),
+        dir.join(".mutator").join("state-agent-1.json")
+    );
+    assert_ne!(state::state_path_in(dir, Some("agent-1")), state::state_path_in(dir, Some("agent-2")));
+}
+
+#[test]
+fn state_path_session_cannot_escape_dir() {
+    let path = state::state_path_in(Path::new("/work"), Some("../../etc/x"));
+    assert_eq!(path.parent().unwrap(), Path::new("/work/.mutator"));
+}
+
+#[test]
+fn state_path_sanitized_sessions_do_not_collide() {
+    let dir = Path::new("/work");
+    let path = |id| state::state_path_in(dir, Some(id));
+    assert_ne!(path("a/b"), path("a_b"));
+    assert_ne!(path("."), path(""));
+    assert_ne!(path(".x"), path("x"));
+    let name = path(".").file_name().unwrap().to_string_lossy().into_owned();
+    assert!(name.starts_with("state--") && name.ends_with(".json"), "{}", name);
+}
+
+fn outcome(file: &str, line: usize, status: MutantStatus) -> MutantOutcome {
+    MutantOutcome {
+        ref_id: None,
+        file: file.into(),
+        line,
+        column: 5,
+        function: None,
+        operator: "boundary".into(),
+        original: ">".into(),
+        replacement: ">=".into(),
+        status,
+        duration_ms: 10,
+        killed_by: None,
+    }
+}
+
+#[test]
+fn mutants_near_returns_any_status_within_window() {
+    let result = RunResult {
+        all_mutants: vec![
+            outcome("src/app.py", 45, MutantStatus::Survived),
+            outcome("src/app.py", 39, MutantStatus::Killed),
+            outcome("src/app.py", 40, MutantStatus::Killed),
+            outcome("src/app.py", 42, MutantStatus::Timeout),
+        ],
+        ..Default::default()
+    };
+
+    let near = result.mutants_near(None, 42, 2);
+    let lines: Vec<_> = near.mutants.iter().map(|m| m.line).collect();
+    assert_eq!(lines, vec![40, 42]);
+
+    let exact = result.mutants_near(None, 42, 0);
+    assert_eq!(exact.mutants.len(), 1);
+    assert_eq!(exact.mutants[0].status, MutantStatus::Timeout);
+}
+
+#[test]
+fn mutants_near_matches_file_by_path_suffix() {
+    let result = RunResult {
+        all_mutants: vec![outcome("src/app.py", 10, MutantStatus::Killed), outcome("", 10, MutantStatus::Killed)],
+        ..Default::default()
+    };
+
+    assert_eq!(result.mutants_near(Some("app.py"), 10, 0).mutants.len(), 2);
+    assert_eq!(result.mutants_near(Some("/repo/src/app.py"), 10, 0).mutants.len(), 2);
+    // Older state without a file matches any query
+    assert_eq!(result.mutants_near(Some("other.py"), 10, 0).mutants.len(), 1);
+}
@@ -0,0 +1,64 @@
+use mutator::state::SurvivedMutant;
+use mutator::suggest;
+
+fn survivor(file: &str, line: &str, operator: &str, original: &str, replacement: &str) -> SurvivedMutant {
+    let column = line.find(original).unwrap() + 1;
+    SurvivedMutant {
+        ref_id: "m1".into(),
+        file: file.into(),
+        line: 2,
+        column,
+        function: Some("isPositive".into()),
+        operator: operator.into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        diff: format!("- {}\n+ {}\n", line, line.replacen(original, replacement, 1)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn boundary_suggests_value_at_and_beside_the_bound() {
+    let s = suggest::suggest(&survivor("app.py", "    if x > 0:", "boundary", ">", ">="));
+    assert_eq!(s.inputs, vec!["x = 0 (`x > 0` vs `x >= 0`)", "x = 1"]);
+
+    let s = suggest::suggest(&survivor("app.py", "    return self.size <= 10", "boundary", "<=", "<"));
+    assert_eq!(s.inputs, vec!["self.size = 10 (`self.size <= 10` vs `self.size < 10`)", "self.size = 11"]);
+}
+
+#[test]
+fn length_boundary_suggests_collection_sizes() {
+    let s = suggest::suggest(&survivor("app.py", "    if len(items) > 0:", "len_boundary", ">", ">="));
+    assert_eq!(s.inputs, vec!["an empty collection", "a collection of 1 item"]);
+
+    let s = suggest::suggest(&survivor("app.ts", "  return 3 == items.length;", "len_boundary", "==", "<="));
+    assert_eq!(s.inputs, vec!["a collection of exactly 3 items", "a collection of 4 items"]);
+}
+
+#[test]
+fn non_literal_bound_describes_both_sides() {
+    let s = suggest::suggest(&survivor("app.py", "    if count >= limit:", "boundary", ">=", ">"));
+    assert_eq!(s.inputs, vec!["count = limit (`count >= limit` vs `count > limit`)", "count just past limit on each side"]);
+}
+
+#[test]
+fn negative_literal_bound() {
+    let s = suggest::suggest(&survivor("app.rs", "    if delta < -5 {", "boundary", "<", "<="));
+    assert_eq!(s.inputs, vec!["delta = -5 (`delta < -5` vs `delta <= -5`)", "delta = -6"]);
+}
+
+#[test]
+fn other_operators_get_hint_only() {
+    let s = suggest::suggest(&survivor("app.py", "    return True", "return_val", "return True", "return False"));
+    assert!(s.inputs.is_empty());
+    assert_eq!(s.hint, "Assert the exact return value");
+}
+
+#[test]
+fn skeleton_matches_file_language() {
+    let m = |file| suggest::suggest(&survivor(file, "    if x > 0:", "boundary", ">", ">=")).skeleton;
+    assert!(m("app.py").starts_with("def test_is_positive_m1():\n    # app.py:2 `>` -> `>=` survived."));
+    assert!(m("src/lib.rs").starts_with("#[test]\nfn is_positive_m1() {\n"));
+    assert!(m("src/app.ts").starts_with("test(\"isPositive m1\", () => {\n"));
+    assert!(m("src/app.ts").contains("  // Try: x = 0"));
+}
@@ -0,0 +1,316 @@
+use mutator::test_adapters::{self, ReportOutcome, Runner, TestArgStyle};
+use mutator::Language;
+
+#[test]
+fn filter_args_per_runner() {
+    let filters = vec!["test_add".to_string(), "test_sub".to_string()];
+    let args = |runner: Runner| runner.adapter().filter_args(&filters);
+    assert_eq!(args(Runner::Pytest), ["-k", "test_add or test_sub"]);
+    assert_eq!(args(Runner::Unittest), ["-k", "test_add", "-k", "test_sub"]);
+    assert_eq!(args(Runner::CargoTest), ["test_add", "test_sub"]);
+    assert_eq!(args(Runner::CargoNextest), ["test_add", "test_sub"]);
+    assert_eq!(args(Runner::Jest), ["-t", "test_add|test_sub"]);
+    assert_eq!(args(Runner::Vitest), ["-t", "test_add|test_sub"]);
+    assert_eq!(args(Runner::Mocha), ["--grep", "test_add|test_sub"]);
+    assert_eq!(args(Runner::GoTest), ["-run", "^(test_add|test_sub)$"]);
+    assert_eq!(args(Runner::Maven), ["-Dtest=*#test_add+test_sub"]);
+    assert_eq!(args(Runner::Gradle), ["--tests", "*.test_add", "--tests", "*.test_sub"]);
+    for runner in Runner::ALL {
+        assert!(runner.adapter().filter_args(&[]).is_empty(), "{}", runner);
+    }
+}
+
+#[test]
+fn js_test_names_match_literally() {
+    for runner in [Runner::Jest, Runner::Vitest, Runner::Mocha] {
+        assert_eq!(runner.adapter().test_name("handles $0.50?"), r"handles \$0\.50\?", "{}", runner);
+    }
+    assert_eq!(Runner::Pytest.adapter().test_name("test_add[a+b]"), "test_add[a+b]");
+    // A filter expression is passed through as a regex
+    let filters = vec!["add|sub".to_string()];
+    assert_eq!(Runner::Jest.adapter().filter_args(&filters), ["-t", "add|sub"]);
+}
+
+#[test]
+fn runner_from_command() {
+    assert_eq!(Runner::from_command("pytest"), Some(Runner::Pytest));
+    assert_eq!(Runner::from_command(".venv/bin/pytest -k 'a and b'"), Some(Runner::Pytest));
+    assert_eq!(Runner::from_command("python -m unittest"), Some(Runner::Unittest));
+    assert_eq!(Runner::from_command("npx jest"), Some(Runner::Jest));
+    assert_eq!(Runner::from_command("npx vitest run"), Some(Runner::Vitest));
+    assert_eq!(Runner::from_command("./node_modules/.bin/mocha"), Some(Runner::Mocha));
+    assert_eq!(Runner::from_command("cargo test"), Some(Runner::CargoTest));
+    assert_eq!(Runner::from_command("cargo nextest run"), Some(Runner::CargoNextest));
+    assert_eq!(Runner::from_command("go test ./..."), Some(Runner::GoTest));
+    assert_eq!(Runner::from_command("mvn -q test"), Some(Runner::Maven));
+    assert_eq!(Runner::from_command("./mvnw test"), Some(Runner::Maven));
+    assert_eq!(Runner::from_command("./gradlew test"), Some(Runner::Gradle));
+    assert_eq!(Runner::from_command("npm test"), None);
+    // Whole words only: wrappers named after a runner aren't that runner
+    assert_eq!(Runner::from_command("./scripts/cargo-wrapper"), None);
+    assert_eq!(Runner::from_command("npm run cargo-ish"), None);
+}
+
+#[test]
+fn runner_detected_from_package_json_then_language() {
+    let dir = tempfile::TempDir::new().unwrap();
+    assert_eq!(Runner::detect("npm test", &Language::TypeScript, dir.path()), Runner::Jest);
+    assert_eq!(Runner::detect("./run.sh", &Language::Python, dir.path()), Runner::Pytest);
+    assert_eq!(Runner::detect("./run.sh", &Language::Rust, dir.path()), Runner::CargoTest);
+
+    std::fs::write(dir.path().join("package.json"), r#"{"devDependencies": {"vitest": "^1.0.0"}}"#).unwrap();
+    assert_eq!(Runner::detect("npm test", &Language::TypeScript, dir.path()), Runner::Vitest);
+    std::fs::write(dir.path().join("package.json"), r#"{"scripts": {"test": "mocha"}}"#).unwrap();
+    assert_eq!(Runner::detect("npm test", &Language::JavaScript, dir.path()), Runner::Mocha);
+    // The command wins over the project
+    assert_eq!(Runner::detect("npx jest", &Language::JavaScript, dir.path()), Runner::Jest);
+}
+
+#[test]
+fn runner_names_round_trip() {
+    for runner in Runner::ALL {
+        assert_eq!(runner.name().parse::<Runner>(), Ok(runner));
+    }
+    assert!("nose".parse::<Runner>().unwrap_err().contains("cargo-nextest"));
+    let parsed: Runner = serde_json::from_str("\"cargo-nextest\"").unwrap();
+    assert_eq!(parsed, Runner::CargoNextest);
+}
+
+#[test]
+fn test_arg_style_names_round_trip() {
+    for style in TestArgStyle::ALL {
+        assert_eq!(style.name().parse::<TestArgStyle>(), Ok(style));
+    }
+    assert!("module".parse::<TestArgStyle>().unwrap_err().contains("relative"));
+    assert_eq!(TestArgStyle::default(), TestArgStyle::Auto);
+}
+
+#[test]
+fn unviable_detection_per_runner() {
+    let unviable = |runner: Runner, stdout: &str, stderr: &str| runner.adapter().is_unviable(stdout, stderr);
+    assert!(unviable(Runner::Pytest, "", "E   SyntaxError: invalid syntax"));
+    assert!(unviable(Runner::Unittest, "", "ModuleNotFoundError: No module named 'app'"));
+    assert!(unviable(Runner::CargoTest, "", "error[E0308]: mismatched types"));
+    assert!(unviable(Runner::CargoNextest, "", "error: could not compile `app`"));
+    assert!(unviable(Runner::Jest, "", "SyntaxError: Unexpected token"));
+    assert!(unviable(Runner::Jest, "", "src/cart.ts:3:7 - error TS2322: Type 'string' is not assignable"));
+    assert!(unviable(Runner::Vitest, "Error: Transform failed with 1 error:", ""));
+    assert!(unviable(Runner::CargoTest, "", "error: aborting due to 2 previous errors"));
+    assert!(!unviable(Runner::Jest, "expect(received).toBe(expected)", "TypeError: x is not a function"));
+    assert!(unviable(Runner::GoTest, "FAIL\tapp [build failed]", ""));
+    assert!(!unviable(Runner::CargoTest, "test add ... FAILED", ""));
+    assert!(!unviable(Runner::Pytest, "FAILED test_app.py::test_add", ""));
+}
+
+#[test]
+fn failing_test_per_runner() {
+    let failing = |runner: Runner, out: &str| runner.adapter().failing_test(out);
+    assert_eq!(
+        failing(Runner::Unittest, "F\n======\nFAIL: test_add (test_app.TestAdd.test_add)\n").as_deref(),
+        Some("test_add")
+    );
+    assert_eq!(
+        failing(Runner::Mocha, "  1 failing\n\n  1) Cart\n       adds items:\n     AssertionError").as_deref(),
+        Some("Cart adds items")
+    );
+    assert_eq!(
+        failing(Runner::CargoNextest, "        FAIL [   0.004s] app tests::adds\n").as_deref(),
+        Some("tests::adds")
+    );
+    assert_eq!(failing(Runner::GoTest, "--- FAIL: TestAdd (0.00s)\n").as_deref(), Some("TestAdd"));
+    assert_eq!(
+        failing(
+            Runner::Maven,
+            "[ERROR] Tests run: 2, Failures: 1 <<< FAILURE! -- in com.x.CartTest\n\
+             [ERROR] com.x.CartTest.adds -- Time elapsed: 0.01 s <<< FAILURE!\n"
+        )
+        .as_deref(),
+        Some("com.x.CartTest.adds")
+    );
+    assert_eq!(failing(Runner::Gradle, "CartTest > adds() FAILED\n").as_deref(), Some("CartTest.adds"));
+    assert_eq!(
+        failing(Runner::Pytest, "FAILED test_app.py::test_add - assert 1 == 2").as_deref(),
+        Some("test_app.py::test_add")
+    );
+}
+
+#[test]
+fn test_cmd_detected_from_project() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("pyproject.toml"), "[project]").unwrap();
+    std::fs::write(root.join("app.py"), "").unwrap();
+    assert_eq!(test_adapters::detect_test_cmd(&root.join("app.py"), None), "pytest");
+
+    let venv_bin = root.join(".venv/bin");
+    std::fs::create_dir_all(&venv_bin).unwrap();
+    std::fs::write(venv_bin.join("pytest"), "").unwrap();
+    let cmd = test_adapters::detect_test_cmd(&root.join("app.py"), None);
+    assert!(cmd.ends_with(".venv/bin/pytest"), "{}", cmd);
+}
+
+#[test]
+fn js_and_rust_test_cmd_detected_from_manifest() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    std::fs::write(root.join("cart.ts"), "").unwrap();
+    std::fs::write(root.join("package.json"), r#"{"devDependencies": {"vitest": "^1.0.0"}}"#).unwrap();
+    assert_eq!(test_adapters::detect_test_cmd(&root.join("cart.ts"), None), "npx vitest run");
+
+    std::fs::write(root.join("package.json"), r#"{"scripts": {"test": "node scripts/test.js"}}"#).unwrap();
+    assert_eq!(test_adapters::detect_test_cmd(&root.join("cart.ts"), None), "npm test --");
+
+    std::fs::write(root.join("Cargo.toml"), "[package]").unwrap();
+    std::fs::write(root.join("lib.rs"), "").unwrap();
+    assert_eq!(test_adapters::detect_test_cmd(&root.join("lib.rs"), None), "cargo test");
+}
+
+#[test]
+fn failures_reported_in_partial_output() {
+    let reports = |runner: Runner, output: &str| runner.adapter().reports_failure(output);
+    assert!(reports(Runner::Pytest, "..F"));
+    assert!(reports(Runner::Pytest, "test_app.py ..F.  [ 50%]"));
+    assert!(!reports(Runner::Pytest, "...."));
+    assert!(!reports(Runner::Pytest, "ERROR collecting test_app.py\nE   SyntaxError"));
+    assert!(reports(Runner::Unittest, "FAIL: test_add (test_app.TestAdd.test_add)"));
+    assert!(reports(Runner::Jest, "  ✕ adds items (3 ms)"));
+    assert!(reports(Runner::Jest, "  ● Cart › adds items"));
+    assert!(!reports(Runner::Jest, "  ● Test suite failed to run"));
+    assert!(reports(Runner::Vitest, " FAIL  src/cart.test.ts > Cart > adds items"));
+    assert!(!reports(Runner::Vitest, " FAIL  src/cart.test.ts [ src/cart.test.ts ]"));
+    assert!(reports(Runner::Mocha, "  ..!"));
+    assert!(reports(Runner::Mocha, "  1) Cart"));
+    assert!(reports(Runner::CargoTest, "test tests::adds ... FAILED"));
+    assert!(!reports(Runner::CargoTest, "test tests::adds ... ok"));
+    assert!(reports(Runner::CargoNextest, "        FAIL [   0.004s] calc tests::adds"));
+    assert!(reports(Runner::GoTest, "--- FAIL: TestAdd (0.00s)"));
+    assert!(reports(Runner::Maven, "[ERROR] com.x.CartTest.adds -- Time elapsed: 0.01 s <<< FAILURE!"));
+    assert!(!reports(Runner::Maven, "[ERROR] COMPILATION ERROR :"));
+    assert!(reports(Runner::Gradle, "CartTest > adds() FAILED"));
+    assert!(!reports(Runner::Gradle, "> Task :compileJava FAILED"));
+    for runner in Runner::ALL {
+        assert!(!reports(runner, ""), "{}", runner);
+    }
+}
+
+#[test]
+fn js_json_reports_name_the_killing_test() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("report.json");
+    let read = |runner: Runner, report: &str| {
+        std::fs::write(&path, report).unwrap();
+        runner.adapter().read_report(&path)
+    };
+    let failed = r#"{"testResults": [{"status": "failed", "assertionResults": [
+        {"ancestorTitles": ["Cart"], "title": "totals", "status": "passed"},
+        {"ancestorTitles": ["Cart", "discounts"], "title": "applies", "status": "failed"}]}]}"#;
+    assert_eq!(read(Runner::Jest, failed), Some(ReportOutcome::TestFailed("Cart › discounts › applies".into())));
+    assert_eq!(read(Runner::Vitest, failed), Some(ReportOutcome::TestFailed("Cart > discounts > applies".into())));
+
+    let load_error = r#"{"testResults": [{"status": "failed", "message": "SyntaxError", "assertionResults": []}]}"#;
+    assert_eq!(read(Runner::Vitest, load_error), Some(ReportOutcome::LoadError));
+
+    let passed = r#"{"testResults": [{"status": "passed", "assertionResults": [{"title": "t", "status": "passed"}]}]}"#;
+    assert_eq!(read(Runner::Jest, passed), None);
+    assert_eq!(read(Runner::Jest, "not json"), None);
+    assert_eq!(read(Runner::Pytest, failed), None);
+
+    let args = Runner::Vitest.adapter().report_args(&path);
+    assert!(args.contains(&"--reporter=json".to_string()));
+    assert!(Runner::Mocha.adapter().report_args(&path).is_empty());
+}
+
+#[test]
+fn pytest_junit_report_separates_failures_collection_and_fixture_errors() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("report.xml");
+    let read = |cases: &str, tests: usize| {
+        let xml = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?><testsuites><testsuite name="pytest" tests="{}">{}</testsuite></testsuites>"#,
+            tests, cases
+        );
+        std::fs::write(&path, xml).unwrap();
+        Runner::Pytest.adapter().read_report(&path)
+    };
+
+    let failure = r#"<testcase classname="tests.test_cart.TestCart" name="test_total"><failure message="assert 3 == 4">...</failure></testcase>"#;
+    assert_eq!(read(failure, 1), Some(ReportOutcome::TestFailed("tests.test_cart.TestCart::test_total".into())));
+
+    let fixture = r#"<testcase classname="test_cart" name="test_total"><error message="failed on setup with &quot;ValueError: bad cart&quot;">...</error></testcase>"#;
+    assert_eq!(read(fixture, 1), Some(ReportOutcome::TestFailed("test_cart::test_total".into())));
+
+    let collection = r#"<testcase classname="" name="test_cart"><error message="collection failure">SyntaxError: invalid syntax</error></testcase>"#;
+    assert_eq!(read(collection, 1), Some(ReportOutcome::LoadError));
+
+    assert_eq!(read("", 0), Some(ReportOutcome::NoTests));
+    assert_eq!(read(r#"<testcase classname="test_cart" name="test_total"/>"#, 1), None);
+    assert_eq!(Runner::Pytest.adapter().report_args(&path), [format!("--junitxml={}", path.display())]);
+}
+
+#[test]
+fn no_tests_detected_per_runner() {
+    let none = |runner: Runner, code: i32, output: &str| runner.adapter().ran_no_tests(Some(code), output);
+    assert!(none(Runner::Pytest, 5, "no tests ran in 0.01s"));
+    assert!(none(Runner::Pytest, 5, "3 deselected in 0.01s"));
+    assert!(!none(Runner::Pytest, 0, "3 passed in 0.01s"));
+    assert!(none(Runner::Unittest, 0, "Ran 0 tests in 0.000s\n\nOK"));
+    assert!(!none(Runner::Unittest, 0, "Ran 10 tests in 0.001s"));
+    assert!(none(Runner::Jest, 1, "No tests found, exiting with code 1"));
+    assert!(none(Runner::Vitest, 1, "No test files found, exiting with code 1"));
+    assert!(none(Runner::Mocha, 0, "\n  0 passing (1ms)\n"));
+    assert!(!none(Runner::Mocha, 1, "  0 passing (1ms)\n  1 failing"));
+    assert!(none(Runner::CargoTest, 0, "running 0 tests\n\nrunning 0 tests\n"));
+    assert!(!none(Runner::CargoTest, 0, "running 0 tests\n\nrunning 2 tests\n"));
+    assert!(!none(Runner::CargoTest, 0, "    Finished `test` profile"));
+    assert!(none(Runner::CargoNextest, 4, "error: no tests to run"));
+    assert!(none(Runner::GoTest, 0, "?   \texample.com/m\t[no test files]\nok  \texample.com/m/b\t0.01s [no tests to run]"));
+    assert!(!none(Runner::GoTest, 0, "?   \texample.com/m\t[no test files]\nok  \texample.com/m/b\t0.01s"));
+    assert!(none(Runner::Maven, 1, "[ERROR] No tests matching pattern \"CartTest\" were executed!"));
+    assert!(none(Runner::Gradle, 1, "> No tests found for given includes: [CartTest](--tests filter)"));
+}
+
+#[test]
+fn maven_and_gradle_select_the_test_class() {
+    let test_file = std::path::Path::new("/p/src/test/java/com/x/CartTest.java");
+    let class = |runner: Runner, filters: &[String]| runner.adapter().class_args(test_file, filters);
+    assert_eq!(
+        class(Runner::Maven, &[]).unwrap(),
+        ["-Dtest=CartTest", "-Dsurefire.failIfNoSpecifiedTests=false"]
+    );
+    let filters = ["adds".to_string(), "removes".to_string()];
+    assert_eq!(class(Runner::Maven, &filters).unwrap()[0], "-Dtest=CartTest#adds+removes");
+    assert_eq!(class(Runner::Gradle, &[]).unwrap(), ["--tests", "CartTest"]);
+    assert_eq!(
+        class(Runner::Gradle, &filters).unwrap(),
+        ["--tests", "CartTest.adds", "--tests", "CartTest.removes"]
+    );
+    assert_eq!(class(Runner::Pytest, &[]), None);
+    assert!(!Runner::Maven.adapter().passes_test_file());
+    assert!(Runner::Maven.adapter().is_unviable("[ERROR] COMPILATION ERROR :", ""));
+    assert!(Runner::Gradle.adapter().is_unviable("", "> Task :compileJava FAILED"));
+}
+
+#[test]
+fn java_test_cmd_detected_from_build() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    let source = root.join("src/main/java/Cart.java");
+    std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+    std::fs::write(&source, "").unwrap();
+    std::fs::write(root.join("pom.xml"), "<project/>").unwrap();
+    assert_eq!(test_adapters::detect_test_cmd(&source, None), "mvn -q test");
+    assert_eq!(Runner::detect("./run.sh", &Language::Java, root), Runner::Maven);
+
+    std::fs::write(root.join("mvnw"), "").unwrap();
+    let cmd = test_adapters::detect_test_cmd(&source, None);
+    assert!(cmd.ends_with("mvnw -q test"), "{}", cmd);
+
+    std::fs::remove_file(root.join("pom.xml")).unwrap();
+    std::fs::remove_file(root.join("mvnw")).unwrap();
+    std::fs::write(root.join("build.gradle.kts"), "").unwrap();
+    assert_eq!(test_adapters::detect_test_cmd(&source, None), "gradle test");
+    assert_eq!(Runner::detect("./run.sh", &Language::Java, root), Runner::Gradle);
+    std::fs::write(root.join("gradlew"), "").unwrap();
+    let cmd = test_adapters::detect_test_cmd(&source, None);
+    assert!(cmd.ends_with("gradlew test"), "{}", cmd);
+}
@@ -0,0 +1,90 @@
+use std::path::Path;
+
+use mutator::test_discovery::{candidates, find_test_file};
+use mutator::EngineError;
+
+fn touch(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+#[test]
+fn python_test_found_next_to_source_or_under_tests() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    touch(root, "pyproject.toml", "");
+    touch(root, "app.py", "");
+    touch(root, "test_app.py", "");
+    touch(root, "src/shop/cart.py", "");
+    touch(root, "tests/shop/test_cart.py", "");
+    touch(root, "src/shop/price.py", "");
+    touch(root, "src/shop/price_test.py", "");
+
+    assert_eq!(find_test_file(&root.join("app.py")).unwrap(), root.join("test_app.py"));
+    assert_eq!(find_test_file(&root.join("src/shop/cart.py")).unwrap(), root.join("tests/shop/test_cart.py"));
+    assert_eq!(find_test_file(&root.join("src/shop/price.py")).unwrap(), root.join("src/shop/price_test.py"));
+}
+
+#[test]
+fn js_and_rust_tests_found_by_convention() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    touch(root, "package.json", "{}");
+    touch(root, "src/math.ts", "");
+    touch(root, "src/math.test.ts", "");
+    touch(root, "src/parse.js", "");
+    touch(root, "src/__tests__/parse.spec.js", "");
+    assert_eq!(find_test_file(&root.join("src/math.ts")).unwrap(), root.join("src/math.test.ts"));
+    assert_eq!(find_test_file(&root.join("src/parse.js")).unwrap(), root.join("src/__tests__/parse.spec.js"));
+
+    let crate_dir = tempfile::TempDir::new().unwrap();
+    let root = crate_dir.path();
+    touch(root, "Cargo.toml", "");
+    touch(root, "src/calc.rs", "pub fn add() {}\n");
+    touch(root, "tests/calc.rs", "");
+    touch(root, "src/inline.rs", "pub fn add() {}\n#[cfg(test)]\nmod tests {}\n");
+    assert_eq!(find_test_file(&root.join("src/calc.rs")).unwrap(), root.join("tests/calc.rs"));
+    assert_eq!(find_test_file(&root.join("src/inline.rs")).unwrap(), root.join("src/inline.rs"));
+}
+
+#[test]
+fn java_tests_found_in_the_mirrored_test_tree() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    touch(root, "pom.xml", "<project/>");
+    touch(root, "src/main/java/com/shop/Cart.java", "");
+    touch(root, "src/test/java/com/shop/CartTest.java", "");
+    touch(root, "src/main/java/com/shop/Price.java", "");
+    touch(root, "src/main/java/com/shop/TestPrice.java", "");
+    assert_eq!(
+        find_test_file(&root.join("src/main/java/com/shop/Cart.java")).unwrap(),
+        root.join("src/test/java/com/shop/CartTest.java")
+    );
+    assert_eq!(
+        find_test_file(&root.join("src/main/java/com/shop/Price.java")).unwrap(),
+        root.join("src/main/java/com/shop/TestPrice.java")
+    );
+}
+
+#[test]
+fn ambiguous_or_missing_test_file_is_a_usage_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let root = dir.path();
+    touch(root, "pyproject.toml", "");
+    touch(root, "app.py", "");
+    touch(root, "test_app.py", "");
+    touch(root, "tests/test_app.py", "");
+    touch(root, "lonely.py", "");
+
+    let found = candidates(&root.join("app.py"));
+    assert_eq!(found, [root.join("test_app.py"), root.join("tests/test_app.py")]);
+    let err = find_test_file(&root.join("app.py")).unwrap_err();
+    assert!(matches!(&err, EngineError::AmbiguousTestFile { candidates, .. } if *candidates == found));
+    assert!(err.to_string().contains("tests/test_app.py"), "{}", err);
+    assert_eq!(err.exit_code(), 2);
+
+    let err = find_test_file(&root.join("lonely.py")).unwrap_err();
+    assert!(matches!(err, EngineError::NoTestFile(_)));
+    assert_eq!(err.exit_code(), 2);
+}
@@ -0,0 +1,27 @@
+use mutator::test_map;
+use std::path::Path;
+
+#[test]
+fn parses_strings_and_lists() {
+    let map = test_map::parse(r#"{"add": ["test_add", "test_add_negative"], "parse": "test_parse"}"#).unwrap();
+    assert_eq!(map["add"], vec!["test_add", "test_add_negative"]);
+    assert_eq!(map["parse"], vec!["test_parse"]);
+}
+
+#[test]
+fn rejects_non_object() {
+    assert!(test_map::parse("[\"test_add\"]").is_err());
+}
+
+#[test]
+fn rejects_bad_values() {
+    assert!(test_map::parse(r#"{"add": 3}"#).unwrap_err().contains("'add'"));
+    assert!(test_map::parse(r#"{"add": ["test_add", 3]}"#).is_err());
+    assert!(test_map::parse(r#"{"add": []}"#).unwrap_err().contains("no tests"));
+}
+
+#[test]
+fn load_missing_file_is_error() {
+    let err = test_map::load(Path::new("/nonexistent/map.json")).unwrap_err();
+    assert!(err.contains("Failed to read test map"));
+}
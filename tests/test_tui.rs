@@ -0,0 +1,131 @@
+#![cfg(feature = "tui")]
+
+use mutator::mutants::{MutantResult, MutantStatus, Mutation};
+use mutator::tui::{self, App, Progress};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+
+fn mutation(line: usize, original: &str, replacement: &str) -> Mutation {
+    Mutation {
+        line,
+        column: 12,
+        start_byte: 0,
+        end_byte: original.len(),
+        operator: "boundary".into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        context_before: vec!["def check(x):".into()],
+        context_after: vec!["        return True".into()],
+        function: Some("check".into()),
+    }
+}
+
+fn result(app: &App, index: usize, status: MutantStatus) -> Progress {
+    let mutation = &app.mutations[index];
+    Progress::Result(index, Box::new(MutantResult {
+        mutation: mutation.clone(),
+        status,
+        duration_ms: 10,
+        diff: format!("--- a\n+++ b\n-    if x {} 0:\n+    if x {} 0:\n", mutation.original, mutation.replacement),
+        output_snippet: None,
+        killed_by: None,
+    }))
+}
+
+fn render(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+    terminal.draw(|frame| tui::draw(frame, app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn app() -> App {
+    App::new("src/app.py", vec![mutation(3, ">", ">="), mutation(7, "<", "<=")])
+}
+
+#[test]
+fn shows_baseline_then_progress() {
+    let mut app = app();
+    assert!(render(&app).contains("running baseline..."));
+
+    app.apply(Progress::BaselineDone);
+    app.apply(result(&app, 0, MutantStatus::Survived));
+    let screen = render(&app);
+
+    assert!(screen.contains("1/2 · 0 killed · 1 survived"));
+    assert!(screen.contains("SURVIVED"));
+    assert!(screen.contains("running"));
+    assert!(screen.contains("> → >="));
+}
+
+#[test]
+fn selection_follows_progress_until_user_navigates() {
+    let mut app = app();
+    app.apply(Progress::BaselineDone);
+    app.apply(result(&app, 0, MutantStatus::Killed));
+    assert_eq!(app.selected, 1);
+
+    app.previous();
+    app.apply(result(&app, 1, MutantStatus::Killed));
+    app.apply(Progress::Finished);
+    assert_eq!(app.selected, 0);
+    assert_eq!(app.count(MutantStatus::Killed), 2);
+
+    app.next();
+    app.next();
+    assert_eq!(app.selected, 1);
+}
+
+#[test]
+fn detail_pane_shows_context_and_diff() {
+    let mut app = app();
+    app.apply(Progress::BaselineDone);
+    app.apply(result(&app, 0, MutantStatus::Survived));
+    app.previous();
+    app.toggle_detail();
+    let screen = render(&app);
+
+    assert!(screen.contains("src/app.py:3 [boundary]"));
+    assert!(screen.contains("def check(x):"));
+    assert!(screen.contains("-    if x > 0:"));
+    assert!(screen.contains("+    if x >= 0:"));
+    assert!(!screen.contains("+++ b"));
+}
+
+#[test]
+fn failure_is_shown_in_header() {
+    let mut app = app();
+    app.apply(Progress::Failed("Baseline tests failed".into()));
+    assert!(app.finished);
+    assert!(render(&app).contains("Baseline tests failed"));
+}
+
+#[test]
+fn shuffled_results_land_on_their_own_rows() {
+    let mut app = App::new("src/app.py", vec![mutation(3, ">", ">="), mutation(7, "<", "<="), mutation(9, "==", "!=")])
+        .with_order(vec![2, 0, 1]);
+    app.apply(Progress::BaselineDone);
+    app.apply(result(&app, 2, MutantStatus::Survived));
+    assert_eq!(app.selected, 0, "follows the next mutant in run order");
+
+    app.apply(result(&app, 0, MutantStatus::Killed));
+    assert_eq!(app.selected, 1);
+    let screen = render(&app);
+    let row = |line: &str| screen.lines().find(|l| l.contains(line)).unwrap().to_string();
+    assert!(row("3:12").contains("killed"), "{}", screen);
+    assert!(row("7:12").contains("running"), "{}", screen);
+    assert!(row("9:12").contains("SURVIVED"), "{}", screen);
+
+    app.toggle_detail();
+    app.previous();
+    app.next();
+    app.next();
+    assert!(render(&app).contains("+    if x != 0:"));
+}
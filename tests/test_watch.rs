@@ -0,0 +1,92 @@
+use mutator::engine;
+use mutator::mutants::{MutantResult, MutantStatus};
+use mutator::watch::{ResultCache, Watcher};
+use mutator::Language;
+use std::time::Duration;
+
+const SOURCE: &str = "def add(a, b):\n    return a + b\n\ndef is_positive(n):\n    return n > 0\n";
+const TESTS: &str = "def test_add(): ...\n";
+
+fn results(source: &str, status: MutantStatus) -> Vec<MutantResult> {
+    engine::discover_mutations(&Language::Python, source, None)
+        .into_iter()
+        .map(|mutation| MutantResult {
+            mutation,
+            status: status.clone(),
+            duration_ms: 5,
+            diff: String::new(),
+            output_snippet: None,
+            killed_by: None,
+        })
+        .collect()
+}
+
+fn cache() -> ResultCache {
+    let mut cache = ResultCache::new();
+    cache.store(&Language::Python, SOURCE, TESTS, &results(SOURCE, MutantStatus::Killed));
+    cache
+}
+
+#[test]
+fn empty_cache_runs_everything() {
+    let mutations = engine::discover_mutations(&Language::Python, SOURCE, None);
+    let cached = ResultCache::new().lookup(&Language::Python, SOURCE, TESTS, &mutations);
+    assert_eq!(cached.len(), mutations.len());
+    assert!(cached.iter().all(Option::is_none));
+}
+
+#[test]
+fn unchanged_functions_reuse_results_at_new_position() {
+    let edited = format!("import math\n\n{}", SOURCE);
+    let mutations = engine::discover_mutations(&Language::Python, &edited, None);
+    let cached = cache().lookup(&Language::Python, &edited, TESTS, &mutations);
+
+    assert!(cached.iter().all(Option::is_some));
+    let first = cached[0].as_ref().unwrap();
+    assert_eq!(first.status, MutantStatus::Killed);
+    assert_eq!(first.mutation.line, mutations[0].line);
+    assert_eq!(first.mutation.start_byte, mutations[0].start_byte);
+}
+
+#[test]
+fn edited_function_reruns_only_its_mutants() {
+    let edited = SOURCE.replace("return n > 0", "return n > 1");
+    let mutations = engine::discover_mutations(&Language::Python, &edited, None);
+    let cached = cache().lookup(&Language::Python, &edited, TESTS, &mutations);
+
+    for (m, c) in mutations.iter().zip(&cached) {
+        match m.function.as_deref() {
+            Some("add") => assert!(c.is_some(), "{:?} should be reused", m),
+            _ => assert!(c.is_none(), "{:?} should rerun", m),
+        }
+    }
+}
+
+#[test]
+fn test_file_change_reruns_everything() {
+    let mutations = engine::discover_mutations(&Language::Python, SOURCE, None);
+    let cached = cache().lookup(&Language::Python, SOURCE, "def test_add(): assert False\n", &mutations);
+    assert!(cached.iter().all(Option::is_none));
+}
+
+#[test]
+fn watcher_reports_changed_file() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let source = dir.path().join("app.py");
+    let other = dir.path().join("notes.txt");
+    std::fs::write(&source, SOURCE).unwrap();
+    let watcher = Watcher::new(&[&source]).unwrap();
+
+    let writer = {
+        let source = source.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(100));
+            std::fs::write(other, "unrelated").unwrap();
+            std::fs::write(source, "def add(a, b):\n    return a - b\n").unwrap();
+        })
+    };
+    let changed = watcher.wait(Duration::from_millis(100));
+    writer.join().unwrap();
+
+    assert_eq!(changed, vec![source.canonicalize().unwrap()]);
+}
@@ -0,0 +1,90 @@
+use mutator::state::{RunResult, SurvivedMutant};
+use mutator::xml_report;
+
+fn mutant(ref_id: &str, file: &str, operator: &str, original: &str, replacement: &str) -> SurvivedMutant {
+    SurvivedMutant {
+        ref_id: ref_id.into(),
+        file: file.into(),
+        line: 10,
+        column: 12,
+        function: Some("check".into()),
+        operator: operator.into(),
+        original: original.into(),
+        replacement: replacement.into(),
+        diff: format!("-     if x {} 0:\n+     if x {} 0:\n", original, replacement),
+        context_before: vec!["def check(x):".into()],
+        context_after: vec!["        return True".into()],
+        ..Default::default()
+    }
+}
+
+fn result() -> RunResult {
+    RunResult {
+        duration_ms: 1500,
+        survived_mutants: vec![
+            mutant("m1", "src/app.py", "boundary", ">", ">="),
+            mutant("m2", "src/util.py", "negate_cmp", ">", "<="),
+        ],
+        uncovered_mutants: vec![mutant("u1", "src/app.py", "arith", "+", "-")],
+        ..Default::default()
+    }
+}
+
+#[test]
+fn junit_has_a_failing_case_per_mutant() {
+    let xml = xml_report::to_junit(&result());
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let suite = doc.descendants().find(|n| n.has_tag_name("testsuite")).unwrap();
+    assert_eq!(suite.attribute("tests"), Some("3"));
+    assert_eq!(suite.attribute("failures"), Some("3"));
+    assert_eq!(suite.attribute("time"), Some("1.500"));
+
+    let cases: Vec<_> = doc.descendants().filter(|n| n.has_tag_name("testcase")).collect();
+    assert_eq!(cases.len(), 3);
+    assert_eq!(cases[0].attribute("classname"), Some("src/app.py"));
+    assert_eq!(cases[0].attribute("name"), Some("@m1"));
+    assert_eq!(cases[2].attribute("name"), Some("@u1"));
+
+    let failure = cases[0].children().find(|n| n.has_tag_name("failure")).unwrap();
+    assert_eq!(failure.attribute("message"), Some("Survived mutant: '>' → '>=' (boundary)"));
+    assert_eq!(failure.attribute("type"), Some("boundary"));
+    let text = failure.text().unwrap();
+    assert!(text.starts_with("src/app.py:10:12 in check\n\n  def check(x):\n-     if x > 0:\n+     if x >= 0:\n          return True\n"));
+    assert!(text.contains("Test the exact boundary value"));
+}
+
+#[test]
+fn junit_keeps_test_output_and_drops_invalid_characters() {
+    let mut m = mutant("m1", "src/app.py", "boundary", ">", ">=");
+    m.output_snippet = Some("1 passed\u{1b}[0m <ok>".into());
+    let xml = xml_report::to_junit(&RunResult { survived_mutants: vec![m], ..Default::default() });
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let out = doc.descendants().find(|n| n.has_tag_name("system-out")).unwrap();
+    assert_eq!(out.text(), Some("1 passed[0m <ok>"));
+}
+
+#[test]
+fn checkstyle_groups_warnings_by_file() {
+    let xml = xml_report::to_checkstyle(&result());
+    let doc = roxmltree::Document::parse(&xml).unwrap();
+    let files: Vec<_> = doc.descendants().filter(|n| n.has_tag_name("file")).collect();
+    assert_eq!(files.iter().map(|f| f.attribute("name").unwrap()).collect::<Vec<_>>(), ["src/app.py", "src/util.py"]);
+
+    let errors: Vec<_> = files[0].children().filter(|n| n.has_tag_name("error")).collect();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].attribute("line"), Some("10"));
+    assert_eq!(errors[0].attribute("severity"), Some("warning"));
+    assert_eq!(errors[0].attribute("source"), Some("mutator.boundary"));
+    assert!(errors[0].attribute("message").unwrap().starts_with("Survived mutant: '>' → '>=' (boundary) (@m1)."));
+    assert!(errors[1].attribute("message").unwrap().starts_with("Uncovered mutant"));
+}
+
+#[test]
+fn no_survivors_is_an_empty_but_valid_report() {
+    let result = RunResult { total: 2, killed: 2, score: 1.0, ..Default::default() };
+    let junit = xml_report::to_junit(&result);
+    assert!(roxmltree::Document::parse(&junit).is_ok());
+    assert!(junit.contains("tests=\"0\" failures=\"0\""));
+    let checkstyle = xml_report::to_checkstyle(&result);
+    assert!(roxmltree::Document::parse(&checkstyle).unwrap().descendants().all(|n| !n.has_tag_name("file")));
+}